@@ -0,0 +1,279 @@
+//! A struct implementing `irelia::rest::endpoint::Endpoint` for every operation in `paths`
+
+use crate::names::{field_name, ident, type_name};
+use crate::types::{doc_attr, rust_type};
+use proc_macro2::TokenStream;
+use quote::quote;
+use serde_json::{Map, Value};
+use std::collections::HashSet;
+
+const METHODS: &[&str] = &["get", "post", "put", "patch", "delete"];
+
+/// Every endpoint for `paths`, along with the helpers they share
+pub(crate) fn endpoints(paths: &Map<String, Value>) -> TokenStream {
+    let mut names = HashSet::new();
+
+    let endpoints: Vec<_> = paths
+        .iter()
+        .flat_map(|(path, operations)| {
+            METHODS.iter().filter_map(move |method| {
+                operations
+                    .get(*method)
+                    .map(|operation| (path, *method, operation))
+            })
+        })
+        .map(|(path, method, operation)| {
+            let mut name = operation
+                .get("operationId")
+                .and_then(Value::as_str)
+                .map_or_else(|| type_name(&format!("{method}{path}")), type_name);
+            while !names.insert(name.clone()) {
+                name.push('_');
+            }
+
+            endpoint(&name, path, method, operation)
+        })
+        .collect();
+
+    if endpoints.is_empty() {
+        return TokenStream::new();
+    }
+
+    quote! {
+        /// Query and path values are sent the way they serialize to JSON, strings without quotes
+        fn __irelia_value<T: ::serde::Serialize>(value: &T) -> ::std::string::String {
+            match ::serde_json::to_value(value) {
+                ::std::result::Result::Ok(::serde_json::Value::String(value)) => value,
+                ::std::result::Result::Ok(value) => value.to_string(),
+                ::std::result::Result::Err(_) => ::std::string::String::new(),
+            }
+        }
+
+        /// Percent encodes everything other than the unreserved characters from RFC 3986
+        fn __irelia_encode(value: &str) -> ::std::string::String {
+            let mut encoded = ::std::string::String::with_capacity(value.len());
+            for byte in value.bytes() {
+                match byte {
+                    b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                        encoded.push(char::from(byte));
+                    }
+                    _ => {
+                        let _ = ::std::fmt::Write::write_fmt(
+                            &mut encoded,
+                            ::std::format_args!("%{byte:02X}"),
+                        );
+                    }
+                }
+            }
+            encoded
+        }
+
+        #(#endpoints)*
+    }
+}
+
+struct Param<'a> {
+    name: &'a str,
+    field: proc_macro2::Ident,
+    ty: TokenStream,
+    required: bool,
+}
+
+fn endpoint(name: &str, path: &str, method: &str, operation: &Value) -> TokenStream {
+    let mut seen = HashSet::new();
+    let mut field = |name: &str| {
+        let mut field = field_name(name);
+        while !seen.insert(field.clone()) {
+            field.push('_');
+        }
+        ident(&field)
+    };
+
+    let parameters = operation
+        .get("parameters")
+        .and_then(Value::as_array)
+        .map(Vec::as_slice)
+        .unwrap_or_default();
+
+    let mut path_params = Vec::new();
+    let mut query_params = Vec::new();
+
+    for parameter in parameters {
+        let Some(param_name) = parameter.get("name").and_then(Value::as_str) else {
+            continue;
+        };
+
+        let location = parameter.get("in").and_then(Value::as_str);
+        let ty = parameter
+            .get("schema")
+            .map_or_else(|| quote!(::serde_json::Value), rust_type);
+
+        let param = Param {
+            name: param_name,
+            field: field(param_name),
+            ty,
+            required: location == Some("path")
+                || parameter.get("required").and_then(Value::as_bool) == Some(true),
+        };
+
+        match location {
+            Some("path") => path_params.push(param),
+            Some("query") => query_params.push(param),
+            _ => {}
+        }
+    }
+
+    let body = operation
+        .pointer("/requestBody/content/application~1json/schema")
+        .map(|schema| (field("body"), rust_type(schema)));
+
+    let response = ["200", "201"]
+        .iter()
+        .find_map(|status| {
+            operation.pointer(&format!(
+                "/responses/{status}/content/application~1json/schema"
+            ))
+        })
+        .map_or_else(|| quote!(::serde::de::IgnoredAny), rust_type);
+
+    let name = ident(name);
+    let method = method.to_ascii_uppercase();
+    let doc = doc_attr(operation);
+    let route = format!(" `{method} {path}`");
+
+    let fields = path_params.iter().chain(&query_params).map(|param| {
+        let Param { field, ty, .. } = param;
+        if param.required {
+            quote!(pub #field: #ty,)
+        } else {
+            quote!(pub #field: ::std::option::Option<#ty>,)
+        }
+    });
+
+    let body_field = body.as_ref().map(|(field, ty)| quote!(pub #field: #ty,));
+    let (body_type, into_body) = match &body {
+        Some((field, ty)) => (
+            quote!(#ty),
+            quote! {
+                fn into_body(self) -> ::std::option::Option<Self::Body> {
+                    ::std::option::Option::Some(self.#field)
+                }
+            },
+        ),
+        None => (quote!(()), TokenStream::new()),
+    };
+
+    let path_fn = path_fn(path, &path_params, &query_params);
+
+    quote! {
+        #doc
+        #[doc = ""]
+        #[doc = #route]
+        #[derive(Debug, Clone, PartialEq)]
+        pub struct #name {
+            #(#fields)*
+            #body_field
+        }
+
+        impl ::irelia::rest::endpoint::Endpoint for #name {
+            type Body = #body_type;
+            type Response = #response;
+
+            const METHOD: &'static str = #method;
+
+            #path_fn
+
+            #into_body
+        }
+    }
+}
+
+fn path_fn(path: &str, path_params: &[Param], query_params: &[Param]) -> TokenStream {
+    let mut format = String::with_capacity(path.len());
+    let mut args = Vec::new();
+    let mut rest = path;
+
+    // Path parameters the schema doesn't list are left as they are
+    while let Some((start, end)) = rest
+        .find('{')
+        .and_then(|start| Some((start, start + rest[start..].find('}')?)))
+    {
+        let name = &rest[start + 1..end];
+        format.push_str(&rest[..start].replace('{', "{{").replace('}', "}}"));
+
+        if let Some(param) = path_params.iter().find(|param| param.name == name) {
+            let field = &param.field;
+            format.push_str("{}");
+            args.push(quote!(__irelia_encode(&__irelia_value(&self.#field))));
+        } else {
+            format.push_str("{{");
+            format.push_str(name);
+            format.push_str("}}");
+        }
+
+        rest = &rest[end + 1..];
+    }
+
+    format.push_str(&rest.replace('{', "{{").replace('}', "}}"));
+
+    if args.is_empty() && query_params.is_empty() {
+        let path = format.replace("{{", "{").replace("}}", "}");
+        return quote! {
+            fn path(&self) -> ::std::borrow::Cow<'_, str> {
+                ::std::borrow::Cow::Borrowed(#path)
+            }
+        };
+    }
+
+    let queries = query_params.iter().map(|param| {
+        let name = param.name;
+        let field = &param.field;
+
+        let push = quote! {
+            path.push(separator);
+            path.push_str(&__irelia_encode(#name));
+            path.push('=');
+            path.push_str(&__irelia_encode(&__irelia_value(value)));
+            separator = '&';
+        };
+
+        if param.required {
+            quote! {
+                let value = &self.#field;
+                #push
+            }
+        } else {
+            quote! {
+                if let ::std::option::Option::Some(value) = &self.#field {
+                    #push
+                }
+            }
+        }
+    });
+
+    let path = if args.is_empty() {
+        let path = format.replace("{{", "{").replace("}}", "}");
+        quote!(::std::string::String::from(#path))
+    } else {
+        quote!(::std::format!(#format, #(#args),*))
+    };
+
+    if query_params.is_empty() {
+        return quote! {
+            fn path(&self) -> ::std::borrow::Cow<'_, str> {
+                ::std::borrow::Cow::Owned(#path)
+            }
+        };
+    }
+
+    // The last query parameter always sets the separator, even though it's never read again
+    quote! {
+        #[allow(unused_assignments)]
+        fn path(&self) -> ::std::borrow::Cow<'_, str> {
+            let mut path = #path;
+            let mut separator = '?';
+            #(#queries)*
+            ::std::borrow::Cow::Owned(path)
+        }
+    }
+}
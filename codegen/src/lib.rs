@@ -0,0 +1,199 @@
+#![warn(clippy::perf)]
+#![warn(clippy::pedantic)]
+#![forbid(unsafe_code)]
+
+//! Generates typed endpoints for irelia from an `OpenAPI` document, such as the one the LCU serves at `/swagger/v3/openapi.json`
+//!
+//! Every schema in `components.schemas` becomes a struct, enum, or type alias, and every operation in `paths`
+//! becomes a struct implementing `irelia::rest::endpoint::Endpoint`, which can be sent with `LcuClient::execute`.
+//!
+//! This is meant to be used from a build script:
+//!
+//! ```no_run
+//! // build.rs
+//! let out_dir = std::env::var_os("OUT_DIR").unwrap();
+//! irelia_codegen::generate_file("lcu.json", std::path::Path::new(&out_dir).join("lcu.rs")).unwrap();
+//! println!("cargo::rerun-if-changed=lcu.json");
+//! ```
+//!
+//! ```ignore
+//! // lib.rs
+//! pub mod lcu {
+//!     include!(concat!(env!("OUT_DIR"), "/lcu.rs"));
+//! }
+//! ```
+//!
+//! The generated code needs `irelia` with the `rest` feature, `serde` with the `derive` feature, and `serde_json`
+
+mod endpoints;
+mod names;
+mod types;
+
+use serde_json::Value;
+use std::path::Path;
+
+/// Errors that can happen while generating code
+#[derive(Debug)]
+pub enum Error {
+    /// The document could not be read, or the output could not be written
+    Io(std::io::Error),
+    /// The document is not valid JSON
+    Json(serde_json::Error),
+    /// The document is JSON, but not an `OpenAPI` document
+    InvalidDocument(&'static str),
+    /// The generated code could not be parsed, this is a bug
+    Syntax(syn::Error),
+}
+
+impl From<std::io::Error> for Error {
+    fn from(value: std::io::Error) -> Self {
+        Self::Io(value)
+    }
+}
+
+impl From<serde_json::Error> for Error {
+    fn from(value: serde_json::Error) -> Self {
+        Self::Json(value)
+    }
+}
+
+impl From<syn::Error> for Error {
+    fn from(value: syn::Error) -> Self {
+        Self::Syntax(value)
+    }
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Io(err) => err.fmt(f),
+            Self::Json(err) => err.fmt(f),
+            Self::InvalidDocument(reason) => f.write_str(reason),
+            Self::Syntax(err) => write!(f, "Generated invalid code: {err}"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+/// Generates the types and endpoints for an `OpenAPI` document, returning formatted Rust source
+///
+/// # Errors
+/// This will return an error if `document` is not JSON, or does not contain `paths` or `components.schemas`
+pub fn generate(document: &str) -> Result<String, Error> {
+    let document: Value = serde_json::from_str(document)?;
+
+    let schemas = document
+        .pointer("/components/schemas")
+        .and_then(Value::as_object);
+    let paths = document.get("paths").and_then(Value::as_object);
+
+    if schemas.is_none() && paths.is_none() {
+        return Err(Error::InvalidDocument(
+            "The document has neither `paths` nor `components.schemas`",
+        ));
+    }
+
+    let types = schemas.map(types::schemas).unwrap_or_default();
+    let endpoints = paths.map(endpoints::endpoints).unwrap_or_default();
+
+    let file = syn::parse2(quote::quote! {
+        #types
+        #endpoints
+    })?;
+
+    Ok(prettyplease::unparse(&file))
+}
+
+/// Reads an `OpenAPI` document from `input`, and writes the generated code to `output`, see [`generate`]
+///
+/// # Errors
+/// This will return an error if `input` can't be read, `output` can't be written, or the document is invalid
+pub fn generate_file(input: impl AsRef<Path>, output: impl AsRef<Path>) -> Result<(), Error> {
+    let document = std::fs::read_to_string(input)?;
+    std::fs::write(output, generate(&document)?)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::generate;
+
+    const DOCUMENT: &str = r##"{
+        "openapi": "3.0.0",
+        "paths": {
+            "/lol-summoner/v1/summoners/{id}": {
+                "get": {
+                    "operationId": "GetLolSummonerV1SummonersById",
+                    "description": "Gets a summoner",
+                    "parameters": [
+                        {"in": "path", "name": "id", "required": true, "schema": {"type": "integer", "format": "uint64"}}
+                    ],
+                    "responses": {
+                        "200": {"content": {"application/json": {"schema": {"$ref": "#/components/schemas/LolSummonerSummoner"}}}}
+                    }
+                }
+            },
+            "/lol-summoner/v1/summoners": {
+                "get": {
+                    "operationId": "GetLolSummonerV1Summoners",
+                    "parameters": [
+                        {"in": "query", "name": "name", "schema": {"type": "string"}}
+                    ]
+                },
+                "post": {
+                    "operationId": "PostLolSummonerV1Summoners",
+                    "requestBody": {"content": {"application/json": {"schema": {"type": "array", "items": {"type": "string"}}}}}
+                }
+            }
+        },
+        "components": {
+            "schemas": {
+                "LolSummonerSummoner": {
+                    "type": "object",
+                    "properties": {
+                        "summonerId": {"type": "integer", "format": "uint64"},
+                        "type": {"type": "string"},
+                        "phase": {"$ref": "#/components/schemas/LolGameflowGameflowPhase"}
+                    },
+                    "required": ["summonerId"]
+                },
+                "LolGameflowGameflowPhase": {
+                    "type": "string",
+                    "enum": ["None", "Lobby", "ChampSelect"]
+                },
+                "LolSummonerIds": {
+                    "type": "array",
+                    "items": {"type": "integer", "format": "uint64"}
+                }
+            }
+        }
+    }"##;
+
+    #[test]
+    fn test_generate() {
+        let code = generate(DOCUMENT).unwrap();
+
+        assert!(code.contains("pub struct LolSummonerSummoner {"));
+        assert!(code.contains("pub summoner_id: u64,"));
+        assert!(code.contains("pub r#type: ::std::option::Option<::std::string::String>,"));
+        assert!(code.contains("pub enum LolGameflowGameflowPhase {"));
+        assert!(code.contains("ChampSelect,"));
+        assert!(code.contains("pub type LolSummonerIds = ::std::vec::Vec<u64>;"));
+
+        assert!(code.contains("pub struct GetLolSummonerV1SummonersById {"));
+        assert!(code.contains("type Response = LolSummonerSummoner;"));
+        assert!(code.contains("\"/lol-summoner/v1/summoners/{}\""));
+
+        assert!(code.contains("pub struct PostLolSummonerV1Summoners {"));
+        assert!(code.contains("pub body: ::std::vec::Vec<::std::string::String>,"));
+        assert!(code.contains("const METHOD: &'static str = \"POST\";"));
+        assert!(code.contains("type Response = ::serde::de::IgnoredAny;"));
+    }
+
+    #[test]
+    fn test_invalid_document() {
+        assert!(generate("{}").is_err());
+        assert!(generate("not json").is_err());
+    }
+}
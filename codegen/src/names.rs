@@ -0,0 +1,119 @@
+//! Turning schema names into valid Rust identifiers
+
+use proc_macro2::{Ident, Span};
+
+const KEYWORDS: &[&str] = &[
+    "abstract", "as", "async", "await", "become", "box", "break", "const", "continue", "do", "dyn",
+    "else", "enum", "extern", "false", "final", "fn", "for", "gen", "if", "impl", "in", "let",
+    "loop", "macro", "match", "mod", "move", "mut", "override", "priv", "pub", "ref", "return",
+    "static", "struct", "trait", "true", "try", "type", "typeof", "unsafe", "unsized", "use",
+    "virtual", "where", "while", "yield",
+];
+
+/// Keywords that can't be raw identifiers
+const RESERVED: &[&str] = &["crate", "self", "Self", "super", "_"];
+
+/// `lol-summoner_Summoner` to `LolSummonerSummoner`, for types and enum variants
+pub(crate) fn type_name(name: &str) -> String {
+    let mut out = String::with_capacity(name.len());
+
+    for part in name.split(|c: char| !c.is_ascii_alphanumeric()) {
+        let mut chars = part.chars();
+        if let Some(first) = chars.next() {
+            out.push(first.to_ascii_uppercase());
+            out.extend(chars);
+        }
+    }
+
+    if out.is_empty() {
+        out.push_str("Empty");
+    } else if out.starts_with(|c: char| c.is_ascii_digit()) {
+        out.insert(0, 'T');
+    }
+
+    out
+}
+
+/// `summonerId` to `summoner_id`, for fields
+pub(crate) fn field_name(name: &str) -> String {
+    let chars: Vec<char> = name.chars().collect();
+    let mut out = String::with_capacity(name.len() + 4);
+
+    for (i, &c) in chars.iter().enumerate() {
+        if !c.is_ascii_alphanumeric() {
+            if !out.ends_with('_') {
+                out.push('_');
+            }
+            continue;
+        }
+
+        if c.is_ascii_uppercase() && i > 0 {
+            let prev = chars[i - 1];
+            let next_lower = chars.get(i + 1).is_some_and(char::is_ascii_lowercase);
+
+            if (prev.is_ascii_lowercase()
+                || prev.is_ascii_digit()
+                || (prev.is_ascii_uppercase() && next_lower))
+                && !out.ends_with('_')
+            {
+                out.push('_');
+            }
+        }
+
+        out.push(c.to_ascii_lowercase());
+    }
+
+    let trimmed = out.trim_matches('_');
+    let mut out = if trimmed.is_empty() {
+        String::from("field")
+    } else {
+        trimmed.to_string()
+    };
+
+    if out.starts_with(|c: char| c.is_ascii_digit()) {
+        out.insert(0, '_');
+    }
+
+    out
+}
+
+/// An identifier for `name`, which should already be a type or field name, escaping keywords
+pub(crate) fn ident(name: &str) -> Ident {
+    if RESERVED.contains(&name) {
+        Ident::new(&format!("{name}_"), Span::call_site())
+    } else if KEYWORDS.contains(&name) {
+        Ident::new_raw(name, Span::call_site())
+    } else {
+        Ident::new(name, Span::call_site())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{field_name, ident, type_name};
+
+    #[test]
+    fn test_type_name() {
+        assert_eq!(type_name("LolSummonerSummoner"), "LolSummonerSummoner");
+        assert_eq!(type_name("lol-summoner_Summoner"), "LolSummonerSummoner");
+        assert_eq!(type_name("3v3"), "T3v3");
+        assert_eq!(type_name(""), "Empty");
+    }
+
+    #[test]
+    fn test_field_name() {
+        assert_eq!(field_name("summonerId"), "summoner_id");
+        assert_eq!(field_name("puuid"), "puuid");
+        assert_eq!(field_name("XMLParser"), "xml_parser");
+        assert_eq!(field_name("level2Name"), "level2_name");
+        assert_eq!(field_name("game-mode"), "game_mode");
+        assert_eq!(field_name("1v1"), "_1v1");
+    }
+
+    #[test]
+    fn test_ident() {
+        assert_eq!(ident("type").to_string(), "r#type");
+        assert_eq!(ident("self").to_string(), "self_");
+        assert_eq!(ident("name").to_string(), "name");
+    }
+}
@@ -0,0 +1,208 @@
+//! Structs, enums, and aliases for `components.schemas`
+
+use crate::names::{field_name, ident, type_name};
+use proc_macro2::TokenStream;
+use quote::quote;
+use serde_json::{Map, Value};
+use std::collections::HashSet;
+
+/// The Rust type for a property or parameter schema
+pub(crate) fn rust_type(schema: &Value) -> TokenStream {
+    if let Some(reference) = schema.get("$ref").and_then(Value::as_str) {
+        let name = ident(&type_name(
+            reference.rsplit('/').next().unwrap_or(reference),
+        ));
+        return quote!(#name);
+    }
+
+    let format = schema.get("format").and_then(Value::as_str);
+
+    match schema.get("type").and_then(Value::as_str) {
+        Some("string") => quote!(::std::string::String),
+        Some("boolean") => quote!(bool),
+        Some("integer") => match format {
+            Some("int8") => quote!(i8),
+            Some("int16") => quote!(i16),
+            Some("int32") => quote!(i32),
+            Some("uint8") => quote!(u8),
+            Some("uint16") => quote!(u16),
+            Some("uint32") => quote!(u32),
+            Some("uint64") => quote!(u64),
+            _ => quote!(i64),
+        },
+        Some("number") => {
+            if format == Some("float") {
+                quote!(f32)
+            } else {
+                quote!(f64)
+            }
+        }
+        Some("array") => {
+            let item = schema
+                .get("items")
+                .map_or_else(|| quote!(::serde_json::Value), rust_type);
+            quote!(::std::vec::Vec<#item>)
+        }
+        Some("object") => {
+            if let Some(value @ Value::Object(_)) = schema.get("additionalProperties") {
+                let value = rust_type(value);
+                quote!(::std::collections::HashMap<::std::string::String, #value>)
+            } else {
+                quote!(::serde_json::Value)
+            }
+        }
+        _ => quote!(::serde_json::Value),
+    }
+}
+
+/// Every item for `components.schemas`, sorted by name
+pub(crate) fn schemas(schemas: &Map<String, Value>) -> TokenStream {
+    schemas
+        .iter()
+        .map(|(name, schema)| schema_item(name, schema))
+        .collect()
+}
+
+fn schema_item(name: &str, schema: &Value) -> TokenStream {
+    let doc = doc_attr(schema);
+    let name = ident(&type_name(name));
+
+    if let Some(values) = schema.get("enum").and_then(Value::as_array) {
+        return enum_item(&name, &doc, values);
+    }
+
+    if let Some(properties) = schema.get("properties").and_then(Value::as_object) {
+        struct_item(&name, &doc, schema, properties)
+    } else {
+        let ty = rust_type(schema);
+        quote! {
+            #doc
+            pub type #name = #ty;
+        }
+    }
+}
+
+fn enum_item(name: &proc_macro2::Ident, doc: &TokenStream, values: &[Value]) -> TokenStream {
+    let mut seen = HashSet::new();
+
+    let variants = values.iter().filter_map(Value::as_str).map(|value| {
+        let mut variant = type_name(value);
+        while !seen.insert(variant.clone()) {
+            variant.push('_');
+        }
+
+        let variant = ident(&variant);
+        quote! {
+            #[serde(rename = #value)]
+            #variant,
+        }
+    });
+    let variants: Vec<_> = variants.collect();
+
+    let mut unknown = String::from("Unknown");
+    while seen.contains(&unknown) {
+        unknown.push('_');
+    }
+    let unknown = ident(&unknown);
+
+    quote! {
+        #doc
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, ::serde::Serialize, ::serde::Deserialize)]
+        pub enum #name {
+            #(#variants)*
+            /// A value this schema did not list
+            #[serde(other)]
+            #unknown,
+        }
+    }
+}
+
+fn struct_item(
+    name: &proc_macro2::Ident,
+    doc: &TokenStream,
+    schema: &Value,
+    properties: &Map<String, Value>,
+) -> TokenStream {
+    let required: HashSet<&str> = schema
+        .get("required")
+        .and_then(Value::as_array)
+        .map(|required| required.iter().filter_map(Value::as_str).collect())
+        .unwrap_or_default();
+
+    let mut seen = HashSet::new();
+
+    let fields = properties.iter().map(|(property, property_schema)| {
+        let mut field = field_name(property);
+        while !seen.insert(field.clone()) {
+            field.push('_');
+        }
+
+        let ident = ident(&field);
+        let ty = rust_type(property_schema);
+        let doc = doc_attr(property_schema);
+
+        let rename = (field != *property).then(|| quote!(#[serde(rename = #property)]));
+
+        if required.contains(property.as_str()) {
+            quote! {
+                #doc
+                #rename
+                pub #ident: #ty,
+            }
+        } else {
+            quote! {
+                #doc
+                #rename
+                #[serde(default, skip_serializing_if = "Option::is_none")]
+                pub #ident: ::std::option::Option<#ty>,
+            }
+        }
+    });
+    let fields: Vec<_> = fields.collect();
+
+    quote! {
+        #doc
+        #[derive(Debug, Clone, PartialEq, ::serde::Serialize, ::serde::Deserialize)]
+        pub struct #name {
+            #(#fields)*
+        }
+    }
+}
+
+/// A doc comment from the schema's `description`, if it has one
+pub(crate) fn doc_attr(schema: &Value) -> TokenStream {
+    match schema.get("description").and_then(Value::as_str) {
+        Some(description) if !description.trim().is_empty() => {
+            let description = format!(" {}", description.trim());
+            quote!(#[doc = #description])
+        }
+        _ => TokenStream::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::rust_type;
+    use serde_json::json;
+
+    #[test]
+    fn test_rust_type() {
+        let ty = |schema| rust_type(&schema).to_string().replace(' ', "");
+
+        assert_eq!(ty(json!({"type": "integer", "format": "uint64"})), "u64");
+        assert_eq!(ty(json!({"type": "number"})), "f64");
+        assert_eq!(
+            ty(json!({"$ref": "#/components/schemas/LolSummonerSummoner"})),
+            "LolSummonerSummoner"
+        );
+        assert_eq!(
+            ty(json!({"type": "array", "items": {"type": "string"}})),
+            "::std::vec::Vec<::std::string::String>"
+        );
+        assert_eq!(
+            ty(json!({"type": "object", "additionalProperties": {"type": "boolean"}})),
+            "::std::collections::HashMap<::std::string::String,bool>"
+        );
+        assert_eq!(ty(json!({})), "::serde_json::Value");
+    }
+}
@@ -0,0 +1,47 @@
+//! Compares the encoder against the `base64` crate, run with `cargo bench -p irelia_encoder --features std`
+//!
+//! This works on stable, unlike the benches in `lib.rs`, which need the `nightly` feature
+
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use base64::{engine::general_purpose, Engine};
+use irelia_encoder::Encoder;
+use rand::{thread_rng, Rng};
+
+const ENCODER: Encoder = Encoder::new();
+
+/// Runs `f` on every input until a second has passed, returning the average time per input
+fn bench(inputs: &[Vec<u8>], mut f: impl FnMut(&[u8]) -> String) -> Duration {
+    let start = Instant::now();
+    let mut runs = 0;
+
+    while start.elapsed() < Duration::from_secs(1) {
+        for input in inputs {
+            black_box(f(black_box(input)));
+        }
+        runs += inputs.len();
+    }
+
+    start.elapsed() / u32::try_from(runs).unwrap()
+}
+
+fn main() {
+    let mut rng = thread_rng();
+
+    // 27 is the length of a usual set of LCU credentials
+    for len in [27, 256, 1924, 65536] {
+        let inputs: Vec<Vec<u8>> = (0..100)
+            .map(|_| {
+                let mut input = vec![0; len];
+                rng.fill(&mut input[..]);
+                input
+            })
+            .collect();
+
+        let irelia = bench(&inputs, |input| ENCODER.encode(input));
+        let base64 = bench(&inputs, |input| general_purpose::STANDARD.encode(input));
+
+        println!("{len:>6} bytes: irelia_encoder {irelia:>10.2?}, base64 {base64:>10.2?}");
+    }
+}
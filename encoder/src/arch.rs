@@ -0,0 +1,299 @@
+//! Encoding with the vector instructions of the CPU, used on stable when the `simd` feature is not enabled
+//!
+//! With the `std` feature, the instructions are picked at runtime, otherwise only the ones enabled at compile time are used.
+//! The unpacking is the same as in the `simd` module, but the translation looks the symbols up in the encode table,
+//! so this works with custom alphabets too.
+//!
+//! These only encode whole blocks, whatever is left over is encoded by the scalar implementation
+
+use core::mem::MaybeUninit;
+
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+/// Encodes as many whole blocks of `buf` into `out` as possible, returning how many bytes were read and written
+pub(crate) fn encode(
+    encode_table: &[u8; 64],
+    buf: &[u8],
+    out: &mut [MaybeUninit<u8>],
+) -> (usize, usize) {
+    // # SAFETY: The instructions are checked to be supported before they're used
+    unsafe {
+        if has_avx2() {
+            x86::encode_avx2(encode_table, buf, out)
+        } else if has_ssse3() {
+            x86::encode_ssse3(encode_table, buf, out)
+        } else {
+            (0, 0)
+        }
+    }
+}
+
+#[cfg(all(target_arch = "aarch64", target_feature = "neon"))]
+/// Encodes as many whole blocks of `buf` into `out` as possible, returning how many bytes were read and written
+pub(crate) fn encode(
+    encode_table: &[u8; 64],
+    buf: &[u8],
+    out: &mut [MaybeUninit<u8>],
+) -> (usize, usize) {
+    // # SAFETY: NEON is enabled at compile time
+    unsafe { neon::encode(encode_table, buf, out) }
+}
+
+#[cfg(not(any(
+    target_arch = "x86",
+    target_arch = "x86_64",
+    all(target_arch = "aarch64", target_feature = "neon")
+)))]
+/// There are no vector instructions for this target, so everything is left to the scalar implementation
+pub(crate) fn encode(
+    _encode_table: &[u8; 64],
+    _buf: &[u8],
+    _out: &mut [MaybeUninit<u8>],
+) -> (usize, usize) {
+    (0, 0)
+}
+
+#[cfg(all(
+    any(target_arch = "x86", target_arch = "x86_64"),
+    any(feature = "std", test)
+))]
+fn has_avx2() -> bool {
+    std::is_x86_feature_detected!("avx2")
+}
+
+#[cfg(all(
+    any(target_arch = "x86", target_arch = "x86_64"),
+    any(feature = "std", test)
+))]
+fn has_ssse3() -> bool {
+    std::is_x86_feature_detected!("ssse3")
+}
+
+#[cfg(all(
+    any(target_arch = "x86", target_arch = "x86_64"),
+    not(any(feature = "std", test))
+))]
+const fn has_avx2() -> bool {
+    cfg!(target_feature = "avx2")
+}
+
+#[cfg(all(
+    any(target_arch = "x86", target_arch = "x86_64"),
+    not(any(feature = "std", test))
+))]
+const fn has_ssse3() -> bool {
+    cfg!(target_feature = "ssse3")
+}
+
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+#[allow(clippy::cast_ptr_alignment, clippy::wildcard_imports)] // Only unaligned loads and stores are used
+mod x86 {
+    use core::mem::MaybeUninit;
+
+    #[cfg(target_arch = "x86")]
+    use core::arch::x86::*;
+    #[cfg(target_arch = "x86_64")]
+    use core::arch::x86_64::*;
+
+    /// Encodes 12 bytes at a time, 16 are loaded for each block, so the last 4 bytes of `buf` are never encoded here
+    #[target_feature(enable = "ssse3")]
+    pub(super) unsafe fn encode_ssse3(
+        encode_table: &[u8; 64],
+        buf: &[u8],
+        out: &mut [MaybeUninit<u8>],
+    ) -> (usize, usize) {
+        let tables = [
+            _mm_loadu_si128(encode_table.as_ptr().cast()),
+            _mm_loadu_si128(encode_table.as_ptr().add(16).cast()),
+            _mm_loadu_si128(encode_table.as_ptr().add(32).cast()),
+            _mm_loadu_si128(encode_table.as_ptr().add(48).cast()),
+        ];
+
+        let mut read = 0;
+        let mut written = 0;
+
+        while buf.len() - read >= 16 && out.len() - written >= 16 {
+            let input = _mm_loadu_si128(buf.as_ptr().add(read).cast());
+
+            let input = _mm_shuffle_epi8(
+                input,
+                _mm_setr_epi8(1, 0, 2, 1, 4, 3, 5, 4, 7, 6, 8, 7, 10, 9, 11, 10),
+            );
+            let t0 = _mm_and_si128(input, _mm_set1_epi32(0x0fc0_fc00));
+            let t1 = _mm_mulhi_epu16(t0, _mm_set1_epi32(0x0400_0040));
+            let t2 = _mm_and_si128(input, _mm_set1_epi32(0x003f_03f0));
+            let t3 = _mm_mullo_epi16(t2, _mm_set1_epi32(0x0100_0010));
+            let indices = _mm_or_si128(t1, t3);
+
+            // Each group of 16 symbols is looked up separately, and only kept for the indices in that group
+            let low = _mm_and_si128(indices, _mm_set1_epi8(0x0f));
+            let high = _mm_and_si128(_mm_srli_epi16(indices, 4), _mm_set1_epi8(0x0f));
+
+            let mut chars = _mm_setzero_si128();
+            for (group, table) in (0..).zip(tables) {
+                let in_group = _mm_cmpeq_epi8(high, _mm_set1_epi8(group));
+                chars = _mm_or_si128(chars, _mm_and_si128(in_group, _mm_shuffle_epi8(table, low)));
+            }
+
+            _mm_storeu_si128(out.as_mut_ptr().add(written).cast(), chars);
+
+            read += 12;
+            written += 16;
+        }
+
+        (read, written)
+    }
+
+    /// Encodes 24 bytes at a time, as two blocks of 12, one in each lane, then finishes with [`encode_ssse3`]
+    #[target_feature(enable = "avx2")]
+    pub(super) unsafe fn encode_avx2(
+        encode_table: &[u8; 64],
+        buf: &[u8],
+        out: &mut [MaybeUninit<u8>],
+    ) -> (usize, usize) {
+        // Shuffles only work within a lane, so both lanes need the whole table
+        let tables = [
+            _mm256_broadcastsi128_si256(_mm_loadu_si128(encode_table.as_ptr().cast())),
+            _mm256_broadcastsi128_si256(_mm_loadu_si128(encode_table.as_ptr().add(16).cast())),
+            _mm256_broadcastsi128_si256(_mm_loadu_si128(encode_table.as_ptr().add(32).cast())),
+            _mm256_broadcastsi128_si256(_mm_loadu_si128(encode_table.as_ptr().add(48).cast())),
+        ];
+
+        let mut read = 0;
+        let mut written = 0;
+
+        while buf.len() - read >= 28 && out.len() - written >= 32 {
+            let input = _mm256_set_m128i(
+                _mm_loadu_si128(buf.as_ptr().add(read + 12).cast()),
+                _mm_loadu_si128(buf.as_ptr().add(read).cast()),
+            );
+
+            let input = _mm256_shuffle_epi8(
+                input,
+                _mm256_setr_epi8(
+                    1, 0, 2, 1, 4, 3, 5, 4, 7, 6, 8, 7, 10, 9, 11, 10, 1, 0, 2, 1, 4, 3, 5, 4, 7,
+                    6, 8, 7, 10, 9, 11, 10,
+                ),
+            );
+            let t0 = _mm256_and_si256(input, _mm256_set1_epi32(0x0fc0_fc00));
+            let t1 = _mm256_mulhi_epu16(t0, _mm256_set1_epi32(0x0400_0040));
+            let t2 = _mm256_and_si256(input, _mm256_set1_epi32(0x003f_03f0));
+            let t3 = _mm256_mullo_epi16(t2, _mm256_set1_epi32(0x0100_0010));
+            let indices = _mm256_or_si256(t1, t3);
+
+            let low = _mm256_and_si256(indices, _mm256_set1_epi8(0x0f));
+            let high = _mm256_and_si256(_mm256_srli_epi16(indices, 4), _mm256_set1_epi8(0x0f));
+
+            let mut chars = _mm256_setzero_si256();
+            for (group, table) in (0..).zip(tables) {
+                let in_group = _mm256_cmpeq_epi8(high, _mm256_set1_epi8(group));
+                chars = _mm256_or_si256(
+                    chars,
+                    _mm256_and_si256(in_group, _mm256_shuffle_epi8(table, low)),
+                );
+            }
+
+            _mm256_storeu_si256(out.as_mut_ptr().add(written).cast(), chars);
+
+            read += 24;
+            written += 32;
+        }
+
+        let (rem_read, rem_written) = encode_ssse3(encode_table, &buf[read..], &mut out[written..]);
+
+        (read + rem_read, written + rem_written)
+    }
+}
+
+#[cfg(all(target_arch = "aarch64", target_feature = "neon"))]
+#[allow(clippy::wildcard_imports)]
+mod neon {
+    use core::arch::aarch64::*;
+    use core::mem::MaybeUninit;
+
+    /// Encodes 48 bytes at a time, which are loaded deinterleaved, so each register holds one byte of every group of 3
+    #[target_feature(enable = "neon")]
+    pub(super) unsafe fn encode(
+        encode_table: &[u8; 64],
+        buf: &[u8],
+        out: &mut [MaybeUninit<u8>],
+    ) -> (usize, usize) {
+        // The whole table fits in four registers, so the symbols can be looked up directly
+        let table = vld1q_u8_x4(encode_table.as_ptr());
+        let mask = vdupq_n_u8(0b0011_1111);
+
+        let mut read = 0;
+        let mut written = 0;
+
+        while buf.len() - read >= 48 && out.len() - written >= 64 {
+            let input = vld3q_u8(buf.as_ptr().add(read));
+
+            let indices = uint8x16x4_t(
+                vshrq_n_u8::<2>(input.0),
+                vandq_u8(
+                    vorrq_u8(vshlq_n_u8::<4>(input.0), vshrq_n_u8::<4>(input.1)),
+                    mask,
+                ),
+                vandq_u8(
+                    vorrq_u8(vshlq_n_u8::<2>(input.1), vshrq_n_u8::<6>(input.2)),
+                    mask,
+                ),
+                vandq_u8(input.2, mask),
+            );
+
+            let chars = uint8x16x4_t(
+                vqtbl4q_u8(table, indices.0),
+                vqtbl4q_u8(table, indices.1),
+                vqtbl4q_u8(table, indices.2),
+                vqtbl4q_u8(table, indices.3),
+            );
+
+            vst4q_u8(out.as_mut_ptr().add(written).cast(), chars);
+
+            read += 48;
+            written += 64;
+        }
+
+        (read, written)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{vec, vec::Vec};
+    use base64::{alphabet::Alphabet, engine::general_purpose, Engine};
+    use core::mem::MaybeUninit;
+    use rand::{thread_rng, Rng};
+
+    #[test]
+    fn matches_scalar() {
+        let mut alphabet = *b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+        let mut rng = thread_rng();
+
+        for _ in 0..8 {
+            for len in 0..200 {
+                let mut bytes = vec![0; len];
+                rng.fill(&mut bytes[..]);
+
+                let mut out = vec![MaybeUninit::new(0); len.div_ceil(3) * 4];
+                let (read, written) = super::encode(&alphabet, &bytes, &mut out);
+
+                assert_eq!(read % 3, 0);
+                assert_eq!(written, read / 3 * 4);
+
+                let alphabet = Alphabet::new(core::str::from_utf8(&alphabet).unwrap()).unwrap();
+                let engine =
+                    general_purpose::GeneralPurpose::new(&alphabet, general_purpose::NO_PAD);
+                let expected = engine.encode(&bytes[..read]);
+                let encoded: Vec<u8> = out[..written]
+                    .iter()
+                    .map(|byte| unsafe { byte.assume_init() })
+                    .collect();
+
+                assert_eq!(encoded, expected.as_bytes());
+            }
+
+            // The lookup has to work for any alphabet, not just the ones in order
+            alphabet.swap(rng.gen_range(0..64), rng.gen_range(0..64));
+        }
+    }
+}
@@ -11,7 +11,18 @@
 //!
 //! The usage of u64s as byte arrays is taken from the base64 crate, which is under the MIT license
 
+#[cfg(any(feature = "alloc", test))]
 extern crate alloc;
+#[cfg(any(feature = "std", test))]
+extern crate std;
+
+#[cfg(not(feature = "simd"))]
+mod arch;
+mod stream;
+
+pub use stream::EncoderState;
+#[cfg(feature = "std")]
+pub use stream::EncoderWriter;
 
 #[cfg(feature = "simd")]
 use core::{
@@ -20,11 +31,104 @@ use core::{
     slice,
 };
 
-use alloc::{string::String, vec};
+#[cfg(feature = "alloc")]
+use alloc::{string::String, vec, vec::Vec};
+use core::mem::MaybeUninit;
+
+/// Errors that can be returned by the checked encoding functions
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EncodeError {
+    /// The encoded output would be larger than the limit given, `required` is `None` if the length overflows a `usize`
+    OutputTooLarge {
+        required: Option<usize>,
+        max_output: usize,
+    },
+}
+
+impl core::fmt::Display for EncodeError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::OutputTooLarge {
+                required: Some(required),
+                max_output,
+            } => write!(
+                f,
+                "the encoded output would be {required} bytes, but the limit is {max_output} bytes"
+            ),
+            Self::OutputTooLarge {
+                required: None,
+                max_output,
+            } => write!(
+                f,
+                "the encoded output would overflow a usize, but the limit is {max_output} bytes"
+            ),
+        }
+    }
+}
+
+/// Errors that can be returned when decoding BASE64
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DecodeError {
+    /// The byte at `index` is not in the alphabet
+    InvalidByte { index: usize, byte: u8 },
+    /// The input is `len` bytes long, without padding, which can't be produced by encoding anything
+    InvalidLength { len: usize },
+    /// The padding is in the wrong place, or there is the wrong amount of it
+    InvalidPadding,
+    /// The last symbol, at `index`, has bits set that don't belong to any byte,
+    /// so it was not produced by encoding anything
+    InvalidLastSymbol { index: usize, byte: u8 },
+}
+
+impl core::fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::InvalidByte { index, byte } => {
+                write!(f, "invalid byte {byte:#04x} at index {index}")
+            }
+            Self::InvalidLength { len } => write!(f, "invalid input length {len}"),
+            Self::InvalidPadding => f.write_str("invalid padding"),
+            Self::InvalidLastSymbol { index, byte } => {
+                write!(f, "invalid last symbol {byte:#04x} at index {index}")
+            }
+        }
+    }
+}
+
+/// How padding is handled, set with [`Encoder::with_padding`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Padding {
+    /// Pads when encoding, and accepts BASE64 with or without padding when decoding
+    #[default]
+    Optional,
+    /// Pads when encoding, and rejects BASE64 without padding when decoding
+    Required,
+    /// Does not pad when encoding, and rejects BASE64 with padding when decoding
+    None,
+}
+
+impl Padding {
+    const fn pads(self) -> bool {
+        !matches!(self, Self::None)
+    }
+}
+
+/// The default alphabet, from RFC 4648
+pub const STANDARD_ALPHABET: [u8; 64] =
+    *b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// The URL and filename safe alphabet, from RFC 4648
+pub const URL_SAFE_ALPHABET: [u8; 64] =
+    *b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+
+/// Marks bytes that are not in the alphabet, in the decode table
+const INVALID: u8 = 0xFF;
 
 /// BASE64 encoder struct
 pub struct Encoder {
     encode_table: [u8; 64],
+    decode_table: [u8; 256],
+    padding: Padding,
 }
 
 impl Default for Encoder {
@@ -45,16 +149,29 @@ impl Encoder {
     #[must_use]
     pub const fn new() -> Self {
         Self {
-            encode_table: [
-                b'A', b'B', b'C', b'D', b'E', b'F', b'G', b'H', b'I', b'J', b'K', b'L', b'M', b'N',
-                b'O', b'P', b'Q', b'R', b'S', b'T', b'U', b'V', b'W', b'X', b'Y', b'Z', b'a', b'b',
-                b'c', b'd', b'e', b'f', b'g', b'h', b'i', b'j', b'k', b'l', b'm', b'n', b'o', b'p',
-                b'q', b'r', b's', b't', b'u', b'v', b'w', b'x', b'y', b'z', b'0', b'1', b'2', b'3',
-                b'4', b'5', b'6', b'7', b'8', b'9', b'+', b'/',
-            ],
+            encode_table: STANDARD_ALPHABET,
+            decode_table: decode_table(STANDARD_ALPHABET),
+            padding: Padding::Optional,
         }
     }
 
+    #[cfg(not(feature = "simd"))]
+    /// Creates a new instance of the encoder using the URL and filename safe alphabet,
+    /// which uses `-` and `_` instead of `+` and `/`
+    ///
+    /// # Examples
+    /// ```
+    /// use irelia_encoder::Encoder;
+    ///
+    /// const ENCODER: Encoder = Encoder::url_safe();
+    ///
+    /// assert_eq!(&ENCODER.encode_to_array::<4>(&[0xfb, 0xff]), b"-_8=");
+    /// ```
+    #[must_use]
+    pub const fn url_safe() -> Self {
+        Self::with_encode_table(URL_SAFE_ALPHABET)
+    }
+
     #[cfg(not(feature = "simd"))]
     /// Creates a new instance of the encoder using a specified alphabet
     ///
@@ -74,13 +191,200 @@ impl Encoder {
     /// ```
     #[must_use]
     pub const fn with_encode_table(encode_table: [u8; 64]) -> Self {
-        Self { encode_table }
+        Self {
+            encode_table,
+            decode_table: decode_table(encode_table),
+            padding: Padding::Optional,
+        }
+    }
+
+    /// Sets how padding is handled, by default, BASE64 is padded when encoding, and padding is optional when decoding
+    ///
+    /// # Examples
+    /// ```
+    /// use irelia_encoder::{Encoder, Padding};
+    ///
+    /// const ENCODER: Encoder = Encoder::new().with_padding(Padding::None);
+    ///
+    /// assert_eq!(&ENCODER.encode_to_array::<6>(b"riot"), b"cmlvdA");
+    /// ```
+    #[must_use]
+    pub const fn with_padding(mut self, padding: Padding) -> Self {
+        self.padding = padding;
+        self
+    }
+
+    #[must_use]
+    /// How padding is handled by this encoder
+    pub const fn padding(&self) -> Padding {
+        self.padding
+    }
+
+    /// The length of the BASE64 this encoder outputs for `input_len` bytes, padded unless [`Padding::None`] is set
+    const fn output_len(&self, input_len: usize) -> Option<usize> {
+        if self.padding.pads() {
+            Self::encoded_len(input_len)
+        } else {
+            // 1 byte encodes to 2 symbols, and 2 bytes to 3
+            let rem = input_len % 3;
+            match (input_len / 3).checked_mul(4) {
+                Some(len) if rem == 0 => Some(len),
+                Some(len) => len.checked_add(rem + 1),
+                None => None,
+            }
+        }
+    }
+
+    #[cfg(feature = "alloc")]
+    /// Encodes into a new buffer, only keeping the padding if `padding` is set
+    fn encode_to_vec(&self, buf: &[u8], padding: bool) -> Vec<u8> {
+        let mut out = vec![b'='; div_ceil(buf.len(), 3) * 4];
+        let written = {
+            // # SAFETY: `MaybeUninit<u8>` has the same layout as `u8`, and `encode_into` only ever writes initialized bytes
+            let out =
+                unsafe { &mut *(core::ptr::from_mut::<[u8]>(&mut out) as *mut [MaybeUninit<u8>]) };
+            self.encode_into(buf, out)
+        };
+
+        if !padding {
+            out.truncate(written);
+        }
+
+        out
     }
 
-    #[rustfmt::skip]
     #[doc(hidden)]
     /// Converts the buffer to base64, uses an out paramater to avoid allocations
     pub fn internal_encode(&self, buf: &[u8], out: &mut [u8]) {
+        // # SAFETY: `MaybeUninit<u8>` has the same layout as `u8`, and `encode_into` only ever writes initialized bytes
+        let out = unsafe { &mut *(core::ptr::from_mut::<[u8]>(out) as *mut [MaybeUninit<u8>]) };
+        self.encode_into(buf, out);
+    }
+
+    /// Converts the bytes to BASE64, writing into a buffer that does not need to be initialized first
+    ///
+    /// This skips filling the buffer before encoding, and writes the padding itself,
+    /// returning the initialized part of `out`, which is the padded length of the encoded bytes,
+    /// or the unpadded length with [`Padding::None`].
+    ///
+    /// # Examples
+    /// ```
+    /// use core::mem::MaybeUninit;
+    /// use irelia_encoder::Encoder;
+    /// const ENCODER: Encoder = Encoder::new();
+    ///
+    /// let mut out = [MaybeUninit::uninit(); 20];
+    /// let base64_encoded = ENCODER.encode_uninit("Hello, World!", &mut out);
+    ///
+    /// assert_eq!(base64_encoded, b"SGVsbG8sIFdvcmxkIQ==");
+    /// ```
+    ///
+    /// # Panics
+    /// This panics if `out` is shorter than the encoded length of `bytes`
+    pub fn encode_uninit<'a, T>(&self, bytes: T, out: &'a mut [MaybeUninit<u8>]) -> &'a mut [u8]
+    where
+        T: AsRef<[u8]>,
+    {
+        let buf = bytes.as_ref();
+        let len = self
+            .output_len(buf.len())
+            .expect("the encoded length fits in a usize, as it fits in `out`");
+
+        assert!(
+            out.len() >= len,
+            "the output buffer needs to be at least {len} bytes long"
+        );
+
+        let out = &mut out[..len];
+        let written = self.encode_into(buf, out);
+
+        for byte in &mut out[written..] {
+            byte.write(b'=');
+        }
+
+        // # SAFETY: Every byte up to `written` was set by `encode_into`, and the rest was set to padding above
+        unsafe { &mut *(core::ptr::from_mut::<[MaybeUninit<u8>]>(out) as *mut [u8]) }
+    }
+
+    /// Converts the buffer to base64, returning how many bytes of `out` were written
+    ///
+    /// Only initialized values are ever written to `out`
+    fn encode_into(&self, buf: &[u8], out: &mut [MaybeUninit<u8>]) -> usize {
+        // Without the `simd` feature, the vector instructions of the CPU are used for as much as possible first
+        #[cfg(not(feature = "simd"))]
+        let (read, written) = arch::encode(&self.encode_table, buf, out);
+        #[cfg(feature = "simd")]
+        let (read, written) = (0, 0);
+
+        written + self.encode_scalar(&buf[read..], &mut out[written..])
+    }
+
+    /// Converts the bytes to BASE64 in a const context, with padding, for inputs that are known at compile time
+    ///
+    /// `N` has to be the padded length of the BASE64, see [`Encoder::encoded_len`], or the unpadded length with [`Padding::None`]
+    ///
+    /// # Examples
+    /// ```
+    /// use irelia_encoder::Encoder;
+    /// const ENCODER: Encoder = Encoder::new();
+    ///
+    /// const AUTH: [u8; 12] = ENCODER.encode_to_array(b"riot:test");
+    ///
+    /// assert_eq!(&AUTH, b"cmlvdDp0ZXN0");
+    /// ```
+    ///
+    /// # Panics
+    /// This panics if `N` is not the padded length of the BASE64, which is a compile error in a const
+    #[must_use]
+    pub const fn encode_to_array<const N: usize>(&self, bytes: &[u8]) -> [u8; N] {
+        assert!(
+            matches!(self.output_len(bytes.len()), Some(len) if len == N),
+            "`N` needs to be the encoded length of the bytes"
+        );
+
+        let mut out = [b'='; N];
+        let mut read = 0;
+        let mut written = 0;
+
+        // Iterators can't be used in a const fn, so this is the scalar implementation with indices
+        while read + 3 <= bytes.len() {
+            let byte_array = u32::from_be_bytes([bytes[read], bytes[read + 1], bytes[read + 2], 0]);
+
+            out[written] = self.encode_table[(byte_array >> 26 & 0b0011_1111) as usize];
+            out[written + 1] = self.encode_table[(byte_array >> 20 & 0b0011_1111) as usize];
+            out[written + 2] = self.encode_table[(byte_array >> 14 & 0b0011_1111) as usize];
+            out[written + 3] = self.encode_table[(byte_array >> 8 & 0b0011_1111) as usize];
+
+            read += 3;
+            written += 4;
+        }
+
+        match bytes.len() - read {
+            1 => {
+                let byte = bytes[read];
+
+                out[written] = self.encode_table[(byte >> 2) as usize];
+                out[written + 1] = self.encode_table[((byte & 0b0000_0011) << 4) as usize];
+            }
+            2 => {
+                let byte_array = u16::from_be_bytes([bytes[read], bytes[read + 1]]);
+
+                out[written] = self.encode_table[(byte_array >> 10 & 0b0011_1111) as usize];
+                out[written + 1] = self.encode_table[(byte_array >> 4 & 0b0011_1111) as usize];
+                out[written + 2] = self.encode_table[(byte_array << 2 & 0b0011_1111) as usize];
+            }
+            _ => {}
+        }
+
+        out
+    }
+
+    #[rustfmt::skip]
+    #[allow(clippy::too_many_lines)]
+    /// Converts the buffer to base64 a block at a time, returning how many bytes of `out` were written
+    ///
+    /// Only initialized values are ever written to `out`
+    fn encode_scalar(&self, buf: &[u8], out: &mut [MaybeUninit<u8>]) -> usize {
         #[cfg(feature = "simd")]
         let chunks = buf.array_chunks::<12>();
         #[cfg(feature = "simd")]
@@ -103,7 +407,7 @@ impl Encoder {
         {
             chunks.zip(out_chunks).for_each(|(chunk, out)| {
                 #[cfg(not(feature = "nightly"))]
-                let out: &mut [u8; 32] = out.try_into().unwrap();
+                let out: &mut [MaybeUninit<u8>; 32] = out.try_into().unwrap();
 
                 let byte_array_1 = u64::from_be_bytes([
                     0, 0, chunk[0], chunk[1], chunk[2], chunk[3], chunk[4], chunk[5],
@@ -154,7 +458,7 @@ impl Encoder {
                     self.encode_table[(byte_array_4 >> 12 & 0b0011_1111) as usize],
                     self.encode_table[(byte_array_4 >> 6 & 0b0011_1111) as usize],
                     self.encode_table[(byte_array_4 & 0b0011_1111) as usize],
-                ];
+                ].map(MaybeUninit::new);
 
                 output_index += 32;
             });
@@ -172,7 +476,7 @@ impl Encoder {
 
             let chars = enc_translate(indices);
 
-            *out = chars.to_array();
+            *out = chars.to_array().map(MaybeUninit::new);
 
             output_index += 16;
         });
@@ -193,7 +497,7 @@ impl Encoder {
 
         chunks.zip(out_chunks).for_each(|(chunk, out)| {
             #[cfg(not(feature = "nightly"))]
-            let out: &mut [u8; 4] = out.try_into().unwrap();
+            let out: &mut [MaybeUninit<u8>; 4] = out.try_into().unwrap();
 
             let byte_array = u32::from_be_bytes([chunk[0], chunk[1], chunk[2], 0]);
             let bit_1 = byte_array >> 26 & 0b0011_1111;
@@ -206,7 +510,7 @@ impl Encoder {
                 self.encode_table[bit_2 as usize],
                 self.encode_table[bit_3 as usize],
                 self.encode_table[bit_4 as usize],
-            ];
+            ].map(MaybeUninit::new);
 
             output_index += 4;
         });
@@ -227,7 +531,7 @@ impl Encoder {
 
         chunks.zip(out_chunks).for_each(|(chunk, out)| {
             #[cfg(not(feature = "nightly"))]
-            let out: &mut [u8; 3] = out.try_into().unwrap();
+            let out: &mut [MaybeUninit<u8>; 3] = out.try_into().unwrap();
 
             let byte_array = u16::from_be_bytes([chunk[0], chunk[1]]);
             let bit_1 = byte_array >> 10 & 0b0011_1111;
@@ -238,7 +542,7 @@ impl Encoder {
                 self.encode_table[bit_1 as usize],
                 self.encode_table[bit_2 as usize],
                 self.encode_table[bit_3 as usize],
-            ];
+            ].map(MaybeUninit::new);
 
             output_index += 3;
         });
@@ -258,7 +562,7 @@ impl Encoder {
 
         chunks.zip(out_chunks).for_each(|(chunk, out)| {
             #[cfg(not(feature = "nightly"))]
-            let out: &mut [u8; 2] = out.try_into().unwrap();
+            let out: &mut [MaybeUninit<u8>; 2] = out.try_into().unwrap();
 
             let byte = chunk[0];
             let bit_1 = byte >> 2;
@@ -267,12 +571,15 @@ impl Encoder {
             *out = [
                 self.encode_table[bit_1 as usize],
                 self.encode_table[bit_2 as usize],
-            ];
+            ].map(MaybeUninit::new);
 
             output_index += 2;
         });
+
+        output_index
     }
 
+    #[cfg(feature = "alloc")]
     /// Converts the bytes to BASE64
     ///
     /// # Examples
@@ -290,13 +597,62 @@ impl Encoder {
     where
         T: AsRef<[u8]>,
     {
-        let buf = bytes.as_ref();
-        let mut out = vec![b'='; div_ceil(buf.len(), 3) * 4];
-        self.internal_encode(buf, &mut out);
+        let out = self.encode_to_vec(bytes.as_ref(), self.padding.pads());
 
         String::from_utf8(out).unwrap()
     }
 
+    /// Returns the padded length of the BASE64 for an input of `input_len` bytes,
+    /// or `None` if that length would overflow a `usize`
+    ///
+    /// # Examples
+    /// ```
+    /// use irelia_encoder::Encoder;
+    ///
+    /// assert_eq!(Encoder::encoded_len(13), Some(20));
+    /// assert_eq!(Encoder::encoded_len(usize::MAX), None);
+    /// ```
+    #[must_use]
+    pub const fn encoded_len(input_len: usize) -> Option<usize> {
+        div_ceil(input_len, 3).checked_mul(4)
+    }
+
+    #[cfg(feature = "alloc")]
+    /// Converts the bytes to BASE64, refusing to allocate more than `max_output` bytes
+    ///
+    /// The length is checked before anything is allocated, so this is safe to use on untrusted input
+    ///
+    /// # Examples
+    /// ```
+    /// use irelia_encoder::{EncodeError, Encoder};
+    /// const ENCODER: Encoder = Encoder::new();
+    ///
+    /// assert_eq!(ENCODER.encode_to_vec_capped(b"Hello, World!", 20).unwrap(), b"SGVsbG8sIFdvcmxkIQ==");
+    /// assert!(matches!(
+    ///     ENCODER.encode_to_vec_capped(b"Hello, World!", 16),
+    ///     Err(EncodeError::OutputTooLarge { required: Some(20), max_output: 16 })
+    /// ));
+    /// ```
+    ///
+    /// # Errors
+    /// This returns [`EncodeError::OutputTooLarge`] if the encoded length is greater than `max_output`
+    pub fn encode_to_vec_capped(
+        &self,
+        input: &[u8],
+        max_output: usize,
+    ) -> Result<Vec<u8>, EncodeError> {
+        let required = self.output_len(input.len());
+        required
+            .filter(|len| *len <= max_output)
+            .ok_or(EncodeError::OutputTooLarge {
+                required,
+                max_output,
+            })?;
+
+        Ok(self.encode_to_vec(input, self.padding.pads()))
+    }
+
+    #[cfg(feature = "alloc")]
     /// Converts the bytes to BASE64, and validates that the BASE64 is all ASCII
     ///
     /// # Examples
@@ -315,9 +671,7 @@ impl Encoder {
     where
         T: AsRef<[u8]>,
     {
-        let buf = bytes.as_ref();
-        let mut out = vec![b'='; div_ceil(buf.len(), 3) * 4];
-        self.internal_encode(buf, &mut out);
+        let out = self.encode_to_vec(bytes.as_ref(), self.padding.pads());
 
         assert!(is_ascii(&out));
 
@@ -326,6 +680,7 @@ impl Encoder {
         unsafe { String::from_utf8_unchecked(out) }
     }
 
+    #[cfg(feature = "alloc")]
     /// Converts the bytes to BASE64, but doesn't check if the output is valid UTF-8
     ///
     /// # Example:
@@ -345,13 +700,12 @@ impl Encoder {
     where
         T: AsRef<[u8]>,
     {
-        let buf = bytes.as_ref();
-        let mut out = vec![b'='; div_ceil(buf.len(), 3) * 4];
-        self.internal_encode(buf, &mut out);
+        let out = self.encode_to_vec(bytes.as_ref(), self.padding.pads());
 
         String::from_utf8_unchecked(out)
     }
 
+    #[cfg(feature = "alloc")]
     /// Converts the bytes to BASE64 without padding
     ///
     /// # Examples
@@ -369,13 +723,12 @@ impl Encoder {
     where
         T: AsRef<[u8]>,
     {
-        let buf = bytes.as_ref();
-        let mut out = vec![0; div_ceil(buf.len(), 3) * 4];
-        self.internal_encode(buf, &mut out);
+        let out = self.encode_to_vec(bytes.as_ref(), false);
 
         String::from_utf8(out).unwrap()
     }
 
+    #[cfg(feature = "alloc")]
     /// Converts the bytes to BASE64 without padding, but doesn't check if the output is valid UTF-8
     ///
     /// # Example:
@@ -395,12 +748,173 @@ impl Encoder {
     where
         T: AsRef<[u8]>,
     {
-        let buf = bytes.as_ref();
-        let mut out = vec![0; div_ceil(buf.len(), 3) * 4];
-        self.internal_encode(buf, &mut out);
+        let out = self.encode_to_vec(bytes.as_ref(), false);
 
         String::from_utf8_unchecked(out)
     }
+
+    /// Returns the most bytes that BASE64 of `input_len` bytes can decode to
+    ///
+    /// # Examples
+    /// ```
+    /// use irelia_encoder::Encoder;
+    ///
+    /// assert_eq!(Encoder::decoded_len_estimate(20), 15);
+    /// ```
+    #[must_use]
+    pub const fn decoded_len_estimate(input_len: usize) -> usize {
+        div_ceil(input_len, 4) * 3
+    }
+
+    #[doc(hidden)]
+    /// Converts the BASE64 back to bytes, uses an out paramater to avoid allocations, returning how many bytes were written
+    ///
+    /// # Panics
+    /// This panics if `out` is shorter than the decoded length of `buf`
+    pub fn internal_decode(&self, buf: &[u8], out: &mut [u8]) -> Result<usize, DecodeError> {
+        let padding = match buf {
+            [.., b'=', b'='] => 2,
+            [.., b'='] => 1,
+            _ => 0,
+        };
+
+        let data = &buf[..buf.len() - padding];
+
+        // When there is padding, it has to pad the input to a multiple of 4
+        let unpadded = data.len() % 4;
+        let valid_padding = match self.padding {
+            Padding::Optional => padding == 0 || unpadded == 4 - padding,
+            Padding::Required => unpadded == (4 - padding) % 4,
+            Padding::None => padding == 0,
+        };
+
+        if !valid_padding {
+            return Err(DecodeError::InvalidPadding);
+        }
+
+        if data.len() % 4 == 1 {
+            return Err(DecodeError::InvalidLength { len: data.len() });
+        }
+
+        let len = data.len() / 4 * 3 + (data.len() % 4).saturating_sub(1);
+        assert!(
+            out.len() >= len,
+            "the output buffer needs to be at least {len} bytes long"
+        );
+
+        let chunks = data.chunks_exact(4);
+        let rem = chunks.remainder();
+        let mut output_index = 0;
+
+        for (index, chunk) in chunks.enumerate() {
+            let index = index * 4;
+            let byte_array = self.decode_symbol(chunk, 0, index)? << 26
+                | self.decode_symbol(chunk, 1, index)? << 20
+                | self.decode_symbol(chunk, 2, index)? << 14
+                | self.decode_symbol(chunk, 3, index)? << 8;
+
+            let [byte_1, byte_2, byte_3, _] = byte_array.to_be_bytes();
+            out[output_index..output_index + 3].copy_from_slice(&[byte_1, byte_2, byte_3]);
+
+            output_index += 3;
+        }
+
+        let index = data.len() - rem.len();
+
+        // Whatever is left over has to be 2 or 3 symbols, and the bits past the last byte have to be 0
+        match rem.len() {
+            2 => {
+                let byte_array = self.decode_symbol(rem, 0, index)? << 26
+                    | self.decode_symbol(rem, 1, index)? << 20;
+
+                let [byte_1, last, ..] = byte_array.to_be_bytes();
+                if last != 0 {
+                    return Err(DecodeError::InvalidLastSymbol {
+                        index: index + 1,
+                        byte: rem[1],
+                    });
+                }
+
+                out[output_index] = byte_1;
+            }
+            3 => {
+                let byte_array = self.decode_symbol(rem, 0, index)? << 26
+                    | self.decode_symbol(rem, 1, index)? << 20
+                    | self.decode_symbol(rem, 2, index)? << 14;
+
+                let [byte_1, byte_2, last, _] = byte_array.to_be_bytes();
+                if last != 0 {
+                    return Err(DecodeError::InvalidLastSymbol {
+                        index: index + 2,
+                        byte: rem[2],
+                    });
+                }
+
+                out[output_index..output_index + 2].copy_from_slice(&[byte_1, byte_2]);
+            }
+            _ => {}
+        }
+
+        Ok(len)
+    }
+
+    /// Looks up the bits the symbol at `offset` in `chunk` stands for, `index` being where the chunk starts in the input
+    #[inline]
+    fn decode_symbol(&self, chunk: &[u8], offset: usize, index: usize) -> Result<u32, DecodeError> {
+        let byte = chunk[offset];
+        match self.decode_table[byte as usize] {
+            // Padding can only be at the end, which was already stripped
+            INVALID if byte == b'=' => Err(DecodeError::InvalidPadding),
+            INVALID => Err(DecodeError::InvalidByte {
+                index: index + offset,
+                byte,
+            }),
+            bits => Ok(u32::from(bits)),
+        }
+    }
+
+    #[cfg(feature = "alloc")]
+    /// Converts the BASE64 back to bytes, padding is optional by default, but has to be correct if present, see [`Encoder::with_padding`]
+    ///
+    /// # Examples
+    /// ```
+    /// use irelia_encoder::{DecodeError, Encoder};
+    /// const ENCODER: Encoder = Encoder::new();
+    ///
+    /// assert_eq!(ENCODER.decode("SGVsbG8sIFdvcmxkIQ==").unwrap(), b"Hello, World!");
+    /// assert_eq!(ENCODER.decode("SGVsbG8sIFdvcmxkIQ").unwrap(), b"Hello, World!");
+    /// assert_eq!(ENCODER.decode("SGVsbG8sIFdvcmxkIQ="), Err(DecodeError::InvalidPadding));
+    /// ```
+    ///
+    /// # Errors
+    /// This returns a [`DecodeError`] if the input is not valid BASE64 in this alphabet
+    pub fn decode<T>(&self, input: T) -> Result<Vec<u8>, DecodeError>
+    where
+        T: AsRef<[u8]>,
+    {
+        let buf = input.as_ref();
+        let mut out = vec![0; Self::decoded_len_estimate(buf.len())];
+        let len = self.internal_decode(buf, &mut out)?;
+        out.truncate(len);
+
+        Ok(out)
+    }
+}
+
+/// Maps every byte of the alphabet back to the bits it stands for, and every other byte to [`INVALID`]
+const fn decode_table(encode_table: [u8; 64]) -> [u8; 256] {
+    let mut decode_table = [INVALID; 256];
+    let mut index = 0;
+
+    while index < encode_table.len() {
+        #[allow(clippy::cast_possible_truncation)]
+        {
+            decode_table[encode_table[index] as usize] = index as u8;
+        }
+        index += 1;
+    }
+
+    decode_table
 }
 
 // Original Github: https://github.com/lemire/fastbase64/tree/master
@@ -493,7 +1007,7 @@ mod simd {
     }
 }
 
-#[cfg(feature = "simd")]
+#[cfg(all(feature = "simd", feature = "alloc"))]
 fn is_ascii(buffer: &[u8]) -> bool {
     let mut mask: Simd<i8, 16> = Simd::splat(0);
     let spans = buffer.array_chunks::<256>();
@@ -539,7 +1053,7 @@ fn is_ascii(buffer: &[u8]) -> bool {
     true
 }
 
-#[cfg(not(feature = "simd"))]
+#[cfg(all(not(feature = "simd"), feature = "alloc"))]
 fn is_ascii(buffer: &[u8]) -> bool {
     #[cfg(feature = "nightly")]
     let spans = buffer.array_chunks::<256>();
@@ -653,7 +1167,7 @@ where
     idxs
 }
 
-#[cfg(all(test, not(feature = "nightly")))]
+#[cfg(all(test, feature = "alloc", not(feature = "nightly")))]
 #[test]
 fn b64_validity_check() {
     use base64::{engine::general_purpose, Engine};
@@ -671,6 +1185,176 @@ fn b64_validity_check() {
     }
 }
 
+#[cfg(all(test, feature = "alloc"))]
+#[test]
+fn b64_uninit_check() {
+    use rand::distributions::{Alphanumeric, DistString};
+    use rand::thread_rng;
+
+    let encoder = Encoder::new();
+    let mut rng = thread_rng();
+
+    for len in 0..256 {
+        let string = Alphanumeric.sample_string(&mut rng, len);
+        let mut out = vec![MaybeUninit::uninit(); div_ceil(len, 3) * 4];
+
+        assert_eq!(
+            encoder.encode_uninit(&string, &mut out),
+            encoder.encode(&string).as_bytes()
+        );
+    }
+}
+
+#[cfg(all(test, feature = "alloc"))]
+#[test]
+fn b64_capped_check() {
+    let encoder = Encoder::new();
+
+    assert_eq!(encoder.encode_to_vec_capped(b"", 0), Ok(vec![]));
+    assert_eq!(
+        encoder.encode_to_vec_capped(b"riot", 8),
+        Ok(b"cmlvdA==".to_vec())
+    );
+    assert_eq!(
+        encoder.encode_to_vec_capped(b"riot", 7),
+        Err(EncodeError::OutputTooLarge {
+            required: Some(8),
+            max_output: 7
+        })
+    );
+}
+
+#[cfg(all(test, feature = "alloc"))]
+#[test]
+fn b64_array_check() {
+    const ENCODER: Encoder = Encoder::new();
+    const AUTH: [u8; 36] = ENCODER.encode_to_array(b"riot:Vp3JX6XlEOjzFZsVyGY8NQ");
+
+    assert_eq!(
+        &AUTH,
+        ENCODER.encode("riot:Vp3JX6XlEOjzFZsVyGY8NQ").as_bytes()
+    );
+
+    assert_eq!(&ENCODER.encode_to_array::<0>(b""), b"");
+    assert_eq!(&ENCODER.encode_to_array::<4>(b"r"), b"cg==");
+    assert_eq!(&ENCODER.encode_to_array::<4>(b"ri"), b"cmk=");
+    assert_eq!(&ENCODER.encode_to_array::<4>(b"rio"), b"cmlv");
+    assert_eq!(&ENCODER.encode_to_array::<8>(b"riot"), b"cmlvdA==");
+}
+
+#[cfg(test)]
+#[test]
+#[should_panic = "`N` needs to be the encoded length of the bytes"]
+fn b64_array_length_check() {
+    let _: [u8; 4] = Encoder::new().encode_to_array(b"riot");
+}
+
+#[cfg(all(test, feature = "alloc"))]
+#[test]
+fn b64_decode_check() {
+    use rand::{thread_rng, Rng};
+
+    let encoder = Encoder::new();
+    let mut rng = thread_rng();
+
+    for len in 0..256 {
+        let mut bytes = vec![0; len];
+        rng.fill(&mut bytes[..]);
+
+        let base64 = encoder.encode(&bytes);
+        assert_eq!(encoder.decode(&base64), Ok(bytes.clone()));
+        assert_eq!(encoder.decode(base64.trim_end_matches('=')), Ok(bytes));
+    }
+}
+
+#[cfg(all(test, feature = "alloc"))]
+#[test]
+fn b64_decode_errors() {
+    let encoder = Encoder::new();
+
+    assert_eq!(encoder.decode("cmlvdDp0ZXN0"), Ok(b"riot:test".to_vec()));
+    assert_eq!(
+        encoder.decode("cmlv!Dp0"),
+        Err(DecodeError::InvalidByte {
+            index: 4,
+            byte: b'!'
+        })
+    );
+    assert_eq!(
+        encoder.decode("cmlvd"),
+        Err(DecodeError::InvalidLength { len: 5 })
+    );
+    assert_eq!(encoder.decode("cm=v"), Err(DecodeError::InvalidPadding));
+    assert_eq!(encoder.decode("cmlvdA="), Err(DecodeError::InvalidPadding));
+    assert_eq!(encoder.decode("cmlv==="), Err(DecodeError::InvalidPadding));
+    assert_eq!(
+        encoder.decode("cmlvdA==="),
+        Err(DecodeError::InvalidPadding)
+    );
+    assert_eq!(
+        encoder.decode("cmlvdB=="),
+        Err(DecodeError::InvalidLastSymbol {
+            index: 5,
+            byte: b'B'
+        })
+    );
+}
+
+#[cfg(all(test, feature = "alloc", not(feature = "simd")))]
+#[test]
+fn b64_url_safe_check() {
+    use base64::{engine::general_purpose, Engine};
+    use rand::{thread_rng, Rng};
+
+    let padded = Encoder::url_safe();
+    let unpadded = Encoder::url_safe().with_padding(Padding::None);
+    let mut rng = thread_rng();
+
+    for len in 0..256 {
+        let mut bytes = vec![0; len];
+        rng.fill(&mut bytes[..]);
+
+        let base64 = general_purpose::URL_SAFE.encode(&bytes);
+        assert_eq!(padded.encode(&bytes), base64);
+        assert_eq!(padded.decode(&base64), Ok(bytes.clone()));
+
+        let base64 = general_purpose::URL_SAFE_NO_PAD.encode(&bytes);
+        assert_eq!(unpadded.encode(&bytes), base64);
+        assert_eq!(padded.encode_without_padding(&bytes), base64);
+        assert_eq!(unpadded.decode(&base64), Ok(bytes));
+    }
+}
+
+#[cfg(all(test, feature = "alloc"))]
+#[test]
+fn b64_padding_check() {
+    let optional = Encoder::new();
+    let required = Encoder::new().with_padding(Padding::Required);
+    let none = Encoder::new().with_padding(Padding::None);
+
+    assert_eq!(optional.encode("riot"), "cmlvdA==");
+    assert_eq!(required.encode("riot"), "cmlvdA==");
+    assert_eq!(none.encode("riot"), "cmlvdA");
+    assert_eq!(
+        none.encode_to_vec_capped(b"riot", 6),
+        Ok(b"cmlvdA".to_vec())
+    );
+
+    let mut out = [MaybeUninit::uninit(); 8];
+    assert_eq!(none.encode_uninit("riot", &mut out), b"cmlvdA");
+    assert_eq!(&none.encode_to_array::<6>(b"riot"), b"cmlvdA");
+
+    for encoder in [&optional, &required] {
+        assert_eq!(encoder.decode("cmlvdA=="), Ok(b"riot".to_vec()));
+        assert_eq!(encoder.decode("cmlvdA="), Err(DecodeError::InvalidPadding));
+    }
+    assert_eq!(optional.decode("cmlvdA"), Ok(b"riot".to_vec()));
+    assert_eq!(required.decode("cmlvdA"), Err(DecodeError::InvalidPadding));
+    assert_eq!(none.decode("cmlvdA"), Ok(b"riot".to_vec()));
+    assert_eq!(none.decode("cmlvdA=="), Err(DecodeError::InvalidPadding));
+    assert_eq!(required.decode("cmlv"), Ok(b"rio".to_vec()));
+}
+
 #[cfg(all(test, feature = "nightly"))]
 /*
 These are the current benchmark results running on a Ryzen 9 7900x
@@ -0,0 +1,291 @@
+//! Encoding BASE64 a piece at a time, for inputs that are too large to have in memory at once
+
+use core::fmt;
+use core::mem::MaybeUninit;
+
+use crate::Encoder;
+
+/// How many bytes are encoded per write, this is a multiple of 3 so only the last write is ever padded
+const CHUNK_LEN: usize = 768;
+
+/// Incremental encoder, which encodes the bytes it's given as they come in, passing the BASE64 to a sink
+///
+/// Bytes that don't fill a block of 3 are kept until the next update, so the output is the same
+/// as encoding the whole input at once, no matter how it's split up
+///
+/// If a write fails, the BASE64 that was passed to the sink is incomplete, and the state should not be used anymore
+///
+/// # Examples
+/// ```
+/// use irelia_encoder::{Encoder, EncoderState};
+/// const ENCODER: Encoder = Encoder::new();
+///
+/// let mut out = String::new();
+/// let mut state = EncoderState::new(&ENCODER);
+///
+/// state.update_fmt(b"Hello, ", &mut out).unwrap();
+/// state.update_fmt(b"World!", &mut out).unwrap();
+/// state.finish_fmt(&mut out).unwrap();
+///
+/// assert_eq!(out, "SGVsbG8sIFdvcmxkIQ==");
+/// ```
+pub struct EncoderState<'a> {
+    encoder: &'a Encoder,
+    pending: [u8; 3],
+    pending_len: usize,
+}
+
+impl<'a> EncoderState<'a> {
+    /// Creates a new state, which encodes with `encoder`
+    #[must_use]
+    pub const fn new(encoder: &'a Encoder) -> Self {
+        Self {
+            encoder,
+            pending: [0; 3],
+            pending_len: 0,
+        }
+    }
+
+    /// Encodes `bytes`, passing the BASE64 to `write` in chunks of at most 1024 bytes
+    ///
+    /// # Errors
+    /// This returns the first error returned by `write`
+    pub fn update<E>(
+        &mut self,
+        mut bytes: &[u8],
+        mut write: impl FnMut(&[u8]) -> Result<(), E>,
+    ) -> Result<(), E> {
+        let mut out = [MaybeUninit::uninit(); CHUNK_LEN / 3 * 4];
+
+        // The block left over from the last update has to be finished first
+        if self.pending_len > 0 {
+            let needed = (3 - self.pending_len).min(bytes.len());
+            self.pending[self.pending_len..self.pending_len + needed]
+                .copy_from_slice(&bytes[..needed]);
+            self.pending_len += needed;
+            bytes = &bytes[needed..];
+
+            if self.pending_len < 3 {
+                return Ok(());
+            }
+
+            self.pending_len = 0;
+            write(self.encoder.encode_uninit(self.pending, &mut out))?;
+        }
+
+        let (blocks, rem) = bytes.split_at(bytes.len() / 3 * 3);
+
+        for chunk in blocks.chunks(CHUNK_LEN) {
+            write(self.encoder.encode_uninit(chunk, &mut out))?;
+        }
+
+        self.pending[..rem.len()].copy_from_slice(rem);
+        self.pending_len = rem.len();
+
+        Ok(())
+    }
+
+    /// Encodes whatever is left over, passing it to `write` if there was anything
+    ///
+    /// This is padded, unless the encoder is set to [`Padding::None`](crate::Padding::None)
+    ///
+    /// # Errors
+    /// This returns the error returned by `write`
+    pub fn finish<E>(self, write: impl FnOnce(&[u8]) -> Result<(), E>) -> Result<(), E> {
+        let padding = self.encoder.padding.pads();
+        self.finish_inner(padding, write)
+    }
+
+    /// Encodes whatever is left over, without padding, passing it to `write` if there was anything
+    ///
+    /// # Errors
+    /// This returns the error returned by `write`
+    pub fn finish_without_padding<E>(
+        self,
+        write: impl FnOnce(&[u8]) -> Result<(), E>,
+    ) -> Result<(), E> {
+        self.finish_inner(false, write)
+    }
+
+    fn finish_inner<E>(
+        self,
+        padding: bool,
+        write: impl FnOnce(&[u8]) -> Result<(), E>,
+    ) -> Result<(), E> {
+        if self.pending_len == 0 {
+            return Ok(());
+        }
+
+        let mut out = [MaybeUninit::uninit(); 4];
+        let encoded = self
+            .encoder
+            .encode_uninit(&self.pending[..self.pending_len], &mut out);
+
+        if padding {
+            write(encoded)
+        } else {
+            // 1 byte encodes to 2 symbols, and 2 bytes to 3
+            write(&encoded[..=self.pending_len])
+        }
+    }
+
+    /// Encodes `bytes`, writing the BASE64 to `out`
+    ///
+    /// # Errors
+    /// This returns an error if writing to `out` fails, or the alphabet is not valid UTF-8
+    pub fn update_fmt(&mut self, bytes: &[u8], out: &mut impl fmt::Write) -> fmt::Result {
+        self.update(bytes, |chunk| write_str(out, chunk))
+    }
+
+    /// Encodes whatever is left over, writing it to `out`, see [`EncoderState::finish`]
+    ///
+    /// # Errors
+    /// This returns an error if writing to `out` fails, or the alphabet is not valid UTF-8
+    pub fn finish_fmt(self, out: &mut impl fmt::Write) -> fmt::Result {
+        self.finish(|chunk| write_str(out, chunk))
+    }
+}
+
+fn write_str(out: &mut impl fmt::Write, chunk: &[u8]) -> fmt::Result {
+    out.write_str(core::str::from_utf8(chunk).map_err(|_| fmt::Error)?)
+}
+
+#[cfg(feature = "std")]
+/// Writer that encodes everything written to it, and writes the BASE64 to the writer it wraps
+///
+/// [`EncoderWriter::finish`] has to be called once everything was written, or the last block is lost
+///
+/// # Examples
+/// ```
+/// use irelia_encoder::{Encoder, EncoderWriter};
+/// use std::io::Write;
+/// const ENCODER: Encoder = Encoder::new();
+///
+/// let mut writer = EncoderWriter::new(&ENCODER, Vec::new());
+///
+/// writer.write_all(b"Hello, World!").unwrap();
+///
+/// assert_eq!(writer.finish().unwrap(), b"SGVsbG8sIFdvcmxkIQ==");
+/// ```
+pub struct EncoderWriter<'a, W: std::io::Write> {
+    state: EncoderState<'a>,
+    inner: W,
+}
+
+#[cfg(feature = "std")]
+impl<'a, W: std::io::Write> EncoderWriter<'a, W> {
+    /// Creates a writer that encodes with `encoder`, and writes the BASE64 to `inner`
+    pub const fn new(encoder: &'a Encoder, inner: W) -> Self {
+        Self {
+            state: EncoderState::new(encoder),
+            inner,
+        }
+    }
+
+    /// Writes whatever is left over, and returns the inner writer, see [`EncoderState::finish`]
+    ///
+    /// # Errors
+    /// This returns an error if writing to the inner writer fails
+    pub fn finish(mut self) -> std::io::Result<W> {
+        let inner = &mut self.inner;
+        self.state.finish(|chunk| inner.write_all(chunk))?;
+
+        Ok(self.inner)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<W: std::io::Write> std::io::Write for EncoderWriter<'_, W> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        let inner = &mut self.inner;
+        self.state.update(buf, |chunk| inner.write_all(chunk))?;
+
+        Ok(buf.len())
+    }
+
+    /// Flushes the inner writer, the last block is only written once it's full, or by [`EncoderWriter::finish`]
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::{string::String, vec, vec::Vec};
+    use base64::{engine::general_purpose, Engine};
+    use rand::{thread_rng, Rng};
+
+    use super::EncoderState;
+    use crate::Encoder;
+
+    const ENCODER: Encoder = Encoder::new();
+
+    #[test]
+    fn split_updates() {
+        let mut rng = thread_rng();
+
+        for len in [0, 1, 2, 3, 100, 767, 768, 769, 5000] {
+            let mut bytes = vec![0; len];
+            rng.fill(&mut bytes[..]);
+
+            let mut out = Vec::new();
+            let mut state = EncoderState::new(&ENCODER);
+
+            let mut rest = &bytes[..];
+            while !rest.is_empty() {
+                let (chunk, next) = rest.split_at(rng.gen_range(1..=rest.len().min(1000)));
+                state
+                    .update(chunk, |encoded| {
+                        out.extend_from_slice(encoded);
+                        Ok::<_, ()>(())
+                    })
+                    .unwrap();
+                rest = next;
+            }
+
+            state
+                .finish_without_padding(|encoded| {
+                    out.extend_from_slice(encoded);
+                    Ok::<_, ()>(())
+                })
+                .unwrap();
+
+            assert_eq!(
+                out,
+                general_purpose::STANDARD_NO_PAD.encode(&bytes).as_bytes()
+            );
+        }
+    }
+
+    #[test]
+    fn fmt_sink() {
+        let mut out = String::new();
+        let mut state = EncoderState::new(&ENCODER);
+
+        for byte in b"riot:test" {
+            state.update_fmt(&[*byte], &mut out).unwrap();
+        }
+        state.finish_fmt(&mut out).unwrap();
+
+        assert_eq!(out, "cmlvdDp0ZXN0");
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn io_sink() {
+        use super::EncoderWriter;
+        use std::io::Write;
+
+        let bytes = [7; 10_000];
+        let mut writer = EncoderWriter::new(&ENCODER, Vec::new());
+
+        for chunk in bytes.chunks(333) {
+            writer.write_all(chunk).unwrap();
+        }
+
+        assert_eq!(
+            writer.finish().unwrap(),
+            general_purpose::STANDARD.encode(bytes).as_bytes()
+        );
+    }
+}
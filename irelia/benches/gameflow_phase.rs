@@ -0,0 +1,35 @@
+//! Compares reading the gameflow phase straight from the response against deserializing it with serde,
+//! run with `cargo bench -p irelia --bench gameflow_phase`
+
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use irelia::rest::gameflow::GameflowPhase;
+
+/// Runs `f` on every input until a second has passed, returning the average time per input
+fn bench(inputs: &[Vec<u8>], mut f: impl FnMut(&[u8]) -> GameflowPhase) -> Duration {
+    let start = Instant::now();
+    let mut runs = 0;
+
+    while start.elapsed() < Duration::from_secs(1) {
+        for input in inputs {
+            black_box(f(black_box(input)));
+        }
+        runs += inputs.len();
+    }
+
+    start.elapsed() / u32::try_from(runs).unwrap()
+}
+
+fn main() {
+    // The shortest, the most common, and the longest names, plus one this version doesn't know about
+    for name in ["None", "ChampSelect", "TerminatedInError", "SomethingNew"] {
+        // This is exactly what the LCU responds with, a msgpack string
+        let inputs = vec![rmp_serde::to_vec(name).unwrap(); 100];
+
+        let fast = bench(&inputs, |input| GameflowPhase::from_msgpack(input).unwrap());
+        let serde = bench(&inputs, |input| rmp_serde::from_slice(input).unwrap());
+
+        println!("{name:>17}: from_msgpack {fast:>10.2?}, serde {serde:>10.2?}");
+    }
+}
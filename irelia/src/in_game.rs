@@ -2,8 +2,10 @@
 //!
 //! All types are all generated from the official JSON snippets
 
+mod stream;
 /// Types returned by the in game API
 pub mod types;
+mod wait;
 
 use self::types::{
     Abilities, ActivePlayer, AllGameData, AllPlayer, Events, GameData, Item, Runes, Scores,
@@ -16,6 +18,9 @@ use hyper::Response;
 use std::future::Future;
 use std::net::{Ipv4Addr, SocketAddrV4};
 
+pub use stream::GameEventStream;
+pub use wait::wait_for_game;
+
 /// The only url the in game API can be used on
 pub const URL: SocketAddrV4 = SocketAddrV4::new(Ipv4Addr::LOCALHOST, 2999);
 
@@ -193,6 +198,7 @@ pub trait GameClient: GameClientInternal {
 
 mod sealed {
     use super::URL;
+    use crate::utils::requests::encode_query_value;
     use crate::{Error, RequestClient};
     use serde::de::DeserializeOwned;
     use std::future::Future;
@@ -208,17 +214,14 @@ mod sealed {
             async move {
                 use hyper::body::Buf;
 
-                let endpoint = riot_id.map_or_else(
-                    || format!("/liveclientdata/{endpoint}"),
-                    |riot_id| format!("/liveclientdata/{endpoint}?riotId={riot_id}"),
-                );
+                let endpoint = live_client_endpoint(endpoint, riot_id);
 
                 let buf = self
                     .request_client()
                     .request_template(URL, &endpoint, "GET", None::<()>, None)
                     .await?;
 
-                Ok(rmp_serde::from_read(buf.aggregate().reader())?)
+                Ok(rmp_serde::from_read(buf.reader())?)
             }
         }
 
@@ -245,11 +248,24 @@ mod sealed {
                     .request_template(URL, endpoint, method, body, None)
                     .await?;
 
-                Ok(rmp_serde::from_read(buffer.aggregate().reader())?)
+                Ok(rmp_serde::from_read(buffer.reader())?)
             }
         }
     }
 
+    /// Riot IDs always contain a `#`, and often spaces, so they have to be encoded to be sent as a query
+    pub(super) fn live_client_endpoint(endpoint: &str, riot_id: Option<&str>) -> String {
+        riot_id.map_or_else(
+            || format!("/liveclientdata/{endpoint}"),
+            |riot_id| {
+                format!(
+                    "/liveclientdata/{endpoint}?riotId={}",
+                    encode_query_value(riot_id)
+                )
+            },
+        )
+    }
+
     impl GameClientInternal for RequestClient {
         fn request_client(&self) -> &RequestClient {
             self
@@ -258,3 +274,20 @@ mod sealed {
 }
 
 impl GameClient for RequestClient {}
+
+#[cfg(test)]
+mod tests {
+    use super::sealed::live_client_endpoint;
+
+    #[test]
+    fn test_live_client_endpoint() {
+        assert_eq!(
+            live_client_endpoint("activeplayer", None),
+            "/liveclientdata/activeplayer"
+        );
+        assert_eq!(
+            live_client_endpoint("playerscores", Some("Hide on bush#KR1")),
+            "/liveclientdata/playerscores?riotId=Hide%20on%20bush%23KR1"
+        );
+    }
+}
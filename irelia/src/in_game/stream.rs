@@ -0,0 +1,181 @@
+use std::collections::VecDeque;
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use std::time::Duration;
+
+use futures_core::Stream;
+
+use super::types::{Event, Events};
+use super::GameClient;
+use crate::utils::runtime::Interval;
+use crate::{Error, RequestClient};
+
+type EventsFuture = Pin<Box<dyn Future<Output = Result<Events, Error>> + Send>>;
+
+/// Stream that polls the in game API for events, yielding each event once, as it happens
+///
+/// Only events after the last one yielded are requested, using `eventID`, so each poll stays small
+/// even late into a game. The first poll happens immediately, and yields every event that already happened.
+///
+/// Errors are yielded without ending the stream, such as while the game is still loading,
+/// or after it closed. Event IDs start over in every game, so a new stream should be created for each game,
+/// or [`GameEventStream::reset`] called once the previous game ended
+///
+/// ```no_run
+/// use futures_util::StreamExt;
+/// use irelia::in_game::{types::EventDetails, GameEventStream};
+/// use irelia::RequestClient;
+/// use std::time::Duration;
+///
+/// # async fn watch() {
+/// let mut events = GameEventStream::new(RequestClient::new(), Duration::from_secs(1));
+///
+/// while let Some(event) = events.next().await {
+///     match event.map(|event| event.details().clone()) {
+///         Ok(EventDetails::DragonKill { dragon_type, .. }) => println!("{dragon_type:?} dragon killed"),
+///         Ok(EventDetails::GameEnd { .. }) => break,
+///         Ok(_) => {}
+///         Err(err) => eprintln!("{err}"),
+///     }
+/// }
+/// # }
+/// ```
+pub struct GameEventStream {
+    client: RequestClient,
+    interval: Interval,
+    last_id: Option<i64>,
+    pending: VecDeque<Event>,
+    request: Option<EventsFuture>,
+}
+
+impl GameEventStream {
+    #[must_use]
+    /// Creates a stream that polls for new events every `interval`
+    ///
+    /// # Panics
+    /// With `runtime-tokio`, this panics if `interval` is zero, or if it's not called from within a tokio runtime with the timer enabled
+    pub fn new(client: RequestClient, interval: Duration) -> Self {
+        Self {
+            client,
+            interval: Interval::new(interval),
+            last_id: None,
+            pending: VecDeque::new(),
+            request: None,
+        }
+    }
+
+    #[must_use]
+    /// The ID of the last event yielded, or queued to be yielded
+    pub const fn last_event_id(&self) -> Option<i64> {
+        self.last_id
+    }
+
+    /// Forgets which events were already seen, so the next poll yields every event in the game again
+    pub fn reset(&mut self) {
+        self.last_id = None;
+        self.pending.clear();
+        self.request = None;
+    }
+
+    /// Queues the events that are newer than the last one seen
+    fn push_new(&mut self, events: Events) {
+        for event in events {
+            if self
+                .last_id
+                .is_some_and(|last_id| event.event_id() <= last_id)
+            {
+                continue;
+            }
+
+            self.last_id = Some(event.event_id());
+            self.pending.push_back(event);
+        }
+    }
+
+    fn request(&self) -> EventsFuture {
+        let client = self.client.clone();
+        // IDs past `i32::MAX` can't be asked for, so every event is requested, and the old ones filtered out
+        let next_id = self
+            .last_id
+            .and_then(|last_id| i32::try_from(last_id + 1).ok());
+
+        Box::pin(async move { client.event_data(next_id).await })
+    }
+}
+
+impl Stream for GameEventStream {
+    type Item = Result<Event, Error>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        loop {
+            if let Some(event) = self.pending.pop_front() {
+                return Poll::Ready(Some(Ok(event)));
+            }
+
+            if self.request.is_none() {
+                if self.interval.poll_tick(cx).is_pending() {
+                    return Poll::Pending;
+                }
+
+                self.request = Some(self.request());
+            }
+
+            let Some(request) = self.request.as_mut() else {
+                continue;
+            };
+
+            let Poll::Ready(result) = request.as_mut().poll(cx) else {
+                return Poll::Pending;
+            };
+
+            self.request = None;
+
+            match result {
+                Ok(events) => self.push_new(events),
+                Err(err) => return Poll::Ready(Some(Err(err))),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::GameEventStream;
+    use crate::in_game::types::{Event, EventDetails, Events};
+    use crate::RequestClient;
+    use serde_json::json;
+    use std::time::Duration;
+
+    fn events(ids: &[i64]) -> Events {
+        let events: Vec<_> = ids
+            .iter()
+            .map(|id| json!({"EventID": id, "EventName": "MinionsSpawning", "EventTime": 65.0}))
+            .collect();
+
+        serde_json::from_value(json!({ "Events": events })).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_push_new() {
+        let mut stream = GameEventStream::new(RequestClient::new(), Duration::from_secs(1));
+
+        stream.push_new(events(&[0, 1]));
+        assert_eq!(stream.last_event_id(), Some(1));
+
+        // The API returns events from the ID asked for, anything already seen is skipped
+        stream.push_new(events(&[1, 2]));
+        assert_eq!(stream.last_event_id(), Some(2));
+
+        let ids: Vec<_> = stream.pending.iter().map(Event::event_id).collect();
+        assert_eq!(ids, [0, 1, 2]);
+        assert!(stream
+            .pending
+            .iter()
+            .all(|event| *event.details() == EventDetails::MinionsSpawning));
+
+        stream.reset();
+        assert_eq!(stream.last_event_id(), None);
+        assert!(stream.pending.is_empty());
+    }
+}
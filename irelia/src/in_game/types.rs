@@ -571,6 +571,7 @@ pub struct AllPlayer {
 /// enum for player position such as `Top`, `Mid`, `Support`, `None`, and `Unknown` if the case was added after the type was written
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "UPPERCASE")]
+#[non_exhaustive]
 pub enum Position {
     Top,
     Jungle,
@@ -834,6 +835,11 @@ pub struct Events {
 }
 
 impl Events {
+    #[must_use]
+    /// Every event, in the order they happened
+    pub fn events(&self) -> &[Event] {
+        &self.events
+    }
     /// Number of dragons killed in the game
     #[must_use]
     pub fn dragons_killed(&self) -> u8 {
@@ -883,6 +889,7 @@ pub struct Event {
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all_fields = "PascalCase")]
 #[serde(tag = "EventName")]
+#[non_exhaustive]
 pub enum EventDetails {
     GameStart,
     MinionsSpawning,
@@ -936,6 +943,7 @@ pub enum EventDetails {
 
 /// Type of the dragon killed by an event, either Fire, Earth, Water, Elder, etc.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[non_exhaustive]
 pub enum DragonType {
     Fire,
     Earth,
@@ -1093,7 +1101,8 @@ impl Structure {
     ///
     /// In Nexus Blitz, Turrets are either inner or outer
     ///
-    /// Returns `None` if the map has no structures
+    /// Returns `None` if the map has no structures, or if the map or the place of the turret
+    /// is newer than this library, such as Twisted Treeline coming back
     pub fn place_determined(&self, map: &MapName) -> Option<StructurePlace> {
         if self.is_inhibitor() {
             return Some(StructurePlace::Inner);
@@ -1107,7 +1116,8 @@ impl Structure {
                     (1, false) => StructurePlace::Inner,
                     (5, true) => StructurePlace::TopNexus,
                     (4, true) => StructurePlace::BotNexus,
-                    _ => unreachable!("Side lanes have three turrets, while mid has five"),
+                    // Side lanes have three turrets, while mid has five
+                    _ => return None,
                 }
                 .into()
             }
@@ -1116,22 +1126,22 @@ impl Structure {
                 2 | 7 => StructurePlace::Inner,
                 3 | 10 => StructurePlace::TopNexus,
                 4 | 9 => StructurePlace::BotNexus,
-                _ => unreachable!("At the time of writing, aram has 4 towers on each side"),
+                // At the time of writing, aram has 4 towers on each side
+                _ => return None,
             }
             .into(),
             MapName::NexusBlitz => match self.place {
                 1 | 2 => StructurePlace::Inner,
                 3 | 4 => StructurePlace::Outer,
-                _ => unreachable!("Nexus Blitz only has four turrets"),
+                // Nexus Blitz only has four turrets
+                _ => return None,
             }
             .into(),
-            MapName::Arena | MapName::TFT | MapName::Swarm => None,
-            MapName::TwistedTreeline => {
-                unimplemented!("This game mode does not exist but let me hope")
-            }
-            MapName::Other(other) => unimplemented!(
-                "Map {other} is new and unsupported, report this on github and it will be fixed"
-            ),
+            MapName::Arena
+            | MapName::TFT
+            | MapName::Swarm
+            | MapName::TwistedTreeline
+            | MapName::Other(_) => None,
         }
     }
 
@@ -1147,28 +1157,28 @@ impl<'de> serde::Deserialize<'de> for Structure {
     where
         D: Deserializer<'de>,
     {
-        fn determine_structure_team(team: &str) -> TeamID {
+        fn determine_structure_team(team: &str) -> Option<TeamID> {
             match team {
-                "T1" | "T100" => TeamID::Order,
-                "T2" | "T200" => TeamID::Chaos,
-                team => unreachable!("Expected T1 | T100 or T2 | T200, found: {:?}", team),
+                "T1" | "T100" => Some(TeamID::Order),
+                "T2" | "T200" => Some(TeamID::Chaos),
+                _ => None,
             }
         }
 
-        fn determine_structure_lane(lane: &str) -> Lane {
+        fn determine_structure_lane(lane: &str) -> Option<Lane> {
             match lane {
-                "L" | "L2" => Lane::Top,
-                "C" | "L1" => Lane::Mid,
-                "R" | "L0" => Lane::Bot,
-                unrecognized => unreachable!("{}", unrecognized),
+                "L" | "L2" => Some(Lane::Top),
+                "C" | "L1" => Some(Lane::Mid),
+                "R" | "L0" => Some(Lane::Bot),
+                _ => None,
             }
         }
 
-        fn determine_structure_type(ty: &str) -> StructureType {
+        fn determine_structure_type(ty: &str) -> Option<StructureType> {
             match ty {
-                "Turret" => StructureType::Turret,
-                "Barracks" | "Inhib" => StructureType::Barracks,
-                unrecognized => unreachable!("{}", unrecognized),
+                "Turret" => Some(StructureType::Turret),
+                "Barracks" | "Inhib" => Some(StructureType::Barracks),
+                _ => None,
             }
         }
 
@@ -1185,38 +1195,44 @@ impl<'de> serde::Deserialize<'de> for Structure {
             where
                 E: Error,
             {
+                let invalid = || E::invalid_value(Unexpected::Str(v), &self);
+
                 let mut split = v.split('_');
 
                 let structure_type = split
                     .next()
-                    .expect("The first string in the split is the structure name");
-
-                let team = split
+                    .and_then(determine_structure_type)
+                    .ok_or_else(invalid)?;
+                let team_id = split
                     .next()
-                    .expect("The second string in the split is the team");
-
+                    .and_then(determine_structure_team)
+                    .ok_or_else(invalid)?;
                 let lane = split
                     .next()
-                    .expect("The third string in the split is the lane");
-
-                let structure_type = determine_structure_type(structure_type);
-                // This is always a single byte
-                let lane = determine_structure_lane(lane);
-                let team_id = determine_structure_team(team);
+                    .map(|lane| match structure_type {
+                        // Inhibitors are numbered after the lane, such as `Barracks_T1_C1`
+                        StructureType::Barracks => {
+                            lane.trim_end_matches(|c: char| c.is_ascii_digit())
+                        }
+                        StructureType::Turret => lane,
+                    })
+                    .and_then(determine_structure_lane)
+                    .ok_or_else(invalid)?;
 
                 let place = if structure_type == StructureType::Turret {
-                    let place = &split
+                    // The place is zero padded, such as `03`
+                    split
                         .next()
-                        .expect("The fourth string in the split is the place")[1..];
-
-                    place.parse().expect("This is always a number")
+                        .and_then(|place| place.parse().ok())
+                        .ok_or_else(invalid)?
                 } else {
                     // This is so we can grab the remainder if it exists
                     let _ = split.next();
                     1
                 };
 
-                let remainder = split.next().map(|inner| inner.parse().unwrap());
+                // Newer turret names end in a letter, such as `Turret_T2_R_03_A`, which isn't a remainder
+                let remainder = split.next().and_then(|inner| inner.parse().ok());
 
                 Ok(Structure {
                     structure_type,
@@ -1263,6 +1279,7 @@ impl SerializeTrait for Structure {
 
 /// Whether the structure is a turret or an inhibitor
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
 pub enum StructureType {
     Turret,
     Barracks,
@@ -1270,6 +1287,7 @@ pub enum StructureType {
 
 /// If it's a top, mid, or bottom, or nexus turret
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
 pub enum Lane {
     Top,
     Mid,
@@ -1278,6 +1296,7 @@ pub enum Lane {
 
 /// Whether the turret is outer, middle, inner, or upper or lower nexus
 #[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
 pub enum StructurePlace {
     Outer,
     Middle,
@@ -1336,6 +1355,19 @@ impl Event {
     pub const fn event_time(&self) -> Duration {
         self.event_time
     }
+    #[must_use]
+    pub const fn details(&self) -> &EventDetails {
+        &self.event_details
+    }
+}
+
+impl IntoIterator for Events {
+    type Item = Event;
+    type IntoIter = std::vec::IntoIter<Event>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.events.into_vec().into_iter()
+    }
 }
 
 /// Contains basic game data, such as mode, time, name, number, and terrain
@@ -1353,6 +1385,7 @@ pub struct GameData {
 /// Current game mode, game modes which are dead at the time of writing, or were added after, would fall under "Other"
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "UPPERCASE")]
+#[non_exhaustive]
 pub enum GameMode {
     #[serde(rename = "CLASSIC")]
     SummonersRift,
@@ -1388,6 +1421,7 @@ pub enum GameMode {
 /// Map name, such as Tutorial, Summoners Rift, etc. This is translated manually from "Map3", "Map10", etc.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "PascalCase")]
+#[non_exhaustive]
 pub enum MapName {
     #[serde(rename = "Map3")]
     TutorialMap,
@@ -1414,6 +1448,7 @@ pub enum MapName {
 
 /// Map terrain, should be equivalent to the currently available dragon soul
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[non_exhaustive]
 pub enum MapTerrain {
     Default,
     Infernal,
@@ -1422,6 +1457,8 @@ pub enum MapTerrain {
     Cloud,
     Hextech,
     Chemtech,
+    #[serde(untagged)]
+    Unknown(Box<str>),
 }
 
 impl GameData {
@@ -1450,6 +1487,7 @@ impl GameData {
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "UPPERCASE")]
 /// Enum representation of different team IDs
+#[non_exhaustive]
 pub enum TeamID {
     All,
     Order,
@@ -1462,6 +1500,7 @@ pub enum TeamID {
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "UPPERCASE")]
 /// Ability Resource
+#[non_exhaustive]
 pub enum AbilityResource {
     Mana,
     Energy,
@@ -1607,7 +1646,47 @@ pub(crate) mod duration {
 
 #[cfg(test)]
 mod tests {
-    use crate::in_game::types::Events;
+    use crate::in_game::types::{
+        Events, GameData, GameMode, Lane, MapName, MapTerrain, Structure, StructurePlace,
+        StructureType, TeamID,
+    };
+    use serde_json::json;
+
+    #[test]
+    fn unknown_deserialize() {
+        // Fields and variants added in a later patch shouldn't stop the rest from deserializing
+        let game_data: GameData = serde_json::from_value(json!({
+            "gameMode": "BRAWL",
+            "gameTime": 12.5,
+            "mapName": "Map35",
+            "mapNumber": 35,
+            "mapTerrain": "Volcanic",
+            "newField": true,
+        }))
+        .unwrap();
+
+        assert_eq!(game_data.game_mode(), &GameMode::Other("BRAWL".into()));
+        assert_eq!(game_data.map_name(), &MapName::Other("Map35".into()));
+        assert_eq!(
+            game_data.map_terrain(),
+            &MapTerrain::Unknown("Volcanic".into())
+        );
+
+        let turret: Structure = serde_json::from_value(json!("Turret_T2_R_03_A")).unwrap();
+        assert_eq!(turret.team_id(), &TeamID::Chaos);
+        assert_eq!(turret.lane(), &Lane::Bot);
+        assert_eq!(
+            turret.place_determined(&MapName::SummonersRift),
+            Some(StructurePlace::Outer)
+        );
+        assert_eq!(turret.place_determined(game_data.map_name()), None);
+
+        let inhib: Structure = serde_json::from_value(json!("Barracks_T1_C1")).unwrap();
+        assert_eq!(inhib.structure_type(), &StructureType::Barracks);
+        assert_eq!(inhib.lane(), &Lane::Mid);
+
+        assert!(serde_json::from_value::<Structure>(json!("Shrine_T1_C_01")).is_err());
+    }
 
     #[test]
     fn event_deserialize() {
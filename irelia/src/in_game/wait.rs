@@ -0,0 +1,93 @@
+use std::time::Duration;
+
+use super::GameClient;
+use crate::process_info::ProcessInfoProvider;
+use crate::utils::runtime;
+use crate::{Error, RequestClient};
+
+/// Waits for a game to start, and for the in game API to become available, returning `client` once it is
+///
+/// The game process is looked for first, as the API can't be reached until it exists.
+/// Once it's running, the API is polled until it responds, as it refuses connections, or returns 404,
+/// for as long as the loading screen is up. Both are checked every `poll_interval`
+///
+/// Finding the process is done on the calling task, this is usually quick, but it does block
+///
+/// ```no_run
+/// use irelia::in_game::{wait_for_game, GameClient};
+/// use irelia::RequestClient;
+/// use std::time::Duration;
+///
+/// # async fn wait() -> Result<(), irelia::Error> {
+/// let client = wait_for_game(
+///     RequestClient::new(),
+///     Duration::from_secs(600),
+///     Duration::from_secs(1),
+/// )
+/// .await?;
+///
+/// println!("{:?}", client.game_stats().await?.game_mode());
+/// # Ok(())
+/// # }
+/// ```
+///
+/// # Errors
+/// This will return [`Error::Timeout`] if the API did not become available within `timeout`
+pub async fn wait_for_game(
+    client: RequestClient,
+    timeout: Duration,
+    poll_interval: Duration,
+) -> Result<RequestClient, Error> {
+    let mut provider = ProcessInfoProvider::new();
+
+    let wait = async {
+        loop {
+            if provider.is_game_running() && client.game_stats().await.is_ok() {
+                break;
+            }
+
+            runtime::sleep(poll_interval).await;
+        }
+    };
+
+    match runtime::timeout(timeout, wait).await {
+        Some(()) => Ok(client),
+        None => Err(Error::Timeout),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::wait_for_game;
+    use crate::{Error, RequestClient};
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn test_wait_for_game_timeout() {
+        let result = wait_for_game(
+            RequestClient::new(),
+            Duration::from_millis(50),
+            Duration::from_millis(10),
+        )
+        .await;
+
+        assert!(matches!(result, Err(Error::Timeout)));
+    }
+
+    #[ignore = "This is only needed for testing, and doesn't need to be run all the time"]
+    #[tokio::test]
+    async fn test_wait_for_game() {
+        let client = wait_for_game(
+            RequestClient::new(),
+            Duration::from_secs(600),
+            Duration::from_secs(1),
+        )
+        .await
+        .unwrap();
+
+        println!(
+            "{:?}",
+            crate::in_game::GameClient::game_stats(&client).await
+        );
+    }
+}
@@ -9,19 +9,40 @@
 //! - `rest`: Allows connections to the LCU `rest` API, providing basic get/post functionality
 //! - `ws`: Allows connections to the LCU websocket API, providing all functionality needed
 //! - `replay`: Allows connections to the `replay` API, also enables the in game API
+//! - `rofl`: Adds a parser for `.rofl` replay files, this needs nothing else
+//! - `spectator`: Allows downloading the chunks of games in progress from the spectator servers, also enables the in game API
+//! - `macros`: Adds `#[derive(Endpoint)]`, for declaring `rest` endpoints as types
+//! - `blocking`: Adds `rest::blocking::LcuClient`, for using the `rest` API without an async runtime
+//! - `mock`: Adds `mock::MockLcu`, an in-process fake of the LCU for tests, over whichever TLS backend is enabled
+//! - `proxy`: Adds `rest::proxy::LcuProxy`, a local HTTP server forwarding allowed requests to the LCU, with the auth header filled in
+//! - `notify`: Watches the lock file in `process_info::ClientWatcher::from_lock_file` for changes, instead of only polling it
+//! - `http2`: Lets the `rest` and `in_game` clients use HTTP/2 when the server offers it through ALPN, only with `rustls`
+//! - `windows-native`: Finds the client through WMI on Windows, instead of listing every process with `sysinfo`
+//! - `runtime-tokio`, `runtime-async-std`, `runtime-smol`: Picks the async runtime the `rest` and `in_game` clients run on, tokio is the default
+//! - `tracing`: Emits `tracing` spans and events for finding the client, every request, websocket subscriptions, and reconnects, the auth token is never recorded
+
+// Lets the derive macros refer to `::irelia` from inside this crate
+#[cfg(feature = "macros")]
+extern crate self as irelia;
 
 #[cfg(feature = "in_game")]
 pub mod in_game;
+#[cfg(any(feature = "mock", all(test, feature = "rest", feature = "ws")))]
+pub mod mock;
 #[cfg(feature = "replay")]
 pub mod replay;
 #[cfg(feature = "rest")]
 pub mod rest;
+#[cfg(feature = "rofl")]
+pub mod rofl;
+#[cfg(feature = "spectator")]
+pub mod spectator;
 #[cfg(any(feature = "rest", feature = "ws", feature = "in_game"))]
 pub(crate) mod tls;
 pub(crate) mod utils;
 #[cfg(feature = "ws")]
 pub mod ws;
-#[cfg(any(feature = "ws", feature = "rest"))]
+#[cfg(any(feature = "ws", feature = "rest", feature = "in_game"))]
 pub use utils::process_info;
 
 #[cfg(any(feature = "rest", feature = "in_game"))]
@@ -30,6 +51,15 @@ pub use error::Error;
 #[cfg(any(feature = "rest", feature = "in_game"))]
 pub use utils::requests::RequestClient;
 
+#[cfg(feature = "rest")]
+pub use utils::requests::{RecordFormat, RecordedRequest};
+
+#[cfg(feature = "rest")]
+pub use error::LcuError;
+
+#[cfg(any(feature = "rest", feature = "in_game"))]
+pub use tls::peer_certificate_fingerprint;
+
 #[cfg(any(feature = "rest", feature = "in_game"))]
 mod error {
     /// Errors that can be produced by the LCU API
@@ -45,13 +75,82 @@ mod error {
         HyperError(hyper::Error),
         /// Error with the request, contains a status code
         RequestError(hyper::StatusCode),
+        /// Error with the request, for responses where the LCU explained what went wrong (only possible with the `rest` feature enabled)
+        #[cfg(feature = "rest")]
+        LcuError(LcuError),
         /// Encode error
         RmpSerdeEncode(rmp_serde::encode::Error),
         /// Decode error
         RmpSerdeDecode(rmp_serde::decode::Error),
+        /// JSON decode error, for the endpoints that only speak JSON
+        #[cfg(any(feature = "rest", feature = "spectator"))]
+        SerdeJsonDecode(serde_json::Error),
+        /// IO error, from connections made outside of hyper
+        Io(std::io::Error),
         /// Error getting process info (only possible with the `rest` feature enabled)
         #[cfg(feature = "rest")]
         ProcessInfoError(crate::process_info::Error),
+        /// What was asked for can't be done in the client's current state, such as a champ select action
+        /// when it's not the player's turn (only possible with the `rest` feature enabled)
+        #[cfg(feature = "rest")]
+        Unavailable(String),
+        /// The operation did not complete before its timeout elapsed
+        Timeout,
+        /// The websocket connection was closed while it was still needed (only possible with the `ws` feature enabled)
+        #[cfg(feature = "ws")]
+        WebSocketClosed,
+    }
+
+    impl Error {
+        #[must_use]
+        /// Returns the status code the request failed with, if it reached the server
+        pub fn status(&self) -> Option<hyper::StatusCode> {
+            match self {
+                Self::RequestError(status) => Some(*status),
+                #[cfg(feature = "rest")]
+                Self::LcuError(err) => hyper::StatusCode::from_u16(err.http_status).ok(),
+                _ => None,
+            }
+        }
+    }
+
+    #[cfg(feature = "rest")]
+    #[derive(Debug, Clone, PartialEq, serde_derive::Serialize, serde_derive::Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    /// The body the LCU sends with a failed request
+    ///
+    /// ```json
+    /// {"errorCode": "RPC_ERROR", "httpStatus": 404, "message": "No active delegate", "implementationDetails": {}}
+    /// ```
+    pub struct LcuError {
+        /// Such as `RPC_ERROR` or `BAD_REQUEST`
+        pub error_code: String,
+        pub http_status: u16,
+        #[serde(default)]
+        pub message: String,
+        #[serde(default)]
+        pub implementation_details: serde_json::Value,
+    }
+
+    #[cfg(feature = "rest")]
+    impl LcuError {
+        /// Reads the error from a failed response's body, which can be either msgpack or JSON
+        pub(crate) fn from_body(body: &[u8]) -> Option<Self> {
+            rmp_serde::from_slice(body)
+                .ok()
+                .or_else(|| serde_json::from_slice(body).ok())
+        }
+    }
+
+    #[cfg(feature = "rest")]
+    impl std::fmt::Display for LcuError {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(
+                f,
+                "{} ({}): {}",
+                self.error_code, self.http_status, self.message
+            )
+        }
     }
 
     impl From<hyper::http::Error> for Error {
@@ -84,6 +183,19 @@ mod error {
         }
     }
 
+    #[cfg(any(feature = "rest", feature = "spectator"))]
+    impl From<serde_json::Error> for Error {
+        fn from(value: serde_json::Error) -> Self {
+            Self::SerdeJsonDecode(value)
+        }
+    }
+
+    impl From<std::io::Error> for Error {
+        fn from(value: std::io::Error) -> Self {
+            Self::Io(value)
+        }
+    }
+
     #[cfg(feature = "rest")]
     impl From<crate::process_info::Error> for Error {
         fn from(value: crate::process_info::Error) -> Self {
@@ -99,9 +211,19 @@ mod error {
                 Self::HyperClientError(err) => err.fmt(f),
                 Self::RequestError(code) => f.write_str(code.as_str()),
                 #[cfg(feature = "rest")]
+                Self::LcuError(err) => err.fmt(f),
+                #[cfg(feature = "rest")]
                 Self::ProcessInfoError(err) => f.write_str(err.reason()),
                 Self::RmpSerdeEncode(err) => err.fmt(f),
                 Self::RmpSerdeDecode(err) => err.fmt(f),
+                #[cfg(any(feature = "rest", feature = "spectator"))]
+                Self::SerdeJsonDecode(err) => err.fmt(f),
+                Self::Io(err) => err.fmt(f),
+                #[cfg(feature = "rest")]
+                Self::Unavailable(reason) => f.write_str(reason),
+                Self::Timeout => f.write_str("The operation timed out"),
+                #[cfg(feature = "ws")]
+                Self::WebSocketClosed => f.write_str("The websocket connection was closed"),
             }
         }
     }
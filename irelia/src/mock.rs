@@ -0,0 +1,897 @@
+//! An in-process stand-in for the LCU, for testing code built on irelia without a running client
+//!
+//! [`MockLcu`] serves HTTPS and WSS on a local port, checking the same basic auth the LCU does.
+//! Responses are registered per route, and websocket events are sent on demand,
+//! to every connection that subscribed to them
+//!
+//! ```
+//! use irelia::mock::MockLcu;
+//! use serde_json::{json, Value};
+//!
+//! # #[tokio::main(flavor = "current_thread")]
+//! # async fn main() {
+//! let mock = MockLcu::start().unwrap();
+//! mock.on_get("/lol-summoner/v1/current-summoner")
+//!     .respond_json(json!({"summonerId": 1, "puuid": "abc"}));
+//!
+//! let client = mock.client();
+//! let summoner: Value = client.get("/lol-summoner/v1/current-summoner").await.unwrap();
+//! assert_eq!(summoner["puuid"], "abc");
+//! # }
+//! ```
+
+use crate::rest::LcuClient;
+use crate::utils::process_info::encode_credentials;
+use crate::ws::types::EventKind;
+use crate::ws::{ErrorHandler, LcuWebSocket, WebSocketError};
+use crate::RequestClient;
+use hyper::http::HeaderValue;
+use hyper::StatusCode;
+use serde::Serialize;
+use serde_json::{json, Value};
+use std::collections::{HashMap, HashSet};
+use std::io::{self, ErrorKind, Read, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::mpsc::{self, Sender};
+use std::sync::{Arc, Mutex, PoisonError};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+use tungstenite::client::IntoClientRequest;
+use tungstenite::handshake::{derive_accept_key, HandshakeError};
+use tungstenite::protocol::Role;
+use tungstenite::{Message, WebSocket};
+
+/// A self-signed certificate for `127.0.0.1` and `localhost`, the LCU's own is self-signed too
+const CERTIFICATE: &[u8] = include_bytes!("mock/cert.pem");
+const PRIVATE_KEY: &[u8] = include_bytes!("mock/key.pem");
+
+/// The token the mock expects, encoded into the auth header the same way the LCU's is
+pub const MOCK_TOKEN: &str = "irelia-mock";
+
+/// How long blocked reads wait before checking whether the mock was dropped
+const POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+/// A fake LCU, serving registered responses until it's dropped
+pub struct MockLcu {
+    addr: SocketAddr,
+    auth_header: HeaderValue,
+    state: Arc<State>,
+    handle: Option<JoinHandle<()>>,
+}
+
+/// A request the mock received, see [`MockLcu::requests`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct MockRequest {
+    /// Such as `GET`
+    pub method: String,
+    /// The path, including the query
+    pub path: String,
+    /// The decoded body, `Null` if there was none
+    pub body: Value,
+}
+
+/// A route being registered, the route only exists once a response is given
+#[must_use = "The route is only registered once a response is given"]
+pub struct MockRoute<'a> {
+    state: &'a State,
+    method: String,
+    path: String,
+    delay: Duration,
+}
+
+#[derive(Clone)]
+struct MockResponse {
+    status: StatusCode,
+    body: Option<Value>,
+    delay: Duration,
+}
+
+#[derive(Default)]
+struct State {
+    routes: Mutex<HashMap<(String, String), MockResponse>>,
+    requests: Mutex<Vec<MockRequest>>,
+    sockets: Mutex<Vec<Socket>>,
+    in_flight: AtomicUsize,
+    peak_in_flight: AtomicUsize,
+    shutdown: AtomicBool,
+}
+
+/// A websocket connection, and the events it's subscribed to
+struct Socket {
+    subscriptions: Arc<Mutex<HashSet<String>>>,
+    sender: Sender<String>,
+}
+
+struct HttpRequest {
+    method: String,
+    path: String,
+    /// Lowercase names, with their values
+    headers: Vec<(String, String)>,
+    body: Vec<u8>,
+}
+
+impl MockLcu {
+    /// Starts serving on a random local port
+    ///
+    /// # Errors
+    /// This will return an error if the port cannot be bound
+    pub fn start() -> io::Result<Self> {
+        let acceptor = tls::acceptor()?;
+
+        let listener = TcpListener::bind("127.0.0.1:0")?;
+        // The accept loop checks for shutdown in between connections
+        listener.set_nonblocking(true)?;
+        let addr = listener.local_addr()?;
+
+        let (_, auth_header) =
+            encode_credentials(&addr.port().to_string(), MOCK_TOKEN, HeaderValue::from_str)
+                .map_err(io::Error::other)?;
+        let auth_header = auth_header.map_err(io::Error::other)?;
+
+        let state = Arc::new(State::default());
+
+        let handle = {
+            let state = state.clone();
+            let auth_header = auth_header.clone();
+            thread::spawn(move || accept_loop(&listener, &acceptor, &state, &auth_header))
+        };
+
+        Ok(Self {
+            addr,
+            auth_header,
+            state,
+            handle: Some(handle),
+        })
+    }
+
+    #[must_use]
+    /// The address the mock is serving on
+    pub const fn addr(&self) -> SocketAddr {
+        self.addr
+    }
+
+    #[must_use]
+    /// The auth header requests have to send, for [`MOCK_TOKEN`]
+    pub const fn auth_header(&self) -> &HeaderValue {
+        &self.auth_header
+    }
+
+    #[must_use]
+    /// A client connected to the mock, which accepts its self-signed certificate
+    pub fn client(&self) -> LcuClient {
+        LcuClient::new_with_credentials_with_request_client(
+            self.addr,
+            self.auth_header.clone(),
+            &RequestClient::danger_accept_invalid_certs(),
+        )
+    }
+
+    /// Opens a websocket connection to the mock, `on_connect` is called on the stream as it would be for the LCU
+    ///
+    /// There is no way to reconnect, so asking to reconnect will pass a `ConnectionClosed` error to the handler
+    ///
+    /// # Errors
+    /// This will return an error if the connection or the upgrade fails, or `on_connect` does
+    pub fn websocket(
+        &self,
+        error_handler: impl ErrorHandler + 'static,
+    ) -> Result<LcuWebSocket, WebSocketError> {
+        let mut error_handler = error_handler;
+
+        let mut request = format!("wss://{}", self.addr).into_client_request()?;
+        request
+            .headers_mut()
+            .insert("Authorization", self.auth_header.clone());
+
+        let (mut stream, _) = tungstenite::client_tls_with_config(
+            request,
+            TcpStream::connect(self.addr)?,
+            None,
+            Some(crate::tls::accept_any_cert_connector().map_err(tungstenite::Error::Tls)?),
+        )
+        .map_err(|err| match err {
+            HandshakeError::Failure(err) => WebSocketError::from(err),
+            HandshakeError::Interrupted(_) => unreachable!("The TCP stream is still blocking here"),
+        })?;
+
+        error_handler.on_connect(&mut stream)?;
+
+        Ok(LcuWebSocket::from_stream(stream, error_handler))
+    }
+
+    /// Registers a response for `method` requests to `path`, replacing any response it already had
+    ///
+    /// A `path` with a query only matches that exact query, otherwise it matches any query
+    pub fn on(&self, method: &str, path: &str) -> MockRoute<'_> {
+        MockRoute {
+            state: &self.state,
+            method: method.to_ascii_uppercase(),
+            path: path.to_owned(),
+            delay: Duration::ZERO,
+        }
+    }
+
+    /// See [`MockLcu::on`]
+    pub fn on_get(&self, path: &str) -> MockRoute<'_> {
+        self.on("GET", path)
+    }
+
+    /// See [`MockLcu::on`]
+    pub fn on_post(&self, path: &str) -> MockRoute<'_> {
+        self.on("POST", path)
+    }
+
+    /// See [`MockLcu::on`]
+    pub fn on_put(&self, path: &str) -> MockRoute<'_> {
+        self.on("PUT", path)
+    }
+
+    /// See [`MockLcu::on`]
+    pub fn on_patch(&self, path: &str) -> MockRoute<'_> {
+        self.on("PATCH", path)
+    }
+
+    /// See [`MockLcu::on`]
+    pub fn on_delete(&self, path: &str) -> MockRoute<'_> {
+        self.on("DELETE", path)
+    }
+
+    #[must_use]
+    /// Every authorized request received so far, in order
+    pub fn requests(&self) -> Vec<MockRequest> {
+        lock(&self.state.requests).clone()
+    }
+
+    #[must_use]
+    /// The most requests the mock was answering at once so far, only requests to [delayed](MockRoute::delay) routes overlap reliably
+    pub fn peak_in_flight(&self) -> usize {
+        self.state.peak_in_flight.load(Ordering::Acquire)
+    }
+
+    /// Sends an `OnJsonApiEvent` for `uri` to every connection subscribed to it,
+    /// or to every json api event, returning how many were sent
+    ///
+    /// `event_type` is one of `Create`, `Update` or `Delete`
+    ///
+    /// # Errors
+    /// This will return an error if `data` cannot be serialized
+    pub fn emit(
+        &self,
+        uri: &str,
+        event_type: &str,
+        data: impl Serialize,
+    ) -> Result<usize, serde_json::Error> {
+        let payload = json!({
+            "data": serde_json::to_value(data)?,
+            "eventType": event_type,
+            "uri": uri,
+        });
+
+        let names = event_names(uri);
+        let mut sent = 0;
+
+        lock(&self.state.sockets).retain(|socket| {
+            let subscriptions = lock(&socket.subscriptions);
+
+            for name in names.iter().filter(|name| subscriptions.contains(*name)) {
+                // The connection is gone once its receiver is
+                if socket
+                    .sender
+                    .send(json!([8, name, payload]).to_string())
+                    .is_err()
+                {
+                    return false;
+                }
+                sent += 1;
+            }
+
+            true
+        });
+
+        Ok(sent)
+    }
+
+    #[must_use]
+    /// Waits until a connection subscribes to the events for `uri`, returning `false` if none did within `timeout`
+    ///
+    /// Subscribing is sent in the background, so this avoids emitting an event before anyone can receive it
+    pub fn wait_for_subscription(&self, uri: &str, timeout: Duration) -> bool {
+        let names = event_names(uri);
+        let deadline = Instant::now() + timeout;
+
+        loop {
+            let subscribed = lock(&self.state.sockets).iter().any(|socket| {
+                let subscriptions = lock(&socket.subscriptions);
+                names.iter().any(|name| subscriptions.contains(name))
+            });
+
+            if subscribed {
+                return true;
+            }
+            if Instant::now() >= deadline {
+                return false;
+            }
+
+            thread::sleep(Duration::from_millis(1));
+        }
+    }
+}
+
+impl Drop for MockLcu {
+    fn drop(&mut self) {
+        self.state.shutdown.store(true, Ordering::Release);
+
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
+
+impl MockRoute<'_> {
+    /// Waits for `delay` before responding, like a slow endpoint would
+    pub fn delay(mut self, delay: Duration) -> Self {
+        self.delay = delay;
+        self
+    }
+
+    /// Responds with `200 OK`, and `body`
+    ///
+    /// # Panics
+    /// This panics if `body` cannot be serialized
+    pub fn respond_json(self, body: impl Serialize) {
+        self.respond(StatusCode::OK, body);
+    }
+
+    /// Responds with `status`, and `body`
+    ///
+    /// # Panics
+    /// This panics if `body` cannot be serialized
+    pub fn respond(self, status: StatusCode, body: impl Serialize) {
+        let body = serde_json::to_value(body).expect("The mock response should be serializable");
+        self.register(status, Some(body));
+    }
+
+    /// Responds with `204 No Content`, the same as the LCU does for most actions
+    pub fn respond_empty(self) {
+        self.register(StatusCode::NO_CONTENT, None);
+    }
+
+    /// Responds with `status`, and an error body shaped like the LCU's, which the client decodes into an [`LcuError`]
+    ///
+    /// [`LcuError`]: crate::LcuError
+    pub fn respond_error(self, status: StatusCode, message: &str) {
+        self.register(status, Some(error_body(status, message)));
+    }
+
+    fn register(self, status: StatusCode, body: Option<Value>) {
+        lock(&self.state.routes).insert(
+            (self.method, self.path),
+            MockResponse {
+                status,
+                body,
+                delay: self.delay,
+            },
+        );
+    }
+}
+
+impl State {
+    fn respond(&self, request: &HttpRequest, auth_header: &HeaderValue) -> Vec<u8> {
+        let msgpack = request
+            .header("accept")
+            .is_some_and(|accept| accept.contains("msgpack"));
+
+        if request.header("authorization") != auth_header.to_str().ok() {
+            return http_response(StatusCode::UNAUTHORIZED, None, msgpack);
+        }
+
+        lock(&self.requests).push(MockRequest {
+            method: request.method.clone(),
+            path: request.path.clone(),
+            body: request.decode_body(),
+        });
+
+        let response = {
+            let routes = lock(&self.routes);
+            let path = request
+                .path
+                .split_once('?')
+                .map_or(&*request.path, |(path, _)| path);
+
+            routes
+                .get(&(request.method.clone(), request.path.clone()))
+                .or_else(|| routes.get(&(request.method.clone(), path.to_owned())))
+                .cloned()
+        };
+
+        if let Some(response) = response {
+            let in_flight = self.in_flight.fetch_add(1, Ordering::AcqRel) + 1;
+            self.peak_in_flight.fetch_max(in_flight, Ordering::AcqRel);
+
+            thread::sleep(response.delay);
+
+            self.in_flight.fetch_sub(1, Ordering::AcqRel);
+            return http_response(response.status, response.body.as_ref(), msgpack);
+        }
+
+        let message = format!(
+            "No route registered for {} {}",
+            request.method, request.path
+        );
+        http_response(
+            StatusCode::NOT_FOUND,
+            Some(&error_body(StatusCode::NOT_FOUND, &message)),
+            msgpack,
+        )
+    }
+}
+
+impl HttpRequest {
+    fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(header, _)| header == name)
+            .map(|(_, value)| &**value)
+    }
+
+    fn decode_body(&self) -> Value {
+        if self.body.is_empty() {
+            return Value::Null;
+        }
+
+        let msgpack = self
+            .header("content-type")
+            .is_some_and(|content_type| content_type.contains("msgpack"));
+
+        if msgpack {
+            rmp_serde::from_slice(&self.body).unwrap_or(Value::Null)
+        } else {
+            serde_json::from_slice(&self.body).unwrap_or(Value::Null)
+        }
+    }
+}
+
+/// The server half of the TLS backend that's enabled, so the mock works with either
+#[cfg(feature = "rustls")]
+mod tls {
+    use super::{CERTIFICATE, PRIVATE_KEY};
+    use rustls::pki_types::pem::PemObject;
+    use rustls::pki_types::{CertificateDer, PrivateKeyDer};
+    use rustls::{ServerConfig, ServerConnection, StreamOwned};
+    use std::io;
+    use std::net::TcpStream;
+    use std::sync::atomic::AtomicBool;
+    use std::sync::Arc;
+
+    pub(super) type Acceptor = Arc<ServerConfig>;
+    pub(super) type TlsStream = StreamOwned<ServerConnection, TcpStream>;
+
+    pub(super) fn acceptor() -> io::Result<Acceptor> {
+        let certificate =
+            CertificateDer::from_pem_slice(CERTIFICATE).expect("The mock certificate is valid PEM");
+        let key = PrivateKeyDer::from_pem_slice(PRIVATE_KEY).expect("The mock key is valid PEM");
+
+        ServerConfig::builder_with_provider(Arc::new(rustls::crypto::ring::default_provider()))
+            .with_safe_default_protocol_versions()
+            .and_then(|builder| {
+                builder
+                    .with_no_client_auth()
+                    .with_single_cert(vec![certificate], key)
+            })
+            .map(Arc::new)
+            .map_err(io::Error::other)
+    }
+
+    /// The handshake happens on the first read, so this never waits
+    pub(super) fn accept(
+        acceptor: &Acceptor,
+        tcp: TcpStream,
+        _: &AtomicBool,
+    ) -> io::Result<Option<TlsStream>> {
+        let connection = ServerConnection::new(acceptor.clone()).map_err(io::Error::other)?;
+        Ok(Some(StreamOwned::new(connection, tcp)))
+    }
+}
+
+/// The server half of the TLS backend that's enabled, so the mock works with either
+#[cfg(feature = "nativetls")]
+mod tls {
+    use super::{CERTIFICATE, PRIVATE_KEY};
+    use native_tls::{HandshakeError, Identity, TlsAcceptor};
+    use std::io;
+    use std::net::TcpStream;
+    use std::sync::atomic::{AtomicBool, Ordering};
+
+    pub(super) type Acceptor = TlsAcceptor;
+    pub(super) type TlsStream = native_tls::TlsStream<TcpStream>;
+
+    pub(super) fn acceptor() -> io::Result<Acceptor> {
+        Identity::from_pkcs8(CERTIFICATE, PRIVATE_KEY)
+            .and_then(TlsAcceptor::new)
+            .map_err(io::Error::other)
+    }
+
+    /// Does the handshake, returning `None` if the mock is dropped before it finishes
+    pub(super) fn accept(
+        acceptor: &Acceptor,
+        tcp: TcpStream,
+        shutdown: &AtomicBool,
+    ) -> io::Result<Option<TlsStream>> {
+        let mut handshake = acceptor.accept(tcp);
+
+        loop {
+            match handshake {
+                Ok(stream) => return Ok(Some(stream)),
+                // The read timeout ran out, which is when shutdown is checked
+                Err(HandshakeError::WouldBlock(_)) if shutdown.load(Ordering::Acquire) => {
+                    return Ok(None)
+                }
+                Err(HandshakeError::WouldBlock(stream)) => handshake = stream.handshake(),
+                Err(HandshakeError::Failure(err)) => return Err(io::Error::other(err)),
+            }
+        }
+    }
+}
+
+fn accept_loop(
+    listener: &TcpListener,
+    acceptor: &tls::Acceptor,
+    state: &Arc<State>,
+    auth_header: &HeaderValue,
+) {
+    while !state.shutdown.load(Ordering::Acquire) {
+        match listener.accept() {
+            Ok((tcp, _)) => {
+                let acceptor = acceptor.clone();
+                let state = state.clone();
+                let auth_header = auth_header.clone();
+
+                // A connection that fails only affects its own requests
+                thread::spawn(move || serve(tcp, &acceptor, &state, &auth_header));
+            }
+            Err(err) if err.kind() == ErrorKind::WouldBlock => thread::sleep(POLL_INTERVAL),
+            Err(_) => return,
+        }
+    }
+}
+
+fn serve(
+    tcp: TcpStream,
+    acceptor: &tls::Acceptor,
+    state: &State,
+    auth_header: &HeaderValue,
+) -> io::Result<()> {
+    // Accepted sockets don't inherit the listener's nonblocking mode on every platform
+    tcp.set_nonblocking(false)?;
+    tcp.set_read_timeout(Some(POLL_INTERVAL))?;
+    tcp.set_nodelay(true)?;
+
+    let Some(mut stream) = tls::accept(acceptor, tcp, &state.shutdown)? else {
+        return Ok(());
+    };
+    let mut buf = Vec::new();
+
+    while let Some(request) = read_request(&mut stream, &mut buf, &state.shutdown)? {
+        if request
+            .header("upgrade")
+            .is_some_and(|upgrade| upgrade.eq_ignore_ascii_case("websocket"))
+        {
+            return serve_websocket(stream, &request, state, auth_header);
+        }
+
+        stream.write_all(&state.respond(&request, auth_header))?;
+        stream.flush()?;
+    }
+
+    Ok(())
+}
+
+fn serve_websocket(
+    mut stream: tls::TlsStream,
+    request: &HttpRequest,
+    state: &State,
+    auth_header: &HeaderValue,
+) -> io::Result<()> {
+    if request.header("authorization") != auth_header.to_str().ok() {
+        stream.write_all(&http_response(StatusCode::UNAUTHORIZED, None, false))?;
+        return stream.flush();
+    }
+
+    let Some(key) = request.header("sec-websocket-key") else {
+        stream.write_all(&http_response(StatusCode::BAD_REQUEST, None, false))?;
+        return stream.flush();
+    };
+
+    write!(
+        stream,
+        "HTTP/1.1 101 Switching Protocols\r\nUpgrade: websocket\r\nConnection: Upgrade\r\nSec-WebSocket-Accept: {}\r\n\r\n",
+        derive_accept_key(key.as_bytes())
+    )?;
+    stream.flush()?;
+
+    let mut socket = WebSocket::from_raw_socket(stream, Role::Server, None);
+    let subscriptions = Arc::<Mutex<HashSet<String>>>::default();
+    let (sender, receiver) = mpsc::channel();
+
+    lock(&state.sockets).push(Socket {
+        subscriptions: subscriptions.clone(),
+        sender,
+    });
+
+    loop {
+        if state.shutdown.load(Ordering::Acquire) {
+            let _ = socket.close(None);
+            let _ = socket.flush();
+            return Ok(());
+        }
+
+        match socket.read() {
+            Ok(Message::Text(text)) => subscribe(&text, &subscriptions),
+            Ok(_) => {}
+            Err(tungstenite::Error::Io(err))
+                if matches!(err.kind(), ErrorKind::WouldBlock | ErrorKind::TimedOut) => {}
+            Err(tungstenite::Error::ConnectionClosed | tungstenite::Error::AlreadyClosed) => {
+                return Ok(())
+            }
+            Err(err) => return Err(io::Error::other(err)),
+        }
+
+        while let Ok(text) = receiver.try_recv() {
+            socket.send(Message::text(text)).map_err(io::Error::other)?;
+        }
+    }
+}
+
+/// Tracks `[5, name]` and `[6, name]`, the messages that subscribe and unsubscribe
+fn subscribe(text: &str, subscriptions: &Mutex<HashSet<String>>) {
+    let Ok(message) = serde_json::from_str::<(u8, String)>(text) else {
+        return;
+    };
+
+    match message {
+        (5, name) => {
+            lock(subscriptions).insert(name);
+        }
+        (6, name) => {
+            lock(subscriptions).remove(&name);
+        }
+        _ => {}
+    }
+}
+
+/// Reads the next request on the connection, `None` once it's closed, or the mock is dropped
+fn read_request(
+    stream: &mut impl Read,
+    buf: &mut Vec<u8>,
+    shutdown: &AtomicBool,
+) -> io::Result<Option<HttpRequest>> {
+    let head_len = loop {
+        if let Some(end) = buf.windows(4).position(|window| window == b"\r\n\r\n") {
+            break end + 4;
+        }
+        if !read_more(stream, buf, shutdown)? {
+            return Ok(None);
+        }
+    };
+
+    let head = std::str::from_utf8(&buf[..head_len])
+        .map_err(|err| io::Error::new(ErrorKind::InvalidData, err))?;
+    let mut lines = head.split("\r\n");
+
+    let mut request_line = lines.next().unwrap_or_default().split(' ');
+    let (Some(method), Some(path)) = (request_line.next(), request_line.next()) else {
+        return Err(io::Error::new(
+            ErrorKind::InvalidData,
+            "malformed request line",
+        ));
+    };
+    let (method, path) = (method.to_owned(), path.to_owned());
+
+    let headers: Vec<_> = lines
+        .filter_map(|line| line.split_once(':'))
+        .map(|(name, value)| (name.trim().to_ascii_lowercase(), value.trim().to_owned()))
+        .collect();
+
+    let body_len = headers
+        .iter()
+        .find(|(name, _)| name == "content-length")
+        .and_then(|(_, value)| value.parse::<usize>().ok())
+        .unwrap_or(0);
+
+    while buf.len() < head_len + body_len {
+        if !read_more(stream, buf, shutdown)? {
+            return Ok(None);
+        }
+    }
+
+    let body = buf[head_len..head_len + body_len].to_vec();
+    buf.drain(..head_len + body_len);
+
+    Ok(Some(HttpRequest {
+        method,
+        path,
+        headers,
+        body,
+    }))
+}
+
+/// Reads whatever is available into `buf`, returning `false` once the connection is closed, or the mock is dropped
+fn read_more(stream: &mut impl Read, buf: &mut Vec<u8>, shutdown: &AtomicBool) -> io::Result<bool> {
+    let mut chunk = [0; 4096];
+
+    loop {
+        if shutdown.load(Ordering::Acquire) {
+            return Ok(false);
+        }
+
+        match stream.read(&mut chunk) {
+            Ok(0) => return Ok(false),
+            Ok(len) => {
+                buf.extend_from_slice(&chunk[..len]);
+                return Ok(true);
+            }
+            Err(err)
+                if matches!(
+                    err.kind(),
+                    ErrorKind::WouldBlock | ErrorKind::TimedOut | ErrorKind::Interrupted
+                ) => {}
+            Err(err) => return Err(err),
+        }
+    }
+}
+
+/// Encodes a response, with `body` as msgpack if the client asked for it, the same as the LCU
+fn http_response(status: StatusCode, body: Option<&Value>, msgpack: bool) -> Vec<u8> {
+    let reason = status.canonical_reason().unwrap_or_default();
+
+    let Some(body) = body else {
+        // A `204` can't have a length, every other status needs one to keep the connection alive
+        return if status == StatusCode::NO_CONTENT {
+            format!("HTTP/1.1 {} {reason}\r\n\r\n", status.as_u16()).into_bytes()
+        } else {
+            format!(
+                "HTTP/1.1 {} {reason}\r\nContent-Length: 0\r\n\r\n",
+                status.as_u16()
+            )
+            .into_bytes()
+        };
+    };
+
+    let (content_type, body) = if msgpack {
+        (
+            "application/x-msgpack",
+            rmp_serde::to_vec_named(body).expect("A JSON value is always valid msgpack"),
+        )
+    } else {
+        ("application/json", body.to_string().into_bytes())
+    };
+
+    let mut response = format!(
+        "HTTP/1.1 {} {reason}\r\nContent-Type: {content_type}\r\nContent-Length: {}\r\n\r\n",
+        status.as_u16(),
+        body.len()
+    )
+    .into_bytes();
+    response.extend_from_slice(&body);
+    response
+}
+
+/// The body the LCU sends with an error
+fn error_body(status: StatusCode, message: &str) -> Value {
+    json!({
+        "errorCode": "RPC_ERROR",
+        "httpStatus": status.as_u16(),
+        "implementationDetails": {},
+        "message": message,
+    })
+}
+
+/// The subscriptions that receive the events for `uri`
+fn event_names(uri: &str) -> [String; 2] {
+    [
+        EventKind::json_api_event().event_name().into_owned(),
+        EventKind::json_api_event_callback(uri.to_owned())
+            .event_name()
+            .into_owned(),
+    ]
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> std::sync::MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{MockLcu, MockRequest};
+    use crate::ws::types::EventKind;
+    use crate::ws::{DefaultErrorHandler, StreamEvent};
+    use crate::Error;
+    use futures_util::StreamExt;
+    use hyper::StatusCode;
+    use serde_json::{json, Value};
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn routes() {
+        let mock = MockLcu::start().unwrap();
+        mock.on_get("/lol-gameflow/v1/gameflow-phase")
+            .respond_json("Lobby");
+        mock.on_post("/lol-lobby/v2/lobby").respond_empty();
+        mock.on_get("/lol-lobby/v2/lobby")
+            .respond_error(StatusCode::NOT_FOUND, "LOBBY_NOT_FOUND");
+
+        let client = mock.client();
+
+        let phase: String = client
+            .get("/lol-gameflow/v1/gameflow-phase?x=1")
+            .await
+            .unwrap();
+        assert_eq!(phase, "Lobby");
+
+        let created = client
+            .post::<_, Value>("/lol-lobby/v2/lobby", Some(json!({"queueId": 420})))
+            .await;
+        assert!(matches!(created, Err(Error::RmpSerdeDecode(_))));
+
+        let lobby = client
+            .get::<Value>("/lol-lobby/v2/lobby")
+            .await
+            .unwrap_err();
+        assert_eq!(lobby.status(), Some(StatusCode::NOT_FOUND));
+
+        let missing = client.get::<Value>("/missing").await.unwrap_err();
+        assert_eq!(missing.status(), Some(StatusCode::NOT_FOUND));
+
+        assert_eq!(
+            mock.requests()[1],
+            MockRequest {
+                method: "POST".into(),
+                path: "/lol-lobby/v2/lobby".into(),
+                body: json!({"queueId": 420}),
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn unauthorized() {
+        let mock = MockLcu::start().unwrap();
+        mock.on_get("/lol-gameflow/v1/gameflow-phase")
+            .respond_json("Lobby");
+
+        let client = crate::rest::LcuClient::new_with_credentials_with_request_client(
+            mock.addr(),
+            hyper::http::HeaderValue::from_static("Basic cmlvdDp0ZXN0"),
+            &crate::RequestClient::danger_accept_invalid_certs(),
+        );
+
+        let err = client
+            .get::<Value>("/lol-gameflow/v1/gameflow-phase")
+            .await
+            .unwrap_err();
+        assert_eq!(err.status(), Some(StatusCode::UNAUTHORIZED));
+        assert!(mock.requests().is_empty());
+    }
+
+    #[tokio::test]
+    async fn events() {
+        const PHASE: &str = "/lol-gameflow/v1/gameflow-phase";
+
+        let mock = MockLcu::start().unwrap();
+        let mut ws = mock.websocket(DefaultErrorHandler).unwrap();
+
+        let (_, mut phases) = ws
+            .subscribe_stream::<String>(EventKind::json_api_event_callback_str(PHASE))
+            .unwrap();
+
+        assert!(mock.wait_for_subscription(PHASE, Duration::from_secs(5)));
+        assert_eq!(mock.emit(PHASE, "Update", "ChampSelect").unwrap(), 1);
+
+        let Some(Ok(StreamEvent::Event(event))) = phases.next().await else {
+            panic!("The event should be received");
+        };
+        assert_eq!(event.data, "ChampSelect");
+
+        ws.abort().unwrap();
+    }
+}
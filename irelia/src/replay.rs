@@ -13,11 +13,14 @@ pub mod types;
 /// A number of endpoints are also shared
 /// Hence why the replay API enables the `in_game` feature
 pub use super::in_game::URL;
-use crate::replay::types::{Playback, RecordingState, Render, Sequence};
+use crate::replay::types::{
+    Playback, PlaybackUpdate, RecordingOptions, RecordingState, Render, Sequence,
+};
 use crate::{in_game, Error, RequestClient};
 use std::borrow::Borrow;
 use std::collections::HashMap;
 use std::future::Future;
+use time::Duration;
 
 /// Trait for replay API endpoints
 #[allow(clippy::module_name_repetitions)]
@@ -76,6 +79,61 @@ pub trait ReplayClient: in_game::GameClient {
         }
     }
 
+    /// Only changes the parts of the playback state that are `Some`, returning the new state
+    ///
+    /// # Errors
+    /// This will return an error if there is not an active replay running
+    fn update_playback(
+        &self,
+        update: PlaybackUpdate,
+    ) -> impl Future<Output = Result<Playback, Error>> + Send {
+        async move { self.replay("/replay/playback", "POST", Some(update)).await }
+    }
+
+    /// Pauses the replay
+    ///
+    /// # Errors
+    /// This will return an error if there is not an active replay running
+    fn pause(&self) -> impl Future<Output = Result<Playback, Error>> + Send {
+        self.update_playback(PlaybackUpdate {
+            paused: Some(true),
+            ..PlaybackUpdate::default()
+        })
+    }
+
+    /// Resumes the replay
+    ///
+    /// # Errors
+    /// This will return an error if there is not an active replay running
+    fn play(&self) -> impl Future<Output = Result<Playback, Error>> + Send {
+        self.update_playback(PlaybackUpdate {
+            paused: Some(false),
+            ..PlaybackUpdate::default()
+        })
+    }
+
+    /// Seeks to `time` since the beginning of the game, the returned state will be `seeking` until it's done
+    ///
+    /// # Errors
+    /// This will return an error if there is not an active replay running
+    fn seek(&self, time: Duration) -> impl Future<Output = Result<Playback, Error>> + Send {
+        self.update_playback(PlaybackUpdate {
+            time: Some(time),
+            ..PlaybackUpdate::default()
+        })
+    }
+
+    /// Sets the playback speed (0.5 is half speed, 2.0 is double speed etc.)
+    ///
+    /// # Errors
+    /// This will return an error if there is not an active replay running
+    fn set_speed(&self, speed: f64) -> impl Future<Output = Result<Playback, Error>> + Send {
+        self.update_playback(PlaybackUpdate {
+            speed: Some(speed),
+            ..PlaybackUpdate::default()
+        })
+    }
+
     /// Returns the current status of video recording. Poll this resource for progress on the output.
     ///
     /// # Errors
@@ -98,6 +156,42 @@ pub trait ReplayClient: in_game::GameClient {
         }
     }
 
+    /// Starts recording with `options`, the progress can be polled with [`ReplayClient::get_recording`]
+    ///
+    /// # Errors
+    /// This will return an error if there is not an active replay running
+    fn start_recording(
+        &self,
+        options: RecordingOptions,
+    ) -> impl Future<Output = Result<RecordingState, Error>> + Send {
+        #[derive(serde_derive::Serialize)]
+        struct Start {
+            #[serde(flatten)]
+            options: RecordingOptions,
+            recording: bool,
+        }
+
+        let body = Start {
+            options,
+            recording: true,
+        };
+
+        async move { self.replay("/replay/recording", "POST", Some(body)).await }
+    }
+
+    /// Stops the current recording, keeping what was recorded so far
+    ///
+    /// # Errors
+    /// This will return an error if there is not an active replay running
+    fn stop_recording(&self) -> impl Future<Output = Result<RecordingState, Error>> + Send {
+        #[derive(serde_derive::Serialize)]
+        struct Stop {
+            recording: bool,
+        }
+
+        self.replay("/replay/recording", "POST", Some(Stop { recording: false }))
+    }
+
     /// Returns the current render properties.
     ///
     /// # Errors
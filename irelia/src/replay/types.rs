@@ -241,6 +241,74 @@ pub struct RecordingState {
     pub width: i32,
 }
 
+#[derive(Default, Debug, Clone, PartialEq, Serialize)]
+/// Changes to the playback state, anything left as `None` is left as is
+pub struct PlaybackUpdate {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// Pause or resume the replay
+    pub paused: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// Replay playback speed (0.5 is half speed, 2.0 is double speed etc.)
+    pub speed: Option<f64>,
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        serialize_with = "serialize_seconds"
+    )]
+    /// Time to seek to, in seconds since the beginning of the game
+    pub time: Option<Duration>,
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+/// Settings for a new recording, anything left as `None` uses the game's current setting
+///
+/// The recording itself is started and stopped with [`super::ReplayClient::start_recording`]
+/// and [`super::ReplayClient::stop_recording`]
+pub struct RecordingOptions {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// Output format of the recording (for example webm or png)
+    pub codec: Option<AVContainer>,
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        serialize_with = "serialize_seconds"
+    )]
+    /// Game time in seconds where the recording starts
+    pub start_time: Option<Duration>,
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        serialize_with = "serialize_seconds"
+    )]
+    /// Game time in seconds where the recording ends
+    pub end_time: Option<Duration>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// True if the recording should match the target frames per second exactly by slowing down the recording if required
+    pub enforce_frame_rate: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// Target number of frames per second to record in the video
+    pub frames_per_second: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// True if the recording should be output in a lossless codec (no compression)
+    pub lossless: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// File path or directory where the recording should be saved
+    pub path: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    /// Playback speed used when recording
+    pub replay_speed: Option<f64>,
+}
+
+// Serde requires it to be this way
+#[allow(clippy::ref_option)]
+fn serialize_seconds<S: serde::Serializer>(
+    duration: &Option<Duration>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    match duration {
+        Some(duration) => duration::serialize(duration, serializer),
+        None => serializer.serialize_none(),
+    }
+}
+
 #[allow(clippy::struct_excessive_bools)]
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -534,3 +602,29 @@ mod pid {
         deserializer.deserialize_u32(PidVisitor)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{PlaybackUpdate, RecordingOptions};
+    use serde_json::json;
+    use time::Duration;
+
+    #[test]
+    fn test_partial_updates() {
+        let seek = PlaybackUpdate {
+            time: Some(Duration::seconds(90)),
+            ..PlaybackUpdate::default()
+        };
+        assert_eq!(serde_json::to_value(seek).unwrap(), json!({"time": 90.0}));
+
+        let recording = RecordingOptions {
+            path: Some("C:/recordings".into()),
+            end_time: Some(Duration::seconds_f64(120.5)),
+            ..RecordingOptions::default()
+        };
+        assert_eq!(
+            serde_json::to_value(recording).unwrap(),
+            json!({"endTime": 120.5, "path": "C:/recordings"})
+        );
+    }
+}
@@ -2,25 +2,271 @@
 //!
 //! For responses that have no body, use `IgnoreAny` instead of supplying a type, or using an `Option<T>`
 
+pub mod batch;
+#[cfg(feature = "blocking")]
+pub mod blocking;
+pub mod body;
+pub mod cache;
+pub mod champ_select;
+pub mod champions;
+pub mod chat;
+pub mod clash;
+pub mod end_of_game;
+pub mod endpoint;
+pub mod fixture;
+pub mod game_settings;
+pub mod gameflow;
+pub mod honor;
+pub mod item_sets;
+pub mod lobby;
+pub mod loot;
+pub mod match_history;
+pub mod matchmaking;
+#[cfg(feature = "metrics")]
+pub mod metrics;
+pub mod middleware;
+pub mod missions;
+pub mod patcher;
+pub mod perks;
+pub mod process_control;
+#[cfg(feature = "proxy")]
+pub mod proxy;
+pub mod ranked;
+pub mod reconnect;
+pub mod replays;
+pub mod request;
+pub mod retry;
+pub mod riot_client;
+pub mod stream;
+pub mod summoner;
+pub mod system;
+pub mod tft;
 #[cfg(feature = "rest_schema")]
 /// This is a list of types pertaining to the LCU, currently only containing the types for the schema.
 pub mod types;
 
-use crate::utils::process_info::{CLIENT_PROCESS_NAME, GAME_PROCESS_NAME};
-use crate::{utils::process_info::get_running_client, Error, RequestClient};
+use crate::utils::process_info::{
+    is_process_running, ClientLocator, LocatedClient, ProcessLocator, RemoteLcu, CLIENT_CLOSED,
+};
+use crate::utils::requests::ConnectionOptions;
+use crate::utils::runtime;
+use crate::{Error, RequestClient};
+use hyper::body::Bytes;
 use hyper::http::HeaderValue;
-use serde::de::DeserializeOwned;
+use middleware::Middleware;
+use serde::de::{DeserializeOwned, IgnoredAny};
 use serde::Serialize;
-use std::net::SocketAddrV4;
+use std::future::Future;
+use std::net::SocketAddr;
+use std::sync::{Arc, OnceLock};
+use std::time::Duration;
 
 /// Struct representing a connection to the LCU
 pub struct LcuClient {
     request_client: RequestClient,
-    url: SocketAddrV4,
+    url: SocketAddr,
     auth_header: HeaderValue,
+    certificate_fingerprint: OnceLock<[u8; 32]>,
+    /// The logged in summoner, fetched once, and cleared when the client is reconnected
+    current_summoner: OnceLock<summoner::Summoner>,
+    /// The PID of the process the credentials came from, `None` if they were supplied directly
+    pid: Option<u32>,
+    /// Where the credentials came from, used again when reconnecting, `None` if the running process was searched for
+    locator: Option<Locator>,
+    /// How long a request can take before it fails with [`Error::Timeout`]
+    timeout: Option<Duration>,
+    /// How requests that fail while the client is starting up are retried, they aren't by default
+    retry: Option<retry::RetryPolicy>,
+    /// Where the responses of get requests are cached, nothing is cached by default
+    cache: Option<cache::ResponseCache>,
+    #[cfg(feature = "metrics")]
+    metrics: metrics::Metrics,
+}
+
+/// Builder for [`LcuClient`], created with [`LcuClient::builder`]
+///
+/// ```no_run
+/// use irelia::rest::LcuClient;
+/// use std::time::Duration;
+///
+/// let client = LcuClient::builder()
+///     .timeout(Duration::from_secs(5))
+///     .force_lockfile(true)
+///     .build()
+///     .unwrap();
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct LcuClientBuilder {
+    request_client: Option<RequestClient>,
+    force_lock_file: bool,
+    timeout: Option<Duration>,
+    retry: Option<retry::RetryPolicy>,
+    cache: Option<cache::ResponseCache>,
+    connection: ConnectionOptions,
+    middleware: middleware::Stack,
+    locator: Option<Locator>,
+}
+
+/// A shared [`ClientLocator`], kept so the client can be found again when reconnecting
+#[derive(Clone)]
+struct Locator(Arc<dyn ClientLocator>);
+
+impl std::fmt::Debug for Locator {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("Locator")
+    }
+}
+
+impl LcuClientBuilder {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    #[must_use]
+    /// Fails every request that takes longer than `timeout` with [`Error::Timeout`], requests never time out by default
+    pub const fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    #[must_use]
+    /// Sends requests that fail while the client is starting up again, see [`retry::RetryPolicy`]
+    ///
+    /// This applies to [`LcuClient::lcu_request`], the methods built on it, and [`request::RequestBuilder`].
+    /// The timeout applies to every attempt separately
+    pub fn retry(mut self, policy: retry::RetryPolicy) -> Self {
+        self.retry = Some(policy);
+        self
+    }
+
+    #[must_use]
+    /// Caches the responses of get requests, see [`cache::ResponseCache`]
+    ///
+    /// This applies to [`LcuClient::lcu_request`], the methods built on it, and [`LcuClient::get_bytes`]
+    pub fn cache(mut self, cache: cache::ResponseCache) -> Self {
+        self.cache = Some(cache);
+        self
+    }
+
+    #[must_use]
+    /// Fails requests that can't open a connection within `connect_timeout`, see [`RequestClient::new_with_connect_timeout`]
+    ///
+    /// This has no effect if a request client is set with [`LcuClientBuilder::request_client`]
+    pub const fn connect_timeout(mut self, connect_timeout: Duration) -> Self {
+        self.connection.connect_timeout = Some(connect_timeout);
+        self
+    }
+
+    #[must_use]
+    /// How long an unused connection is kept open to be reused, hyper's default is 90 seconds
+    ///
+    /// This has no effect if a request client is set with [`LcuClientBuilder::request_client`]
+    pub const fn pool_idle_timeout(mut self, idle_timeout: Duration) -> Self {
+        self.connection.pool_idle_timeout = Some(idle_timeout);
+        self
+    }
+
+    #[must_use]
+    /// The most unused connections kept open to the client, any more are closed once their request completes,
+    /// there is no limit by default
+    ///
+    /// This has no effect if a request client is set with [`LcuClientBuilder::request_client`]
+    pub const fn pool_max_idle(mut self, max_idle: usize) -> Self {
+        self.connection.pool_max_idle_per_host = Some(max_idle);
+        self
+    }
+
+    #[must_use]
+    /// Reads the lock file regardless of whether the client or the game is running
+    pub const fn force_lockfile(mut self, force_lock_file: bool) -> Self {
+        self.force_lock_file = force_lock_file;
+        self
+    }
+
+    #[must_use]
+    /// Accepts any certificate the client serves, see [`RequestClient::danger_accept_invalid_certs`]
+    ///
+    /// By default, only the certificate signed by riot's root certificate, which is bundled with this crate, is accepted.
+    /// This has no effect if a request client is set with [`LcuClientBuilder::request_client`]
+    pub const fn danger_accept_invalid_certs(mut self) -> Self {
+        self.connection.accept_invalid_certs = true;
+        self
+    }
+
+    #[must_use]
+    /// The client used to make requests, a new one is created by default
+    pub fn request_client(mut self, request_client: RequestClient) -> Self {
+        self.request_client = Some(request_client);
+        self
+    }
+
+    #[must_use]
+    /// Runs `middleware` around every request, see [`middleware::Middleware`]
+    ///
+    /// Middleware runs in the order it was added, after any already on the request client
+    pub fn middleware(mut self, middleware: impl Middleware + 'static) -> Self {
+        self.middleware.0.push(Arc::new(middleware));
+        self
+    }
+
+    #[must_use]
+    /// Finds the client with `locator`, instead of searching the running processes, see [`LcuClient::connect_with_locator`]
+    ///
+    /// [`LcuClientBuilder::force_lockfile`] has no effect once a locator is set
+    pub fn locator(mut self, locator: impl ClientLocator + 'static) -> Self {
+        self.locator = Some(Locator(Arc::new(locator)));
+        self
+    }
+
+    #[must_use]
+    /// Connects to `remote`, a client that isn't on this machine's loopback, see [`RemoteLcu`]
+    ///
+    /// The TLS options of `remote` have no effect if a request client is set with [`LcuClientBuilder::request_client`]
+    pub fn remote(mut self, remote: RemoteLcu) -> Self {
+        self.connection.remote = Some(remote.tls().clone());
+        self.locator(remote)
+    }
+
+    /// Finds the running client, and creates an [`LcuClient`] connected to it
+    ///
+    /// # Errors
+    /// This will return an error if the LCU API is not running, this can include
+    /// the client being down, the lock file being unable to be opened, or the LCU
+    /// not running at all
+    pub fn build(self) -> Result<LcuClient, Error> {
+        let request_client = self
+            .request_client
+            .unwrap_or_else(|| RequestClient::new_with_options(self.connection));
+
+        let request_client = self
+            .middleware
+            .0
+            .into_iter()
+            .fold(request_client, RequestClient::with_middleware);
+
+        let locator = self.locator.unwrap_or_else(|| {
+            Locator(Arc::new(ProcessLocator {
+                force_lock_file: self.force_lock_file,
+            }))
+        });
+
+        let mut client = LcuClient::connect_with_locator_arc(locator, &request_client)?;
+        client.timeout = self.timeout;
+        client.retry = self.retry;
+        client.cache = self.cache;
+
+        Ok(client)
+    }
 }
 
 impl LcuClient {
+    #[must_use]
+    /// Creates a builder, for configuring the client before connecting
+    pub fn builder() -> LcuClientBuilder {
+        LcuClientBuilder::new()
+    }
+
     /// Attempts to create a connection to the LCU, errors if it fails
     /// to spin up the child process, or fails to get data from the client.
     ///
@@ -43,14 +289,7 @@ impl LcuClient {
     /// the client being down, the lock file being unable to be opened, or the LCU
     /// not running at all
     pub fn connect_force_lockfile(force_lock_file: bool) -> Result<Self, Error> {
-        let (addr, pass) =
-            get_running_client(CLIENT_PROCESS_NAME, GAME_PROCESS_NAME, force_lock_file)?;
-
-        Ok(Self::new_with_credentials_with_request_client(
-            addr,
-            pass?,
-            &RequestClient::new(),
-        ))
+        Self::connect_with_request_client_force_lockfile(force_lock_file, &RequestClient::new())
     }
 
     /// Attempts to create a connection to the LCU, errors if it fails
@@ -81,52 +320,142 @@ impl LcuClient {
         force_lock_file: bool,
         request_client: &RequestClient,
     ) -> Result<Self, Error> {
-        let (addr, pass) =
-            get_running_client(CLIENT_PROCESS_NAME, GAME_PROCESS_NAME, force_lock_file)?;
+        let located = ProcessLocator { force_lock_file }.locate()?;
+        Self::new_with_located(&located, request_client)
+    }
+
+    /// Creates a connection to the client found by `locator`, which is used again by [`LcuClient::reconnect`]
+    ///
+    /// This is how tests, or anything embedding the client, can supply their own credentials or address,
+    /// without searching the running processes
+    ///
+    /// ```no_run
+    /// use irelia::process_info::LocatedClient;
+    /// use irelia::rest::LcuClient;
+    /// use irelia::RequestClient;
+    ///
+    /// let located = LocatedClient::new(([127, 0, 0, 1], 2999), "token").unwrap();
+    /// let client = LcuClient::connect_with_locator(located, &RequestClient::new()).unwrap();
+    /// ```
+    ///
+    /// # Errors
+    /// This will return an error if `locator` does, or the auth header it returns is not a valid header
+    pub fn connect_with_locator(
+        locator: impl ClientLocator + 'static,
+        request_client: &RequestClient,
+    ) -> Result<Self, Error> {
+        Self::connect_with_locator_arc(Locator(Arc::new(locator)), request_client)
+    }
+
+    /// Creates a connection to `remote`, a client that isn't on this machine's loopback, see [`RemoteLcu`]
+    ///
+    /// # Errors
+    /// This will return an error if the host of `remote` cannot be resolved
+    pub fn connect_remote(remote: RemoteLcu) -> Result<Self, Error> {
+        LcuClientBuilder::new().remote(remote).build()
+    }
+
+    fn connect_with_locator_arc(
+        locator: Locator,
+        request_client: &RequestClient,
+    ) -> Result<Self, Error> {
+        let mut client = Self::new_with_located(&locator.0.locate()?, request_client)?;
+        client.locator = Some(locator);
+        Ok(client)
+    }
+
+    fn new_with_located(
+        located: &LocatedClient,
+        request_client: &RequestClient,
+    ) -> Result<Self, Error> {
+        let auth_header = HeaderValue::from_str(&located.auth_header)?;
 
-        Ok(Self::new_with_credentials_with_request_client(
-            addr,
-            pass?,
+        let mut client = Self::new_with_credentials_with_request_client(
+            located.addr,
+            auth_header,
             request_client,
-        ))
+        );
+        client.pid = located.pid;
+
+        Ok(client)
     }
 
     #[must_use]
     /// Creates a new LCU Client that implicitly trusts the port and auth string given,
     /// Encoding them in a URL and header respectively
+    ///
+    /// `url` can be either IPv4 or IPv6, for clients bound to `::1`
     pub fn new_with_credentials_with_request_client(
-        url: SocketAddrV4,
+        url: impl Into<SocketAddr>,
         auth_header: HeaderValue,
         request_client: &RequestClient,
     ) -> Self {
         Self {
-            url,
+            url: url.into(),
             auth_header,
             request_client: request_client.clone(),
+            certificate_fingerprint: OnceLock::new(),
+            current_summoner: OnceLock::new(),
+            pid: None,
+            locator: None,
+            timeout: None,
+            retry: None,
+            cache: None,
+            #[cfg(feature = "metrics")]
+            metrics: metrics::Metrics::default(),
         }
     }
 
     /// Queries the client or lock file, getting a new url and auth header
     ///
+    /// A client created with a [`ClientLocator`] asks it again instead, and `force_lock_file` has no effect
+    ///
     /// # Errors
     /// This will return an error if the lock file is inaccessible, or if
     /// the LCU is not running
     pub fn reconnect(&mut self, force_lock_file: bool) -> Result<(), Error> {
-        let (addr, pass): (_, Result<HeaderValue, _>) =
-            get_running_client(CLIENT_PROCESS_NAME, GAME_PROCESS_NAME, force_lock_file)?;
-        self.reconnect_with_credentials(addr, pass?);
+        let located = self.reconnect_locator(force_lock_file).locate()?;
+        self.reconnect_with_located(&located)
+    }
+
+    /// The locator [`LcuClient::reconnect`] asks, so the client can be found without holding on to this
+    pub(crate) fn reconnect_locator(&self, force_lock_file: bool) -> Arc<dyn ClientLocator> {
+        match &self.locator {
+            Some(locator) => Arc::clone(&locator.0),
+            None => Arc::new(ProcessLocator { force_lock_file }),
+        }
+    }
+
+    /// Swaps in the credentials of a client found by [`LcuClient::reconnect_locator`]
+    pub(crate) fn reconnect_with_located(&mut self, located: &LocatedClient) -> Result<(), Error> {
+        let auth_header = HeaderValue::from_str(&located.auth_header)?;
+        self.reconnect_with_credentials(located.addr, auth_header);
+        self.pid = located.pid;
         Ok(())
     }
 
     /// Sets the url and auth header according to the auth and port provided
-    pub fn reconnect_with_credentials(&mut self, url: SocketAddrV4, auth: HeaderValue) {
-        self.url = url;
+    pub fn reconnect_with_credentials(&mut self, url: impl Into<SocketAddr>, auth: HeaderValue) {
+        self.url = url.into();
         self.auth_header = auth;
+        self.certificate_fingerprint = OnceLock::new();
+        self.current_summoner = OnceLock::new();
+        self.pid = None;
+
+        if let Some(cache) = &self.cache {
+            cache.clear();
+        }
+
+        #[cfg(feature = "metrics")]
+        self.metrics.record_reconnect();
+
+        #[cfg(feature = "tracing")]
+        tracing::info!(addr = %self.url, "reconnected to the client");
     }
 
     #[must_use]
     /// Returns a reference to the URL in use
-    pub fn url(&self) -> SocketAddrV4 {
+    pub fn url(&self) -> SocketAddr {
         self.url
     }
 
@@ -136,6 +465,38 @@ impl LcuClient {
         &self.auth_header
     }
 
+    #[must_use]
+    /// Returns the cache set with [`LcuClientBuilder::cache`], for invalidating responses
+    pub fn cache(&self) -> Option<&cache::ResponseCache> {
+        self.cache.as_ref()
+    }
+
+    #[cfg(feature = "metrics")]
+    #[must_use]
+    /// Returns the number of requests, failures, and reconnects made with this client so far
+    pub fn metrics(&self) -> metrics::MetricsSnapshot {
+        self.metrics.snapshot()
+    }
+
+    /// Returns the SHA-256 fingerprint of the certificate the client presents, see [`crate::peer_certificate_fingerprint`]
+    ///
+    /// This is only fetched once, and is cached until the client is reconnected.
+    /// The handshake is blocking, so it's done on the runtime's blocking thread pool
+    ///
+    /// # Errors
+    /// This will return an error if the LCU API is not running, or the TLS handshake fails
+    pub async fn certificate_fingerprint(&self) -> Result<[u8; 32], Error> {
+        if let Some(fingerprint) = self.certificate_fingerprint.get() {
+            return Ok(*fingerprint);
+        }
+
+        let url = self.url;
+        let fingerprint =
+            runtime::spawn_blocking(move || crate::peer_certificate_fingerprint(url)).await?;
+
+        Ok(*self.certificate_fingerprint.get_or_init(|| fingerprint))
+    }
+
     /// Sends a delete request to the LCU
     ///
     /// # Errors
@@ -172,15 +533,16 @@ impl LcuClient {
         &self,
         endpoint: impl AsRef<str> + Send,
     ) -> Result<hyper::Response<hyper::body::Incoming>, Error> {
-        self.request_client
-            .raw_request_template(
-                self.url,
-                endpoint.as_ref(),
-                "HEAD",
-                None,
-                Some(&self.auth_header),
-            )
-            .await
+        let request = self.request_client.raw_request_template(
+            self.url,
+            endpoint.as_ref(),
+            "HEAD",
+            None,
+            Some(&self.auth_header),
+        );
+
+        let response = self.timed(request).await;
+        self.finish(response).await
     }
 
     /// Sends a patch request to the LCU
@@ -228,20 +590,198 @@ impl LcuClient {
     /// This will return an error if the LCU API is not running, or the provided type or body is invalid
     ///
     /// If the response body is empty, this will return an unexpected EOF error
+    ///
+    /// Requests can be cancelled by dropping the returned future, such as in `tokio::select!`,
+    /// the connection is closed rather than being reused
     pub async fn lcu_request<T: Serialize + Send, R: DeserializeOwned>(
         &self,
         endpoint: &str,
         method: &str,
         body: Option<T>,
+    ) -> Result<R, Error> {
+        self.lcu_request_inner(endpoint, method, body, self.timeout)
+            .await
+    }
+
+    /// The same as [`LcuClient::lcu_request`], but fails with [`Error::Timeout`] after `timeout`,
+    /// instead of the timeout set on the client
+    ///
+    /// # Errors
+    /// This will return an error if the LCU API is not running, the provided type or body is invalid,
+    /// or the request did not complete in time
+    pub async fn lcu_request_with_timeout<T: Serialize + Send, R: DeserializeOwned>(
+        &self,
+        endpoint: &str,
+        method: &str,
+        body: Option<T>,
+        timeout: Duration,
+    ) -> Result<R, Error> {
+        self.lcu_request_inner(endpoint, method, body, Some(timeout))
+            .await
+    }
+
+    /// Sends a request whose response is ignored, the LCU answers many of the requests that change something
+    /// with an empty body, which isn't valid msgpack
+    ///
+    /// # Errors
+    /// This will return an error if the LCU API is not running, the provided body is invalid,
+    /// or the response isn't empty and isn't valid msgpack either
+    pub(crate) async fn lcu_request_empty<T: Serialize + Send>(
+        &self,
+        endpoint: &str,
+        method: &str,
+        body: Option<T>,
+    ) -> Result<(), Error> {
+        self.lcu_request_decoded(endpoint, method, body, self.timeout, |body| {
+            if !body.is_empty() {
+                rmp_serde::from_slice::<IgnoredAny>(&body)?;
+            }
+
+            Ok(())
+        })
+        .await
+    }
+
+    async fn lcu_request_inner<T: Serialize + Send, R: DeserializeOwned>(
+        &self,
+        endpoint: &str,
+        method: &str,
+        body: Option<T>,
+        timeout: Option<Duration>,
     ) -> Result<R, Error> {
         use hyper::body::Buf;
 
-        let buf = self
-            .request_client
-            .request_template(self.url, endpoint, method, body, Some(&self.auth_header))
-            .await?;
+        self.lcu_request_decoded(endpoint, method, body, timeout, |body| {
+            Ok(rmp_serde::from_read(body.reader())?)
+        })
+        .await
+    }
+
+    async fn lcu_request_decoded<T: Serialize + Send, R>(
+        &self,
+        endpoint: &str,
+        method: &str,
+        body: Option<T>,
+        timeout: Option<Duration>,
+        decode: impl FnOnce(Bytes) -> Result<R, Error> + Send,
+    ) -> Result<R, Error> {
+        use crate::utils::requests::MIME;
+        use http_body_util::Full;
+        use hyper::HeaderMap;
+
+        let body = match body.map(|body| rmp_serde::to_vec_named(&body)).transpose() {
+            Ok(body) => body.map(Full::from),
+            Err(err) => return self.finish(Err(err.into())).await,
+        };
+
+        let cache = self.cache_for(method);
+
+        if let Some(body) = cache.and_then(|cache| cache.get(endpoint)) {
+            return self.finish(decode(body)).await;
+        }
+
+        let response = self
+            .retried(timeout, || {
+                self.request_client.bytes_request(
+                    self.url,
+                    endpoint,
+                    method,
+                    body.clone(),
+                    Some(&self.auth_header),
+                    HeaderValue::from_static(MIME),
+                    HeaderMap::new(),
+                )
+            })
+            .await
+            .inspect(|body| {
+                if let Some(cache) = cache {
+                    cache.insert(endpoint, body.clone());
+                }
+            })
+            .and_then(decode);
+
+        self.finish(response).await
+    }
+
+    /// Returns the cache, if there is one, and `method` is `GET`
+    fn cache_for(&self, method: &str) -> Option<&cache::ResponseCache> {
+        self.cache
+            .as_ref()
+            .filter(|_| method.eq_ignore_ascii_case("GET"))
+    }
+
+    /// Sends the request created by `request`, creating and sending it again for as long as the
+    /// [`retry::RetryPolicy`] allows, with `timeout` applying to every attempt
+    async fn retried<T, F: Future<Output = Result<T, Error>> + Send>(
+        &self,
+        timeout: Option<Duration>,
+        mut request: impl FnMut() -> F + Send,
+    ) -> Result<T, Error> {
+        let mut attempt = 1;
+
+        loop {
+            let result = Self::timed_with(timeout, request()).await;
+
+            let policy = match (&result, &self.retry) {
+                (Err(err), Some(policy)) if policy.should_retry(attempt, err) => policy,
+                _ => return result,
+            };
+
+            runtime::sleep(policy.backoff(attempt)).await;
+            attempt += 1;
+        }
+    }
+
+    /// Applies the timeout set with [`LcuClientBuilder::timeout`] to the request, if there is one
+    async fn timed<T>(
+        &self,
+        request: impl Future<Output = Result<T, Error>> + Send,
+    ) -> Result<T, Error> {
+        Self::timed_with(self.timeout, request).await
+    }
+
+    async fn timed_with<T>(
+        timeout: Option<Duration>,
+        request: impl Future<Output = Result<T, Error>> + Send,
+    ) -> Result<T, Error> {
+        match timeout {
+            Some(timeout) => runtime::timeout(timeout, request)
+                .await
+                .unwrap_or(Err(Error::Timeout)),
+            None => request.await,
+        }
+    }
+
+    /// Runs once every request is complete, mapping the error through [`Self::check_closed`],
+    /// and recording the result if metrics are enabled
+    async fn finish<T>(&self, result: Result<T, Error>) -> Result<T, Error> {
+        let result = match result {
+            Err(err) => Err(self.check_closed(err).await),
+            result => result,
+        };
+
+        #[cfg(feature = "metrics")]
+        self.metrics.record(&result);
 
-        Ok(rmp_serde::from_read(buf.aggregate().reader())?)
+        result
+    }
+
+    /// Turns transport errors into `ClientClosed` if the process the credentials came from has exited
+    ///
+    /// Any other error, or a client without a known PID, is returned as is.
+    /// Listing the processes blocks, so it's done on the runtime's blocking thread pool
+    async fn check_closed(&self, err: Error) -> Error {
+        let transport = matches!(err, Error::HyperClientError(_) | Error::HyperError(_));
+
+        let Some(pid) = self.pid.filter(|_| transport) else {
+            return err;
+        };
+
+        if runtime::spawn_blocking(move || is_process_running(pid)).await {
+            err
+        } else {
+            CLIENT_CLOSED.into()
+        }
     }
 }
 
@@ -269,20 +809,235 @@ pub async fn schema(remote: &'static str) -> Result<Option<types::Schema>, Error
         .ok();
 
     let https = if let Some(https) = https {
-        https.https_only().enable_http1().build()
+        https
+            .https_only()
+            .enable_http1()
+            .wrap_connector(runtime::http_connector(None))
     } else {
         return Ok(None);
     };
 
-    let client = hyper_util::client::legacy::Client::builder(hyper_util::rt::TokioExecutor::new())
+    let client = hyper_util::client::legacy::Client::builder(runtime::Executor::new())
         .build::<_, http_body_util::Full<hyper::body::Bytes>>(https);
     let mut request = client.get(uri).await?;
     let tmp = request.body_mut().collect().await?;
-    Ok(serde_json::from_reader(tmp.aggregate().reader()).ok())
+    Ok(serde_json::from_reader(tmp.reader()).ok())
+}
+
+#[cfg(test)]
+/// A client for a port that's bound but never accepted on, so requests only end by timing out
+///
+/// Dropping the listener makes the port refuse connections instead
+pub(crate) fn unreachable_client() -> (std::net::TcpListener, LcuClient) {
+    unreachable_client_with(&RequestClient::new())
+}
+
+#[cfg(test)]
+/// The same as [`unreachable_client`], but sending requests through `request_client`
+pub(crate) fn unreachable_client_with(
+    request_client: &RequestClient,
+) -> (std::net::TcpListener, LcuClient) {
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let client = LcuClient::new_with_credentials_with_request_client(
+        listener.local_addr().unwrap(),
+        HeaderValue::from_static("Basic cmlvdDp0ZXN0"),
+        request_client,
+    );
+    (listener, client)
 }
 
 #[cfg(test)]
 mod tests {
+    #[cfg(feature = "ws")]
+    #[tokio::test]
+    async fn test_locator() {
+        use super::LcuClient;
+        use crate::mock::{MockLcu, MOCK_TOKEN};
+        use crate::process_info::{Error, LocatedClient};
+        use crate::RequestClient;
+        use std::sync::atomic::{AtomicU32, Ordering};
+        use std::sync::Arc;
+
+        let mock = MockLcu::start().unwrap();
+        mock.on_get("/lol-gameflow/v1/gameflow-phase")
+            .respond_json("Lobby");
+
+        let located = Arc::new(AtomicU32::new(0));
+        let counter = located.clone();
+        let addr = mock.addr();
+
+        let locator = move || -> Result<LocatedClient, Error> {
+            counter.fetch_add(1, Ordering::Relaxed);
+            LocatedClient::new(addr, MOCK_TOKEN)
+        };
+
+        let mut client =
+            LcuClient::connect_with_locator(locator, &RequestClient::danger_accept_invalid_certs())
+                .unwrap();
+        assert_eq!(client.auth_header(), mock.auth_header());
+
+        let phase: String = client.get("/lol-gameflow/v1/gameflow-phase").await.unwrap();
+        assert_eq!(phase, "Lobby");
+
+        // Reconnecting asks the locator again, rather than searching for the process
+        client.reconnect(true).unwrap();
+        assert_eq!(located.load(Ordering::Relaxed), 2);
+    }
+
+    #[cfg(feature = "ws")]
+    #[tokio::test]
+    async fn test_remote() {
+        use super::LcuClient;
+        use crate::mock::{MockLcu, MOCK_TOKEN};
+        use crate::process_info::RemoteLcu;
+
+        let mock = MockLcu::start().unwrap();
+        mock.on_get("/lol-gameflow/v1/gameflow-phase")
+            .respond_json("Lobby");
+
+        let remote = RemoteLcu::new("localhost", mock.addr().port(), MOCK_TOKEN);
+
+        // The mock's certificate isn't signed by riot
+        let client = LcuClient::connect_remote(remote.clone()).unwrap();
+        let phase = client
+            .get::<String>("/lol-gameflow/v1/gameflow-phase")
+            .await;
+        assert!(phase.is_err());
+
+        let client =
+            LcuClient::connect_remote(remote.sni("lcu.example").danger_accept_invalid_certs())
+                .unwrap();
+        assert_eq!(client.auth_header(), mock.auth_header());
+
+        let phase: String = client.get("/lol-gameflow/v1/gameflow-phase").await.unwrap();
+        assert_eq!(phase, "Lobby");
+    }
+
+    #[tokio::test]
+    async fn test_certificate_fingerprint() {
+        use crate::Error;
+        use std::time::{Duration, Instant};
+
+        // Connections are queued by the OS, but the handshake is never answered
+        let (_listener, client) = super::unreachable_client();
+
+        let started = Instant::now();
+        let result = client.certificate_fingerprint().await;
+        assert!(matches!(result, Err(Error::Io(_))));
+        assert!(started.elapsed() < Duration::from_secs(5));
+    }
+
+    #[tokio::test]
+    async fn test_timeout() {
+        use crate::Error;
+        use std::time::Duration;
+
+        // Nothing is ever accepted, so the request can only end by timing out
+        let (_listener, mut client) = super::unreachable_client();
+        client.timeout = Some(Duration::from_millis(50));
+
+        let result = client.get::<serde_json::Value>("/example").await;
+        assert!(matches!(result, Err(Error::Timeout)));
+
+        // The per request timeout takes priority over the client's
+        client.timeout = Some(Duration::from_secs(60));
+        let result = client
+            .lcu_request_with_timeout::<(), serde_json::Value>(
+                "/example",
+                "GET",
+                None,
+                Duration::from_millis(50),
+            )
+            .await;
+        assert!(matches!(result, Err(Error::Timeout)));
+    }
+
+    #[tokio::test]
+    async fn test_retry() {
+        use super::retry::RetryPolicy;
+        use crate::Error;
+        use std::sync::atomic::{AtomicU32, Ordering};
+        use std::sync::Arc;
+        use std::time::Duration;
+
+        let (_listener, mut client) = super::unreachable_client();
+        client.timeout = Some(Duration::from_millis(20));
+
+        let retries = Arc::new(AtomicU32::new(0));
+        let counter = retries.clone();
+        client.retry = Some(
+            RetryPolicy::new()
+                .max_attempts(3)
+                .initial_backoff(Duration::from_millis(1))
+                .retry_on(move |err| {
+                    counter.fetch_add(1, Ordering::Relaxed);
+                    matches!(err, Error::Timeout)
+                }),
+        );
+
+        let result = client.get::<serde_json::Value>("/example").await;
+        assert!(matches!(result, Err(Error::Timeout)));
+        assert_eq!(retries.load(Ordering::Relaxed), 2);
+
+        let result = client.request("GET", "/example").send_bytes().await;
+        assert!(matches!(result, Err(Error::Timeout)));
+        assert_eq!(retries.load(Ordering::Relaxed), 4);
+    }
+
+    #[tokio::test]
+    async fn test_cache() {
+        use super::cache::ResponseCache;
+        use crate::Error;
+        use std::time::Duration;
+
+        // Nothing is ever accepted, so anything that isn't cached times out
+        let (_listener, mut client) = super::unreachable_client();
+        client.timeout = Some(Duration::from_millis(20));
+
+        let cache = ResponseCache::new().route("/cached", Duration::from_secs(60));
+        let body = rmp_serde::to_vec(&"cached").unwrap();
+        cache.insert("/cached", body.clone().into());
+        client.cache = Some(cache);
+
+        let cached: String = client.get("/cached").await.unwrap();
+        assert_eq!(cached, "cached");
+        assert_eq!(client.get_bytes("/cached").await.unwrap(), body);
+
+        let result = client.get::<String>("/uncached").await;
+        assert!(matches!(result, Err(Error::Timeout)));
+
+        // Only get requests are served from the cache
+        let result = client.post::<(), String>("/cached", ()).await;
+        assert!(matches!(result, Err(Error::Timeout)));
+    }
+
+    #[tokio::test]
+    async fn test_lcu_request_empty() {
+        use super::cache::ResponseCache;
+        use crate::Error;
+        use std::time::Duration;
+
+        // Responses are served from the cache, so nothing is sent
+        let (_listener, mut client) = super::unreachable_client();
+        let cache = ResponseCache::new().default_ttl(Duration::from_secs(60));
+        cache.insert("/empty", Vec::new().into());
+        cache.insert("/ignored", rmp_serde::to_vec(&"ignored").unwrap().into());
+        // 0xc1 is never used in msgpack
+        cache.insert("/invalid", vec![0xc1].into());
+        client.cache = Some(cache);
+
+        let empty = client.lcu_request_empty("/empty", "GET", None::<()>).await;
+        assert!(empty.is_ok());
+        let ignored = client
+            .lcu_request_empty("/ignored", "GET", None::<()>)
+            .await;
+        assert!(ignored.is_ok());
+        let invalid = client
+            .lcu_request_empty("/invalid", "GET", None::<()>)
+            .await;
+        assert!(matches!(invalid, Err(Error::RmpSerdeDecode(_))));
+    }
+
     #[cfg(feature = "rest_schema")]
     #[tokio::test]
     async fn test_schema_des() {
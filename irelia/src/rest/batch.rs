@@ -0,0 +1,245 @@
+//! Sending many requests at once, without overloading the client
+
+use super::LcuClient;
+use crate::Error;
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+use std::future::Future;
+use std::pin::Pin;
+use std::task::Poll;
+
+/// How many requests a [`Batch`] has in flight at once, unless set with [`Batch::concurrency`]
+pub const DEFAULT_CONCURRENCY: usize = 4;
+
+type RequestFuture<'a, R> = Pin<Box<dyn Future<Output = Result<R, Error>> + Send + 'a>>;
+
+/// A set of requests sent together, created with [`LcuClient::batch`]
+///
+/// At most [`Batch::concurrency`] requests are in flight at any time, sharing the client's connection pool,
+/// and the results are returned in the order the requests were added
+///
+/// ```no_run
+/// use irelia::rest::LcuClient;
+///
+/// # async fn run(client: LcuClient, puuids: Vec<String>) {
+/// let summoners: Vec<Result<serde_json::Value, irelia::Error>> = client
+///     .batch()
+///     .concurrency(2)
+///     .get_all(
+///         puuids
+///             .iter()
+///             .map(|puuid| format!("/lol-summoner/v2/summoners/puuid/{puuid}")),
+///     )
+///     .send()
+///     .await;
+/// # }
+/// ```
+pub struct Batch<'a> {
+    client: &'a LcuClient,
+    requests: Vec<(String, String, Option<Value>)>,
+    concurrency: usize,
+}
+
+impl<'a> Batch<'a> {
+    #[must_use]
+    /// The most requests in flight at once, this is [`DEFAULT_CONCURRENCY`] by default, and is at least 1
+    pub fn concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = concurrency.max(1);
+        self
+    }
+
+    #[must_use]
+    /// Adds a request with any method, see [`LcuClient::lcu_request`]
+    pub fn request(
+        mut self,
+        method: impl Into<String>,
+        endpoint: impl Into<String>,
+        body: Option<Value>,
+    ) -> Self {
+        self.requests.push((method.into(), endpoint.into(), body));
+        self
+    }
+
+    #[must_use]
+    /// Adds a delete request
+    pub fn delete(self, endpoint: impl Into<String>) -> Self {
+        self.request("DELETE", endpoint, None)
+    }
+
+    #[must_use]
+    /// Adds a get request
+    pub fn get(self, endpoint: impl Into<String>) -> Self {
+        self.request("GET", endpoint, None)
+    }
+
+    #[must_use]
+    /// Adds a get request for every endpoint, see the [`Extend`] implementation
+    pub fn get_all<E: Into<String>>(mut self, endpoints: impl IntoIterator<Item = E>) -> Self {
+        self.extend(endpoints);
+        self
+    }
+
+    #[must_use]
+    /// Adds a patch request
+    pub fn patch(self, endpoint: impl Into<String>, body: Value) -> Self {
+        self.request("PATCH", endpoint, Some(body))
+    }
+
+    #[must_use]
+    /// Adds a post request
+    pub fn post(self, endpoint: impl Into<String>, body: Value) -> Self {
+        self.request("POST", endpoint, Some(body))
+    }
+
+    #[must_use]
+    /// Adds a put request
+    pub fn put(self, endpoint: impl Into<String>, body: Value) -> Self {
+        self.request("PUT", endpoint, Some(body))
+    }
+
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.requests.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.requests.is_empty()
+    }
+
+    /// Sends every request, returning one result per request, in the order they were added
+    ///
+    /// A failed request does not stop the rest of the batch
+    pub async fn send<R: DeserializeOwned + Send + 'a>(self) -> Vec<Result<R, Error>> {
+        let client = self.client;
+        let mut results: Vec<Option<Result<R, Error>>> =
+            self.requests.iter().map(|_| None).collect();
+
+        let mut pending = self.requests.into_iter().enumerate();
+        let mut in_flight: Vec<(usize, RequestFuture<'a, R>)> =
+            Vec::with_capacity(self.concurrency);
+
+        std::future::poll_fn(|cx| loop {
+            while in_flight.len() < self.concurrency {
+                let Some((index, (method, endpoint, body))) = pending.next() else {
+                    break;
+                };
+
+                let request =
+                    Box::pin(async move { client.lcu_request(&endpoint, &method, body).await });
+                in_flight.push((index, request));
+            }
+
+            if in_flight.is_empty() {
+                return Poll::Ready(());
+            }
+
+            let before = in_flight.len();
+
+            in_flight.retain_mut(|(index, request)| match request.as_mut().poll(cx) {
+                Poll::Ready(result) => {
+                    results[*index] = Some(result);
+                    false
+                }
+                Poll::Pending => true,
+            });
+
+            // Only loop again if a slot was freed, otherwise every request has registered a wake up
+            if in_flight.len() == before {
+                return Poll::Pending;
+            }
+        })
+        .await;
+
+        results.into_iter().flatten().collect()
+    }
+}
+
+/// Adds a get request for every endpoint
+impl<E: Into<String>> Extend<E> for Batch<'_> {
+    fn extend<I: IntoIterator<Item = E>>(&mut self, endpoints: I) {
+        self.requests.extend(
+            endpoints
+                .into_iter()
+                .map(|endpoint| ("GET".to_owned(), endpoint.into(), None)),
+        );
+    }
+}
+
+impl LcuClient {
+    #[must_use]
+    /// Starts a [`Batch`] of requests, sent together with [`Batch::send`]
+    pub fn batch(&self) -> Batch<'_> {
+        Batch {
+            client: self,
+            requests: Vec::new(),
+            concurrency: DEFAULT_CONCURRENCY,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::rest::unreachable_client;
+    use crate::Error;
+    use serde_json::Value;
+
+    #[tokio::test]
+    async fn test_batch() {
+        // Drop the listener, so the port refuses connections
+        let (_, client) = unreachable_client();
+
+        let mut batch = client
+            .batch()
+            .get_all((0..3).map(|i| format!("/example/{i}")))
+            .post("/example", Value::Null);
+        batch.extend(["/example/3", "/example/4"]);
+        assert_eq!(batch.len(), 6);
+
+        let results = batch.send::<Value>().await;
+        assert_eq!(results.len(), 6);
+        assert!(results
+            .iter()
+            .all(|result| matches!(result, Err(Error::HyperClientError(_)))));
+
+        assert!(client.batch().send::<Value>().await.is_empty());
+    }
+
+    #[cfg(feature = "ws")]
+    #[tokio::test]
+    async fn batch_keeps_order_and_concurrency() {
+        use crate::mock::MockLcu;
+        use hyper::StatusCode;
+        use std::time::Duration;
+
+        let mock = MockLcu::start().unwrap();
+        // The first requests take the longest, so they finish last
+        for i in [0, 1, 2, 4, 5_u64] {
+            mock.on_get(&format!("/example/{i}"))
+                .delay(Duration::from_millis((6 - i) * 20))
+                .respond_json(i);
+        }
+        mock.on_get("/example/3")
+            .delay(Duration::from_millis(60))
+            .respond_error(StatusCode::NOT_FOUND, "Not found");
+
+        let results = mock
+            .client()
+            .batch()
+            .concurrency(2)
+            .get_all((0..6).map(|i| format!("/example/{i}")))
+            .send::<u64>()
+            .await;
+
+        assert_eq!(mock.peak_in_flight(), 2);
+        assert_eq!(mock.requests().len(), 6);
+
+        for (i, result) in (0..6).zip(results) {
+            if i == 3 {
+                assert_eq!(result.unwrap_err().status(), Some(StatusCode::NOT_FOUND));
+            } else {
+                assert_eq!(result.unwrap(), i);
+            }
+        }
+    }
+}
@@ -0,0 +1,223 @@
+//! A blocking version of [`LcuClient`](super::LcuClient), for code that doesn't use an async runtime
+//!
+//! Requests are run on a single threaded runtime owned by the client with `runtime-tokio`, or on the calling thread otherwise,
+//! so these functions must not be called from within an async runtime, or they will panic or block it
+
+use crate::utils::runtime::Runtime;
+use crate::{Error, RequestClient};
+use hyper::http::HeaderValue;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::future::Future;
+use std::net::SocketAddr;
+
+/// A blocking connection to the LCU, with the same methods as the async [`LcuClient`](super::LcuClient)
+///
+/// ```no_run
+/// use irelia::rest::blocking::LcuClient;
+///
+/// let client = LcuClient::connect().unwrap();
+/// let phase: String = client.get("/lol-gameflow/v1/gameflow-phase").unwrap();
+/// ```
+pub struct LcuClient {
+    client: super::LcuClient,
+    runtime: Runtime,
+}
+
+impl LcuClient {
+    /// Wraps an async client, so it can be used without a runtime
+    ///
+    /// # Errors
+    /// This will return an error if the runtime fails to start
+    pub fn from_async(client: super::LcuClient) -> Result<Self, Error> {
+        let runtime = Runtime::new()?;
+
+        Ok(Self { client, runtime })
+    }
+
+    /// Attempts to create a connection to the LCU, see [`super::LcuClient::connect`]
+    ///
+    /// # Errors
+    /// This will return an error if the LCU API is not running, this can include
+    /// the client being down, the lock file being unable to be opened, or the LCU
+    /// not running at all
+    pub fn connect() -> Result<Self, Error> {
+        Self::from_async(super::LcuClient::connect()?)
+    }
+
+    /// Attempts to create a connection to the LCU, see [`super::LcuClient::connect_force_lockfile`]
+    ///
+    /// # Errors
+    /// This will return an error if the LCU API is not running, this can include
+    /// the client being down, the lock file being unable to be opened, or the LCU
+    /// not running at all
+    pub fn connect_force_lockfile(force_lock_file: bool) -> Result<Self, Error> {
+        Self::from_async(super::LcuClient::connect_force_lockfile(force_lock_file)?)
+    }
+
+    /// Attempts to create a connection to the LCU, see [`super::LcuClient::connect_with_request_client`]
+    ///
+    /// # Errors
+    /// This will return an error if the LCU API is not running, this can include
+    /// the client being down, the lock file being unable to be opened, or the LCU
+    /// not running at all
+    pub fn connect_with_request_client(request_client: &RequestClient) -> Result<Self, Error> {
+        Self::from_async(super::LcuClient::connect_with_request_client(
+            request_client,
+        )?)
+    }
+
+    /// Creates a client that implicitly trusts the port and auth string given,
+    /// see [`super::LcuClient::new_with_credentials_with_request_client`]
+    ///
+    /// # Errors
+    /// This will return an error if the runtime fails to start
+    pub fn new_with_credentials_with_request_client(
+        url: impl Into<SocketAddr>,
+        auth_header: HeaderValue,
+        request_client: &RequestClient,
+    ) -> Result<Self, Error> {
+        Self::from_async(super::LcuClient::new_with_credentials_with_request_client(
+            url,
+            auth_header,
+            request_client,
+        ))
+    }
+
+    /// Queries the client or lock file, getting a new url and auth header
+    ///
+    /// # Errors
+    /// This will return an error if the lock file is inaccessible, or if
+    /// the LCU is not running
+    pub fn reconnect(&mut self, force_lock_file: bool) -> Result<(), Error> {
+        self.client.reconnect(force_lock_file)
+    }
+
+    /// Sets the url and auth header according to the auth and port provided
+    pub fn reconnect_with_credentials(&mut self, url: impl Into<SocketAddr>, auth: HeaderValue) {
+        self.client.reconnect_with_credentials(url, auth);
+    }
+
+    #[must_use]
+    /// Returns a reference to the URL in use
+    pub fn url(&self) -> SocketAddr {
+        self.client.url()
+    }
+
+    #[must_use]
+    /// Returns a reference to the auth header in use
+    pub fn auth_header(&self) -> &HeaderValue {
+        self.client.auth_header()
+    }
+
+    #[must_use]
+    /// Returns the async client this wraps
+    pub fn as_async(&self) -> &super::LcuClient {
+        &self.client
+    }
+
+    #[must_use]
+    /// Unwraps the async client, dropping the runtime
+    pub fn into_async(self) -> super::LcuClient {
+        self.client
+    }
+
+    /// Sends a delete request to the LCU
+    ///
+    /// # Errors
+    /// This will return an error if the LCU API is not running, or the provided type is invalid
+    pub fn delete<R: DeserializeOwned>(
+        &self,
+        endpoint: impl AsRef<str> + Send,
+    ) -> Result<R, Error> {
+        self.block_on(self.client.delete(endpoint))
+    }
+
+    /// Sends a get request to the LCU
+    ///
+    /// # Errors
+    /// This will return an error if the LCU API is not running, or the provided type is invalid
+    pub fn get<R: DeserializeOwned>(&self, endpoint: impl AsRef<str> + Send) -> Result<R, Error> {
+        self.block_on(self.client.get(endpoint))
+    }
+
+    /// Sends a patch request to the LCU
+    ///
+    /// # Errors
+    /// This will return an error if the LCU API is not running, or the provided type or body is invalid
+    pub fn patch<T: Serialize + Send, R: DeserializeOwned>(
+        &self,
+        endpoint: impl AsRef<str> + Send,
+        body: T,
+    ) -> Result<R, Error> {
+        self.block_on(self.client.patch(endpoint, body))
+    }
+
+    /// Sends a post request to the LCU
+    ///
+    /// # Errors
+    /// This will return an error if the LCU API is not running, or the provided type or body is invalid
+    pub fn post<T: Serialize + Send, R: DeserializeOwned>(
+        &self,
+        endpoint: impl AsRef<str> + Send,
+        body: T,
+    ) -> Result<R, Error> {
+        self.block_on(self.client.post(endpoint, body))
+    }
+
+    /// Sends a put request to the LCU
+    ///
+    /// # Errors
+    /// This will return an error if the LCU API is not running, or the provided type or body is invalid
+    pub fn put<T: Serialize + Send, R: DeserializeOwned>(
+        &self,
+        endpoint: impl AsRef<str> + Send,
+        body: T,
+    ) -> Result<R, Error> {
+        self.block_on(self.client.put(endpoint, body))
+    }
+
+    /// Makes a request to the LCU with an unspecified method, see [`super::LcuClient::lcu_request`]
+    ///
+    /// # Errors
+    /// This will return an error if the LCU API is not running, or the provided type or body is invalid
+    pub fn lcu_request<T: Serialize + Send, R: DeserializeOwned>(
+        &self,
+        endpoint: &str,
+        method: &str,
+        body: Option<T>,
+    ) -> Result<R, Error> {
+        self.block_on(self.client.lcu_request(endpoint, method, body))
+    }
+
+    fn block_on<T>(&self, future: impl Future<Output = T>) -> T {
+        self.runtime.block_on(future)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::LcuClient;
+    use crate::rest::unreachable_client;
+    use crate::Error;
+
+    #[test]
+    fn test_blocking() {
+        // Drop the listener, so the port refuses connections
+        let (_, client) = unreachable_client();
+        let addr = client.url();
+        let client = LcuClient::from_async(client).unwrap();
+
+        let result = client.get::<serde_json::Value>("/example");
+        assert!(matches!(result, Err(Error::HyperClientError(_))));
+        assert_eq!(client.url(), addr);
+    }
+
+    #[ignore = "This is only needed for testing, and doesn't need to be run all the time"]
+    #[test]
+    fn test_blocking_live() {
+        let client = LcuClient::connect().unwrap();
+        let phase: serde_json::Value = client.get("/lol-gameflow/v1/gameflow-phase").unwrap();
+        println!("{phase}");
+    }
+}
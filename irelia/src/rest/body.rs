@@ -0,0 +1,230 @@
+//! Requests with bodies that aren't msgpack, such as images, or `multipart/form-data` uploads
+
+use super::LcuClient;
+use crate::Error;
+use http_body_util::Full;
+use hyper::body::Bytes;
+use hyper::http::{HeaderMap, HeaderValue};
+use serde::de::DeserializeOwned;
+use std::fmt::Write;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// A `multipart/form-data` body, sent with [`LcuClient::multipart`]
+///
+/// ```no_run
+/// use irelia::rest::{body::Multipart, LcuClient};
+///
+/// # async fn run(client: LcuClient) -> Result<(), irelia::Error> {
+/// let form = Multipart::new()
+///     .text("title", "My item set")
+///     .file("file", "item_set.json", "application/json", std::fs::read("item_set.json")?);
+///
+/// let response: serde_json::Value = client.multipart("/example", "POST", form).await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Multipart {
+    parts: Vec<Part>,
+}
+
+#[derive(Debug, Clone)]
+struct Part {
+    name: String,
+    file_name: Option<String>,
+    content_type: Option<String>,
+    data: Bytes,
+}
+
+impl Multipart {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    #[must_use]
+    /// Adds a plain text field
+    pub fn text(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.parts.push(Part {
+            name: name.into(),
+            file_name: None,
+            content_type: None,
+            data: Bytes::from(value.into()),
+        });
+        self
+    }
+
+    #[must_use]
+    /// Adds a file, `content_type` being the type of `data`, such as `image/png`
+    pub fn file(
+        mut self,
+        name: impl Into<String>,
+        file_name: impl Into<String>,
+        content_type: impl Into<String>,
+        data: impl Into<Bytes>,
+    ) -> Self {
+        self.parts.push(Part {
+            name: name.into(),
+            file_name: Some(file_name.into()),
+            content_type: Some(content_type.into()),
+            data: data.into(),
+        });
+        self
+    }
+
+    /// Encodes the form, returning the `Content-Type`, which holds the boundary, and the body
+    ///
+    /// # Errors
+    /// This will return an error if a file's content type is not a valid header value
+    pub fn encode(self) -> Result<(HeaderValue, Bytes), Error> {
+        let boundary = self.boundary();
+        let mut body = Vec::new();
+
+        for part in self.parts {
+            body.extend_from_slice(format!("--{boundary}\r\n").as_bytes());
+
+            let mut disposition = format!("form-data; name=\"{}\"", escape(&part.name));
+            if let Some(file_name) = &part.file_name {
+                let _ = write!(disposition, "; filename=\"{}\"", escape(file_name));
+            }
+            body.extend_from_slice(format!("Content-Disposition: {disposition}\r\n").as_bytes());
+
+            if let Some(content_type) = &part.content_type {
+                HeaderValue::from_str(content_type)?;
+                body.extend_from_slice(format!("Content-Type: {content_type}\r\n").as_bytes());
+            }
+
+            body.extend_from_slice(b"\r\n");
+            body.extend_from_slice(&part.data);
+            body.extend_from_slice(b"\r\n");
+        }
+
+        body.extend_from_slice(format!("--{boundary}--\r\n").as_bytes());
+
+        let content_type =
+            HeaderValue::from_str(&format!("multipart/form-data; boundary={boundary}"))?;
+        Ok((content_type, Bytes::from(body)))
+    }
+
+    /// Picks a boundary that doesn't appear in any of the parts
+    fn boundary(&self) -> String {
+        static COUNTER: AtomicU32 = AtomicU32::new(0);
+
+        let nanos = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |elapsed| elapsed.subsec_nanos());
+
+        loop {
+            let count = COUNTER.fetch_add(1, Ordering::Relaxed);
+            let boundary = format!("irelia-{nanos:08x}{count:08x}");
+
+            let collides = self.parts.iter().any(|part| {
+                part.data
+                    .windows(boundary.len())
+                    .any(|window| window == boundary.as_bytes())
+            });
+
+            if !collides {
+                return boundary;
+            }
+        }
+    }
+}
+
+/// Escapes a field or file name, the same way browsers do
+fn escape(name: &str) -> String {
+    name.replace('"', "%22")
+        .replace('\r', "%0D")
+        .replace('\n', "%0A")
+}
+
+impl LcuClient {
+    /// Makes a request with a body that's already encoded, `content_type` being its type, such as `image/png`
+    ///
+    /// The response is deserialized the same way as [`LcuClient::lcu_request`]
+    ///
+    /// # Errors
+    /// This will return an error if the LCU API is not running, or the provided type is invalid
+    pub async fn request_raw<R: DeserializeOwned>(
+        &self,
+        endpoint: impl AsRef<str> + Send,
+        method: &str,
+        content_type: HeaderValue,
+        body: impl Into<Bytes>,
+    ) -> Result<R, Error> {
+        let request = self.request_client.bytes_request(
+            self.url,
+            endpoint.as_ref(),
+            method,
+            Some(Full::new(body.into())),
+            Some(&self.auth_header),
+            content_type,
+            HeaderMap::new(),
+        );
+
+        let response = self
+            .timed(request)
+            .await
+            .and_then(|buf| Ok(rmp_serde::from_slice(&buf)?));
+
+        self.finish(response).await
+    }
+
+    /// Sends a `multipart/form-data` body, see [`Multipart`]
+    ///
+    /// # Errors
+    /// This will return an error if the LCU API is not running, the form could not be encoded,
+    /// or the provided type is invalid
+    pub async fn multipart<R: DeserializeOwned>(
+        &self,
+        endpoint: impl AsRef<str> + Send,
+        method: &str,
+        form: Multipart,
+    ) -> Result<R, Error> {
+        let (content_type, body) = form.encode()?;
+        self.request_raw(endpoint, method, content_type, body).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Multipart;
+
+    #[test]
+    fn test_multipart() {
+        let (content_type, body) = Multipart::new()
+            .text("title", "My \"set\"")
+            .file("file", "set.json", "application/json", &b"{}"[..])
+            .encode()
+            .unwrap();
+
+        let boundary = content_type
+            .to_str()
+            .unwrap()
+            .strip_prefix("multipart/form-data; boundary=")
+            .unwrap()
+            .to_string();
+
+        let expected = format!(
+            "--{boundary}\r\n\
+            Content-Disposition: form-data; name=\"title\"\r\n\
+            \r\n\
+            My \"set\"\r\n\
+            --{boundary}\r\n\
+            Content-Disposition: form-data; name=\"file\"; filename=\"set.json\"\r\n\
+            Content-Type: application/json\r\n\
+            \r\n\
+            {{}}\r\n\
+            --{boundary}--\r\n"
+        );
+
+        assert_eq!(body, expected.as_bytes());
+    }
+
+    #[test]
+    fn test_invalid_content_type() {
+        let form = Multipart::new().file("file", "a.txt", "text/plain\r\nX-Evil: 1", &b""[..]);
+        assert!(form.encode().is_err());
+    }
+}
@@ -0,0 +1,273 @@
+//! Caching the responses of get requests, for data that rarely changes, such as `/lol-game-data/assets`
+//!
+//! Nothing is cached unless a route is given a time to live, so endpoints that change, such as the
+//! gameflow phase, are always fetched from the client
+
+use hyper::body::Bytes;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::{Duration, Instant};
+
+/// How many responses are kept by default, see [`ResponseCache::max_entries`]
+pub const DEFAULT_MAX_ENTRIES: usize = 1024;
+
+#[derive(Debug)]
+struct Entry {
+    /// `None` when the time to live is too long to be added to the current time, so it never expires
+    expires: Option<Instant>,
+    body: Bytes,
+}
+
+impl Entry {
+    fn is_fresh(&self, now: Instant) -> bool {
+        self.expires.map_or(true, |expires| expires > now)
+    }
+}
+
+/// A cache for the responses of get requests, set with [`super::LcuClientBuilder::cache`]
+///
+/// Responses are keyed by their path and query, and kept for as long as the time to live of the
+/// longest route that's a prefix of the path. Only successful responses are cached,
+/// and the cache is cleared when the client is reconnected
+///
+/// ```no_run
+/// use irelia::rest::{cache::ResponseCache, LcuClient};
+/// use std::time::Duration;
+///
+/// let client = LcuClient::builder()
+///     .cache(
+///         ResponseCache::new()
+///             .route("/lol-game-data/assets/", Duration::from_secs(60 * 60))
+///             .route("/lol-champions/v1/inventories/", Duration::from_secs(30)),
+///     )
+///     .build()
+///     .unwrap();
+/// ```
+///
+/// Clones share the same cached responses
+#[derive(Debug, Clone)]
+pub struct ResponseCache {
+    routes: Vec<(String, Duration)>,
+    default_ttl: Option<Duration>,
+    max_entries: usize,
+    entries: Arc<Mutex<HashMap<String, Entry>>>,
+}
+
+impl Default for ResponseCache {
+    fn default() -> Self {
+        Self {
+            routes: Vec::new(),
+            default_ttl: None,
+            max_entries: DEFAULT_MAX_ENTRIES,
+            entries: Arc::default(),
+        }
+    }
+}
+
+impl ResponseCache {
+    #[must_use]
+    /// A cache with no routes, which doesn't cache anything until [`ResponseCache::route`] is used
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    #[must_use]
+    /// Keeps responses for paths starting with `prefix` for `ttl`, a `ttl` of zero never caches them
+    ///
+    /// When more than one route matches, the longest one is used
+    pub fn route(mut self, prefix: impl Into<String>, ttl: Duration) -> Self {
+        let prefix = prefix.into();
+        self.routes.retain(|(route, _)| *route != prefix);
+        self.routes.push((prefix, ttl));
+        self
+    }
+
+    #[must_use]
+    /// Keeps responses for paths that don't match any route for `ttl`
+    pub const fn default_ttl(mut self, ttl: Duration) -> Self {
+        self.default_ttl = Some(ttl);
+        self
+    }
+
+    #[must_use]
+    /// The most responses kept at once, once full the response closest to expiring is removed
+    pub const fn max_entries(mut self, max_entries: usize) -> Self {
+        self.max_entries = max_entries;
+        self
+    }
+
+    #[must_use]
+    /// How long a response for `endpoint` is kept, `None` if it isn't cached
+    pub fn ttl(&self, endpoint: &str) -> Option<Duration> {
+        let path = endpoint.split_once('?').map_or(endpoint, |(path, _)| path);
+
+        self.routes
+            .iter()
+            .filter(|(prefix, _)| path.starts_with(prefix.as_str()))
+            .max_by_key(|(prefix, _)| prefix.len())
+            .map(|(_, ttl)| *ttl)
+            .or(self.default_ttl)
+            .filter(|ttl| !ttl.is_zero())
+    }
+
+    #[must_use]
+    /// The cached response for `endpoint`, if there is one that hasn't expired
+    pub fn get(&self, endpoint: &str) -> Option<Bytes> {
+        let mut entries = self.lock();
+        let entry = entries.get(endpoint)?;
+
+        if entry.is_fresh(Instant::now()) {
+            return Some(entry.body.clone());
+        }
+
+        entries.remove(endpoint);
+        None
+    }
+
+    /// Caches `body` as the response for `endpoint`, if it has a route
+    pub fn insert(&self, endpoint: &str, body: Bytes) {
+        let Some(ttl) = self.ttl(endpoint) else {
+            return;
+        };
+
+        if self.max_entries == 0 {
+            return;
+        }
+
+        let now = Instant::now();
+        let mut entries = self.lock();
+
+        if entries.len() >= self.max_entries && !entries.contains_key(endpoint) {
+            entries.retain(|_, entry| entry.is_fresh(now));
+        }
+
+        if entries.len() >= self.max_entries && !entries.contains_key(endpoint) {
+            let oldest = entries
+                .iter()
+                // Entries that never expire sort after every other
+                .min_by_key(|(_, entry)| (entry.expires.is_none(), entry.expires))
+                .map(|(key, _)| key.clone());
+
+            if let Some(oldest) = oldest {
+                entries.remove(&oldest);
+            }
+        }
+
+        entries.insert(
+            endpoint.to_string(),
+            Entry {
+                expires: now.checked_add(ttl),
+                body,
+            },
+        );
+    }
+
+    /// Removes the cached response for `endpoint`, so the next request fetches it again
+    pub fn invalidate(&self, endpoint: &str) {
+        self.lock().remove(endpoint);
+    }
+
+    /// Removes every cached response
+    pub fn clear(&self) {
+        self.lock().clear();
+    }
+
+    #[must_use]
+    /// How many responses are cached, including any that have expired, but haven't been removed yet
+    pub fn len(&self) -> usize {
+        self.lock().len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<String, Entry>> {
+        // The map is never left half updated, so it's still usable if another thread panicked
+        self.entries.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ResponseCache;
+    use hyper::body::Bytes;
+    use std::time::Duration;
+
+    #[test]
+    fn test_ttl() {
+        let cache = ResponseCache::new()
+            .route("/lol-game-data/assets/", Duration::from_secs(60))
+            .route(
+                "/lol-game-data/assets/v1/champion-summary.json",
+                Duration::from_secs(5),
+            )
+            .route("/lol-gameflow/", Duration::ZERO);
+
+        assert_eq!(
+            cache.ttl("/lol-game-data/assets/v1/profile-icons/1.jpg"),
+            Some(Duration::from_secs(60))
+        );
+        assert_eq!(
+            cache.ttl("/lol-game-data/assets/v1/champion-summary.json?x=1"),
+            Some(Duration::from_secs(5))
+        );
+        assert_eq!(cache.ttl("/lol-gameflow/v1/gameflow-phase"), None);
+        assert_eq!(cache.ttl("/lol-summoner/v1/current-summoner"), None);
+
+        let cache = cache.default_ttl(Duration::from_secs(1));
+        assert_eq!(
+            cache.ttl("/lol-summoner/v1/current-summoner"),
+            Some(Duration::from_secs(1))
+        );
+        assert_eq!(cache.ttl("/lol-gameflow/v1/gameflow-phase"), None);
+    }
+
+    #[test]
+    fn test_entries() {
+        let cache = ResponseCache::new()
+            .route("/cached/", Duration::from_secs(60))
+            .route("/expired/", Duration::from_nanos(1))
+            .max_entries(2);
+
+        cache.insert("/uncached", Bytes::from_static(b"a"));
+        assert!(cache.is_empty());
+
+        cache.insert("/cached/1", Bytes::from_static(b"1"));
+        assert_eq!(cache.get("/cached/1"), Some(Bytes::from_static(b"1")));
+        assert_eq!(cache.get("/cached/1?query"), None);
+
+        cache.insert("/expired/1", Bytes::from_static(b"2"));
+        std::thread::sleep(Duration::from_millis(1));
+        assert_eq!(cache.get("/expired/1"), None);
+
+        cache.insert("/cached/2", Bytes::from_static(b"2"));
+        cache.insert("/cached/3", Bytes::from_static(b"3"));
+        assert_eq!(cache.len(), 2);
+        assert_eq!(cache.get("/cached/1"), None);
+        assert!(cache.get("/cached/3").is_some());
+
+        cache.clone().invalidate("/cached/3");
+        assert_eq!(cache.get("/cached/3"), None);
+
+        cache.clear();
+        assert!(cache.is_empty());
+    }
+
+    #[test]
+    fn test_never_expires() {
+        let cache = ResponseCache::new()
+            .route("/forever/", Duration::MAX)
+            .route("/cached/", Duration::from_secs(60))
+            .max_entries(2);
+
+        cache.insert("/forever/1", Bytes::from_static(b"1"));
+        assert_eq!(cache.get("/forever/1"), Some(Bytes::from_static(b"1")));
+
+        cache.insert("/cached/1", Bytes::from_static(b"2"));
+        cache.insert("/cached/2", Bytes::from_static(b"3"));
+        assert_eq!(cache.get("/forever/1"), Some(Bytes::from_static(b"1")));
+        assert_eq!(cache.get("/cached/1"), None);
+    }
+}
@@ -0,0 +1,565 @@
+//! Typed wrappers around the `/lol-champ-select/v1/session` endpoint
+//!
+//! The session is sent in full on every update, [`ChampSelectTracker`] turns these snapshots into what actually changed.
+//! [`ChampSelect`] finds the local player's actions in the session, to hover, pick, and ban with
+
+use super::LcuClient;
+use crate::Error;
+use serde_derive::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+#[cfg(feature = "ws")]
+use crate::ws::{
+    types::{Event, EventKind},
+    EventStream, LcuWebSocket, StreamEvent, SubscriberID, WebSocketError,
+};
+#[cfg(feature = "ws")]
+use futures_core::Stream;
+#[cfg(feature = "ws")]
+use std::pin::Pin;
+#[cfg(feature = "ws")]
+use std::task::{Context, Poll};
+
+/// The endpoint the champ select session is served from, and the uri of its websocket event
+pub const CHAMP_SELECT_SESSION_ENDPOINT: &str = "/lol-champ-select/v1/session";
+
+/// The state of the current champ select
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChampSelectSession {
+    /// Every pick and ban, grouped by the turn they happen on
+    #[serde(default)]
+    pub actions: Vec<Vec<ChampSelectAction>>,
+    #[serde(default)]
+    pub my_team: Vec<ChampSelectPlayer>,
+    #[serde(default)]
+    pub their_team: Vec<ChampSelectPlayer>,
+    pub local_player_cell_id: i64,
+    pub timer: ChampSelectTimer,
+}
+
+impl ChampSelectSession {
+    /// The actions the local player has to make, in the order they happen
+    pub fn my_actions(&self) -> impl Iterator<Item = &ChampSelectAction> {
+        self.actions
+            .iter()
+            .flatten()
+            .filter(|action| action.actor_cell_id == self.local_player_cell_id)
+    }
+
+    #[must_use]
+    /// The local player's next action of `action_type`, such as `pick` or `ban`, that is not completed yet
+    ///
+    /// The one in progress is preferred, so during planning, this is the pick that's coming up
+    pub fn my_next_action(&self, action_type: &str) -> Option<&ChampSelectAction> {
+        let mut pending = self
+            .my_actions()
+            .filter(|action| !action.completed && action.action_type == action_type);
+
+        let first = pending.next()?;
+        if first.is_in_progress {
+            return Some(first);
+        }
+
+        Some(
+            pending
+                .find(|action| action.is_in_progress)
+                .unwrap_or(first),
+        )
+    }
+}
+
+/// A single pick or ban
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChampSelectAction {
+    pub id: i64,
+    pub actor_cell_id: i64,
+    /// The champion hovered or locked in, `0` if none has been selected yet
+    pub champion_id: i32,
+    pub completed: bool,
+    #[serde(default)]
+    pub is_in_progress: bool,
+    /// The kind of action, such as `pick`, `ban`, or `ten_bans_reveal`
+    #[serde(rename = "type")]
+    pub action_type: String,
+}
+
+/// A player in champ select
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChampSelectPlayer {
+    pub cell_id: i64,
+    pub champion_id: i32,
+    #[serde(default)]
+    pub champion_pick_intent: i32,
+    #[serde(default)]
+    pub summoner_id: u64,
+    /// The position the player was assigned, empty in modes without positions
+    #[serde(default)]
+    pub assigned_position: String,
+}
+
+/// The timer for the current phase of champ select
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChampSelectTimer {
+    /// The current phase, such as `PLANNING`, `BAN_PICK`, `FINALIZATION`, or `GAME_STARTING`
+    pub phase: String,
+    #[serde(default)]
+    pub adjusted_time_left_in_phase: i64,
+}
+
+/// A champion being hovered, picked, or banned by a player
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ChampionAction {
+    pub cell_id: i64,
+    pub champion_id: i32,
+}
+
+/// What changed between two champ select sessions
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ChampSelectDelta {
+    /// The new phase, if it changed
+    pub phase: Option<String>,
+    /// Champions newly hovered during a pick that is not completed yet
+    pub hovers: Vec<ChampionAction>,
+    /// Picks that were locked in
+    pub picks: Vec<ChampionAction>,
+    /// Bans that were completed
+    pub bans: Vec<ChampionAction>,
+}
+
+impl ChampSelectDelta {
+    #[must_use]
+    /// Returns true if nothing tracked changed
+    pub fn is_empty(&self) -> bool {
+        self.phase.is_none()
+            && self.hovers.is_empty()
+            && self.picks.is_empty()
+            && self.bans.is_empty()
+    }
+}
+
+/// Holds the last champ select session, producing a [`ChampSelectDelta`] for each new one
+///
+/// ```no_run
+/// use irelia::rest::champ_select::{ChampSelectTracker, CHAMP_SELECT_SESSION_ENDPOINT};
+/// use irelia::ws::{types::{Event, EventKind}, LcuWebSocket};
+///
+/// let mut ws = LcuWebSocket::new();
+/// let mut tracker = ChampSelectTracker::new();
+///
+/// ws.subscribe(
+///     EventKind::json_api_event_callback_str(CHAMP_SELECT_SESSION_ENDPOINT),
+///     move |event: &Event| {
+///         if let Ok(Some(delta)) = tracker.on_event(event) {
+///             println!("{delta:?}");
+///         }
+///     },
+/// );
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct ChampSelectTracker {
+    session: Option<ChampSelectSession>,
+}
+
+impl ChampSelectTracker {
+    #[must_use]
+    pub const fn new() -> Self {
+        Self { session: None }
+    }
+
+    #[must_use]
+    /// The last session seen, `None` if there is no champ select in progress
+    pub const fn session(&self) -> Option<&ChampSelectSession> {
+        self.session.as_ref()
+    }
+
+    /// Forgets the last session, such as when champ select ends
+    pub fn reset(&mut self) {
+        self.session = None;
+    }
+
+    /// Stores `session`, returning what changed since the previous one
+    ///
+    /// If there was no previous session, everything in `session` is treated as new
+    pub fn update(&mut self, session: ChampSelectSession) -> ChampSelectDelta {
+        let delta = diff(self.session.as_ref(), &session);
+        self.session = Some(session);
+        delta
+    }
+
+    #[cfg(feature = "ws")]
+    /// Updates the tracker from a session event, resetting it when the session is deleted
+    ///
+    /// Returns `None` if the event was a deletion
+    ///
+    /// # Errors
+    /// This will return an error if the event data is not a champ select session
+    pub fn on_event(
+        &mut self,
+        event: &Event,
+    ) -> Result<Option<ChampSelectDelta>, serde_json::Error> {
+        if event.2.event_type == "Delete" {
+            self.reset();
+            return Ok(None);
+        }
+
+        let session = serde::Deserialize::deserialize(&event.2.data)?;
+        Ok(Some(self.update(session)))
+    }
+}
+
+fn diff(previous: Option<&ChampSelectSession>, current: &ChampSelectSession) -> ChampSelectDelta {
+    let previous_actions: HashMap<i64, &ChampSelectAction> = previous
+        .into_iter()
+        .flat_map(|session| session.actions.iter().flatten())
+        .map(|action| (action.id, action))
+        .collect();
+
+    let mut delta = ChampSelectDelta {
+        phase: (previous.map(|session| &session.timer.phase) != Some(&current.timer.phase))
+            .then(|| current.timer.phase.clone()),
+        ..ChampSelectDelta::default()
+    };
+
+    for action in current.actions.iter().flatten() {
+        let previous = previous_actions.get(&action.id);
+        let champion = ChampionAction {
+            cell_id: action.actor_cell_id,
+            champion_id: action.champion_id,
+        };
+
+        if action.completed {
+            if previous.is_some_and(|previous| previous.completed) {
+                continue;
+            }
+
+            match action.action_type.as_str() {
+                "pick" => delta.picks.push(champion),
+                "ban" => delta.bans.push(champion),
+                _ => {}
+            }
+        } else if action.action_type == "pick"
+            && action.champion_id != 0
+            && !previous.is_some_and(|previous| previous.champion_id == action.champion_id)
+        {
+            delta.hovers.push(champion);
+        }
+    }
+
+    delta
+}
+
+impl LcuClient {
+    /// Gets the current champ select session
+    ///
+    /// # Errors
+    /// This will return an error if the LCU API is not running, or the client is not in champ select
+    pub async fn champ_select_session(&self) -> Result<ChampSelectSession, Error> {
+        self.get(CHAMP_SELECT_SESSION_ENDPOINT).await
+    }
+
+    #[must_use]
+    /// Helpers for acting as the local player in champ select, see [`ChampSelect`]
+    pub const fn champ_select(&self) -> ChampSelect<'_> {
+        ChampSelect { client: self }
+    }
+}
+
+/// Acts as the local player in champ select, created with [`LcuClient::champ_select`]
+///
+/// Each of these fetches the session first, to find the action to make
+///
+/// ```no_run
+/// use irelia::rest::LcuClient;
+///
+/// # async fn pick() -> Result<(), irelia::Error> {
+/// let client = LcuClient::connect()?;
+/// let champ_select = client.champ_select();
+///
+/// champ_select.ban(157).await?;
+/// champ_select.pick(103).await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Copy)]
+pub struct ChampSelect<'a> {
+    client: &'a LcuClient,
+}
+
+impl ChampSelect<'_> {
+    /// Gets the current champ select session
+    ///
+    /// # Errors
+    /// This will return an error if the LCU API is not running, or the client is not in champ select
+    pub async fn current_session(&self) -> Result<ChampSelectSession, Error> {
+        self.client.champ_select_session().await
+    }
+
+    /// Gets the actions the local player has to make, in the order they happen
+    ///
+    /// # Errors
+    /// This will return an error if the LCU API is not running, or the client is not in champ select
+    pub async fn my_actions(&self) -> Result<Vec<ChampSelectAction>, Error> {
+        let session = self.current_session().await?;
+        Ok(session.my_actions().cloned().collect())
+    }
+
+    /// Hovers `champion_id` for the local player's next pick, without locking it in
+    ///
+    /// # Errors
+    /// This will return an error if the LCU API is not running, the client is not in champ select,
+    /// or the local player has no pick left
+    pub async fn hover(&self, champion_id: i32) -> Result<(), Error> {
+        self.act("pick", champion_id, false).await
+    }
+
+    /// Locks in `champion_id` for the local player's current pick
+    ///
+    /// # Errors
+    /// This will return an error if the LCU API is not running, the client is not in champ select,
+    /// the local player has no pick left, or the LCU rejects it, such as when it's not their turn
+    pub async fn pick(&self, champion_id: i32) -> Result<(), Error> {
+        self.act("pick", champion_id, true).await
+    }
+
+    /// Bans `champion_id` with the local player's current ban
+    ///
+    /// # Errors
+    /// This will return an error if the LCU API is not running, the client is not in champ select,
+    /// the local player has no ban left, or the LCU rejects it, such as when it's not their turn
+    pub async fn ban(&self, champion_id: i32) -> Result<(), Error> {
+        self.act("ban", champion_id, true).await
+    }
+
+    async fn act(&self, action_type: &str, champion_id: i32, completed: bool) -> Result<(), Error> {
+        let session = self.current_session().await?;
+        let action = session.my_next_action(action_type).ok_or_else(|| {
+            Error::Unavailable(format!(
+                "the local player has no {action_type} left to make"
+            ))
+        })?;
+
+        let endpoint = format!("{CHAMP_SELECT_SESSION_ENDPOINT}/actions/{}", action.id);
+        let body = ActionUpdate {
+            champion_id,
+            completed,
+        };
+
+        // The response is empty when the action was updated
+        self.client
+            .lcu_request_empty(&endpoint, "PATCH", Some(body))
+            .await
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ActionUpdate {
+    champion_id: i32,
+    completed: bool,
+}
+
+#[cfg(feature = "ws")]
+/// What a [`ChampSelectUpdates`] stream yields
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ChampSelectUpdate {
+    /// The session changed, `delta` is what changed since the last one
+    Session {
+        session: Box<ChampSelectSession>,
+        delta: ChampSelectDelta,
+    },
+    /// Champ select ended, either because the game is starting, or someone dodged
+    Ended,
+    /// The connection was lost, see [`StreamEvent::ConnectionLost`]
+    ConnectionLost,
+    /// The connection was established again, the next session is compared against nothing,
+    /// as updates in between were missed, see [`StreamEvent::Reconnected`]
+    Reconnected,
+}
+
+#[cfg(feature = "ws")]
+/// Champ select sessions as they're updated, with what changed between them
+///
+/// ```no_run
+/// use futures_util::StreamExt;
+/// use irelia::rest::champ_select::{ChampSelectUpdate, ChampSelectUpdates};
+/// use irelia::ws::LcuWebSocket;
+///
+/// # async fn run() {
+/// let mut ws = LcuWebSocket::new();
+/// let (_, mut updates) = ChampSelectUpdates::subscribe(&mut ws).unwrap();
+///
+/// while let Some(Ok(update)) = updates.next().await {
+///     if let ChampSelectUpdate::Session { delta, .. } = update {
+///         println!("{:?} was picked", delta.picks);
+///     }
+/// }
+/// # }
+/// ```
+pub struct ChampSelectUpdates {
+    events: EventStream<Option<ChampSelectSession>>,
+    tracker: ChampSelectTracker,
+}
+
+#[cfg(feature = "ws")]
+impl ChampSelectUpdates {
+    /// Subscribes to the champ select session on `ws`
+    ///
+    /// Returns `None` is the websocket connection has already been closed previously
+    pub fn subscribe(ws: &mut LcuWebSocket) -> Option<(SubscriberID, Self)> {
+        let event_kind = EventKind::json_api_event_callback_str(CHAMP_SELECT_SESSION_ENDPOINT);
+        let (id, events) = ws.subscribe_stream(event_kind)?;
+
+        Some((
+            id,
+            Self {
+                events,
+                tracker: ChampSelectTracker::new(),
+            },
+        ))
+    }
+
+    #[must_use]
+    /// The last session seen, `None` if there is no champ select in progress
+    pub const fn session(&self) -> Option<&ChampSelectSession> {
+        self.tracker.session()
+    }
+}
+
+#[cfg(feature = "ws")]
+impl Stream for ChampSelectUpdates {
+    type Item = Result<ChampSelectUpdate, WebSocketError>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+
+        let event = match Pin::new(&mut this.events).poll_next(cx) {
+            Poll::Ready(Some(Ok(event))) => event,
+            Poll::Ready(Some(Err(err))) => return Poll::Ready(Some(Err(err))),
+            Poll::Ready(None) => return Poll::Ready(None),
+            Poll::Pending => return Poll::Pending,
+        };
+
+        let update = match event {
+            StreamEvent::Event(event) => match event.data {
+                Some(session) if event.event_type != "Delete" => {
+                    let delta = this.tracker.update(session.clone());
+                    ChampSelectUpdate::Session {
+                        session: Box::new(session),
+                        delta,
+                    }
+                }
+                _ => {
+                    this.tracker.reset();
+                    ChampSelectUpdate::Ended
+                }
+            },
+            StreamEvent::ConnectionLost => ChampSelectUpdate::ConnectionLost,
+            StreamEvent::Reconnected => {
+                this.tracker.reset();
+                ChampSelectUpdate::Reconnected
+            }
+        };
+
+        Poll::Ready(Some(Ok(update)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ChampSelectSession, ChampSelectTracker, ChampionAction};
+    use crate::rest::LcuClient;
+    use serde_json::json;
+
+    fn session(phase: &str, actions: &serde_json::Value) -> ChampSelectSession {
+        serde_json::from_value(json!({
+            "actions": actions,
+            "localPlayerCellId": 0,
+            "timer": { "phase": phase },
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_tracker() {
+        let mut tracker = ChampSelectTracker::new();
+
+        let delta = tracker.update(session(
+            "BAN_PICK",
+            &json!([[
+                { "id": 1, "actorCellId": 0, "championId": 0, "completed": false, "type": "ban" },
+                { "id": 2, "actorCellId": 5, "championId": 0, "completed": false, "type": "pick" },
+            ]]),
+        ));
+        assert_eq!(delta.phase.as_deref(), Some("BAN_PICK"));
+        assert!(delta.hovers.is_empty() && delta.picks.is_empty() && delta.bans.is_empty());
+
+        let delta = tracker.update(session(
+            "BAN_PICK",
+            &json!([[
+                { "id": 1, "actorCellId": 0, "championId": 157, "completed": true, "type": "ban" },
+                { "id": 2, "actorCellId": 5, "championId": 103, "completed": false, "type": "pick" },
+            ]]),
+        ));
+        let champion = |cell_id, champion_id| ChampionAction {
+            cell_id,
+            champion_id,
+        };
+        assert_eq!(delta.phase, None);
+        assert_eq!(delta.bans, [champion(0, 157)]);
+        assert_eq!(delta.hovers, [champion(5, 103)]);
+
+        let delta = tracker.update(session(
+            "FINALIZATION",
+            &json!([[
+                { "id": 1, "actorCellId": 0, "championId": 157, "completed": true, "type": "ban" },
+                { "id": 2, "actorCellId": 5, "championId": 103, "completed": true, "type": "pick" },
+            ]]),
+        ));
+        assert_eq!(delta.phase.as_deref(), Some("FINALIZATION"));
+        assert!(delta.bans.is_empty() && delta.hovers.is_empty());
+        assert_eq!(delta.picks, [champion(5, 103)]);
+
+        let delta = tracker.update(tracker.session().unwrap().clone());
+        assert!(delta.is_empty());
+    }
+
+    #[test]
+    fn test_my_next_action() {
+        let session = session(
+            "BAN_PICK",
+            &json!([
+                [
+                    { "id": 1, "actorCellId": 0, "championId": 157, "completed": true, "type": "ban" },
+                    { "id": 2, "actorCellId": 3, "championId": 0, "completed": false, "type": "ban" },
+                ],
+                [
+                    { "id": 3, "actorCellId": 0, "championId": 0, "completed": false, "type": "pick" },
+                    { "id": 4, "actorCellId": 0, "championId": 0, "completed": false, "isInProgress": true, "type": "pick" },
+                ],
+            ]),
+        );
+
+        let ids: Vec<_> = session.my_actions().map(|action| action.id).collect();
+        assert_eq!(ids, [1, 3, 4]);
+
+        assert_eq!(
+            session.my_next_action("pick").map(|action| action.id),
+            Some(4)
+        );
+        assert_eq!(session.my_next_action("ban"), None);
+    }
+
+    #[ignore = "This is only needed for testing, and doesn't need to be run all the time"]
+    #[tokio::test]
+    async fn test_champ_select() {
+        let client = LcuClient::connect().unwrap();
+        let champ_select = client.champ_select();
+
+        println!("{:?}", champ_select.my_actions().await);
+        println!("{:?}", champ_select.hover(103).await);
+    }
+}
@@ -0,0 +1,242 @@
+//! Typed wrappers around the `/lol-champions/v1` and `/lol-inventory/v2` endpoints
+//!
+//! Every champion is listed whether it's owned or not, [`ChampionCollection`] filters them by ownership
+
+use super::LcuClient;
+use crate::Error;
+use serde_derive::{Deserialize, Serialize};
+
+/// The endpoint the champions of `summoner_id` are served from, with their skins and chromas
+#[must_use]
+pub fn champions_endpoint(summoner_id: u64) -> String {
+    format!("/lol-champions/v1/inventories/{summoner_id}/champions")
+}
+
+/// Every champion, with whether the player owns it
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct ChampionCollection {
+    pub champions: Vec<Champion>,
+}
+
+/// A champion, with its skins
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Champion {
+    pub id: i32,
+    pub name: String,
+    /// The name used in asset paths, such as `MonkeyKing` for Wukong
+    #[serde(default)]
+    pub alias: String,
+    #[serde(default)]
+    pub title: String,
+    /// Whether the champion is in the free rotation
+    #[serde(default)]
+    pub free_to_play: bool,
+    pub ownership: Ownership,
+    #[serde(default)]
+    pub skins: Vec<Skin>,
+}
+
+/// A skin, the champion's base skin included
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Skin {
+    pub id: i32,
+    pub champion_id: i32,
+    pub name: String,
+    /// The default look of the champion, which is owned with the champion
+    #[serde(default)]
+    pub is_base: bool,
+    pub ownership: Ownership,
+    #[serde(default)]
+    pub chromas: Vec<Chroma>,
+}
+
+/// A chroma of a skin
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Chroma {
+    pub id: i32,
+    pub champion_id: i32,
+    pub name: String,
+    pub ownership: Ownership,
+}
+
+/// Whether the player owns a champion, skin, or chroma
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Ownership {
+    #[serde(default)]
+    pub owned: bool,
+    /// Whether it was given as a reward for a free to play account, these can't be kept
+    #[serde(default)]
+    pub free_to_play_reward: bool,
+    #[serde(default)]
+    pub rental: Rental,
+}
+
+/// Whether a champion or skin is rented, such as through a boost
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Rental {
+    #[serde(default)]
+    pub rented: bool,
+}
+
+impl Ownership {
+    #[must_use]
+    /// Whether the player can use it, either because they own it, or are renting it
+    pub const fn is_available(&self) -> bool {
+        self.owned || self.rental.rented
+    }
+}
+
+impl ChampionCollection {
+    /// The champions the player owns
+    pub fn owned(&self) -> impl Iterator<Item = &Champion> {
+        self.champions
+            .iter()
+            .filter(|champion| champion.ownership.owned)
+    }
+
+    /// The champions in the free rotation, owned or not
+    pub fn free_rotation(&self) -> impl Iterator<Item = &Champion> {
+        self.champions
+            .iter()
+            .filter(|champion| champion.free_to_play)
+    }
+
+    #[must_use]
+    /// The champion with `champion_id`, `None` if there is none
+    pub fn champion(&self, champion_id: i32) -> Option<&Champion> {
+        self.champions
+            .iter()
+            .find(|champion| champion.id == champion_id)
+    }
+
+    /// Every skin the player owns, of every champion, base skins excluded
+    pub fn owned_skins(&self) -> impl Iterator<Item = &Skin> {
+        self.champions
+            .iter()
+            .flat_map(|champion| &champion.skins)
+            .filter(|skin| !skin.is_base && skin.ownership.owned)
+    }
+
+    /// The skins of `champion_id` the player doesn't own, base skins excluded
+    pub fn unowned_skins_for(&self, champion_id: i32) -> impl Iterator<Item = &Skin> {
+        self.champion(champion_id)
+            .into_iter()
+            .flat_map(|champion| &champion.skins)
+            .filter(|skin| !skin.is_base && !skin.ownership.owned)
+    }
+}
+
+/// An item in the player's inventory, as served by `/lol-inventory/v2/inventory/{type}`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InventoryItem {
+    /// The ID of the champion, skin, or other item
+    pub item_id: i32,
+    /// Such as `CHAMPION`, `CHAMPION_SKIN`, or `WARD_SKIN`
+    pub inventory_type: String,
+    /// Such as `OWNED` or `RENTED`
+    #[serde(default)]
+    pub ownership_type: String,
+    /// When the item was bought, such as `20230101T000000.000Z`
+    #[serde(default)]
+    pub purchase_date: String,
+}
+
+impl LcuClient {
+    /// Gets every champion, with their skins and chromas, and whether `summoner_id` owns them
+    ///
+    /// # Errors
+    /// This will return an error if the LCU API is not running, or the inventory of `summoner_id` is not loaded
+    pub async fn champion_collection(&self, summoner_id: u64) -> Result<ChampionCollection, Error> {
+        self.get(champions_endpoint(summoner_id)).await
+    }
+
+    /// Gets every item of `inventory_type` the logged in summoner owns, such as `CHAMPION_SKIN`
+    ///
+    /// # Errors
+    /// This will return an error if the LCU API is not running
+    pub async fn inventory(&self, inventory_type: &str) -> Result<Vec<InventoryItem>, Error> {
+        self.get(format!("/lol-inventory/v2/inventory/{inventory_type}"))
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ChampionCollection;
+    use crate::rest::LcuClient;
+
+    #[test]
+    fn collection_filters() {
+        let collection: ChampionCollection = serde_json::from_str(
+            r#"[
+                {
+                    "id": 39,
+                    "name": "Irelia",
+                    "freeToPlay": false,
+                    "ownership": {"owned": true, "rental": {"rented": false}},
+                    "skins": [
+                        {"id": 39000, "championId": 39, "name": "Irelia", "isBase": true, "ownership": {"owned": true}},
+                        {"id": 39001, "championId": 39, "name": "Nightblade Irelia", "ownership": {"owned": true}},
+                        {"id": 39002, "championId": 39, "name": "Aviator Irelia", "ownership": {"owned": false}}
+                    ]
+                },
+                {
+                    "id": 157,
+                    "name": "Yasuo",
+                    "freeToPlay": true,
+                    "ownership": {"owned": false, "rental": {"rented": false}},
+                    "skins": [
+                        {"id": 157000, "championId": 157, "name": "Yasuo", "isBase": true, "ownership": {"owned": false}}
+                    ]
+                }
+            ]"#,
+        )
+        .unwrap();
+
+        let owned: Vec<_> = collection.owned().map(|champion| champion.id).collect();
+        assert_eq!(owned, [39]);
+
+        let free: Vec<_> = collection
+            .free_rotation()
+            .map(|champion| champion.id)
+            .collect();
+        assert_eq!(free, [157]);
+
+        let unowned: Vec<_> = collection
+            .unowned_skins_for(39)
+            .map(|skin| skin.id)
+            .collect();
+        assert_eq!(unowned, [39002]);
+        assert_eq!(collection.unowned_skins_for(157).count(), 0);
+
+        let owned_skins: Vec<_> = collection.owned_skins().map(|skin| skin.id).collect();
+        assert_eq!(owned_skins, [39001]);
+    }
+
+    #[ignore = "This is only needed for testing, and doesn't need to be run all the time"]
+    #[tokio::test]
+    async fn test_champion_collection() {
+        let client = LcuClient::connect().unwrap();
+        let summoner = client.current_summoner().await.unwrap();
+
+        let collection = client
+            .champion_collection(summoner.summoner_id)
+            .await
+            .unwrap();
+        println!("{}", collection.owned().count());
+        println!(
+            "{:?}",
+            client
+                .inventory("CHAMPION_SKIN")
+                .await
+                .map(|items| items.len())
+        );
+    }
+}
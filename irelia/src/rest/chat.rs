@@ -0,0 +1,381 @@
+//! Typed wrappers around the `/lol-chat/v1` endpoints, for friends, conversations, and the local player's presence
+//!
+//! With the `ws` feature, [`IncomingMessages`] receives messages as they arrive
+
+use super::LcuClient;
+use crate::Error;
+use serde_derive::{Deserialize, Serialize};
+
+#[cfg(feature = "ws")]
+use crate::ws::{EventStream, LcuWebSocket, StreamEvent, SubscriberID, WebSocketError};
+#[cfg(feature = "ws")]
+use futures_core::Stream;
+#[cfg(feature = "ws")]
+use std::pin::Pin;
+#[cfg(feature = "ws")]
+use std::task::{Context, Poll};
+
+/// The endpoint the friend list is served from
+pub const FRIENDS_ENDPOINT: &str = "/lol-chat/v1/friends";
+
+/// The endpoint the conversations are served from
+pub const CONVERSATIONS_ENDPOINT: &str = "/lol-chat/v1/conversations";
+
+/// The endpoint the local player's presence is served from, and updated with
+pub const ME_ENDPOINT: &str = "/lol-chat/v1/me";
+
+/// The glob matching the uri of every message's websocket event, see [`crate::ws::Glob`]
+pub const MESSAGES_GLOB: &str = "/lol-chat/v1/conversations/*/messages/*";
+
+/// Whether a player can be messaged, shown as the color of their status
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Availability {
+    /// Online, shown in green
+    Chat,
+    Away,
+    /// In a game, or in queue
+    Dnd,
+    /// Only signed in to the mobile app
+    Mobile,
+    Offline,
+    /// An availability this version of the crate does not know about
+    #[serde(other)]
+    Unknown,
+}
+
+/// A player on the friend list
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Friend {
+    /// The ID their conversation is under
+    pub id: String,
+    pub puuid: String,
+    #[serde(default)]
+    pub summoner_id: u64,
+    #[serde(default)]
+    pub game_name: String,
+    #[serde(default)]
+    pub game_tag: String,
+    pub availability: Availability,
+    #[serde(default)]
+    pub status_message: String,
+    /// The game they're playing, such as `league_of_legends`, empty while they're offline
+    #[serde(default)]
+    pub product: String,
+    #[serde(default)]
+    pub group_name: String,
+    #[serde(default)]
+    pub note: String,
+}
+
+/// The local player's presence, as their friends see it
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChatMe {
+    pub id: String,
+    pub puuid: String,
+    #[serde(default)]
+    pub summoner_id: u64,
+    #[serde(default)]
+    pub game_name: String,
+    #[serde(default)]
+    pub game_tag: String,
+    pub availability: Availability,
+    #[serde(default)]
+    pub status_message: String,
+    #[serde(default)]
+    pub icon: i32,
+}
+
+/// The parts of [`ChatMe`] that can be changed, fields that are `None` are left as they are
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PresenceUpdate {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub availability: Option<Availability>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status_message: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub icon: Option<i32>,
+}
+
+/// A chat conversation, either with a single friend, or a group such as a lobby or champ select
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Conversation {
+    pub id: String,
+    /// The kind of conversation, such as `chat`, `customGame`, or `championSelect`
+    #[serde(rename = "type")]
+    pub conversation_type: String,
+    pub name: String,
+    #[serde(default)]
+    pub unread_message_count: u32,
+}
+
+/// A single message sent in a conversation
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChatMessage {
+    pub id: String,
+    pub body: String,
+    /// ISO 8601 timestamp of when the message was sent
+    pub timestamp: String,
+    pub from_summoner_id: u64,
+    /// The kind of message, `chat` for anything sent by a player, `system` for client generated messages
+    #[serde(rename = "type")]
+    pub message_type: String,
+}
+
+#[derive(Serialize)]
+struct MessageBody<'a> {
+    body: &'a str,
+    #[serde(rename = "type")]
+    message_type: &'static str,
+}
+
+impl LcuClient {
+    /// Gets all conversations the current summoner is part of
+    ///
+    /// # Errors
+    /// This will return an error if the LCU API is not running, or chat is not connected
+    pub async fn conversations(&self) -> Result<Vec<Conversation>, Error> {
+        self.get(CONVERSATIONS_ENDPOINT).await
+    }
+
+    /// Gets every player on the friend list
+    ///
+    /// # Errors
+    /// This will return an error if the LCU API is not running, or chat is not connected
+    pub async fn friends(&self) -> Result<Vec<Friend>, Error> {
+        self.get(FRIENDS_ENDPOINT).await
+    }
+
+    /// Gets the local player's presence
+    ///
+    /// # Errors
+    /// This will return an error if the LCU API is not running, or chat is not connected
+    pub async fn chat_me(&self) -> Result<ChatMe, Error> {
+        self.get(ME_ENDPOINT).await
+    }
+
+    /// Updates the local player's presence, returning it as it is now
+    ///
+    /// # Errors
+    /// This will return an error if the LCU API is not running, or chat is not connected
+    pub async fn update_presence(&self, update: &PresenceUpdate) -> Result<ChatMe, Error> {
+        self.put(ME_ENDPOINT, update).await
+    }
+
+    /// Sets the status message shown under the local player's name
+    ///
+    /// # Errors
+    /// This will return an error if the LCU API is not running, or chat is not connected
+    pub async fn set_status_message(
+        &self,
+        message: impl Into<String> + Send,
+    ) -> Result<ChatMe, Error> {
+        let update = PresenceUpdate {
+            status_message: Some(message.into()),
+            ..PresenceUpdate::default()
+        };
+
+        self.update_presence(&update).await
+    }
+
+    /// Sets the local player's availability, such as appearing offline with [`Availability::Offline`]
+    ///
+    /// # Errors
+    /// This will return an error if the LCU API is not running, or chat is not connected
+    pub async fn set_availability(&self, availability: Availability) -> Result<ChatMe, Error> {
+        let update = PresenceUpdate {
+            availability: Some(availability),
+            ..PresenceUpdate::default()
+        };
+
+        self.update_presence(&update).await
+    }
+
+    /// Gets the message history of a conversation
+    ///
+    /// # Errors
+    /// This will return an error if the LCU API is not running, or the conversation does not exist
+    pub async fn messages(
+        &self,
+        conversation_id: impl AsRef<str> + Send,
+    ) -> Result<Vec<ChatMessage>, Error> {
+        let endpoint = format!(
+            "{CONVERSATIONS_ENDPOINT}/{}/messages",
+            conversation_id.as_ref()
+        );
+
+        self.get(endpoint).await
+    }
+
+    /// Sends a chat message to a conversation, returning the message as the client stored it
+    ///
+    /// # Errors
+    /// This will return an error if the LCU API is not running, or the conversation does not exist
+    pub async fn send_message(
+        &self,
+        conversation_id: impl AsRef<str> + Send,
+        body: impl AsRef<str> + Send,
+    ) -> Result<ChatMessage, Error> {
+        let endpoint = format!(
+            "{CONVERSATIONS_ENDPOINT}/{}/messages",
+            conversation_id.as_ref()
+        );
+
+        let body = MessageBody {
+            body: body.as_ref(),
+            message_type: "chat",
+        };
+
+        self.post(endpoint, body).await
+    }
+}
+
+#[cfg(feature = "ws")]
+/// A message that was just sent, by anyone in the conversation, including the local player
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IncomingMessage {
+    pub conversation_id: String,
+    pub message: ChatMessage,
+}
+
+#[cfg(feature = "ws")]
+/// Messages in every conversation, as they arrive
+///
+/// Messages that arrive while the connection is lost are missed, fetch them with [`LcuClient::messages`]
+///
+/// ```no_run
+/// use futures_util::StreamExt;
+/// use irelia::rest::chat::IncomingMessages;
+/// use irelia::ws::LcuWebSocket;
+///
+/// # async fn run() {
+/// let mut ws = LcuWebSocket::new();
+/// let (_, mut messages) = IncomingMessages::subscribe(&mut ws).unwrap();
+///
+/// while let Some(Ok(incoming)) = messages.next().await {
+///     println!("{}: {}", incoming.message.from_summoner_id, incoming.message.body);
+/// }
+/// # }
+/// ```
+pub struct IncomingMessages {
+    events: EventStream<Option<ChatMessage>>,
+}
+
+#[cfg(feature = "ws")]
+impl IncomingMessages {
+    /// Subscribes to the messages of every conversation on `ws`
+    ///
+    /// Returns `None` is the websocket connection has already been closed previously
+    pub fn subscribe(ws: &mut LcuWebSocket) -> Option<(SubscriberID, Self)> {
+        let (id, events) = ws.subscribe_stream_filtered(MESSAGES_GLOB)?;
+        Some((id, Self { events }))
+    }
+}
+
+#[cfg(feature = "ws")]
+impl Stream for IncomingMessages {
+    type Item = Result<IncomingMessage, WebSocketError>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+
+        loop {
+            let event = match Pin::new(&mut this.events).poll_next(cx) {
+                Poll::Ready(Some(Ok(StreamEvent::Event(event)))) => event,
+                // Only new messages are passed on, not the connection's state
+                Poll::Ready(Some(Ok(_))) => continue,
+                Poll::Ready(Some(Err(err))) => return Poll::Ready(Some(Err(err))),
+                Poll::Ready(None) => return Poll::Ready(None),
+                Poll::Pending => return Poll::Pending,
+            };
+
+            if event.event_type != "Create" {
+                continue;
+            }
+
+            if let (Some(message), Some(conversation_id)) =
+                (event.data, conversation_id(&event.uri))
+            {
+                return Poll::Ready(Some(Ok(IncomingMessage {
+                    conversation_id: conversation_id.to_owned(),
+                    message,
+                })));
+            }
+        }
+    }
+}
+
+#[cfg(feature = "ws")]
+/// The conversation a message's uri is under, `/lol-chat/v1/conversations/{id}/messages/{message}`
+fn conversation_id(uri: &str) -> Option<&str> {
+    uri.strip_prefix(CONVERSATIONS_ENDPOINT)?
+        .strip_prefix('/')?
+        .split('/')
+        .next()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Availability, Friend, PresenceUpdate};
+    use crate::rest::LcuClient;
+
+    #[test]
+    fn friend_deserialize() {
+        let friend: Friend = serde_json::from_str(
+            r#"{
+                "availability": "dnd",
+                "gameName": "Sylv",
+                "gameTag": "NA1",
+                "id": "abc@na1.pvp.net",
+                "puuid": "abc",
+                "summonerId": 1,
+                "statusMessage": "In game",
+                "product": "league_of_legends"
+            }"#,
+        )
+        .unwrap();
+
+        assert_eq!(friend.availability, Availability::Dnd);
+        assert_eq!(friend.status_message, "In game");
+
+        let availability: Availability = serde_json::from_str("\"spectating\"").unwrap();
+        assert_eq!(availability, Availability::Unknown);
+    }
+
+    #[test]
+    fn presence_update_body() {
+        let update = PresenceUpdate {
+            availability: Some(Availability::Away),
+            ..PresenceUpdate::default()
+        };
+
+        assert_eq!(
+            serde_json::to_value(update).unwrap(),
+            serde_json::json!({"availability": "away"})
+        );
+    }
+
+    #[cfg(feature = "ws")]
+    #[test]
+    fn message_conversation_id() {
+        assert_eq!(
+            super::conversation_id("/lol-chat/v1/conversations/abc@na1.pvp.net/messages/1"),
+            Some("abc@na1.pvp.net")
+        );
+        assert_eq!(super::conversation_id("/lol-chat/v1/friends/abc"), None);
+    }
+
+    #[ignore = "This is only needed for testing, and doesn't need to be run all the time"]
+    #[tokio::test]
+    async fn test_friends() {
+        let client = LcuClient::connect().unwrap();
+
+        println!("{:?}", client.friends().await);
+        println!("{:?}", client.chat_me().await);
+    }
+}
@@ -0,0 +1,383 @@
+//! Typed wrappers around the `/lol-clash/v1` endpoints
+//!
+//! Tournaments are split into phases, usually one per day, each with its own registration and start time.
+//! Rosters and brackets are looked up by the IDs in [`TournamentSummary`], scouting takes the summoner IDs of a roster
+
+use super::LcuClient;
+use crate::Error;
+use serde_derive::{Deserialize, Serialize};
+
+/// The endpoint every tournament is served from, past and upcoming
+pub const TOURNAMENTS_ENDPOINT: &str = "/lol-clash/v1/tournaments";
+
+/// The endpoint the local player's roster and bracket in each tournament are served from
+pub const TOURNAMENT_SUMMARY_ENDPOINT: &str = "/lol-clash/v1/tournament-summary";
+
+/// The endpoint the champions a list of players are best on are posted to
+pub const SCOUTING_CHAMPIONS_ENDPOINT: &str = "/lol-clash/v1/scouting/champions";
+
+/// A clash tournament
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Tournament {
+    pub id: i64,
+    /// The key of the localized name, such as `clash_tournament_name_ionia`
+    #[serde(default)]
+    pub name_loc_key: String,
+    #[serde(default)]
+    pub name_loc_key_secondary: String,
+    #[serde(default)]
+    pub theme_id: i32,
+    #[serde(default)]
+    pub queue_id: i32,
+    /// Such as `UPCOMING`, `IN_PROGRESS`, or `FINISHED`
+    #[serde(default)]
+    pub status: String,
+    #[serde(default)]
+    pub phases: Vec<TournamentPhase>,
+    /// Unix timestamp of when the first phase starts, in milliseconds
+    #[serde(default)]
+    pub schedule_start_time: i64,
+    /// Unix timestamp of when the last phase ends, in milliseconds
+    #[serde(default)]
+    pub schedule_end_time: i64,
+}
+
+impl Tournament {
+    #[must_use]
+    /// The first phase that starts after `now`, a unix timestamp in milliseconds, cancelled phases are skipped
+    pub fn next_phase(&self, now: i64) -> Option<&TournamentPhase> {
+        self.phases
+            .iter()
+            .filter(|phase| !phase.cancelled && phase.start_time > now)
+            .min_by_key(|phase| phase.start_time)
+    }
+}
+
+/// A single day of a tournament
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TournamentPhase {
+    pub id: i64,
+    #[serde(default)]
+    pub tournament_id: i64,
+    /// Unix timestamp of when registration opens, in milliseconds
+    #[serde(default)]
+    pub registration_time: i64,
+    /// Unix timestamp of when the first game starts, in milliseconds
+    #[serde(default)]
+    pub start_time: i64,
+    /// The highest tier that can register, as its index, such as `4` for tier IV
+    #[serde(default)]
+    pub limit_tier: i32,
+    #[serde(default)]
+    pub cancelled: bool,
+}
+
+/// The local player's roster and bracket in a tournament
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TournamentSummary {
+    pub tournament_id: i64,
+    /// `None` until the player joins a roster
+    #[serde(default)]
+    pub roster_id: Option<String>,
+    /// `None` until the roster is placed in a bracket
+    #[serde(default)]
+    pub bracket_id: Option<i64>,
+    #[serde(default)]
+    pub state: String,
+}
+
+/// A clash team
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Roster {
+    pub id: String,
+    #[serde(default)]
+    pub tournament_id: i64,
+    #[serde(default)]
+    pub name: String,
+    /// The tag shown next to the name, such as `SYLV`
+    #[serde(default)]
+    pub short_name: String,
+    #[serde(default)]
+    pub icon_id: i32,
+    #[serde(default)]
+    pub captain_id: u64,
+    #[serde(default)]
+    pub members: Vec<RosterMember>,
+}
+
+impl Roster {
+    /// The summoner IDs of the members, to pass to [`Clash::scouting`]
+    pub fn summoner_ids(&self) -> impl Iterator<Item = u64> + '_ {
+        self.members.iter().map(|member| member.summoner_id)
+    }
+}
+
+/// A player on a [`Roster`]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RosterMember {
+    pub summoner_id: u64,
+    #[serde(default)]
+    pub puuid: String,
+    /// Such as `TOP` or `UTILITY`, `UNSELECTED` if the member hasn't picked one
+    #[serde(default)]
+    pub position: String,
+    /// Such as `ACCEPTED` or `PENDING`
+    #[serde(default)]
+    pub state: String,
+}
+
+/// The bracket a roster is placed in
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Bracket {
+    pub id: i64,
+    #[serde(default)]
+    pub size: u32,
+    #[serde(default)]
+    pub matches: Vec<BracketMatch>,
+    #[serde(default)]
+    pub rosters: Vec<BracketRoster>,
+}
+
+impl Bracket {
+    #[must_use]
+    /// The roster in the bracket with `roster_id`
+    pub fn roster(&self, roster_id: &str) -> Option<&BracketRoster> {
+        self.rosters
+            .iter()
+            .find(|roster| roster.roster_id == roster_id)
+    }
+
+    #[must_use]
+    /// The next match of `roster_id` that has no winner yet
+    pub fn next_match(&self, roster_id: &str) -> Option<&BracketMatch> {
+        self.matches
+            .iter()
+            .filter(|bracket_match| bracket_match.winner_id.is_none())
+            .find(|bracket_match| {
+                bracket_match.roster_id1.as_deref() == Some(roster_id)
+                    || bracket_match.roster_id2.as_deref() == Some(roster_id)
+            })
+    }
+}
+
+/// A match between two rosters in a [`Bracket`]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BracketMatch {
+    pub id: i64,
+    #[serde(default)]
+    pub round_id: i32,
+    #[serde(default)]
+    pub order: i32,
+    /// `None` until the roster is decided by an earlier match
+    #[serde(default)]
+    pub roster_id1: Option<String>,
+    #[serde(default)]
+    pub roster_id2: Option<String>,
+    /// `None` until the match is played
+    #[serde(default)]
+    pub winner_id: Option<String>,
+    #[serde(default)]
+    pub status: String,
+}
+
+/// A roster as shown in a [`Bracket`]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BracketRoster {
+    pub roster_id: String,
+    #[serde(default)]
+    pub name: String,
+    #[serde(default)]
+    pub short_name: String,
+    #[serde(default)]
+    pub icon_id: i32,
+}
+
+/// The champions a player is best on, as served by [`SCOUTING_CHAMPIONS_ENDPOINT`]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PlayerScouting {
+    /// The summoner ID of the player
+    pub player_id: u64,
+    #[serde(default)]
+    pub total_mastery_score: u32,
+    #[serde(default)]
+    pub top_masteries: Vec<ScoutingMastery>,
+    #[serde(default)]
+    pub top_season_champions: Vec<ScoutingSeasonChampion>,
+}
+
+/// A champion a player has a lot of mastery on
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScoutingMastery {
+    pub champion_id: i32,
+    #[serde(default)]
+    pub champion_level: u32,
+    #[serde(default)]
+    pub champion_points: u64,
+}
+
+/// A champion a player has played a lot this season
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScoutingSeasonChampion {
+    pub champion_id: i32,
+    #[serde(default)]
+    pub game_count: u32,
+    #[serde(default)]
+    pub win_count: u32,
+    #[serde(default)]
+    pub kda: f64,
+}
+
+/// Looks up clash tournaments and teams, created with [`LcuClient::clash`]
+#[derive(Clone, Copy)]
+pub struct Clash<'a> {
+    client: &'a LcuClient,
+}
+
+impl Clash<'_> {
+    /// Gets every tournament the LCU knows about
+    ///
+    /// # Errors
+    /// This will return an error if the LCU API is not running
+    pub async fn tournaments(&self) -> Result<Vec<Tournament>, Error> {
+        self.client.get(TOURNAMENTS_ENDPOINT).await
+    }
+
+    /// Gets the tournaments that have a phase starting after `now`, a unix timestamp in milliseconds, soonest first
+    ///
+    /// # Errors
+    /// This will return an error if the LCU API is not running
+    pub async fn upcoming_tournaments(&self, now: i64) -> Result<Vec<Tournament>, Error> {
+        let tournaments = self.tournaments().await?;
+        Ok(upcoming(tournaments, now))
+    }
+
+    /// Gets the local player's roster and bracket in each tournament they registered for
+    ///
+    /// # Errors
+    /// This will return an error if the LCU API is not running
+    pub async fn summaries(&self) -> Result<Vec<TournamentSummary>, Error> {
+        self.client.get(TOURNAMENT_SUMMARY_ENDPOINT).await
+    }
+
+    /// Gets the roster with `roster_id`
+    ///
+    /// # Errors
+    /// This will return an error if the LCU API is not running, or there is no roster with `roster_id`
+    pub async fn roster(&self, roster_id: &str) -> Result<Roster, Error> {
+        self.client
+            .get(format!("/lol-clash/v1/roster/{roster_id}"))
+            .await
+    }
+
+    /// Gets the bracket with `bracket_id`
+    ///
+    /// # Errors
+    /// This will return an error if the LCU API is not running, or there is no bracket with `bracket_id`
+    pub async fn bracket(&self, bracket_id: i64) -> Result<Bracket, Error> {
+        self.client
+            .get(format!("/lol-clash/v1/bracket/{bracket_id}"))
+            .await
+    }
+
+    /// Gets the champions each of `summoner_ids` is best on, such as the members of a [`Roster`]
+    ///
+    /// # Errors
+    /// This will return an error if the LCU API is not running
+    pub async fn scouting(&self, summoner_ids: &[u64]) -> Result<Vec<PlayerScouting>, Error> {
+        self.client
+            .post(SCOUTING_CHAMPIONS_ENDPOINT, summoner_ids)
+            .await
+    }
+}
+
+impl LcuClient {
+    #[must_use]
+    /// Helpers for clash tournaments, see [`Clash`]
+    pub const fn clash(&self) -> Clash<'_> {
+        Clash { client: self }
+    }
+}
+
+/// The tournaments with a phase after `now`, ordered by that phase
+fn upcoming(mut tournaments: Vec<Tournament>, now: i64) -> Vec<Tournament> {
+    tournaments.retain(|tournament| tournament.next_phase(now).is_some());
+    tournaments.sort_by_key(|tournament| tournament.next_phase(now).map(|phase| phase.start_time));
+    tournaments
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{upcoming, Bracket, PlayerScouting, Tournament};
+    use crate::rest::LcuClient;
+
+    #[test]
+    fn upcoming_phases() {
+        let tournaments: Vec<Tournament> = serde_json::from_str(
+            r#"[
+                {"id": 1, "phases": [{"id": 10, "startTime": 100}, {"id": 11, "startTime": 300}]},
+                {"id": 2, "phases": [{"id": 20, "startTime": 200}, {"id": 21, "startTime": 250, "cancelled": true}]},
+                {"id": 3, "phases": [{"id": 30, "startTime": 50}]}
+            ]"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            tournaments[0].next_phase(150).map(|phase| phase.id),
+            Some(11)
+        );
+        assert_eq!(tournaments[1].next_phase(200), None);
+
+        let ids: Vec<_> = upcoming(tournaments, 150)
+            .iter()
+            .map(|tournament| tournament.id)
+            .collect();
+        assert_eq!(ids, [2, 1]);
+    }
+
+    #[test]
+    fn bracket_matches() {
+        let bracket: Bracket = serde_json::from_str(
+            r#"{
+                "id": 7,
+                "size": 8,
+                "matches": [
+                    {"id": 1, "roundId": 1, "rosterId1": "a", "rosterId2": "b", "winnerId": "a"},
+                    {"id": 2, "roundId": 2, "rosterId1": "a", "rosterId2": null, "winnerId": null}
+                ],
+                "rosters": [{"rosterId": "a", "shortName": "SYLV"}]
+            }"#,
+        )
+        .unwrap();
+
+        assert_eq!(bracket.next_match("a").map(|m| m.id), Some(2));
+        assert_eq!(bracket.next_match("b"), None);
+        assert_eq!(bracket.roster("a").unwrap().short_name, "SYLV");
+
+        let scouting: Vec<PlayerScouting> = serde_json::from_str(
+            r#"[{"playerId": 1, "topMasteries": [{"championId": 39, "championPoints": 500000}], "topSeasonChampions": [{"championId": 39, "kda": 3.5}]}]"#,
+        )
+        .unwrap();
+        assert_eq!(scouting[0].top_masteries[0].champion_id, 39);
+    }
+
+    #[ignore = "This is only needed for testing, and doesn't need to be run all the time"]
+    #[tokio::test]
+    async fn test_clash() {
+        let client = LcuClient::connect().unwrap();
+        let clash = client.clash();
+
+        println!("{:?}", clash.tournaments().await.map(|t| t.len()));
+        println!("{:?}", clash.summaries().await);
+    }
+}
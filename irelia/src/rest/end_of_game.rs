@@ -0,0 +1,100 @@
+//! Typed wrappers around the `/lol-end-of-game/v1/eog-stats-block` endpoint
+//!
+//! This is only available on the end of game screen, after the game has finished
+
+use super::LcuClient;
+use crate::Error;
+use serde_derive::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+/// The endpoint the end of game stats are served from, and the uri of its websocket event
+pub const EOG_STATS_ENDPOINT: &str = "/lol-end-of-game/v1/eog-stats-block";
+
+/// The stats shown on the end of game screen
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EndOfGameStats {
+    pub game_id: u64,
+    /// How long the game lasted, in seconds
+    #[serde(default)]
+    pub game_length: u32,
+    /// Such as `CLASSIC` or `ARAM`
+    #[serde(default)]
+    pub game_mode: String,
+    #[serde(default)]
+    pub queue_type: String,
+    pub local_player: EndOfGamePlayer,
+    #[serde(default)]
+    pub teams: Vec<EndOfGameTeam>,
+}
+
+/// A team on the end of game screen
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EndOfGameTeam {
+    pub team_id: i32,
+    #[serde(default)]
+    pub is_winning_team: bool,
+    #[serde(default)]
+    pub is_player_team: bool,
+    #[serde(default)]
+    pub players: Vec<EndOfGamePlayer>,
+}
+
+/// A player on the end of game screen
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EndOfGamePlayer {
+    #[serde(default)]
+    pub puuid: String,
+    #[serde(default)]
+    pub summoner_id: u64,
+    pub champion_id: i32,
+    /// Every stat the client tracked, such as `CHAMPIONS_KILLED` or `GOLD_EARNED`
+    ///
+    /// These change between patches and game modes, so they're left untyped, see [`EndOfGamePlayer::stat`]
+    #[serde(default)]
+    pub stats: Map<String, Value>,
+}
+
+impl EndOfGamePlayer {
+    #[must_use]
+    /// Reads a numeric stat, such as `CHAMPIONS_KILLED`, returning `None` if it's missing
+    pub fn stat(&self, name: &str) -> Option<i64> {
+        self.stats.get(name)?.as_i64()
+    }
+}
+
+impl LcuClient {
+    /// Gets the stats for the game that just ended
+    ///
+    /// # Errors
+    /// This will return an error if the LCU API is not running, or the client is not on the end of game screen
+    pub async fn end_of_game_stats(&self) -> Result<EndOfGameStats, Error> {
+        self.get(EOG_STATS_ENDPOINT).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::EndOfGameStats;
+
+    #[test]
+    fn stats_deserialize() {
+        let stats: EndOfGameStats = serde_json::from_str(
+            r#"{
+                "gameId": 1,
+                "gameLength": 1800,
+                "gameMode": "CLASSIC",
+                "queueType": "RANKED_SOLO_5x5",
+                "localPlayer": {"puuid": "a", "championId": 103, "stats": {"CHAMPIONS_KILLED": 7, "WIN": 1}},
+                "teams": [{"teamId": 100, "isWinningTeam": true, "isPlayerTeam": true, "players": [{"puuid": "a", "championId": 103}]}]
+            }"#,
+        )
+        .unwrap();
+
+        assert_eq!(stats.local_player.stat("CHAMPIONS_KILLED"), Some(7));
+        assert_eq!(stats.local_player.stat("ASSISTS"), None);
+        assert!(stats.teams[0].is_winning_team);
+    }
+}
@@ -0,0 +1,151 @@
+//! Endpoints declared once as types, and sent with [`LcuClient::execute`]
+
+use super::LcuClient;
+use crate::Error;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::borrow::Cow;
+
+#[cfg(feature = "macros")]
+/// Implements [`Endpoint`], this requires the `macros` feature
+///
+/// ```no_run
+/// use irelia::rest::{endpoint::Endpoint, summoner::Summoner, LcuClient};
+///
+/// #[derive(Endpoint)]
+/// #[endpoint(method = "GET", path = "/lol-summoner/v1/summoners/{id}", response = Summoner)]
+/// struct GetSummoner {
+///     id: u64,
+/// }
+///
+/// #[derive(Endpoint)]
+/// #[endpoint(method = "PUT", path = "/lol-summoner/v1/current-summoner/icon", response = Summoner)]
+/// struct SetIcon {
+///     #[endpoint(body)]
+///     icon: serde_json::Value,
+/// }
+///
+/// # async fn run(client: LcuClient) -> Result<(), irelia::Error> {
+/// let summoner = client.execute(GetSummoner { id: 1 }).await?;
+/// # Ok(())
+/// # }
+/// ```
+pub use irelia_macros::Endpoint;
+
+/// A single LCU endpoint, with the types it takes and returns
+///
+/// This can be implemented by hand, or derived with the `macros` feature
+///
+/// ```no_run
+/// use irelia::rest::{endpoint::Endpoint, summoner::Summoner};
+/// use std::borrow::Cow;
+///
+/// struct GetCurrentSummoner;
+///
+/// impl Endpoint for GetCurrentSummoner {
+///     type Body = ();
+///     type Response = Summoner;
+///
+///     const METHOD: &'static str = "GET";
+///
+///     fn path(&self) -> Cow<'_, str> {
+///         Cow::Borrowed("/lol-summoner/v1/current-summoner")
+///     }
+/// }
+/// ```
+pub trait Endpoint {
+    /// Sent as msgpack, this is `()` for endpoints without a body
+    type Body: Serialize + Send;
+    /// For responses without a body, use `IgnoredAny`
+    type Response: DeserializeOwned;
+
+    /// Such as `GET` or `POST`
+    const METHOD: &'static str;
+
+    /// The path, including any query
+    fn path(&self) -> Cow<'_, str>;
+
+    /// Takes the body out of the endpoint, `None` sends no body
+    fn into_body(self) -> Option<Self::Body>
+    where
+        Self: Sized,
+    {
+        None
+    }
+}
+
+impl LcuClient {
+    /// Sends a request to `endpoint`, see [`Endpoint`]
+    ///
+    /// # Errors
+    /// This will return an error if the LCU API is not running, or the response does not match [`Endpoint::Response`]
+    pub async fn execute<E: Endpoint + Send>(&self, endpoint: E) -> Result<E::Response, Error> {
+        let path = endpoint.path().into_owned();
+        self.lcu_request(&path, E::METHOD, endpoint.into_body())
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Endpoint;
+    use std::borrow::Cow;
+
+    struct GetSummoner {
+        id: u64,
+    }
+
+    impl Endpoint for GetSummoner {
+        type Body = ();
+        type Response = serde_json::Value;
+
+        const METHOD: &'static str = "GET";
+
+        fn path(&self) -> Cow<'_, str> {
+            Cow::Owned(format!("/lol-summoner/v1/summoners/{}", self.id))
+        }
+    }
+
+    #[test]
+    fn test_endpoint() {
+        let endpoint = GetSummoner { id: 42 };
+        assert_eq!(endpoint.path(), "/lol-summoner/v1/summoners/42");
+        assert!(endpoint.into_body().is_none());
+    }
+
+    #[cfg(feature = "macros")]
+    #[test]
+    fn test_derive() {
+        #[derive(super::Endpoint)]
+        #[endpoint(
+            method = "PUT",
+            path = "/lol-summoner/v1/summoners/{id}/icon/{icon}",
+            response = serde_json::Value
+        )]
+        struct SetIcon {
+            id: u64,
+            icon: i32,
+            #[endpoint(body)]
+            body: Vec<u8>,
+        }
+
+        #[derive(super::Endpoint)]
+        #[endpoint(method = "GET", path = "/lol-summoner/v1/current-summoner")]
+        struct GetCurrentSummoner;
+
+        let endpoint = SetIcon {
+            id: 1,
+            icon: 29,
+            body: vec![1, 2],
+        };
+        assert_eq!(SetIcon::METHOD, "PUT");
+        assert_eq!(endpoint.path(), "/lol-summoner/v1/summoners/1/icon/29");
+        assert_eq!(endpoint.into_body(), Some(vec![1, 2]));
+
+        assert_eq!(
+            GetCurrentSummoner.path(),
+            "/lol-summoner/v1/current-summoner"
+        );
+        assert_eq!(GetCurrentSummoner.into_body(), None);
+    }
+}
@@ -0,0 +1,449 @@
+//! Recording real LCU responses to a fixture file, and answering requests from it later, without a running client
+//!
+//! A fixture is recorded once against a live client with [`RequestClient::record_fixture`],
+//! or [`RequestClient::record_to_as`] with [`RecordFormat::Fixture`] for any other sink,
+//! then tests replay it with [`RequestClient::replay_fixture`], or [`LcuClient::from_fixture`].
+//! Credentials are never written to the file, they're replaced with [`REDACTED`]
+//!
+//! Each line of a fixture is an [`Interaction`], encoded as JSON, so it can be read and edited by hand
+//!
+//! ```no_run
+//! use irelia::rest::LcuClient;
+//! use irelia::RequestClient;
+//!
+//! # async fn run() -> Result<(), Box<dyn std::error::Error>> {
+//! // Once, with the client running
+//! let request_client = RequestClient::new().record_fixture("tests/fixtures/lobby.jsonl")?;
+//! let client = LcuClient::connect_with_request_client(&request_client)?;
+//! let phase: String = client.get("/lol-gameflow/v1/gameflow-phase").await?;
+//!
+//! // In tests, without the client
+//! let client = LcuClient::from_fixture("tests/fixtures/lobby.jsonl")?;
+//! assert_eq!(client.get::<String>("/lol-gameflow/v1/gameflow-phase").await?, phase);
+//! # Ok(())
+//! # }
+//! ```
+
+use super::LcuClient;
+use crate::utils::requests::{status_error, MIME};
+use crate::{Error, RecordFormat, RequestClient};
+use http_body_util::{BodyExt, Full};
+use hyper::body::Bytes;
+use hyper::header::{HeaderMap, ACCEPT, AUTHORIZATION, CONTENT_TYPE, PROXY_AUTHORIZATION};
+use hyper::http::HeaderValue;
+use hyper::StatusCode;
+use serde_derive::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::fmt::Debug;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, Read};
+use std::net::{Ipv4Addr, SocketAddr};
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+
+/// What credentials are replaced with in a fixture
+pub const REDACTED: &str = "[REDACTED]";
+
+/// A request and the response it got, one line of a fixture
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Interaction {
+    pub request: FixtureRequest,
+    pub response: FixtureResponse,
+}
+
+impl Interaction {
+    /// The interaction for a response that was just received, bodies that aren't msgpack are written as `null`
+    pub(crate) fn recorded(request: FixtureRequest, status: StatusCode, body: &[u8]) -> Self {
+        Self {
+            request,
+            response: FixtureResponse {
+                status: status.as_u16(),
+                body: rmp_serde::from_slice(body).unwrap_or_default(),
+            },
+        }
+    }
+}
+
+/// A request as it was sent, see [`Interaction`]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FixtureRequest {
+    pub method: String,
+    /// The endpoint requested, including the query
+    pub path: String,
+    /// The headers sent, with lowercase names, credentials are replaced with [`REDACTED`]
+    #[serde(default)]
+    pub headers: BTreeMap<String, String>,
+    /// The body, converted from msgpack, `null` if there was none
+    #[serde(default)]
+    pub body: Value,
+}
+
+/// A response as it was received, see [`Interaction`]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FixtureResponse {
+    pub status: u16,
+    /// The body, converted from msgpack, a `null` body is replayed as an empty one
+    #[serde(default)]
+    pub body: Value,
+}
+
+/// The interactions of a fixture, in the order they were recorded
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Fixture {
+    pub interactions: Vec<Interaction>,
+}
+
+impl Fixture {
+    /// Reads the fixture at `path`
+    ///
+    /// # Errors
+    /// This will return an error if the file cannot be read, or a line is not an [`Interaction`]
+    pub fn load(path: impl AsRef<Path>) -> io::Result<Self> {
+        Self::from_reader(File::open(path)?)
+    }
+
+    /// Reads a fixture from `reader`, blank lines are skipped
+    ///
+    /// # Errors
+    /// This will return an error if `reader` fails, or a line is not an [`Interaction`]
+    pub fn from_reader(reader: impl Read) -> io::Result<Self> {
+        let mut interactions = Vec::new();
+
+        for line in BufReader::new(reader).lines() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            interactions.push(serde_json::from_str(&line)?);
+        }
+
+        Ok(Self { interactions })
+    }
+}
+
+impl RequestClient {
+    /// Records every request made through this client, and its response, to a new fixture at `path`
+    ///
+    /// Requests are still sent to the client, the file is written as responses arrive.
+    /// This is the same as [`RequestClient::record_to_as`] with [`RecordFormat::Fixture`], writing to a new file
+    ///
+    /// # Errors
+    /// This will return an error if the file cannot be created
+    pub fn record_fixture(self, path: impl AsRef<Path>) -> io::Result<Self> {
+        Ok(self.record_to_as(File::create(path)?, RecordFormat::Fixture))
+    }
+
+    /// Answers every request made through this client from the fixture at `path`, nothing is sent over the network
+    ///
+    /// # Errors
+    /// This will return an error if the fixture cannot be read, see [`Fixture::load`]
+    pub fn replay_fixture(self, path: impl AsRef<Path>) -> io::Result<Self> {
+        Ok(self.with_fixture(Fixture::load(path)?))
+    }
+
+    #[must_use]
+    /// Answers every request made through this client from `fixture`, nothing is sent over the network
+    ///
+    /// A request is answered with the first interaction with the same method, path, and body that hasn't been used yet,
+    /// so an endpoint that was requested several times replays its responses in order.
+    /// Once they're all used, the last one is repeated. A request with no interaction fails with [`Error::Unavailable`]
+    pub fn with_fixture(mut self, fixture: Fixture) -> Self {
+        let used = vec![false; fixture.interactions.len()];
+        self.fixture = Some(Arc::new(Mutex::new(Replay {
+            interactions: fixture.interactions,
+            used,
+        })));
+        self
+    }
+}
+
+impl LcuClient {
+    /// A client that answers every request from the fixture at `path`, see [`RequestClient::replay_fixture`]
+    ///
+    /// # Errors
+    /// This will return an error if the fixture cannot be read
+    pub fn from_fixture(path: impl AsRef<Path>) -> Result<Self, Error> {
+        let request_client = RequestClient::new().replay_fixture(path)?;
+
+        Ok(Self::new_with_credentials_with_request_client(
+            SocketAddr::from((Ipv4Addr::LOCALHOST, 0)),
+            HeaderValue::from_static(REDACTED),
+            &request_client,
+        ))
+    }
+}
+
+/// The interactions a [`RequestClient`] answers requests from, and which of them were used
+#[derive(Debug)]
+pub(crate) struct Replay {
+    interactions: Vec<Interaction>,
+    used: Vec<bool>,
+}
+
+impl Replay {
+    /// Returns the response for `request`, see [`RequestClient::with_fixture`]
+    pub(crate) fn respond(&mut self, request: &FixtureRequest) -> Result<Bytes, Error> {
+        let Self { interactions, used } = self;
+
+        let matches = |interaction: &Interaction| {
+            interaction.request.method == request.method
+                && interaction.request.path == request.path
+                && interaction.request.body == request.body
+        };
+
+        let unused = interactions
+            .iter()
+            .zip(used.iter())
+            .position(|(interaction, used)| !used && matches(interaction));
+
+        let index = match unused {
+            Some(index) => {
+                used[index] = true;
+                index
+            }
+            None => match interactions.iter().rposition(matches) {
+                Some(index) => index,
+                None => {
+                    return Err(Error::Unavailable(format!(
+                        "no interaction for {} {}",
+                        request.method, request.path
+                    )))
+                }
+            },
+        };
+
+        interactions[index].response.to_result()
+    }
+}
+
+impl FixtureRequest {
+    /// Captures a request as it's about to be sent, `headers` take priority over `defaults`, the same as when it's sent
+    ///
+    /// Credential headers are redacted wherever they came from, including the defaults and `headers`
+    pub(crate) async fn capture(
+        method: &str,
+        endpoint: &str,
+        body: Option<&Full<Bytes>>,
+        auth_header: Option<&HeaderValue>,
+        content_type: &HeaderValue,
+        (defaults, headers): (&HeaderMap, &HeaderMap),
+    ) -> Self {
+        let mut sent = defaults.clone();
+        sent.extend(headers.clone());
+        sent.insert(CONTENT_TYPE, content_type.clone());
+        sent.insert(ACCEPT, HeaderValue::from_static(MIME));
+
+        if let Some(auth_header) = auth_header {
+            sent.insert(AUTHORIZATION, auth_header.clone());
+        }
+
+        for name in [AUTHORIZATION, PROXY_AUTHORIZATION] {
+            if sent.contains_key(&name) {
+                sent.insert(name, HeaderValue::from_static(REDACTED));
+            }
+        }
+
+        let headers = sent
+            .iter()
+            .map(|(name, value)| {
+                let value = String::from_utf8_lossy(value.as_bytes()).into_owned();
+                (name.as_str().to_owned(), value)
+            })
+            .collect();
+
+        let body = match body {
+            // A `Full` body is always ready, and can't fail
+            Some(body) => body.clone().collect().await.unwrap_or_default().to_bytes(),
+            None => Bytes::new(),
+        };
+
+        Self {
+            method: method.to_owned(),
+            path: endpoint.to_owned(),
+            headers,
+            body: decode_body(content_type, &body),
+        }
+    }
+}
+
+impl FixtureResponse {
+    fn to_result(&self) -> Result<Bytes, Error> {
+        let body = if self.body.is_null() {
+            Bytes::new()
+        } else {
+            Bytes::from(rmp_serde::to_vec_named(&self.body)?)
+        };
+
+        match StatusCode::from_u16(self.status) {
+            Ok(status) if status.is_success() => Ok(body),
+            Ok(status) => Err(status_error(status, &body)),
+            Err(_) => Err(Error::Unavailable(format!(
+                "the fixture responds with {}, which is not a status code",
+                self.status
+            ))),
+        }
+    }
+}
+
+/// Converts a request body to JSON, bodies that aren't msgpack or JSON are kept as text
+fn decode_body(content_type: &HeaderValue, body: &[u8]) -> Value {
+    if body.is_empty() {
+        return Value::Null;
+    }
+
+    if content_type.as_bytes() == MIME.as_bytes() {
+        rmp_serde::from_slice(body).unwrap_or_default()
+    } else {
+        serde_json::from_slice(body)
+            .unwrap_or_else(|_| Value::String(String::from_utf8_lossy(body).into_owned()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{FixtureRequest, REDACTED};
+
+    #[cfg(feature = "ws")]
+    #[tokio::test]
+    async fn record_and_replay() {
+        use super::Fixture;
+        use crate::mock::MockLcu;
+        use crate::rest::LcuClient;
+        use crate::{Error, RequestClient};
+        use hyper::StatusCode;
+        use serde_json::{json, Value};
+
+        const PHASE: &str = "/lol-gameflow/v1/gameflow-phase";
+
+        let path =
+            std::env::temp_dir().join(format!("irelia-fixture-{}.jsonl", std::process::id()));
+
+        {
+            let mock = MockLcu::start().unwrap();
+            mock.on_get(PHASE).respond_json("Lobby");
+            mock.on_post("/lol-lobby/v2/lobby")
+                .respond_json(json!({"gameConfig": {"queueId": 420}}));
+            mock.on_get("/lol-lobby/v2/lobby")
+                .respond_error(StatusCode::NOT_FOUND, "LOBBY_NOT_FOUND");
+
+            let request_client = RequestClient::danger_accept_invalid_certs()
+                .record_fixture(&path)
+                .unwrap();
+            let client = LcuClient::new_with_credentials_with_request_client(
+                mock.addr(),
+                mock.auth_header().clone(),
+                &request_client,
+            );
+
+            client.get::<String>(PHASE).await.unwrap();
+            client
+                .post::<_, Value>("/lol-lobby/v2/lobby", Some(json!({"queueId": 420})))
+                .await
+                .unwrap();
+            client
+                .get::<Value>("/lol-lobby/v2/lobby")
+                .await
+                .unwrap_err();
+
+            mock.on_get(PHASE).respond_json("ChampSelect");
+            client.get::<String>(PHASE).await.unwrap();
+        }
+
+        let file = std::fs::read_to_string(&path).unwrap();
+        assert!(!file.contains("Basic"));
+
+        let fixture = Fixture::load(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(fixture.interactions.len(), 4);
+        assert_eq!(
+            fixture.interactions[0].request.headers["authorization"],
+            REDACTED
+        );
+        assert_eq!(
+            fixture.interactions[1].request.body,
+            json!({"queueId": 420})
+        );
+
+        let client = LcuClient::new_with_credentials_with_request_client(
+            ([127, 0, 0, 1], 0),
+            hyper::http::HeaderValue::from_static(REDACTED),
+            &RequestClient::new().with_fixture(fixture),
+        );
+
+        assert_eq!(client.get::<String>(PHASE).await.unwrap(), "Lobby");
+        assert_eq!(client.get::<String>(PHASE).await.unwrap(), "ChampSelect");
+        // Every response was used, so the last one repeats
+        assert_eq!(client.get::<String>(PHASE).await.unwrap(), "ChampSelect");
+
+        let lobby: Value = client
+            .post("/lol-lobby/v2/lobby", Some(json!({"queueId": 420})))
+            .await
+            .unwrap();
+        assert_eq!(lobby["gameConfig"]["queueId"], 420);
+
+        let missing = client
+            .get::<Value>("/lol-lobby/v2/lobby")
+            .await
+            .unwrap_err();
+        assert_eq!(missing.status(), Some(StatusCode::NOT_FOUND));
+
+        let other_body = client
+            .post::<_, Value>("/lol-lobby/v2/lobby", Some(json!({"queueId": 450})))
+            .await;
+        assert!(matches!(other_body, Err(Error::Unavailable(_))));
+    }
+
+    #[tokio::test]
+    async fn credentials_redacted() {
+        use hyper::header::{HeaderMap, AUTHORIZATION, PROXY_AUTHORIZATION};
+        use hyper::http::HeaderValue;
+
+        let secret = HeaderValue::from_static("Basic cmlvdDp0ZXN0");
+        let content_type = HeaderValue::from_static(crate::utils::requests::MIME);
+
+        let mut defaults = HeaderMap::new();
+        defaults.insert(AUTHORIZATION, secret.clone());
+        let mut headers = HeaderMap::new();
+        headers.insert(PROXY_AUTHORIZATION, secret.clone());
+
+        // From the defaults and the request's own headers, without an auth header
+        let request = FixtureRequest::capture(
+            "GET",
+            "/liveclientdata/allgamedata",
+            None,
+            None,
+            &content_type,
+            (&defaults, &headers),
+        )
+        .await;
+        assert_eq!(request.headers["authorization"], REDACTED);
+        assert_eq!(request.headers["proxy-authorization"], REDACTED);
+
+        // From the request's own headers only
+        let request = FixtureRequest::capture(
+            "GET",
+            "/liveclientdata/allgamedata",
+            None,
+            None,
+            &content_type,
+            (&HeaderMap::new(), &defaults),
+        )
+        .await;
+        assert_eq!(request.headers["authorization"], REDACTED);
+        assert!(!request.headers.contains_key("proxy-authorization"));
+
+        let request = FixtureRequest::capture(
+            "GET",
+            "/lol-gameflow/v1/gameflow-phase",
+            None,
+            Some(&secret),
+            &content_type,
+            (&HeaderMap::new(), &HeaderMap::new()),
+        )
+        .await;
+        assert_eq!(request.headers["authorization"], REDACTED);
+    }
+}
@@ -0,0 +1,209 @@
+//! Typed wrappers around the `/lol-game-settings/v1` endpoints, which hold the in game and key binding settings
+//!
+//! Both are split into sections, such as `General` or `HUD`, each holding a map of setting names to values.
+//! [`SettingsStore::update`] only sends the settings that were changed, so nothing else is overwritten,
+//! then saves them, which the LCU does not do on its own
+
+use super::LcuClient;
+use crate::Error;
+use serde::de::DeserializeOwned;
+use serde_derive::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+/// The endpoint the in game settings are served from, and patched through
+pub const GAME_SETTINGS_ENDPOINT: &str = "/lol-game-settings/v1/game-settings";
+
+/// The endpoint the key bindings are served from, and patched through
+pub const INPUT_SETTINGS_ENDPOINT: &str = "/lol-game-settings/v1/input-settings";
+
+/// The endpoint that writes patched settings to disk, without this they only last until the client restarts
+pub const SAVE_ENDPOINT: &str = "/lol-game-settings/v1/save";
+
+/// Settings, split into sections, such as `General` or `HUD`
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Settings {
+    pub sections: Map<String, Value>,
+}
+
+impl Settings {
+    #[must_use]
+    /// The value of `key` in `section`, `None` if either doesn't exist
+    pub fn get(&self, section: &str, key: &str) -> Option<&Value> {
+        self.sections.get(section)?.get(key)
+    }
+
+    #[must_use]
+    /// The value of `key` in `section` as `T`, `None` if either doesn't exist, or the value isn't a `T`
+    pub fn get_as<T: DeserializeOwned>(&self, section: &str, key: &str) -> Option<T> {
+        T::deserialize(self.get(section, key)?).ok()
+    }
+
+    /// Sets `key` in `section` to `value`, creating the section if it doesn't exist
+    pub fn set(&mut self, section: &str, key: &str, value: impl Into<Value>) {
+        let section = self
+            .sections
+            .entry(section)
+            .or_insert_with(|| Value::Object(Map::new()));
+
+        if !section.is_object() {
+            *section = Value::Object(Map::new());
+        }
+
+        if let Value::Object(section) = section {
+            section.insert(key.to_owned(), value.into());
+        }
+    }
+
+    #[must_use]
+    /// The settings in `self` that are not the same in `old`, nested the same way, so they can be sent as a patch
+    ///
+    /// Settings that were removed are not included, the LCU has no way to remove them
+    pub fn changes_from(&self, old: &Self) -> Map<String, Value> {
+        diff(&self.sections, &old.sections)
+    }
+}
+
+fn diff(new: &Map<String, Value>, old: &Map<String, Value>) -> Map<String, Value> {
+    let mut changes = Map::new();
+
+    for (key, value) in new {
+        match (value, old.get(key)) {
+            (Value::Object(new), Some(Value::Object(old))) => {
+                let nested = diff(new, old);
+                if !nested.is_empty() {
+                    changes.insert(key.clone(), Value::Object(nested));
+                }
+            }
+            (value, Some(old)) if value == old => {}
+            (value, _) => {
+                changes.insert(key.clone(), value.clone());
+            }
+        }
+    }
+
+    changes
+}
+
+/// Reads and writes one set of settings, created with [`LcuClient::game_settings`] or [`LcuClient::input_settings`]
+#[derive(Clone, Copy)]
+pub struct SettingsStore<'a> {
+    client: &'a LcuClient,
+    endpoint: &'static str,
+}
+
+impl SettingsStore<'_> {
+    /// Gets every setting
+    ///
+    /// # Errors
+    /// This will return an error if the LCU API is not running
+    pub async fn get(&self) -> Result<Settings, Error> {
+        self.client.get(self.endpoint).await
+    }
+
+    /// Sends `changes`, which is merged into the existing settings, this is not saved until [`LcuClient::save_settings`] is called
+    ///
+    /// # Errors
+    /// This will return an error if the LCU API is not running, or `changes` is invalid
+    pub async fn patch(&self, changes: &Map<String, Value>) -> Result<(), Error> {
+        // The response is empty when the settings were patched
+        self.client
+            .lcu_request_empty(self.endpoint, "PATCH", Some(changes))
+            .await
+    }
+
+    /// Gets the settings, passes them to `f`, then sends and saves the ones `f` changed
+    ///
+    /// Nothing is sent if `f` changed nothing. Returns the settings as `f` left them
+    ///
+    /// # Errors
+    /// This will return an error if the LCU API is not running, or one of the changed settings is invalid
+    pub async fn update<F>(&self, f: F) -> Result<Settings, Error>
+    where
+        F: FnOnce(&mut Settings) + Send,
+    {
+        let old = self.get().await?;
+        let mut new = old.clone();
+        f(&mut new);
+
+        let changes = new.changes_from(&old);
+        if !changes.is_empty() {
+            self.patch(&changes).await?;
+            self.client.save_settings().await?;
+        }
+
+        Ok(new)
+    }
+}
+
+impl LcuClient {
+    #[must_use]
+    /// The in game settings, such as the HUD scale or the volume
+    pub const fn game_settings(&self) -> SettingsStore<'_> {
+        SettingsStore {
+            client: self,
+            endpoint: GAME_SETTINGS_ENDPOINT,
+        }
+    }
+
+    #[must_use]
+    /// The key bindings
+    pub const fn input_settings(&self) -> SettingsStore<'_> {
+        SettingsStore {
+            client: self,
+            endpoint: INPUT_SETTINGS_ENDPOINT,
+        }
+    }
+
+    /// Writes the game and input settings to disk
+    ///
+    /// # Errors
+    /// This will return an error if the LCU API is not running
+    pub async fn save_settings(&self) -> Result<(), Error> {
+        // The response is empty when the settings were saved
+        self.lcu_request_empty(SAVE_ENDPOINT, "POST", None::<()>)
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Settings;
+    use crate::rest::LcuClient;
+    use serde_json::json;
+
+    #[test]
+    fn changes_only() {
+        let old: Settings = serde_json::from_value(json!({
+            "General": {"WindowMode": 0, "Colorblind": false},
+            "HUD": {"GlobalScale": 0.5, "MinimapScale": 1.0},
+        }))
+        .unwrap();
+
+        let mut new = old.clone();
+        assert!(new.changes_from(&old).is_empty());
+
+        new.set("HUD", "GlobalScale", 0.75);
+        new.set("General", "Colorblind", false);
+        new.set("Volume", "MasterVolume", 0.2);
+
+        assert_eq!(new.get_as::<f64>("HUD", "GlobalScale"), Some(0.75));
+        assert_eq!(new.get_as::<bool>("HUD", "GlobalScale"), None);
+        assert_eq!(
+            serde_json::Value::Object(new.changes_from(&old)),
+            json!({
+                "HUD": {"GlobalScale": 0.75},
+                "Volume": {"MasterVolume": 0.2},
+            })
+        );
+    }
+
+    #[ignore = "This is only needed for testing, and doesn't need to be run all the time"]
+    #[tokio::test]
+    async fn test_game_settings() {
+        let client = LcuClient::connect().unwrap();
+
+        println!("{:?}", client.game_settings().get().await);
+        println!("{:?}", client.input_settings().get().await);
+    }
+}
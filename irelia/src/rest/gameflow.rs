@@ -0,0 +1,660 @@
+//! Typed wrappers around the `/lol-gameflow/v1` endpoints
+//!
+//! The gameflow phase is the state machine the client moves through, from sitting in a lobby to the end of game screen.
+//! With the `ws` feature, [`Gameflow::watch_phase`] follows it as it changes
+
+use super::LcuClient;
+use crate::Error;
+use serde_derive::{Deserialize, Serialize};
+
+#[cfg(feature = "ws")]
+use crate::ws::{types::EventKind, EventStream, LcuWebSocket, StreamEvent, SubscriberID};
+#[cfg(feature = "ws")]
+use futures_core::Stream;
+#[cfg(feature = "ws")]
+use std::future::{poll_fn, Future};
+#[cfg(feature = "ws")]
+use std::pin::Pin;
+#[cfg(feature = "ws")]
+use std::task::{Context, Poll};
+#[cfg(feature = "ws")]
+use std::time::Duration;
+
+/// The endpoint the current gameflow phase is served from, and the uri of its websocket event
+pub const GAMEFLOW_PHASE_ENDPOINT: &str = "/lol-gameflow/v1/gameflow-phase";
+
+/// The phase the client is currently in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum GameflowPhase {
+    None,
+    Lobby,
+    Matchmaking,
+    CheckedIntoTournament,
+    ReadyCheck,
+    ChampSelect,
+    GameStart,
+    FailedToLaunch,
+    InProgress,
+    Reconnect,
+    WaitingForStats,
+    PreEndOfGame,
+    EndOfGame,
+    TerminatedInError,
+    /// A phase this version of the crate does not know about
+    #[serde(other)]
+    Unknown,
+}
+
+impl GameflowPhase {
+    #[must_use]
+    /// Parses the phase from the name the LCU uses for it, returning `Unknown` if it isn't recognized
+    pub fn from_name(name: &[u8]) -> Self {
+        match name {
+            b"None" => Self::None,
+            b"Lobby" => Self::Lobby,
+            b"Matchmaking" => Self::Matchmaking,
+            b"CheckedIntoTournament" => Self::CheckedIntoTournament,
+            b"ReadyCheck" => Self::ReadyCheck,
+            b"ChampSelect" => Self::ChampSelect,
+            b"GameStart" => Self::GameStart,
+            b"FailedToLaunch" => Self::FailedToLaunch,
+            b"InProgress" => Self::InProgress,
+            b"Reconnect" => Self::Reconnect,
+            b"WaitingForStats" => Self::WaitingForStats,
+            b"PreEndOfGame" => Self::PreEndOfGame,
+            b"EndOfGame" => Self::EndOfGame,
+            b"TerminatedInError" => Self::TerminatedInError,
+            _ => Self::Unknown,
+        }
+    }
+
+    /// Parses the phase from the msgpack body of a response, see [`LcuClient::gameflow_phase_fast`]
+    ///
+    /// The name is read straight from the bytes when the body is only a string,
+    /// otherwise this falls back to deserializing it normally
+    ///
+    /// # Errors
+    /// This will return an error if the body is not a phase
+    pub fn from_msgpack(body: &[u8]) -> Result<Self, Error> {
+        match msgpack_str(body) {
+            Some(name) => Ok(Self::from_name(name)),
+            None => Ok(rmp_serde::from_slice(body)?),
+        }
+    }
+}
+
+/// Reads a response that is only a msgpack string, returning `None` if it's in any other shape
+fn msgpack_str(bytes: &[u8]) -> Option<&[u8]> {
+    let (&marker, rest) = bytes.split_first()?;
+
+    let (len, rest) = match marker {
+        // fixstr, the length is in the lower 5 bits
+        0xa0..=0xbf => (usize::from(marker & 0x1f), rest),
+        // str 8, the length is the next byte
+        0xd9 => {
+            let (&len, rest) = rest.split_first()?;
+            (usize::from(len), rest)
+        }
+        _ => return None,
+    };
+
+    (rest.len() == len).then_some(rest)
+}
+
+impl LcuClient {
+    /// Gets the phase the client is currently in
+    ///
+    /// # Errors
+    /// This will return an error if the LCU API is not running
+    pub async fn gameflow_phase(&self) -> Result<GameflowPhase, Error> {
+        self.get(GAMEFLOW_PHASE_ENDPOINT).await
+    }
+
+    #[must_use]
+    /// Helpers that follow the gameflow phase, see [`Gameflow`]
+    pub const fn gameflow(&self) -> Gameflow<'_> {
+        Gameflow { client: self }
+    }
+
+    /// Gets the phase the client is currently in, reading the name straight from the response
+    ///
+    /// This is the same as [`LcuClient::gameflow_phase`], but skips serde, as the response is only ever a string.
+    /// If the response is ever in another shape, this falls back to deserializing it normally, see [`GameflowPhase::from_msgpack`].
+    /// The parsing is compared against serde in `benches/gameflow_phase.rs`
+    ///
+    /// # Errors
+    /// This will return an error if the LCU API is not running
+    pub async fn gameflow_phase_fast(&self) -> Result<GameflowPhase, Error> {
+        let request = self.request_client.request_template(
+            self.url,
+            GAMEFLOW_PHASE_ENDPOINT,
+            "GET",
+            None::<()>,
+            Some(&self.auth_header),
+        );
+
+        let phase = self
+            .timed(request)
+            .await
+            .and_then(|body| GameflowPhase::from_msgpack(&body));
+
+        self.finish(phase).await
+    }
+
+    #[cfg(feature = "ws")]
+    /// Waits until the client reaches `phase`, returning immediately if it's already there
+    ///
+    /// This follows the phase with [`Gameflow::watch_phase`], so a change that happens before the current phase
+    /// is fetched is not missed. The subscription is removed before returning
+    ///
+    /// # Errors
+    /// This will return an error if the LCU API is not running, the websocket connection was closed,
+    /// or `phase` was not reached within `timeout`
+    pub async fn wait_for_phase(
+        &self,
+        websocket: &mut LcuWebSocket,
+        phase: GameflowPhase,
+        timeout: Duration,
+    ) -> Result<(), Error> {
+        let (id, mut phases) = self.gameflow().watch_phase(websocket)?;
+
+        let wait = async {
+            while let Some(next) = poll_fn(|cx| Pin::new(&mut phases).poll_next(cx)).await {
+                if next? == phase {
+                    return Ok(());
+                }
+            }
+
+            Err(Error::WebSocketClosed)
+        };
+
+        let result = crate::utils::runtime::timeout(timeout, wait).await;
+        websocket.unsubscribe(
+            EventKind::json_api_event_callback_str(GAMEFLOW_PHASE_ENDPOINT),
+            id,
+        );
+
+        result.unwrap_or(Err(Error::Timeout))
+    }
+}
+
+/// Follows the gameflow phase, created with [`LcuClient::gameflow`]
+#[derive(Clone, Copy)]
+pub struct Gameflow<'a> {
+    client: &'a LcuClient,
+}
+
+impl<'a> Gameflow<'a> {
+    /// Gets the phase the client is currently in
+    ///
+    /// # Errors
+    /// This will return an error if the LCU API is not running
+    pub async fn phase(&self) -> Result<GameflowPhase, Error> {
+        self.client.gameflow_phase().await
+    }
+
+    #[cfg(feature = "ws")]
+    /// Follows the phase as it changes, see [`PhaseStream`]
+    ///
+    /// The phase event is subscribed to on `ws` before the current phase is fetched, so no change is missed.
+    /// The subscription can be removed with the returned ID, which ends the stream
+    ///
+    /// ```no_run
+    /// use futures_util::StreamExt;
+    /// use irelia::rest::LcuClient;
+    /// use irelia::ws::LcuWebSocket;
+    ///
+    /// # async fn run() -> Result<(), irelia::Error> {
+    /// let client = LcuClient::connect()?;
+    /// let mut ws = LcuWebSocket::new();
+    ///
+    /// let (_, mut phases) = client.gameflow().watch_phase(&mut ws)?;
+    ///
+    /// while let Some(phase) = phases.next().await {
+    ///     println!("{:?}", phase?);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    /// This will return an error if the websocket connection has already been closed
+    pub fn watch_phase(
+        &self,
+        ws: &mut LcuWebSocket,
+    ) -> Result<(SubscriberID, PhaseStream<'a>), Error> {
+        let event_kind = EventKind::json_api_event_callback_str(GAMEFLOW_PHASE_ENDPOINT);
+        let (id, events) = ws
+            .subscribe_stream(event_kind)
+            .ok_or(Error::WebSocketClosed)?;
+
+        let client = self.client;
+        Ok((
+            id,
+            PhaseStream {
+                client,
+                events,
+                fetch: Some(Box::pin(client.gameflow_phase())),
+                missed: false,
+                last: None,
+            },
+        ))
+    }
+}
+
+#[cfg(feature = "ws")]
+type PhaseFuture<'a> = Pin<Box<dyn Future<Output = Result<GameflowPhase, Error>> + Send + 'a>>;
+
+#[cfg(feature = "ws")]
+/// The gameflow phase as it changes, created with [`Gameflow::watch_phase`]
+///
+/// The first item is always the current phase, fetched from the REST endpoint, after that a phase is only yielded
+/// when it's different from the last one. When the websocket reconnects, the phase is fetched again,
+/// as changes in between were missed. Events that arrive while the phase is being fetched can be older or newer
+/// than the fetched phase, so they're dropped, and the phase is fetched once more after the fetch finishes
+///
+/// Errors are only from fetching the phase, the stream keeps following events after one
+pub struct PhaseStream<'a> {
+    client: &'a LcuClient,
+    events: EventStream<GameflowPhase>,
+    /// Fetching the current phase, this takes priority over events
+    fetch: Option<PhaseFuture<'a>>,
+    /// Whether an event was dropped during the current fetch
+    missed: bool,
+    last: Option<GameflowPhase>,
+}
+
+#[cfg(feature = "ws")]
+impl PhaseStream<'_> {
+    #[must_use]
+    /// The last phase this yielded, `None` before the first one
+    pub const fn last(&self) -> Option<GameflowPhase> {
+        self.last
+    }
+
+    /// Starts fetching the current phase, replacing any fetch in flight
+    fn refetch(&mut self) {
+        self.fetch = Some(Box::pin(self.client.gameflow_phase()));
+        self.missed = false;
+    }
+
+    /// Yields `phase` if it's a change from the last phase
+    fn transition(&mut self, phase: GameflowPhase) -> Option<GameflowPhase> {
+        (self.last.replace(phase) != Some(phase)).then_some(phase)
+    }
+}
+
+#[cfg(feature = "ws")]
+impl Stream for PhaseStream<'_> {
+    type Item = Result<GameflowPhase, Error>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+
+        loop {
+            if let Some(fetch) = &mut this.fetch {
+                let result = match fetch.as_mut().poll(cx) {
+                    Poll::Ready(result) => result,
+                    // Events received while fetching can be older than the fetched phase, so they're dropped
+                    Poll::Pending => match Pin::new(&mut this.events).poll_next(cx) {
+                        Poll::Ready(Some(Ok(StreamEvent::Reconnected))) => {
+                            this.refetch();
+                            continue;
+                        }
+                        Poll::Ready(Some(Ok(StreamEvent::Event(_)))) => {
+                            this.missed = true;
+                            continue;
+                        }
+                        Poll::Ready(Some(_)) => continue,
+                        Poll::Ready(None) => return Poll::Ready(None),
+                        Poll::Pending => return Poll::Pending,
+                    },
+                };
+                this.fetch = None;
+
+                // A dropped event can be newer than the fetched phase, which only another fetch can tell
+                if this.missed {
+                    this.refetch();
+                }
+
+                match result {
+                    Ok(phase) => match this.transition(phase) {
+                        Some(phase) => return Poll::Ready(Some(Ok(phase))),
+                        None => continue,
+                    },
+                    Err(err) => return Poll::Ready(Some(Err(err))),
+                }
+            }
+
+            let event = match Pin::new(&mut this.events).poll_next(cx) {
+                Poll::Ready(Some(event)) => event,
+                Poll::Ready(None) => return Poll::Ready(None),
+                Poll::Pending => return Poll::Pending,
+            };
+
+            match event {
+                Ok(StreamEvent::Event(event)) => {
+                    if let Some(phase) = this.transition(event.data) {
+                        return Poll::Ready(Some(Ok(phase)));
+                    }
+                }
+                Ok(StreamEvent::Reconnected) => this.refetch(),
+                // Events that can't be decoded, such as when the phase is deleted, carry no phase
+                Ok(StreamEvent::ConnectionLost) | Err(_) => {}
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{msgpack_str, GameflowPhase};
+
+    #[test]
+    fn phase_deserialize() {
+        let phase: GameflowPhase = serde_json::from_str("\"ChampSelect\"").unwrap();
+        assert_eq!(phase, GameflowPhase::ChampSelect);
+
+        let phase: GameflowPhase = serde_json::from_str("\"SomeNewPhase\"").unwrap();
+        assert_eq!(phase, GameflowPhase::Unknown);
+
+        let bytes = rmp_serde::to_vec(&"InProgress").unwrap();
+        let phase: GameflowPhase = rmp_serde::from_slice(&bytes).unwrap();
+        assert_eq!(phase, GameflowPhase::InProgress);
+    }
+
+    #[test]
+    fn phase_fast_path() {
+        for name in ["None", "ChampSelect", "TerminatedInError", "SomeNewPhase"] {
+            let bytes = rmp_serde::to_vec(name).unwrap();
+            let fast = GameflowPhase::from_name(msgpack_str(&bytes).unwrap());
+            let generic: GameflowPhase = rmp_serde::from_slice(&bytes).unwrap();
+            assert_eq!(fast, generic);
+        }
+
+        // str 8 is used for anything longer than 31 bytes
+        let bytes = rmp_serde::to_vec(&"A".repeat(40)).unwrap();
+        assert_eq!(msgpack_str(&bytes).map(<[u8]>::len), Some(40));
+
+        assert_eq!(msgpack_str(&rmp_serde::to_vec(&1).unwrap()), None);
+        assert_eq!(msgpack_str(&[0xa5, b'L', b'o']), None);
+    }
+
+    #[cfg(feature = "ws")]
+    #[tokio::test]
+    async fn watch_phase_dedup() {
+        use super::{PhaseStream, GAMEFLOW_PHASE_ENDPOINT};
+        use crate::rest::unreachable_client;
+        use crate::ws::{test_util, types::EventKind, DefaultErrorHandler, LcuWebSocket};
+        use futures_util::StreamExt;
+        use std::time::Duration;
+
+        let (_listener, client) = unreachable_client();
+
+        let (stream, mut server) = test_util::mock_stream();
+        let mut ws = LcuWebSocket::from_stream(stream, DefaultErrorHandler);
+
+        let event_kind = EventKind::json_api_event_callback_str(GAMEFLOW_PHASE_ENDPOINT);
+        let (_, events) = ws.subscribe_stream(event_kind).unwrap();
+
+        // The current phase is fetched as Lobby, so the first event is not a change
+        let phases = PhaseStream {
+            client: &client,
+            events,
+            fetch: Some(Box::pin(async { Ok(GameflowPhase::Lobby) })),
+            missed: false,
+            last: None,
+        };
+
+        while server.receive().unwrap().is_none() {
+            std::thread::sleep(Duration::from_millis(1));
+        }
+
+        for phase in ["Lobby", "Matchmaking", "Matchmaking", "ReadyCheck"] {
+            server
+                .send_text(format!(
+                    r#"[8, "OnJsonApiEvent_lol-gameflow_v1_gameflow-phase", {{"data": "{phase}", "eventType": "Update", "uri": "{GAMEFLOW_PHASE_ENDPOINT}"}}]"#
+                ))
+                .unwrap();
+        }
+
+        let phases: Vec<_> = phases.take(3).map(Result::unwrap).collect().await;
+        assert_eq!(
+            phases,
+            [
+                GameflowPhase::Lobby,
+                GameflowPhase::Matchmaking,
+                GameflowPhase::ReadyCheck
+            ]
+        );
+
+        ws.abort().unwrap();
+    }
+
+    #[cfg(feature = "ws")]
+    /// Waits until every subscription made on `ws` so far is in place
+    ///
+    /// Only the first subscription to an event is sent to the server, so this subscribes to another event,
+    /// and waits for both to arrive, as subscriptions are handled in order
+    fn wait_for_subscriptions(
+        ws: &mut crate::ws::LcuWebSocket,
+        server: &mut crate::ws::test_util::MockServer,
+    ) {
+        use crate::ws::types::EventKind;
+        use std::time::Duration;
+
+        ws.subscribe_closure(EventKind::Log, |_| {}).unwrap();
+
+        let mut received = 0;
+        while received < 2 {
+            match server.receive().unwrap() {
+                Some(_) => received += 1,
+                None => std::thread::sleep(Duration::from_millis(1)),
+            }
+        }
+    }
+
+    #[cfg(feature = "ws")]
+    fn send_phase(server: &mut crate::ws::test_util::MockServer, phase: &str) {
+        server
+            .send_text(format!(
+                r#"[8, "OnJsonApiEvent_lol-gameflow_v1_gameflow-phase", {{"data": "{phase}", "eventType": "Update", "uri": "{}"}}]"#,
+                super::GAMEFLOW_PHASE_ENDPOINT
+            ))
+            .unwrap();
+    }
+
+    #[cfg(feature = "ws")]
+    #[tokio::test]
+    async fn watch_phase_drops_stale_events() {
+        use super::{PhaseStream, GAMEFLOW_PHASE_ENDPOINT};
+        use crate::mock::MockLcu;
+        use crate::ws::{test_util, types::EventKind, DefaultErrorHandler, LcuWebSocket};
+        use futures_util::StreamExt;
+        use std::time::Duration;
+        use tokio::sync::oneshot;
+
+        let mock = MockLcu::start().unwrap();
+        mock.on_get(GAMEFLOW_PHASE_ENDPOINT)
+            .respond_json("Matchmaking");
+        let client = mock.client();
+
+        let (stream, mut server) = test_util::mock_stream();
+        let mut ws = LcuWebSocket::from_stream(stream, DefaultErrorHandler);
+
+        let event_kind = EventKind::json_api_event_callback_str(GAMEFLOW_PHASE_ENDPOINT);
+        let (_, events) = ws.subscribe_stream(event_kind.clone()).unwrap();
+        // Events are sent to subscribers in order, so once this has an event, so does the phase stream
+        let (_, mut delivered) = ws.subscribe_stream::<GameflowPhase>(event_kind).unwrap();
+        wait_for_subscriptions(&mut ws, &mut server);
+
+        // The fetch only finishes once the phase is sent
+        let (sender, receiver) = oneshot::channel();
+        let mut phases = PhaseStream {
+            client: &client,
+            events,
+            fetch: Some(Box::pin(async { Ok(receiver.await.unwrap()) })),
+            missed: false,
+            last: None,
+        };
+
+        // Lobby arrives while the fetch is in flight, and is older than the fetched phase
+        send_phase(&mut server, "Lobby");
+        delivered.next().await.unwrap().unwrap();
+        let pending = tokio::time::timeout(Duration::from_millis(10), phases.next()).await;
+        assert!(pending.is_err());
+
+        sender.send(GameflowPhase::Matchmaking).unwrap();
+        assert_eq!(
+            phases.next().await.unwrap().unwrap(),
+            GameflowPhase::Matchmaking
+        );
+
+        // The phase is fetched again for the dropped event, which isn't a change
+        while phases.fetch.is_some() {
+            let pending = tokio::time::timeout(Duration::from_millis(10), phases.next()).await;
+            assert!(pending.is_err());
+        }
+
+        send_phase(&mut server, "ReadyCheck");
+        assert_eq!(
+            phases.next().await.unwrap().unwrap(),
+            GameflowPhase::ReadyCheck
+        );
+
+        ws.abort().unwrap();
+    }
+
+    #[cfg(feature = "ws")]
+    #[tokio::test]
+    async fn watch_phase_refetches_missed_events() {
+        use super::{PhaseStream, GAMEFLOW_PHASE_ENDPOINT};
+        use crate::mock::MockLcu;
+        use crate::ws::{test_util, types::EventKind, DefaultErrorHandler, LcuWebSocket};
+        use futures_util::StreamExt;
+        use std::time::Duration;
+        use tokio::sync::oneshot;
+
+        // The client moved on to ReadyCheck after building the response to the first fetch
+        let mock = MockLcu::start().unwrap();
+        mock.on_get(GAMEFLOW_PHASE_ENDPOINT)
+            .respond_json("ReadyCheck");
+        let client = mock.client();
+
+        let (stream, mut server) = test_util::mock_stream();
+        let mut ws = LcuWebSocket::from_stream(stream, DefaultErrorHandler);
+
+        let event_kind = EventKind::json_api_event_callback_str(GAMEFLOW_PHASE_ENDPOINT);
+        let (_, events) = ws.subscribe_stream(event_kind.clone()).unwrap();
+        let (_, mut delivered) = ws.subscribe_stream::<GameflowPhase>(event_kind).unwrap();
+        wait_for_subscriptions(&mut ws, &mut server);
+
+        let (sender, receiver) = oneshot::channel();
+        let mut phases = PhaseStream {
+            client: &client,
+            events,
+            fetch: Some(Box::pin(async { Ok(receiver.await.unwrap()) })),
+            missed: false,
+            last: None,
+        };
+
+        send_phase(&mut server, "ReadyCheck");
+        delivered.next().await.unwrap().unwrap();
+        let pending = tokio::time::timeout(Duration::from_millis(10), phases.next()).await;
+        assert!(pending.is_err());
+
+        sender.send(GameflowPhase::Matchmaking).unwrap();
+
+        let phases: Vec<_> = phases.take(2).map(Result::unwrap).collect().await;
+        assert_eq!(
+            phases,
+            [GameflowPhase::Matchmaking, GameflowPhase::ReadyCheck]
+        );
+
+        ws.abort().unwrap();
+    }
+
+    #[cfg(feature = "ws")]
+    #[tokio::test]
+    async fn wait_for_phase_follows_events() {
+        use super::GAMEFLOW_PHASE_ENDPOINT;
+        use crate::mock::MockLcu;
+        use crate::rest::LcuClient;
+        use crate::ws::{test_util, DefaultErrorHandler, LcuWebSocket};
+        use crate::{Error, RequestClient};
+        use std::sync::Arc;
+        use std::time::Duration;
+
+        let mock = Arc::new(MockLcu::start().unwrap());
+        mock.on_get(GAMEFLOW_PHASE_ENDPOINT).respond_json("Lobby");
+        let client = LcuClient::new_with_credentials_with_request_client(
+            mock.addr(),
+            mock.auth_header().clone(),
+            &RequestClient::danger_accept_invalid_certs(),
+        );
+
+        let (stream, mut server) = test_util::mock_stream();
+        let mut ws = LcuWebSocket::from_stream(stream, DefaultErrorHandler);
+
+        let server = std::thread::spawn({
+            let mock = Arc::clone(&mock);
+            move || {
+                while server.receive().unwrap().is_none() {
+                    std::thread::sleep(Duration::from_millis(1));
+                }
+
+                // The event can arrive while the phase is being fetched, which fetches it again
+                mock.on_get(GAMEFLOW_PHASE_ENDPOINT)
+                    .respond_json("Matchmaking");
+                send_phase(&mut server, "Matchmaking");
+                server
+            }
+        });
+
+        client
+            .wait_for_phase(&mut ws, GameflowPhase::Matchmaking, Duration::from_secs(5))
+            .await
+            .unwrap();
+        drop(server.join().unwrap());
+
+        // Already in matchmaking, with nothing else sent
+        let timed_out = client
+            .wait_for_phase(
+                &mut ws,
+                GameflowPhase::ReadyCheck,
+                Duration::from_millis(50),
+            )
+            .await;
+        assert!(matches!(timed_out, Err(Error::Timeout)));
+
+        client
+            .wait_for_phase(
+                &mut ws,
+                GameflowPhase::Matchmaking,
+                Duration::from_millis(50),
+            )
+            .await
+            .unwrap();
+
+        ws.abort().unwrap();
+    }
+
+    #[cfg(feature = "ws")]
+    #[ignore = "This is only needed for testing, and doesn't need to be run all the time"]
+    #[tokio::test]
+    async fn wait_for_lobby() {
+        use std::time::Duration;
+
+        let client = crate::rest::LcuClient::connect().unwrap();
+        let mut websocket = crate::ws::LcuWebSocket::new();
+
+        client
+            .wait_for_phase(
+                &mut websocket,
+                GameflowPhase::Lobby,
+                Duration::from_secs(30),
+            )
+            .await
+            .unwrap();
+    }
+}
@@ -0,0 +1,185 @@
+//! Typed wrappers around the `/lol-honor-v2/v1` endpoints, which serve the honor screen shown after a game
+
+use super::LcuClient;
+use crate::Error;
+use hyper::StatusCode;
+use serde_derive::{Deserialize, Serialize};
+
+/// The endpoint the ballot of the last game is served from
+pub const BALLOT_ENDPOINT: &str = "/lol-honor-v2/v1/ballot";
+
+/// The endpoint votes are submitted to
+pub const HONOR_PLAYER_ENDPOINT: &str = "/lol-honor-v2/v1/honor-player";
+
+/// The players that can be honored after a game
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Ballot {
+    pub game_id: u64,
+    /// The players on the local player's team, not including them
+    #[serde(default, alias = "eligiblePlayers")]
+    pub eligible_allies: Vec<BallotPlayer>,
+    #[serde(default)]
+    pub eligible_opponents: Vec<BallotPlayer>,
+}
+
+impl Ballot {
+    /// Every player that can be honored, allies first
+    pub fn players(&self) -> impl Iterator<Item = &BallotPlayer> {
+        self.eligible_allies.iter().chain(&self.eligible_opponents)
+    }
+}
+
+/// A player that can be honored
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BallotPlayer {
+    #[serde(default)]
+    pub puuid: String,
+    #[serde(default)]
+    pub summoner_id: u64,
+    #[serde(default)]
+    pub game_name: String,
+    #[serde(default)]
+    pub tag_line: String,
+    #[serde(default)]
+    pub champion_name: String,
+}
+
+/// What a player is honored for
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum HonorCategory {
+    /// Stay cool
+    Cool,
+    /// Great shotcalling
+    Shotcaller,
+    /// GG <3
+    Heart,
+    /// Skips the ballot without honoring anyone
+    OptOut,
+}
+
+/// A vote, as sent to [`HONOR_PLAYER_ENDPOINT`]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HonorVote {
+    pub game_id: u64,
+    pub honor_category: HonorCategory,
+    #[serde(default)]
+    pub puuid: String,
+    #[serde(default)]
+    pub summoner_id: u64,
+}
+
+impl HonorVote {
+    #[must_use]
+    /// A vote honoring `player` for `category` in `game_id`
+    pub fn new(game_id: u64, player: &BallotPlayer, category: HonorCategory) -> Self {
+        Self {
+            game_id,
+            honor_category: category,
+            puuid: player.puuid.clone(),
+            summoner_id: player.summoner_id,
+        }
+    }
+
+    #[must_use]
+    /// A vote that skips the ballot of `game_id`
+    pub const fn opt_out(game_id: u64) -> Self {
+        Self {
+            game_id,
+            honor_category: HonorCategory::OptOut,
+            puuid: String::new(),
+            summoner_id: 0,
+        }
+    }
+}
+
+impl LcuClient {
+    /// Gets the ballot of the last game, `None` if there is nobody left to honor
+    ///
+    /// # Errors
+    /// This will return an error if the LCU API is not running
+    pub async fn honor_ballot(&self) -> Result<Option<Ballot>, Error> {
+        match self.get(BALLOT_ENDPOINT).await {
+            Ok(ballot) => Ok(Some(ballot)),
+            // There's no ballot outside of the honor screen
+            Err(err) if err.status() == Some(StatusCode::NOT_FOUND) => Ok(None),
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Submits `vote`, the honor screen closes once a vote is submitted
+    ///
+    /// # Errors
+    /// This will return an error if the LCU API is not running, or the vote is not for the current ballot
+    pub async fn honor_player(&self, vote: &HonorVote) -> Result<(), Error> {
+        // The response is empty when the vote was submitted
+        self.lcu_request_empty(HONOR_PLAYER_ENDPOINT, "POST", Some(vote))
+            .await
+    }
+
+    /// Skips the current ballot without honoring anyone, returns `false` if there was no ballot
+    ///
+    /// # Errors
+    /// This will return an error if the LCU API is not running
+    pub async fn skip_honor(&self) -> Result<bool, Error> {
+        let Some(ballot) = self.honor_ballot().await? else {
+            return Ok(false);
+        };
+
+        self.honor_player(&HonorVote::opt_out(ballot.game_id))
+            .await?;
+        Ok(true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Ballot, HonorCategory, HonorVote};
+    use crate::rest::LcuClient;
+
+    #[test]
+    fn ballot_vote() {
+        let ballot: Ballot = serde_json::from_str(
+            r#"{
+                "gameId": 5000,
+                "eligibleAllies": [{"puuid": "abc", "summonerId": 1, "gameName": "Sylv", "championName": "Irelia"}],
+                "eligibleOpponents": [{"puuid": "def", "summonerId": 2}]
+            }"#,
+        )
+        .unwrap();
+
+        assert_eq!(ballot.players().count(), 2);
+
+        let vote = HonorVote::new(
+            ballot.game_id,
+            &ballot.eligible_allies[0],
+            HonorCategory::Heart,
+        );
+        let json = serde_json::to_value(&vote).unwrap();
+        assert_eq!(json["honorCategory"], "HEART");
+        assert_eq!(json["puuid"], "abc");
+
+        let json = serde_json::to_value(HonorVote::opt_out(5000)).unwrap();
+        assert_eq!(json["honorCategory"], "OPT_OUT");
+    }
+
+    #[test]
+    fn legacy_ballot() {
+        let ballot: Ballot =
+            serde_json::from_str(r#"{"gameId": 1, "eligiblePlayers": [{"summonerId": 3}]}"#)
+                .unwrap();
+
+        assert_eq!(ballot.eligible_allies[0].summoner_id, 3);
+    }
+
+    #[ignore = "This is only needed for testing, and doesn't need to be run all the time"]
+    #[tokio::test]
+    async fn test_honor_ballot() {
+        let client = LcuClient::connect().unwrap();
+
+        println!("{:?}", client.honor_ballot().await);
+    }
+}
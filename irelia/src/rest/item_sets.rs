@@ -0,0 +1,360 @@
+//! Typed wrappers around the `/lol-item-sets/v1` endpoints
+//!
+//! The LCU only replaces every set of a summoner at once, so [`LcuClient::upsert_item_sets`] merges new sets
+//! into the existing ones by title, rather than replacing them
+
+use super::LcuClient;
+use crate::Error;
+use serde_derive::{Deserialize, Serialize};
+
+/// The endpoint the item sets of `summoner_id` are served from, and replaced with
+#[must_use]
+pub fn item_sets_endpoint(summoner_id: u64) -> String {
+    format!("/lol-item-sets/v1/item-sets/{summoner_id}/sets")
+}
+
+/// Every item set of a summoner
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ItemSets {
+    #[serde(default)]
+    pub account_id: u64,
+    #[serde(default)]
+    pub item_sets: Vec<ItemSet>,
+    #[serde(default)]
+    pub timestamp: u64,
+    /// Fields that aren't modelled here, kept so they're written back unchanged
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+/// A single item set, this is also the format the client imports and exports sets as
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ItemSet {
+    pub title: String,
+    /// Such as `custom` or `global`
+    #[serde(rename = "type", default = "custom")]
+    pub set_type: String,
+    /// Such as `any` or `SR`
+    #[serde(default = "any")]
+    pub map: String,
+    /// Such as `any` or `CLASSIC`
+    #[serde(default = "any")]
+    pub mode: String,
+    /// The champions the set is shown for, it's shown for every champion if this is empty
+    #[serde(default)]
+    pub associated_champions: Vec<i32>,
+    /// The maps the set is shown on, it's shown on every map if this is empty
+    #[serde(default)]
+    pub associated_maps: Vec<i32>,
+    #[serde(default)]
+    pub blocks: Vec<ItemBlock>,
+    #[serde(default)]
+    pub sortrank: i32,
+    #[serde(default)]
+    pub started_from: String,
+    #[serde(default)]
+    pub uid: String,
+    /// Kept as is, so sets that use it are written back unchanged
+    #[serde(default)]
+    pub preferred_item_slots: Vec<serde_json::Value>,
+    /// Fields that aren't modelled here, kept so they're written back unchanged
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+/// A named group of items in an item set
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ItemBlock {
+    /// The title of the block, such as `Starting Items`
+    #[serde(rename = "type")]
+    pub name: String,
+    #[serde(default)]
+    pub items: Vec<ItemBlockItem>,
+    /// The summoner spell the block is hidden for, such as `SummonerSmite`
+    #[serde(default)]
+    pub hide_if_summoner_spell: String,
+    /// The summoner spell the block is only shown for
+    #[serde(default)]
+    pub show_if_summoner_spell: String,
+    /// Fields that aren't modelled here, such as `recMath` or `minSummonerLevel`, kept so they're written back unchanged
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+/// An item in an [`ItemBlock`]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ItemBlockItem {
+    /// The ID of the item, which the LCU sends as a string
+    pub id: String,
+    pub count: u32,
+}
+
+fn custom() -> String {
+    "custom".to_owned()
+}
+
+fn any() -> String {
+    "any".to_owned()
+}
+
+impl ItemSet {
+    #[must_use]
+    /// Creates an empty custom set called `title`, shown for every champion on every map
+    pub fn new(title: impl Into<String>) -> Self {
+        Self {
+            title: title.into(),
+            set_type: custom(),
+            map: any(),
+            mode: any(),
+            associated_champions: Vec::new(),
+            associated_maps: Vec::new(),
+            blocks: Vec::new(),
+            sortrank: 0,
+            started_from: "blank".to_owned(),
+            uid: String::new(),
+            preferred_item_slots: Vec::new(),
+            extra: serde_json::Map::new(),
+        }
+    }
+
+    #[must_use]
+    /// Adds `block` after the blocks already in the set
+    pub fn block(mut self, block: ItemBlock) -> Self {
+        self.blocks.push(block);
+        self
+    }
+
+    /// Reads a set in the format the client exports them as
+    ///
+    /// # Errors
+    /// This will return an error if `json` is not a valid item set
+    pub fn import(json: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+
+    /// Writes the set in the format the client imports them as
+    ///
+    /// # Errors
+    /// This will return an error if a value in `preferred_item_slots` can't be written as JSON
+    pub fn export(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string_pretty(self)
+    }
+}
+
+impl ItemBlock {
+    #[must_use]
+    /// Creates a block called `name`, with one of each item in `item_ids`
+    pub fn new(name: impl Into<String>, item_ids: impl IntoIterator<Item = u32>) -> Self {
+        Self {
+            name: name.into(),
+            items: item_ids
+                .into_iter()
+                .map(|id| ItemBlockItem {
+                    id: id.to_string(),
+                    count: 1,
+                })
+                .collect(),
+            hide_if_summoner_spell: String::new(),
+            show_if_summoner_spell: String::new(),
+            extra: serde_json::Map::new(),
+        }
+    }
+}
+
+impl ItemSets {
+    /// Replaces the sets with the same title as one of `sets`, and adds the rest after the existing sets
+    ///
+    /// A replaced set keeps its `uid` if the new one has none, so the client treats it as the same set
+    pub fn merge(&mut self, sets: impl IntoIterator<Item = ItemSet>) {
+        for mut set in sets {
+            match self
+                .item_sets
+                .iter_mut()
+                .find(|existing| existing.title == set.title)
+            {
+                Some(existing) => {
+                    if set.uid.is_empty() {
+                        set.uid = std::mem::take(&mut existing.uid);
+                    }
+                    *existing = set;
+                }
+                None => self.item_sets.push(set),
+            }
+        }
+    }
+
+    /// Removes every set whose title is one of `titles`, returning how many were removed
+    pub fn remove<'a>(&mut self, titles: impl IntoIterator<Item = &'a str>) -> usize {
+        let titles: Vec<_> = titles.into_iter().collect();
+        let len = self.item_sets.len();
+
+        self.item_sets
+            .retain(|set| !titles.contains(&set.title.as_str()));
+
+        len - self.item_sets.len()
+    }
+}
+
+impl LcuClient {
+    /// Gets every item set of `summoner_id`
+    ///
+    /// # Errors
+    /// This will return an error if the LCU API is not running
+    pub async fn item_sets(&self, summoner_id: u64) -> Result<ItemSets, Error> {
+        self.get(item_sets_endpoint(summoner_id)).await
+    }
+
+    /// Replaces every item set of `summoner_id` with `item_sets`, sets that are not in it are deleted
+    ///
+    /// To keep the existing sets, use [`LcuClient::upsert_item_sets`] instead
+    ///
+    /// # Errors
+    /// This will return an error if the LCU API is not running, or one of the sets is invalid
+    pub async fn replace_item_sets(
+        &self,
+        summoner_id: u64,
+        item_sets: &ItemSets,
+    ) -> Result<(), Error> {
+        // The response is empty when the sets were replaced
+        self.lcu_request_empty(&item_sets_endpoint(summoner_id), "PUT", Some(item_sets))
+            .await
+    }
+
+    /// Adds `sets` to the item sets of `summoner_id`, replacing the ones with the same title, see [`ItemSets::merge`]
+    ///
+    /// Returns the sets as they were written
+    ///
+    /// # Errors
+    /// This will return an error if the LCU API is not running, or one of the sets is invalid
+    pub async fn upsert_item_sets(
+        &self,
+        summoner_id: u64,
+        sets: impl IntoIterator<Item = ItemSet> + Send,
+    ) -> Result<ItemSets, Error> {
+        let mut item_sets = self.item_sets(summoner_id).await?;
+        item_sets.merge(sets);
+
+        self.replace_item_sets(summoner_id, &item_sets).await?;
+        Ok(item_sets)
+    }
+
+    /// Removes the item sets of `summoner_id` whose title is one of `titles`, returning how many were removed
+    ///
+    /// Nothing is written if none of them exist
+    ///
+    /// # Errors
+    /// This will return an error if the LCU API is not running
+    pub async fn remove_item_sets(
+        &self,
+        summoner_id: u64,
+        titles: &[&str],
+    ) -> Result<usize, Error> {
+        let mut item_sets = self.item_sets(summoner_id).await?;
+        let removed = item_sets.remove(titles.iter().copied());
+
+        if removed > 0 {
+            self.replace_item_sets(summoner_id, &item_sets).await?;
+        }
+
+        Ok(removed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ItemBlock, ItemSet, ItemSets};
+    use crate::rest::LcuClient;
+
+    #[test]
+    fn merge_by_title() {
+        let mut existing = ItemSet::new("Irelia");
+        existing.uid = "1".to_owned();
+
+        let mut item_sets = ItemSets {
+            account_id: 1,
+            item_sets: vec![existing, ItemSet::new("Riven")],
+            timestamp: 0,
+            extra: serde_json::Map::new(),
+        };
+
+        let updated = ItemSet::new("Irelia").block(ItemBlock::new("Start", [1055, 2003]));
+        item_sets.merge([updated, ItemSet::new("Fiora")]);
+
+        let titles: Vec<_> = item_sets
+            .item_sets
+            .iter()
+            .map(|set| set.title.as_str())
+            .collect();
+        assert_eq!(titles, ["Irelia", "Riven", "Fiora"]);
+        assert_eq!(item_sets.item_sets[0].uid, "1");
+        assert_eq!(item_sets.item_sets[0].blocks[0].items[1].id, "2003");
+
+        assert_eq!(item_sets.remove(["Riven", "Yasuo"]), 1);
+        assert_eq!(item_sets.item_sets.len(), 2);
+    }
+
+    #[test]
+    fn import_export() {
+        let set = ItemSet::import(
+            r#"{
+                "title": "Irelia",
+                "associatedChampions": [39],
+                "blocks": [{"type": "Start", "items": [{"id": "1055", "count": 1}]}]
+            }"#,
+        )
+        .unwrap();
+
+        assert_eq!(set.set_type, "custom");
+        assert_eq!(set.map, "any");
+        assert_eq!(set.blocks[0].name, "Start");
+
+        let exported = set.export().unwrap();
+        assert_eq!(ItemSet::import(&exported).unwrap(), set);
+    }
+
+    #[test]
+    fn unknown_fields_kept() {
+        let item_sets: ItemSets = serde_json::from_value(serde_json::json!({
+            "accountId": 1,
+            "itemSets": [{
+                "title": "Irelia",
+                "newSetField": {"nested": true},
+                "blocks": [{
+                    "type": "Start",
+                    "recMath": true,
+                    "minSummonerLevel": -1,
+                    "items": [{"id": "1055", "count": 1}]
+                }]
+            }],
+            "timestamp": 0,
+            "newField": "kept"
+        }))
+        .unwrap();
+
+        assert_eq!(item_sets.extra["newField"], "kept");
+        assert_eq!(item_sets.item_sets[0].extra["newSetField"]["nested"], true);
+        assert_eq!(item_sets.item_sets[0].blocks[0].extra["recMath"], true);
+
+        // The sets are written back as msgpack, which has to keep every field too
+        let mut round_tripped: ItemSets =
+            rmp_serde::from_slice(&rmp_serde::to_vec_named(&item_sets).unwrap()).unwrap();
+        assert_eq!(round_tripped, item_sets);
+
+        round_tripped.merge([ItemSet::new("Riven")]);
+        let written = serde_json::to_value(&round_tripped).unwrap();
+        assert_eq!(written["newField"], "kept");
+        assert_eq!(written["itemSets"][0]["blocks"][0]["minSummonerLevel"], -1);
+    }
+
+    #[ignore = "This is only needed for testing, and doesn't need to be run all the time"]
+    #[tokio::test]
+    async fn test_item_sets() {
+        let client = LcuClient::connect().unwrap();
+        let summoner = client.summoner_by_name("Sylv").await.unwrap().unwrap();
+
+        println!("{:?}", client.item_sets(summoner.summoner_id).await);
+    }
+}
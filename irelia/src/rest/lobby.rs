@@ -0,0 +1,456 @@
+//! Typed wrappers around the `/lol-lobby/v2/lobby` endpoints
+//!
+//! These create lobbies, either for a queue or a custom game, invite players to them, and start matchmaking
+
+use super::LcuClient;
+use crate::Error;
+use serde_derive::{Deserialize, Serialize};
+
+#[cfg(feature = "ws")]
+use crate::ws::{
+    types::EventKind, EventStream, LcuWebSocket, StreamEvent, SubscriberID, WebSocketError,
+};
+#[cfg(feature = "ws")]
+use futures_core::Stream;
+#[cfg(feature = "ws")]
+use std::pin::Pin;
+#[cfg(feature = "ws")]
+use std::task::{Context, Poll};
+
+/// The endpoint the current lobby is served from, and the uri of its websocket event
+pub const LOBBY_ENDPOINT: &str = "/lol-lobby/v2/lobby";
+
+/// The endpoint invitations to the current lobby are sent with
+pub const INVITATIONS_ENDPOINT: &str = "/lol-lobby/v2/lobby/invitations";
+
+/// The endpoint the local player's position preferences are set with
+pub const POSITION_PREFERENCES_ENDPOINT: &str =
+    "/lol-lobby/v2/lobby/members/localMember/position-preferences";
+
+/// The endpoint matchmaking is started and stopped with
+pub const MATCHMAKING_SEARCH_ENDPOINT: &str = "/lol-lobby/v2/lobby/matchmaking/search";
+
+/// The lobby the client is currently in
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Lobby {
+    #[serde(default)]
+    pub party_id: String,
+    /// Such as `open` or `closed`
+    #[serde(default)]
+    pub party_type: String,
+    #[serde(default)]
+    pub can_start_activity: bool,
+    pub game_config: LobbyGameConfig,
+    #[serde(default)]
+    pub members: Vec<LobbyMember>,
+    pub local_member: LobbyMember,
+}
+
+/// The queue the lobby is set up for
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LobbyGameConfig {
+    pub queue_id: i32,
+    /// Such as `CLASSIC` or `ARAM`
+    #[serde(default)]
+    pub game_mode: String,
+    #[serde(default)]
+    pub map_id: i32,
+    #[serde(default)]
+    pub is_custom: bool,
+    #[serde(default)]
+    pub max_lobby_size: u32,
+}
+
+/// A player in the lobby
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LobbyMember {
+    pub puuid: String,
+    #[serde(default)]
+    pub summoner_id: u64,
+    #[serde(default)]
+    pub is_leader: bool,
+    #[serde(default)]
+    pub ready: bool,
+    /// Such as `TOP` or `UTILITY`, empty in queues without positions
+    #[serde(default)]
+    pub first_position_preference: String,
+    #[serde(default)]
+    pub second_position_preference: String,
+}
+
+/// The settings of a custom game lobby, created with [`CustomLobby::new`]
+///
+/// ```
+/// use irelia::rest::lobby::CustomLobby;
+///
+/// // A 1v1 on the Howling Abyss
+/// let lobby = CustomLobby::new("Practice")
+///     .password("secret")
+///     .game_mode("ARAM")
+///     .map_id(12)
+///     .team_size(1);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CustomLobby {
+    lobby_name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    lobby_password: Option<String>,
+    configuration: CustomGameConfiguration,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct CustomGameConfiguration {
+    game_mode: String,
+    map_id: i32,
+    mutators: Mutators,
+    spectator_policy: String,
+    team_size: u32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+struct Mutators {
+    id: i32,
+}
+
+impl CustomLobby {
+    #[must_use]
+    /// Creates the settings for a 5v5 blind pick lobby on Summoner's Rift, called `name`
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            lobby_name: name.into(),
+            lobby_password: None,
+            configuration: CustomGameConfiguration {
+                game_mode: "CLASSIC".to_owned(),
+                map_id: 11,
+                mutators: Mutators { id: 1 },
+                spectator_policy: "AllAllowed".to_owned(),
+                team_size: 5,
+            },
+        }
+    }
+
+    #[must_use]
+    /// Only lets players that know `password` join, the lobby is open by default
+    pub fn password(mut self, password: impl Into<String>) -> Self {
+        self.lobby_password = Some(password.into());
+        self
+    }
+
+    #[must_use]
+    /// Such as `CLASSIC` or `ARAM`
+    pub fn game_mode(mut self, game_mode: impl Into<String>) -> Self {
+        self.configuration.game_mode = game_mode.into();
+        self
+    }
+
+    #[must_use]
+    /// Such as `11` for Summoner's Rift, or `12` for the Howling Abyss
+    pub const fn map_id(mut self, map_id: i32) -> Self {
+        self.configuration.map_id = map_id;
+        self
+    }
+
+    #[must_use]
+    /// The pick mode, `1` for blind pick, `2` for draft, `4` for all random, and `6` for tournament draft
+    pub const fn pick_mode(mut self, mutator_id: i32) -> Self {
+        self.configuration.mutators.id = mutator_id;
+        self
+    }
+
+    #[must_use]
+    /// Such as `AllAllowed`, `LobbyAllowed`, or `NotAllowed`
+    pub fn spectator_policy(mut self, policy: impl Into<String>) -> Self {
+        self.configuration.spectator_policy = policy.into();
+        self
+    }
+
+    #[must_use]
+    /// How many players are on each team, between 1 and 5
+    pub const fn team_size(mut self, team_size: u32) -> Self {
+        self.configuration.team_size = team_size;
+        self
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct CreateLobby<'a> {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    queue_id: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    custom_game_lobby: Option<&'a CustomLobby>,
+    is_custom: bool,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Invitation {
+    to_summoner_id: u64,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct PositionPreferences<'a> {
+    first_preference: &'a str,
+    second_preference: &'a str,
+}
+
+impl LcuClient {
+    /// Gets the lobby the client is currently in
+    ///
+    /// # Errors
+    /// This will return an error if the LCU API is not running, or the client is not in a lobby
+    pub async fn lobby(&self) -> Result<Lobby, Error> {
+        self.get(LOBBY_ENDPOINT).await
+    }
+
+    /// Creates a lobby for `queue_id`, such as `420` for ranked solo, leaving the current one
+    ///
+    /// # Errors
+    /// This will return an error if the LCU API is not running, or the queue is not available
+    pub async fn create_lobby(&self, queue_id: i32) -> Result<Lobby, Error> {
+        let body = CreateLobby {
+            queue_id: Some(queue_id),
+            custom_game_lobby: None,
+            is_custom: false,
+        };
+
+        self.post(LOBBY_ENDPOINT, body).await
+    }
+
+    /// Creates a custom game lobby, leaving the current one
+    ///
+    /// # Errors
+    /// This will return an error if the LCU API is not running, or the settings are invalid
+    pub async fn create_custom_lobby(&self, lobby: &CustomLobby) -> Result<Lobby, Error> {
+        let body = CreateLobby {
+            queue_id: None,
+            custom_game_lobby: Some(lobby),
+            is_custom: true,
+        };
+
+        self.post(LOBBY_ENDPOINT, body).await
+    }
+
+    /// Leaves the current lobby
+    ///
+    /// # Errors
+    /// This will return an error if the LCU API is not running, or the client is not in a lobby
+    pub async fn leave_lobby(&self) -> Result<(), Error> {
+        self.lcu_request_empty(LOBBY_ENDPOINT, "DELETE", None::<()>)
+            .await
+    }
+
+    /// Invites each of `summoner_ids` to the current lobby
+    ///
+    /// # Errors
+    /// This will return an error if the LCU API is not running, or the client is not in a lobby
+    pub async fn invite_summoners(
+        &self,
+        summoner_ids: impl IntoIterator<Item = u64> + Send,
+    ) -> Result<(), Error> {
+        let body: Vec<_> = summoner_ids
+            .into_iter()
+            .map(|to_summoner_id| Invitation { to_summoner_id })
+            .collect();
+
+        self.lcu_request_empty(INVITATIONS_ENDPOINT, "POST", Some(body))
+            .await
+    }
+
+    /// Sets the local player's position preferences, such as `TOP`, `JUNGLE`, `MIDDLE`, `BOTTOM`, `UTILITY`, or `FILL`
+    ///
+    /// # Errors
+    /// This will return an error if the LCU API is not running, or the lobby's queue has no positions
+    pub async fn set_position_preferences(&self, first: &str, second: &str) -> Result<(), Error> {
+        let body = PositionPreferences {
+            first_preference: first,
+            second_preference: second,
+        };
+
+        self.lcu_request_empty(POSITION_PREFERENCES_ENDPOINT, "PUT", Some(body))
+            .await
+    }
+
+    /// Starts searching for a match with the current lobby
+    ///
+    /// # Errors
+    /// This will return an error if the LCU API is not running, or the lobby can't start searching,
+    /// such as when not every position is picked
+    pub async fn start_matchmaking(&self) -> Result<(), Error> {
+        self.lcu_request_empty(MATCHMAKING_SEARCH_ENDPOINT, "POST", None::<()>)
+            .await
+    }
+
+    /// Stops searching for a match
+    ///
+    /// # Errors
+    /// This will return an error if the LCU API is not running, or the lobby is not searching
+    pub async fn stop_matchmaking(&self) -> Result<(), Error> {
+        self.lcu_request_empty(MATCHMAKING_SEARCH_ENDPOINT, "DELETE", None::<()>)
+            .await
+    }
+}
+
+#[cfg(feature = "ws")]
+/// What a [`LobbyUpdates`] stream yields
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LobbyUpdate {
+    /// The lobby changed, it's sent in full every time
+    Lobby(Box<Lobby>),
+    /// The client left the lobby, either because the game is starting, or the player left
+    Left,
+    /// The connection was lost, see [`StreamEvent::ConnectionLost`]
+    ConnectionLost,
+    /// The connection was established again, see [`StreamEvent::Reconnected`]
+    Reconnected,
+}
+
+#[cfg(feature = "ws")]
+/// The lobby as it's updated
+///
+/// ```no_run
+/// use futures_util::StreamExt;
+/// use irelia::rest::lobby::{LobbyUpdate, LobbyUpdates};
+/// use irelia::ws::LcuWebSocket;
+///
+/// # async fn run() {
+/// let mut ws = LcuWebSocket::new();
+/// let (_, mut updates) = LobbyUpdates::subscribe(&mut ws).unwrap();
+///
+/// while let Some(Ok(update)) = updates.next().await {
+///     if let LobbyUpdate::Lobby(lobby) = update {
+///         println!("{} players in the lobby", lobby.members.len());
+///     }
+/// }
+/// # }
+/// ```
+pub struct LobbyUpdates {
+    events: EventStream<Option<Lobby>>,
+}
+
+#[cfg(feature = "ws")]
+impl LobbyUpdates {
+    /// Subscribes to the lobby on `ws`
+    ///
+    /// Returns `None` is the websocket connection has already been closed previously
+    pub fn subscribe(ws: &mut LcuWebSocket) -> Option<(SubscriberID, Self)> {
+        let event_kind = EventKind::json_api_event_callback_str(LOBBY_ENDPOINT);
+        let (id, events) = ws.subscribe_stream(event_kind)?;
+
+        Some((id, Self { events }))
+    }
+}
+
+#[cfg(feature = "ws")]
+impl Stream for LobbyUpdates {
+    type Item = Result<LobbyUpdate, WebSocketError>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let event = match Pin::new(&mut self.get_mut().events).poll_next(cx) {
+            Poll::Ready(Some(Ok(event))) => event,
+            Poll::Ready(Some(Err(err))) => return Poll::Ready(Some(Err(err))),
+            Poll::Ready(None) => return Poll::Ready(None),
+            Poll::Pending => return Poll::Pending,
+        };
+
+        let update = match event {
+            StreamEvent::Event(event) => match event.data {
+                Some(lobby) if event.event_type != "Delete" => LobbyUpdate::Lobby(Box::new(lobby)),
+                _ => LobbyUpdate::Left,
+            },
+            StreamEvent::ConnectionLost => LobbyUpdate::ConnectionLost,
+            StreamEvent::Reconnected => LobbyUpdate::Reconnected,
+        };
+
+        Poll::Ready(Some(Ok(update)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CreateLobby, CustomLobby, Lobby};
+    use crate::rest::LcuClient;
+
+    #[test]
+    fn lobby_deserialize() {
+        let lobby: Lobby = serde_json::from_str(
+            r#"{
+                "canStartActivity": true,
+                "gameConfig": {"queueId": 420, "gameMode": "CLASSIC", "mapId": 11, "isCustom": false, "maxLobbySize": 5},
+                "localMember": {"puuid": "a", "summonerId": 1, "isLeader": true, "firstPositionPreference": "MIDDLE"},
+                "members": [{"puuid": "a", "summonerId": 1, "isLeader": true, "firstPositionPreference": "MIDDLE"}],
+                "partyId": "party",
+                "partyType": "open"
+            }"#,
+        )
+        .unwrap();
+
+        assert_eq!(lobby.game_config.queue_id, 420);
+        assert_eq!(lobby.members, std::slice::from_ref(&lobby.local_member));
+        assert_eq!(lobby.local_member.first_position_preference, "MIDDLE");
+    }
+
+    #[test]
+    fn custom_lobby_body() {
+        let lobby = CustomLobby::new("Practice")
+            .password("secret")
+            .game_mode("ARAM")
+            .map_id(12)
+            .pick_mode(4)
+            .team_size(1);
+
+        let body = CreateLobby {
+            queue_id: None,
+            custom_game_lobby: Some(&lobby),
+            is_custom: true,
+        };
+
+        assert_eq!(
+            serde_json::to_value(body).unwrap(),
+            serde_json::json!({
+                "customGameLobby": {
+                    "lobbyName": "Practice",
+                    "lobbyPassword": "secret",
+                    "configuration": {
+                        "gameMode": "ARAM",
+                        "mapId": 12,
+                        "mutators": {"id": 4},
+                        "spectatorPolicy": "AllAllowed",
+                        "teamSize": 1
+                    }
+                },
+                "isCustom": true
+            })
+        );
+
+        let body = CreateLobby {
+            queue_id: Some(420),
+            custom_game_lobby: None,
+            is_custom: false,
+        };
+
+        assert_eq!(
+            serde_json::to_value(body).unwrap(),
+            serde_json::json!({"queueId": 420, "isCustom": false})
+        );
+    }
+
+    #[ignore = "This is only needed for testing, and doesn't need to be run all the time"]
+    #[tokio::test]
+    async fn test_create_lobby() {
+        let client = LcuClient::connect().unwrap();
+
+        println!("{:?}", client.create_lobby(430).await);
+        println!(
+            "{:?}",
+            client.set_position_preferences("MIDDLE", "TOP").await
+        );
+        println!("{:?}", client.leave_lobby().await);
+    }
+}
@@ -0,0 +1,266 @@
+//! Typed wrappers around the `/lol-loot/v1` endpoints
+//!
+//! Crafting is done through recipes, such as `CHAMPION_RENTAL_disenchant`, which consume the loot passed to them.
+//! [`disenchant_recipe`] names the recipe for each type of loot that can be disenchanted
+
+use super::LcuClient;
+use crate::Error;
+use serde_derive::{Deserialize, Serialize};
+
+/// The endpoint the player's loot is served from
+pub const PLAYER_LOOT_ENDPOINT: &str = "/lol-loot/v1/player-loot";
+
+/// A single item in the player's loot, such as a champion shard, key fragment, or chest
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LootItem {
+    /// Unique ID of the item, such as `CHAMPION_RENTAL_157`, this is what gets passed to recipes
+    pub loot_id: String,
+    /// The name of the kind of loot, such as `CHAMPION_RENTAL_157` or `MATERIAL_key_fragment`
+    #[serde(default)]
+    pub loot_name: String,
+    pub count: i32,
+    /// The type of loot, such as `CHAMPION_RENTAL`, `SKIN_RENTAL`, or `MATERIAL`
+    #[serde(rename = "type")]
+    pub loot_type: String,
+    /// Human readable name, such as the name of the champion a shard is for
+    #[serde(default)]
+    pub item_desc: String,
+    /// The category shown in the loot tab, such as `CHAMPION` or `SKIN`
+    #[serde(default)]
+    pub display_categories: String,
+    /// How much of `disenchant_loot_name` disenchanting one of these gives
+    #[serde(default)]
+    pub disenchant_value: i32,
+    /// What disenchanting this gives, such as `CURRENCY_champion` for blue essence
+    #[serde(default)]
+    pub disenchant_loot_name: String,
+}
+
+impl LootItem {
+    #[must_use]
+    /// The recipe that disenchants this, see [`disenchant_recipe`]
+    pub fn disenchant_recipe(&self) -> Option<&'static str> {
+        disenchant_recipe(&self.loot_type)
+    }
+}
+
+/// A recipe that can be crafted with an item
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LootRecipe {
+    /// The name passed to [`LcuClient::craft`], such as `CHAMPION_RENTAL_disenchant`
+    pub recipe_name: String,
+    /// Such as `DISENCHANT`, `UPGRADE`, or `OPEN`
+    #[serde(rename = "type", default)]
+    pub recipe_type: String,
+    #[serde(default)]
+    pub description: String,
+}
+
+/// The recipe that disenchants loot of `loot_type`, `None` if it can't be disenchanted
+///
+/// Each type of loot has its own recipe, named after the type, so shards and permanents of the same thing
+/// use different recipes
+#[must_use]
+pub fn disenchant_recipe(loot_type: &str) -> Option<&'static str> {
+    Some(match loot_type {
+        "CHAMPION_RENTAL" => "CHAMPION_RENTAL_disenchant",
+        "CHAMPION" => "CHAMPION_disenchant",
+        "SKIN_RENTAL" => "SKIN_RENTAL_disenchant",
+        "SKIN" => "SKIN_disenchant",
+        "WARDSKIN_RENTAL" => "WARDSKIN_RENTAL_disenchant",
+        "WARDSKIN" => "WARDSKIN_disenchant",
+        "EMOTE" => "EMOTE_disenchant",
+        "SUMMONERICON" => "SUMMONERICON_disenchant",
+        "COMPANION" => "COMPANION_disenchant",
+        _ => return None,
+    })
+}
+
+/// The loot that changed after crafting a recipe
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CraftResult {
+    #[serde(default)]
+    pub added: Vec<CraftedLoot>,
+    #[serde(default)]
+    pub redeemed: Vec<CraftedLoot>,
+    #[serde(default)]
+    pub removed: Vec<CraftedLoot>,
+}
+
+/// A single change to the player's loot
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CraftedLoot {
+    /// How much the count of the item changed by
+    pub delta_count: i32,
+    /// The item, after the change
+    pub player_loot: LootItem,
+}
+
+impl LcuClient {
+    /// Gets all loot the current summoner owns
+    ///
+    /// # Errors
+    /// This will return an error if the LCU API is not running
+    pub async fn player_loot(&self) -> Result<Vec<LootItem>, Error> {
+        self.get(PLAYER_LOOT_ENDPOINT).await
+    }
+
+    /// Gets the recipes that can be crafted with the item with `loot_id`
+    ///
+    /// # Errors
+    /// This will return an error if the LCU API is not running
+    pub async fn loot_recipes(
+        &self,
+        loot_id: impl AsRef<str> + Send,
+    ) -> Result<Vec<LootRecipe>, Error> {
+        let endpoint = format!("/lol-loot/v1/recipes/initial-item/{}", loot_id.as_ref());
+        self.get(endpoint).await
+    }
+
+    /// Crafts `recipe_name` once, consuming the loot with the given IDs
+    ///
+    /// # Errors
+    /// This will return an error if the LCU API is not running, the recipe does not exist,
+    /// or the loot given can't be used with the recipe
+    pub async fn craft<I: AsRef<str> + Sync>(
+        &self,
+        recipe_name: impl AsRef<str> + Send,
+        loot_ids: &[I],
+    ) -> Result<CraftResult, Error> {
+        self.craft_repeat(recipe_name, loot_ids, 1).await
+    }
+
+    /// Crafts `recipe_name` `repeat` times in one request, consuming the loot with the given IDs each time
+    ///
+    /// # Errors
+    /// This will return an error if the LCU API is not running, the recipe does not exist,
+    /// or there is not enough of the loot given for every repeat
+    pub async fn craft_repeat<I: AsRef<str> + Sync>(
+        &self,
+        recipe_name: impl AsRef<str> + Send,
+        loot_ids: &[I],
+        repeat: u32,
+    ) -> Result<CraftResult, Error> {
+        let endpoint = format!(
+            "/lol-loot/v1/recipes/{}/craft?repeat={repeat}",
+            recipe_name.as_ref()
+        );
+
+        let loot_ids: Vec<&str> = loot_ids.iter().map(AsRef::as_ref).collect();
+
+        self.post(endpoint, loot_ids).await
+    }
+
+    /// Disenchants every copy of each item `filter` returns `true` for, skipping loot that can't be disenchanted
+    ///
+    /// Each item is disenchanted with the recipe for its type, in a single request per item,
+    /// returning the results in the same order as the loot
+    ///
+    /// ```no_run
+    /// # async fn disenchant() -> Result<(), irelia::Error> {
+    /// let client = irelia::rest::LcuClient::connect()?;
+    ///
+    /// // Every champion shard, keeping the permanents
+    /// let results = client
+    ///     .disenchant_all(|item| item.loot_type == "CHAMPION_RENTAL")
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// # Errors
+    /// This will return an error if the LCU API is not running, or disenchanting an item failed,
+    /// in which case the items before it have already been disenchanted
+    pub async fn disenchant_all(
+        &self,
+        mut filter: impl FnMut(&LootItem) -> bool + Send,
+    ) -> Result<Vec<CraftResult>, Error> {
+        let loot = self.player_loot().await?;
+        let mut results = Vec::new();
+
+        for item in &loot {
+            let Ok(repeat) = u32::try_from(item.count) else {
+                continue;
+            };
+
+            let Some(recipe) = item.disenchant_recipe() else {
+                continue;
+            };
+
+            if repeat == 0 || !filter(item) {
+                continue;
+            }
+
+            results.push(self.craft_repeat(recipe, &[&item.loot_id], repeat).await?);
+        }
+
+        Ok(results)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{disenchant_recipe, CraftResult, LootItem};
+    use crate::rest::LcuClient;
+
+    #[test]
+    fn craft_result_deserialize() {
+        let result: CraftResult = serde_json::from_str(
+            r#"{
+                "added": [{
+                    "deltaCount": 450,
+                    "playerLoot": { "lootId": "CURRENCY_champion", "count": 900, "type": "CURRENCY" }
+                }],
+                "redeemed": [],
+                "removed": [{
+                    "deltaCount": -1,
+                    "playerLoot": {
+                        "lootId": "CHAMPION_RENTAL_157",
+                        "count": 0,
+                        "type": "CHAMPION_RENTAL",
+                        "itemDesc": "Yasuo"
+                    }
+                }]
+            }"#,
+        )
+        .unwrap();
+
+        assert_eq!(result.added[0].delta_count, 450);
+        assert_eq!(result.removed[0].player_loot.item_desc, "Yasuo");
+    }
+
+    #[test]
+    fn recipe_per_type() {
+        let item: LootItem = serde_json::from_str(
+            r#"{
+                "lootId": "CHAMPION_RENTAL_157",
+                "count": 2,
+                "type": "CHAMPION_RENTAL",
+                "disenchantValue": 1890,
+                "disenchantLootName": "CURRENCY_champion"
+            }"#,
+        )
+        .unwrap();
+
+        assert_eq!(item.disenchant_recipe(), Some("CHAMPION_RENTAL_disenchant"));
+        assert_eq!(item.disenchant_value, 1890);
+
+        assert_eq!(disenchant_recipe("SKIN"), Some("SKIN_disenchant"));
+        assert_eq!(disenchant_recipe("MATERIAL"), None);
+    }
+
+    #[ignore = "This is only needed for testing, and doesn't need to be run all the time"]
+    #[tokio::test]
+    async fn test_loot_recipes() {
+        let client = LcuClient::connect().unwrap();
+        let loot = client.player_loot().await.unwrap();
+
+        if let Some(item) = loot.first() {
+            println!("{:?}", client.loot_recipes(&item.loot_id).await);
+        }
+    }
+}
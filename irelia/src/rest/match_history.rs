@@ -0,0 +1,302 @@
+//! Typed wrappers around the `/lol-match-history/v1` endpoints
+//!
+//! The LCU serves at most [`MAX_PAGE_SIZE`] games per request, [`LcuClient::matches`] splits larger ranges into pages.
+//! For the game that just ended, see [`super::end_of_game`]
+
+use super::LcuClient;
+use crate::utils::requests::encode_query_value;
+use crate::Error;
+use serde_derive::{Deserialize, Serialize};
+use std::ops::Range;
+
+/// The most games the LCU returns for a single request
+pub const MAX_PAGE_SIZE: u32 = 20;
+
+/// A page of a player's match history
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MatchHistory {
+    #[serde(default)]
+    pub account_id: u64,
+    #[serde(default)]
+    pub platform_id: String,
+    pub games: MatchHistoryGames,
+}
+
+/// The games in a page of match history
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MatchHistoryGames {
+    #[serde(default)]
+    pub game_index_begin: u32,
+    #[serde(default)]
+    pub game_index_end: u32,
+    #[serde(default)]
+    pub game_count: u32,
+    #[serde(default)]
+    pub games: Vec<Game>,
+}
+
+/// A single game, as shown in the match history
+///
+/// Match history pages only include the requesting player, [`LcuClient::game`] includes every player
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Game {
+    pub game_id: u64,
+    /// Unix timestamp of when the game was created, in milliseconds
+    #[serde(default)]
+    pub game_creation: u64,
+    /// How long the game lasted, in seconds
+    #[serde(default)]
+    pub game_duration: u32,
+    /// Such as `CLASSIC` or `ARAM`
+    #[serde(default)]
+    pub game_mode: String,
+    /// Such as `MATCHED_GAME` or `CUSTOM_GAME`
+    #[serde(default)]
+    pub game_type: String,
+    #[serde(default)]
+    pub game_version: String,
+    #[serde(default)]
+    pub map_id: i32,
+    #[serde(default)]
+    pub queue_id: i32,
+    #[serde(default)]
+    pub participant_identities: Vec<ParticipantIdentity>,
+    #[serde(default)]
+    pub participants: Vec<Participant>,
+    #[serde(default)]
+    pub teams: Vec<GameTeam>,
+}
+
+impl Game {
+    #[must_use]
+    /// The participant that was played by `puuid`, `None` if they weren't in the game
+    pub fn participant(&self, puuid: &str) -> Option<&Participant> {
+        let identity = self
+            .participant_identities
+            .iter()
+            .find(|identity| identity.player.puuid == puuid)?;
+
+        self.participants
+            .iter()
+            .find(|participant| participant.participant_id == identity.participant_id)
+    }
+}
+
+/// Who played a participant
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ParticipantIdentity {
+    pub participant_id: i32,
+    pub player: GamePlayer,
+}
+
+/// A player in a game
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GamePlayer {
+    #[serde(default)]
+    pub puuid: String,
+    #[serde(default)]
+    pub summoner_id: u64,
+    #[serde(default)]
+    pub game_name: String,
+    #[serde(default)]
+    pub tag_line: String,
+}
+
+/// A champion played in a game
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Participant {
+    pub participant_id: i32,
+    pub champion_id: i32,
+    /// `100` for blue side, `200` for red side
+    pub team_id: i32,
+    #[serde(default)]
+    pub spell1_id: i32,
+    #[serde(default)]
+    pub spell2_id: i32,
+    pub stats: ParticipantStats,
+}
+
+/// The stats of a participant at the end of the game
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ParticipantStats {
+    pub win: bool,
+    pub kills: u32,
+    pub deaths: u32,
+    pub assists: u32,
+    pub champ_level: u32,
+    pub gold_earned: u32,
+    pub total_minions_killed: u32,
+    pub neutral_minions_killed: u32,
+    pub total_damage_dealt_to_champions: u64,
+    pub total_damage_taken: u64,
+    pub vision_score: u32,
+    pub item0: i32,
+    pub item1: i32,
+    pub item2: i32,
+    pub item3: i32,
+    pub item4: i32,
+    pub item5: i32,
+    pub item6: i32,
+}
+
+/// A team in a game
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GameTeam {
+    pub team_id: i32,
+    /// `Win` or `Fail`
+    #[serde(default)]
+    pub win: String,
+}
+
+impl LcuClient {
+    /// Gets the games of `puuid` in `range`, with the most recent game at index 0
+    ///
+    /// Ranges longer than [`MAX_PAGE_SIZE`] are fetched a page at a time. This stops early if the player has no more games,
+    /// so fewer games than asked for can be returned
+    ///
+    /// # Errors
+    /// This will return an error if the LCU API is not running, or there is no player with `puuid`
+    pub async fn matches(&self, puuid: &str, range: Range<u32>) -> Result<Vec<Game>, Error> {
+        let mut games = Vec::new();
+
+        for page in pages(range) {
+            let len = page.len();
+            let history = self.match_history_page(puuid, page).await?;
+            let fetched = history.games.games.len();
+
+            games.extend(history.games.games);
+
+            if fetched < len {
+                break;
+            }
+        }
+
+        Ok(games)
+    }
+
+    /// Gets a single page of the games of `puuid`, `range` should be at most [`MAX_PAGE_SIZE`] long
+    ///
+    /// # Errors
+    /// This will return an error if the LCU API is not running, or there is no player with `puuid`
+    pub async fn match_history_page(
+        &self,
+        puuid: &str,
+        range: Range<u32>,
+    ) -> Result<MatchHistory, Error> {
+        if range.is_empty() {
+            return Ok(MatchHistory {
+                account_id: 0,
+                platform_id: String::new(),
+                games: MatchHistoryGames {
+                    game_index_begin: range.start,
+                    game_index_end: range.start,
+                    game_count: 0,
+                    games: Vec::new(),
+                },
+            });
+        }
+
+        // The end index is inclusive
+        let endpoint = format!(
+            "/lol-match-history/v1/products/lol/{}/matches?begIndex={}&endIndex={}",
+            encode_query_value(puuid),
+            range.start,
+            range.end - 1
+        );
+
+        self.get(endpoint).await
+    }
+
+    /// Gets a single game, with every player in it
+    ///
+    /// # Errors
+    /// This will return an error if the LCU API is not running, or there is no game with `game_id`
+    pub async fn game(&self, game_id: u64) -> Result<Game, Error> {
+        self.get(format!("/lol-match-history/v1/games/{game_id}"))
+            .await
+    }
+}
+
+/// Splits `range` into pages of at most [`MAX_PAGE_SIZE`] games
+fn pages(range: Range<u32>) -> impl Iterator<Item = Range<u32>> {
+    range
+        .clone()
+        .step_by(MAX_PAGE_SIZE as usize)
+        .map(move |start| start..range.end.min(start.saturating_add(MAX_PAGE_SIZE)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{pages, MatchHistory};
+    use crate::rest::LcuClient;
+
+    #[test]
+    fn page_ranges() {
+        let split: Vec<_> = pages(5..50).collect();
+        assert_eq!(split, [5..25, 25..45, 45..50]);
+
+        assert_eq!(pages(0..20).last(), Some(0..20));
+        assert_eq!(pages(3..3).count(), 0);
+        assert_eq!(
+            pages(u32::MAX - 5..u32::MAX).last(),
+            Some(u32::MAX - 5..u32::MAX)
+        );
+    }
+
+    #[test]
+    fn match_history_deserialize() {
+        let history: MatchHistory = serde_json::from_str(
+            r#"{
+                "accountId": 1,
+                "platformId": "NA1",
+                "games": {
+                    "gameIndexBegin": 0,
+                    "gameIndexEnd": 0,
+                    "gameCount": 1,
+                    "games": [{
+                        "gameId": 5000,
+                        "gameCreation": 1700000000000,
+                        "gameDuration": 1800,
+                        "gameMode": "CLASSIC",
+                        "queueId": 420,
+                        "participantIdentities": [{"participantId": 1, "player": {"puuid": "abc", "summonerId": 2}}],
+                        "participants": [{
+                            "participantId": 1,
+                            "championId": 39,
+                            "teamId": 100,
+                            "stats": {"win": true, "kills": 10, "deaths": 2, "assists": 5, "somethingNew": 1}
+                        }],
+                        "teams": [{"teamId": 100, "win": "Win"}]
+                    }]
+                }
+            }"#,
+        )
+        .unwrap();
+
+        let game = &history.games.games[0];
+        let participant = game.participant("abc").unwrap();
+
+        assert_eq!(participant.champion_id, 39);
+        assert!(participant.stats.win);
+        assert_eq!(participant.stats.kills, 10);
+        assert_eq!(game.participant("def"), None);
+    }
+
+    #[ignore = "This is only needed for testing, and doesn't need to be run all the time"]
+    #[tokio::test]
+    async fn test_matches() {
+        let client = LcuClient::connect().unwrap();
+        let summoner = client.current_summoner().await.unwrap();
+
+        let games = client.matches(&summoner.puuid, 0..30).await.unwrap();
+        println!("{}", games.len());
+    }
+}
@@ -0,0 +1,400 @@
+//! Typed wrappers around the `/lol-matchmaking/v1` endpoints
+//!
+//! The search covers the whole time in queue, and the ready check is the accept or decline prompt once a match is found.
+//! With the `ws` feature, [`AutoAccept`] accepts every ready check as it comes up
+
+use super::LcuClient;
+use crate::Error;
+use serde_derive::{Deserialize, Serialize};
+
+#[cfg(feature = "ws")]
+use crate::{
+    utils::runtime,
+    ws::{types::EventKind, EventStream, LcuWebSocket, StreamEvent, SubscriberID},
+};
+#[cfg(feature = "ws")]
+use futures_core::Stream;
+#[cfg(feature = "ws")]
+use std::{
+    future::{poll_fn, Future},
+    pin::{pin, Pin},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex,
+    },
+    task::{Poll, Waker},
+    time::Duration,
+};
+
+/// The endpoint the matchmaking search is served from, and the uri of its websocket event
+pub const SEARCH_ENDPOINT: &str = "/lol-matchmaking/v1/search";
+
+/// The endpoint the ready check is served from, and the uri of its websocket event
+pub const READY_CHECK_ENDPOINT: &str = "/lol-matchmaking/v1/ready-check";
+
+/// The endpoint the ready check is accepted with
+pub const READY_CHECK_ACCEPT_ENDPOINT: &str = "/lol-matchmaking/v1/ready-check/accept";
+
+/// The state of the current search for a match
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MatchmakingSearch {
+    pub search_state: SearchState,
+    #[serde(default)]
+    pub queue_id: i32,
+    #[serde(default)]
+    pub is_currently_in_queue: bool,
+    /// How long the search has been going on, in seconds
+    #[serde(default)]
+    pub time_in_queue: f64,
+    /// How long the search is expected to take, in seconds
+    #[serde(default)]
+    pub estimated_queue_time: f64,
+    #[serde(default)]
+    pub ready_check: Option<ReadyCheck>,
+}
+
+/// Where the search for a match is at
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum SearchState {
+    Invalid,
+    AbandonedLowPriorityQueue,
+    Canceled,
+    Searching,
+    Found,
+    Error,
+    ServiceError,
+    ServiceShutdown,
+    /// A state this version of the crate does not know about
+    #[serde(other)]
+    Unknown,
+}
+
+/// The prompt to accept or decline a match that was found
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReadyCheck {
+    pub state: ReadyCheckState,
+    pub player_response: ReadyCheckResponse,
+    /// How long the ready check has been going on, in seconds
+    #[serde(default)]
+    pub timer: f64,
+    #[serde(default)]
+    pub decliner_ids: Vec<u64>,
+}
+
+/// Where the ready check is at
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ReadyCheckState {
+    Invalid,
+    InProgress,
+    EveryoneReady,
+    StrangerNotReady,
+    PartyNotReady,
+    Error,
+    /// A state this version of the crate does not know about
+    #[serde(other)]
+    Unknown,
+}
+
+/// How the local player responded to the ready check
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ReadyCheckResponse {
+    None,
+    Accepted,
+    Declined,
+    /// A response this version of the crate does not know about
+    #[serde(other)]
+    Unknown,
+}
+
+impl LcuClient {
+    /// Gets the current search for a match
+    ///
+    /// # Errors
+    /// This will return an error if the LCU API is not running, or the client is not in queue
+    pub async fn matchmaking_search(&self) -> Result<MatchmakingSearch, Error> {
+        self.get(SEARCH_ENDPOINT).await
+    }
+
+    /// Gets the current ready check
+    ///
+    /// # Errors
+    /// This will return an error if the LCU API is not running, or there is no ready check
+    pub async fn ready_check(&self) -> Result<ReadyCheck, Error> {
+        self.get(READY_CHECK_ENDPOINT).await
+    }
+
+    /// Accepts the current ready check
+    ///
+    /// # Errors
+    /// This will return an error if the LCU API is not running, or there is no ready check
+    pub async fn accept_ready_check(&self) -> Result<(), Error> {
+        // The response is empty when the ready check was accepted
+        self.lcu_request_empty(READY_CHECK_ACCEPT_ENDPOINT, "POST", None::<()>)
+            .await
+    }
+}
+
+impl ReadyCheck {
+    #[must_use]
+    /// Whether the ready check is still waiting on the local player
+    pub fn is_pending(&self) -> bool {
+        self.state == ReadyCheckState::InProgress
+            && self.player_response == ReadyCheckResponse::None
+    }
+}
+
+#[cfg(feature = "ws")]
+/// Accepts every ready check, `delay` after it comes up, until it's disabled with its [`AutoAcceptHandle`]
+///
+/// Nothing is accepted until [`AutoAccept::run`] is awaited. The delay should be shorter than the ready check,
+/// which lasts about 10 seconds, and the ready check is fetched again once it's over, so one that was
+/// accepted, declined, or cancelled in the meantime is left alone
+///
+/// ```no_run
+/// use irelia::rest::{matchmaking::AutoAccept, LcuClient};
+/// use irelia::ws::LcuWebSocket;
+/// use std::time::Duration;
+///
+/// # async fn run() -> Result<(), irelia::Error> {
+/// let client = LcuClient::connect()?;
+/// let mut ws = LcuWebSocket::new();
+///
+/// let (_, auto_accept) = AutoAccept::subscribe(&mut ws, Duration::from_secs(2)).unwrap();
+/// let handle = auto_accept.handle();
+///
+/// // Once the handle is disabled from elsewhere, `run` returns
+/// # handle.disable();
+/// auto_accept.run(&client).await?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct AutoAccept {
+    events: EventStream<Option<ReadyCheck>>,
+    delay: Duration,
+    state: Arc<DisableState>,
+}
+
+#[cfg(feature = "ws")]
+/// Disables the [`AutoAccept`] it was created from, which can be done from any thread
+#[derive(Clone)]
+pub struct AutoAcceptHandle {
+    state: Arc<DisableState>,
+}
+
+#[cfg(feature = "ws")]
+#[derive(Default)]
+struct DisableState {
+    disabled: AtomicBool,
+    /// The task running [`AutoAccept::run`], woken up when it's disabled
+    waker: Mutex<Option<Waker>>,
+}
+
+#[cfg(feature = "ws")]
+impl AutoAccept {
+    /// Subscribes to the ready check on `ws`, accepting `delay` after each ready check comes up
+    ///
+    /// The subscription is left in place once this is disabled, and can be removed with the returned ID
+    ///
+    /// Returns `None` is the websocket connection has already been closed previously
+    pub fn subscribe(ws: &mut LcuWebSocket, delay: Duration) -> Option<(SubscriberID, Self)> {
+        let event_kind = EventKind::json_api_event_callback_str(READY_CHECK_ENDPOINT);
+        let (id, events) = ws.subscribe_stream(event_kind)?;
+
+        Some((
+            id,
+            Self {
+                events,
+                delay,
+                state: Arc::default(),
+            },
+        ))
+    }
+
+    #[must_use]
+    /// Creates a handle that disables this
+    pub fn handle(&self) -> AutoAcceptHandle {
+        AutoAcceptHandle {
+            state: Arc::clone(&self.state),
+        }
+    }
+
+    /// Accepts ready checks until this is disabled, or the stream of events ends
+    ///
+    /// Events that could not be decoded are skipped
+    ///
+    /// # Errors
+    /// This will return an error if the LCU API is not running, errors the client responds with are skipped
+    pub async fn run(mut self, client: &LcuClient) -> Result<(), Error> {
+        loop {
+            let Some(Some(event)) = self.state.until_disabled(next(&mut self.events)).await else {
+                return Ok(());
+            };
+
+            let Ok(StreamEvent::Event(event)) = event else {
+                continue;
+            };
+
+            if !event.data.as_ref().is_some_and(ReadyCheck::is_pending) {
+                continue;
+            }
+
+            if self
+                .state
+                .until_disabled(runtime::sleep(self.delay))
+                .await
+                .is_none()
+            {
+                return Ok(());
+            }
+
+            // Someone might have declined, or the player accepted themselves, while this was waiting
+            let accepted = match client.ready_check().await {
+                Ok(ready_check) if ready_check.is_pending() => client.accept_ready_check().await,
+                other => other.map(drop),
+            };
+
+            // The ready check can be gone by the time it's accepted, which the client answers with a 404,
+            // so only errors that never reached the client stop this
+            match accepted {
+                Ok(()) => {}
+                Err(err) if err.status().is_some() => {
+                    #[cfg(feature = "tracing")]
+                    tracing::debug!(error = %err, "accepting the ready check failed");
+                }
+                Err(err) => return Err(err),
+            }
+        }
+    }
+}
+
+#[cfg(feature = "ws")]
+impl AutoAcceptHandle {
+    /// Stops accepting ready checks, a ready check that is waiting on the delay is not accepted either
+    pub fn disable(&self) {
+        self.state.disabled.store(true, Ordering::Release);
+
+        if let Some(waker) = self
+            .state
+            .waker
+            .lock()
+            .ok()
+            .and_then(|mut waker| waker.take())
+        {
+            waker.wake();
+        }
+    }
+
+    #[must_use]
+    /// Whether [`AutoAcceptHandle::disable`] has been called
+    pub fn is_disabled(&self) -> bool {
+        self.state.disabled.load(Ordering::Acquire)
+    }
+}
+
+#[cfg(feature = "ws")]
+impl DisableState {
+    /// Runs `future` to completion, returning `None` if this is disabled first
+    async fn until_disabled<F: Future>(&self, future: F) -> Option<F::Output> {
+        let mut future = pin!(future);
+
+        poll_fn(|cx| {
+            if let Ok(mut waker) = self.waker.lock() {
+                *waker = Some(cx.waker().clone());
+            }
+
+            // Checked after the waker is stored, so disabling in between still wakes this up
+            if self.disabled.load(Ordering::Acquire) {
+                return Poll::Ready(None);
+            }
+
+            future.as_mut().poll(cx).map(Some)
+        })
+        .await
+    }
+}
+
+#[cfg(feature = "ws")]
+async fn next<S: Stream + Unpin>(stream: &mut S) -> Option<S::Item> {
+    poll_fn(|cx| Pin::new(&mut *stream).poll_next(cx)).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{MatchmakingSearch, ReadyCheck, ReadyCheckResponse, ReadyCheckState, SearchState};
+
+    #[test]
+    fn search_deserialize() {
+        let search: MatchmakingSearch = serde_json::from_str(
+            r#"{
+                "errors": [],
+                "estimatedQueueTime": 120.5,
+                "isCurrentlyInQueue": true,
+                "queueId": 420,
+                "readyCheck": {"declinerIds": [], "playerResponse": "None", "state": "InProgress", "timer": 3.0},
+                "searchState": "Found",
+                "timeInQueue": 12.0
+            }"#,
+        )
+        .unwrap();
+
+        assert_eq!(search.search_state, SearchState::Found);
+        let ready_check = search.ready_check.unwrap();
+        assert_eq!(ready_check.state, ReadyCheckState::InProgress);
+        assert_eq!(ready_check.player_response, ReadyCheckResponse::None);
+
+        let state: SearchState = serde_json::from_str("\"SomeNewState\"").unwrap();
+        assert_eq!(state, SearchState::Unknown);
+    }
+
+    #[test]
+    fn ready_check_pending() {
+        let mut ready_check = ReadyCheck {
+            state: ReadyCheckState::InProgress,
+            player_response: ReadyCheckResponse::None,
+            timer: 0.0,
+            decliner_ids: Vec::new(),
+        };
+        assert!(ready_check.is_pending());
+
+        ready_check.player_response = ReadyCheckResponse::Accepted;
+        assert!(!ready_check.is_pending());
+
+        ready_check.player_response = ReadyCheckResponse::None;
+        ready_check.state = ReadyCheckState::StrangerNotReady;
+        assert!(!ready_check.is_pending());
+    }
+
+    #[cfg(feature = "ws")]
+    #[tokio::test]
+    async fn auto_accept_disable() {
+        use super::DisableState;
+        use std::sync::Arc;
+        use std::time::Duration;
+
+        let state = Arc::new(DisableState::default());
+        let handle = super::AutoAcceptHandle {
+            state: Arc::clone(&state),
+        };
+
+        assert_eq!(state.until_disabled(async { 1 }).await, Some(1));
+
+        let disable = tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+            handle.disable();
+        });
+
+        let waited = state.until_disabled(tokio::time::sleep(Duration::from_secs(60)));
+        assert_eq!(waited.await, None);
+        disable.await.unwrap();
+    }
+
+    #[ignore = "This is only needed for testing, and doesn't need to be run all the time"]
+    #[tokio::test]
+    async fn test_accept_ready_check() {
+        let client = crate::rest::LcuClient::connect().unwrap();
+
+        println!("{:?}", client.accept_ready_check().await);
+    }
+}
@@ -0,0 +1,155 @@
+//! Counters kept by [`LcuClient`](super::LcuClient) for every request and reconnect
+//!
+//! Everything is stored in atomics, so recording never takes a lock, and a
+//! [`MetricsSnapshot`] can be read at any point with [`LcuClient::metrics`](super::LcuClient::metrics)
+
+use crate::process_info::ErrorKind;
+use crate::Error;
+use std::sync::atomic::{AtomicU64, AtomicU8, Ordering};
+
+/// Whether the last request made it to the client
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ConnectionState {
+    /// No request has been made since connecting or reconnecting
+    Unknown,
+    /// The last request got a response, even if it was an error status
+    Connected,
+    /// The last request failed before getting a response, or the client has closed
+    Disconnected,
+}
+
+impl ConnectionState {
+    const fn from_u8(value: u8) -> Self {
+        match value {
+            1 => Self::Connected,
+            2 => Self::Disconnected,
+            _ => Self::Unknown,
+        }
+    }
+}
+
+/// How many requests failed, grouped by the kind of failure
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct FailureCounts {
+    /// The request never got a response, such as a refused connection
+    pub transport: u64,
+    /// The client responded with a status that was not successful
+    pub status: u64,
+    /// The body could not be encoded, or the response could not be decoded
+    pub serialization: u64,
+    /// The request failed because the client process exited, see [`ErrorKind::ClientClosed`]
+    pub client_closed: u64,
+    /// Any other failure
+    pub other: u64,
+}
+
+/// The state of the metrics at a single point in time
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct MetricsSnapshot {
+    /// Every request made, including those that failed
+    pub requests: u64,
+    pub failures: FailureCounts,
+    /// The number of times new credentials were set, through reconnecting or otherwise
+    pub reconnects: u64,
+    pub state: ConnectionState,
+}
+
+#[derive(Debug, Default)]
+pub(crate) struct Metrics {
+    requests: AtomicU64,
+    transport: AtomicU64,
+    status: AtomicU64,
+    serialization: AtomicU64,
+    client_closed: AtomicU64,
+    other: AtomicU64,
+    reconnects: AtomicU64,
+    state: AtomicU8,
+}
+
+impl Metrics {
+    pub(crate) fn record<T>(&self, result: &Result<T, Error>) {
+        self.requests.fetch_add(1, Ordering::Relaxed);
+
+        let Err(err) = result else {
+            self.set_state(ConnectionState::Connected);
+            return;
+        };
+
+        let (counter, state) = match err {
+            Error::HyperClientError(_) | Error::HyperError(_) | Error::Io(_) => {
+                (&self.transport, ConnectionState::Disconnected)
+            }
+            Error::ProcessInfoError(err) if err.kind() == ErrorKind::ClientClosed => {
+                (&self.client_closed, ConnectionState::Disconnected)
+            }
+            Error::RequestError(_) | Error::LcuError(_) => {
+                (&self.status, ConnectionState::Connected)
+            }
+            Error::RmpSerdeEncode(_) | Error::RmpSerdeDecode(_) | Error::SerdeJsonDecode(_) => {
+                (&self.serialization, ConnectionState::Connected)
+            }
+            _ => (&self.other, ConnectionState::Connected),
+        };
+
+        counter.fetch_add(1, Ordering::Relaxed);
+        self.set_state(state);
+    }
+
+    pub(crate) fn record_reconnect(&self) {
+        self.reconnects.fetch_add(1, Ordering::Relaxed);
+        self.set_state(ConnectionState::Unknown);
+    }
+
+    fn set_state(&self, state: ConnectionState) {
+        self.state.store(state as u8, Ordering::Relaxed);
+    }
+
+    pub(crate) fn snapshot(&self) -> MetricsSnapshot {
+        MetricsSnapshot {
+            requests: self.requests.load(Ordering::Relaxed),
+            failures: FailureCounts {
+                transport: self.transport.load(Ordering::Relaxed),
+                status: self.status.load(Ordering::Relaxed),
+                serialization: self.serialization.load(Ordering::Relaxed),
+                client_closed: self.client_closed.load(Ordering::Relaxed),
+                other: self.other.load(Ordering::Relaxed),
+            },
+            reconnects: self.reconnects.load(Ordering::Relaxed),
+            state: ConnectionState::from_u8(self.state.load(Ordering::Relaxed)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ConnectionState, Metrics};
+    use crate::process_info::CLIENT_CLOSED;
+    use crate::Error;
+    use hyper::StatusCode;
+
+    #[test]
+    fn test_metrics() {
+        let metrics = Metrics::default();
+        assert_eq!(metrics.snapshot().state, ConnectionState::Unknown);
+
+        metrics.record(&Ok(()));
+        metrics.record::<()>(&Err(Error::RequestError(StatusCode::NOT_FOUND)));
+        assert_eq!(metrics.snapshot().state, ConnectionState::Connected);
+
+        // The client answered, it just can't do what was asked
+        metrics.record::<()>(&Err(Error::Unavailable("no ban left".to_owned())));
+        assert_eq!(metrics.snapshot().state, ConnectionState::Connected);
+
+        metrics.record::<()>(&Err(CLIENT_CLOSED.into()));
+        let snapshot = metrics.snapshot();
+        assert_eq!(snapshot.requests, 4);
+        assert_eq!(snapshot.failures.status, 1);
+        assert_eq!(snapshot.failures.client_closed, 1);
+        assert_eq!(snapshot.state, ConnectionState::Disconnected);
+
+        metrics.record_reconnect();
+        let snapshot = metrics.snapshot();
+        assert_eq!(snapshot.reconnects, 1);
+        assert_eq!(snapshot.state, ConnectionState::Unknown);
+    }
+}
@@ -0,0 +1,110 @@
+//! Hooks that run around every request, for logging, metrics, or changing requests before they're sent
+//!
+//! Middleware is added with [`LcuClientBuilder::middleware`](super::LcuClientBuilder::middleware),
+//! or [`RequestClient::with_middleware`](crate::RequestClient::with_middleware), and runs in the order it was added
+
+use crate::Error;
+use http_body_util::Full;
+use hyper::body::{Bytes, Incoming};
+use hyper::{Request, Response};
+use std::fmt::Debug;
+use std::sync::Arc;
+
+/// Runs around every request made through a client
+///
+/// Both hooks do nothing by default, so only the ones needed have to be implemented
+///
+/// ```
+/// use irelia::rest::middleware::Middleware;
+/// use irelia::Error;
+/// use hyper::{body::Incoming, Request, Response};
+///
+/// struct Logger;
+///
+/// impl Middleware for Logger {
+///     fn after_response(&self, request: &Request<()>, response: Result<&Response<Incoming>, &Error>) {
+///         match response {
+///             Ok(response) => println!("{} {} -> {}", request.method(), request.uri(), response.status()),
+///             Err(err) => println!("{} {} failed: {err}", request.method(), request.uri()),
+///         }
+///     }
+/// }
+/// ```
+pub trait Middleware: Send + Sync {
+    /// Runs just before the request is sent, with every header set, including `Authorization`
+    ///
+    /// The request can be changed freely, such as adding headers, or replacing the body
+    fn before_request(&self, request: &mut Request<Full<Bytes>>) {
+        let _ = request;
+    }
+
+    /// Runs once the response headers are received, or the request fails
+    ///
+    /// `request` is a copy of the request that was sent, without the body
+    fn after_response(&self, request: &Request<()>, response: Result<&Response<Incoming>, &Error>) {
+        let _ = (request, response);
+    }
+}
+
+/// The middleware added to a client, in the order it runs
+#[derive(Clone, Default)]
+pub(crate) struct Stack(pub(crate) Vec<Arc<dyn Middleware>>);
+
+impl Debug for Stack {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Stack({} middleware)", self.0.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Middleware;
+    use crate::rest::unreachable_client_with;
+    use crate::{Error, RequestClient};
+    use http_body_util::Full;
+    use hyper::body::{Bytes, Incoming};
+    use hyper::http::HeaderValue;
+    use hyper::{Request, Response};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    #[derive(Default)]
+    struct Counter {
+        before: AtomicUsize,
+        after: AtomicUsize,
+    }
+
+    impl Middleware for Counter {
+        fn before_request(&self, request: &mut Request<Full<Bytes>>) {
+            request
+                .headers_mut()
+                .insert("x-test", HeaderValue::from_static("irelia"));
+            self.before.fetch_add(1, Ordering::Relaxed);
+        }
+
+        fn after_response(
+            &self,
+            request: &Request<()>,
+            response: Result<&Response<Incoming>, &Error>,
+        ) {
+            assert_eq!(request.headers()["x-test"], "irelia");
+            assert!(response.is_err());
+            self.after.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_middleware() {
+        let counter = Arc::new(Counter::default());
+        let request_client = RequestClient::new().with_middleware(counter.clone());
+
+        // Drop the listener, so the port refuses connections
+        let (_, client) = unreachable_client_with(&request_client);
+
+        let _ = client.get::<serde_json::Value>("/example").await;
+        let _ = client.head("/example").await;
+
+        assert_eq!(counter.before.load(Ordering::Relaxed), 2);
+        assert_eq!(counter.after.load(Ordering::Relaxed), 2);
+    }
+}
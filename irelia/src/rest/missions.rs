@@ -0,0 +1,227 @@
+//! Typed wrappers around the `/lol-missions/v1` and `/lol-event-hub/v1` endpoints
+//!
+//! Missions are the objectives of events and passes, their progress is tracked per objective.
+//! The event hub serves the events themselves, with how many tokens have been earned for each
+
+use super::LcuClient;
+use crate::Error;
+use serde_derive::{Deserialize, Serialize};
+
+/// The endpoint every mission of the player is served from, and the uri of its websocket event
+pub const MISSIONS_ENDPOINT: &str = "/lol-missions/v1/missions";
+
+/// The endpoint every active event is served from
+pub const EVENTS_ENDPOINT: &str = "/lol-event-hub/v1/events";
+
+/// A mission, such as one from an event pass
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Mission {
+    pub id: String,
+    #[serde(default)]
+    pub title: String,
+    #[serde(default)]
+    pub description: String,
+    /// The series the mission is part of, such as an event
+    #[serde(default)]
+    pub series_name: String,
+    #[serde(default)]
+    pub status: MissionStatus,
+    #[serde(default)]
+    pub objectives: Vec<MissionObjective>,
+    #[serde(default)]
+    pub rewards: Vec<MissionReward>,
+    /// Unix timestamp of when the mission started, in milliseconds
+    #[serde(default)]
+    pub start_time: u64,
+    /// Unix timestamp of when the mission ends, in milliseconds
+    #[serde(default)]
+    pub end_time: u64,
+}
+
+impl Mission {
+    #[must_use]
+    /// Whether every objective is done, this can be true before the LCU marks the mission completed
+    pub fn is_done(&self) -> bool {
+        self.status == MissionStatus::Completed
+            || !self.objectives.is_empty()
+                && self
+                    .objectives
+                    .iter()
+                    .all(|objective| objective.progress.is_done())
+    }
+}
+
+/// Whether a mission can still be progressed
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum MissionStatus {
+    Pending,
+    Completed,
+    Expired,
+    /// A status this version of the crate does not know about
+    #[default]
+    #[serde(other)]
+    Unknown,
+}
+
+/// A single objective of a mission
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MissionObjective {
+    #[serde(default)]
+    pub description: String,
+    /// Such as `CHAMPION` or `WIN`
+    #[serde(rename = "type", default)]
+    pub objective_type: String,
+    #[serde(default)]
+    pub progress: MissionProgress,
+}
+
+/// How far along an objective is
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MissionProgress {
+    #[serde(default)]
+    pub current_progress: u32,
+    #[serde(default)]
+    pub total_count: u32,
+}
+
+impl MissionProgress {
+    #[must_use]
+    /// Whether the objective is done
+    pub const fn is_done(&self) -> bool {
+        self.current_progress >= self.total_count
+    }
+}
+
+/// Something a mission gives when it's completed
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MissionReward {
+    #[serde(default)]
+    pub description: String,
+    /// The ID of the reward, such as the loot name of the tokens
+    #[serde(default)]
+    pub item_id: String,
+    #[serde(default)]
+    pub quantity: u32,
+    /// Whether the reward was already given
+    #[serde(default)]
+    pub rewarded: bool,
+}
+
+/// An active event, such as a seasonal pass
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Event {
+    pub event_id: String,
+    pub event_info: EventInfo,
+}
+
+/// The details of an [`Event`]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EventInfo {
+    #[serde(default)]
+    pub event_name: String,
+    /// Such as `kEventPass`
+    #[serde(default)]
+    pub event_type: String,
+    /// How many tokens the player has that haven't been spent
+    #[serde(default)]
+    pub current_token_balance: u32,
+    /// Whether the player bought the pass for this event
+    #[serde(default)]
+    pub is_pass_purchased: bool,
+    #[serde(default)]
+    pub start_date: String,
+    #[serde(default)]
+    pub end_date: String,
+}
+
+impl LcuClient {
+    /// Gets every mission of the player, including completed and expired ones
+    ///
+    /// # Errors
+    /// This will return an error if the LCU API is not running
+    pub async fn missions(&self) -> Result<Vec<Mission>, Error> {
+        self.get(MISSIONS_ENDPOINT).await
+    }
+
+    /// Gets the missions of the player that can still be progressed
+    ///
+    /// # Errors
+    /// This will return an error if the LCU API is not running
+    pub async fn pending_missions(&self) -> Result<Vec<Mission>, Error> {
+        let mut missions = self.missions().await?;
+        missions.retain(|mission| mission.status == MissionStatus::Pending);
+        Ok(missions)
+    }
+
+    /// Gets every active event, with the player's tokens for each
+    ///
+    /// # Errors
+    /// This will return an error if the LCU API is not running
+    pub async fn events(&self) -> Result<Vec<Event>, Error> {
+        self.get(EVENTS_ENDPOINT).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Event, Mission, MissionStatus};
+    use crate::rest::LcuClient;
+
+    #[test]
+    fn mission_progress() {
+        let missions: Vec<Mission> = serde_json::from_str(
+            r#"[
+                {
+                    "id": "a",
+                    "status": "PENDING",
+                    "objectives": [
+                        {"type": "WIN", "progress": {"currentProgress": 2, "totalCount": 2}},
+                        {"type": "CHAMPION", "progress": {"currentProgress": 1, "totalCount": 3}}
+                    ],
+                    "rewards": [{"itemId": "MATERIAL_123", "quantity": 50}]
+                },
+                {"id": "b", "status": "COMPLETED"},
+                {"id": "c", "status": "SOMETHING_NEW"}
+            ]"#,
+        )
+        .unwrap();
+
+        assert!(!missions[0].is_done());
+        assert_eq!(missions[0].rewards[0].quantity, 50);
+        assert!(missions[1].is_done());
+        assert!(!missions[2].is_done());
+        assert_eq!(missions[2].status, MissionStatus::Unknown);
+    }
+
+    #[test]
+    fn event_tokens() {
+        let event: Event = serde_json::from_str(
+            r#"{"eventId": "e", "eventInfo": {"eventName": "Pass", "currentTokenBalance": 120, "isPassPurchased": true}}"#,
+        )
+        .unwrap();
+
+        assert_eq!(event.event_info.current_token_balance, 120);
+    }
+
+    #[ignore = "This is only needed for testing, and doesn't need to be run all the time"]
+    #[tokio::test]
+    async fn test_missions() {
+        let client = LcuClient::connect().unwrap();
+
+        println!(
+            "{:?}",
+            client
+                .pending_missions()
+                .await
+                .map(|missions| missions.len())
+        );
+        println!("{:?}", client.events().await);
+    }
+}
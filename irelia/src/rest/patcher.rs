@@ -0,0 +1,353 @@
+//! Typed wrappers around the `/patcher/v1` endpoints, which report how far along an update of the game is
+//!
+//! The state is split into components, such as the game and the client, each downloading and then applying its own update.
+//! With the `ws` feature, [`LcuClient::watch_patch_progress`] follows the state as it changes
+
+use super::LcuClient;
+use crate::Error;
+use serde_derive::{Deserialize, Serialize};
+
+#[cfg(feature = "ws")]
+use crate::utils::runtime;
+#[cfg(feature = "ws")]
+use crate::ws::{types::EventKind, EventStream, LcuWebSocket, StreamEvent, SubscriberID};
+#[cfg(feature = "ws")]
+use futures_core::Stream;
+#[cfg(feature = "ws")]
+use std::future::Future;
+#[cfg(feature = "ws")]
+use std::pin::Pin;
+#[cfg(feature = "ws")]
+use std::task::{Context, Poll};
+#[cfg(feature = "ws")]
+use std::time::Duration;
+
+/// The endpoint the patch state of League is served from, and the uri of its websocket event
+pub const PATCHER_STATE_ENDPOINT: &str = "/patcher/v1/products/league_of_legends/state";
+
+/// The patch state of League
+#[allow(clippy::struct_excessive_bools)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PatcherState {
+    /// Such as `Idle` or `Patching`
+    #[serde(default)]
+    pub action: String,
+    #[serde(default)]
+    pub components: Vec<PatcherComponent>,
+    #[serde(default)]
+    pub is_corrupted: bool,
+    #[serde(default)]
+    pub is_stopped: bool,
+    #[serde(default)]
+    pub is_up_to_date: bool,
+    #[serde(default)]
+    pub is_update_available: bool,
+    /// How much of the whole update is done, from `0` to `100`
+    #[serde(default)]
+    pub percent_patched: f64,
+}
+
+impl PatcherState {
+    #[must_use]
+    /// How far along downloading and applying the update is, across every component
+    pub fn progress(&self) -> PatchProgress {
+        let mut download = BytesProgress::default();
+        let mut apply = BytesProgress::default();
+
+        for progress in self
+            .components
+            .iter()
+            .filter_map(|component| component.progress.as_ref())
+        {
+            download.add(progress.network);
+            apply.add(progress.total);
+        }
+
+        PatchProgress {
+            download: download.percent(),
+            apply: apply.percent(),
+            total: self.percent_patched,
+        }
+    }
+}
+
+/// A part of the game that is updated on its own, such as the game or the client
+#[allow(clippy::struct_excessive_bools)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PatcherComponent {
+    pub id: String,
+    #[serde(default)]
+    pub action: String,
+    #[serde(default)]
+    pub is_corrupted: bool,
+    #[serde(default)]
+    pub is_up_to_date: bool,
+    #[serde(default)]
+    pub is_update_available: bool,
+    /// `None` when the component isn't being updated
+    #[serde(default)]
+    pub progress: Option<ComponentProgress>,
+}
+
+/// How far along the update of a component is
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ComponentProgress {
+    /// What's being downloaded
+    #[serde(default)]
+    pub network: BytesProgress,
+    /// What's being written to disk
+    #[serde(default)]
+    pub total: BytesProgress,
+}
+
+/// How many bytes of some work are done
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BytesProgress {
+    #[serde(default)]
+    pub bytes_complete: u64,
+    #[serde(default)]
+    pub bytes_required: u64,
+}
+
+impl BytesProgress {
+    #[must_use]
+    /// How much is done, from `0` to `100`, this is `100` when nothing is required
+    #[allow(clippy::cast_precision_loss)]
+    pub fn percent(&self) -> f64 {
+        if self.bytes_required == 0 {
+            return 100.0;
+        }
+
+        (self.bytes_complete as f64 / self.bytes_required as f64 * 100.0).min(100.0)
+    }
+
+    fn add(&mut self, other: Self) {
+        self.bytes_complete += other.bytes_complete;
+        self.bytes_required += other.bytes_required;
+    }
+}
+
+/// The progress of an update, as percentages from `0` to `100`, see [`PatcherState::progress`]
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct PatchProgress {
+    pub download: f64,
+    pub apply: f64,
+    /// As reported by the LCU for the whole update
+    pub total: f64,
+}
+
+impl LcuClient {
+    /// Gets the patch state of League
+    ///
+    /// # Errors
+    /// This will return an error if the LCU API is not running
+    pub async fn patcher_state(&self) -> Result<PatcherState, Error> {
+        self.get(PATCHER_STATE_ENDPOINT).await
+    }
+
+    #[cfg(feature = "ws")]
+    /// Follows the patch state as it changes, see [`PatchProgressStream`]
+    ///
+    /// The state event is subscribed to on `ws`, and the state is also fetched every `poll_interval`,
+    /// as the LCU doesn't send an event for every bit of progress. The subscription can be removed with the returned ID,
+    /// which ends the stream
+    ///
+    /// # Errors
+    /// This will return an error if the websocket connection has already been closed
+    pub fn watch_patch_progress(
+        &self,
+        ws: &mut LcuWebSocket,
+        poll_interval: Duration,
+    ) -> Result<(SubscriberID, PatchProgressStream<'_>), Error> {
+        let event_kind = EventKind::json_api_event_callback_str(PATCHER_STATE_ENDPOINT);
+        let (id, events) = ws
+            .subscribe_stream(event_kind)
+            .ok_or(Error::WebSocketClosed)?;
+
+        Ok((
+            id,
+            PatchProgressStream {
+                client: self,
+                events,
+                poll_interval,
+                fetch: Some(Box::pin(self.patcher_state())),
+                sleep: None,
+                last: None,
+            },
+        ))
+    }
+}
+
+#[cfg(feature = "ws")]
+type StateFuture<'a> = Pin<Box<dyn Future<Output = Result<PatcherState, Error>> + Send + 'a>>;
+
+#[cfg(feature = "ws")]
+/// The patch state as it changes, created with [`LcuClient::watch_patch_progress`]
+///
+/// The first item is the current state, after that a state is yielded whenever an event or a poll has one
+/// that's different from the last. When the websocket reconnects, the state is fetched right away
+///
+/// Errors are only from fetching the state, the stream keeps polling after one
+pub struct PatchProgressStream<'a> {
+    client: &'a LcuClient,
+    events: EventStream<PatcherState>,
+    poll_interval: Duration,
+    fetch: Option<StateFuture<'a>>,
+    /// Waiting to fetch the state again, this is only set while nothing is being fetched
+    sleep: Option<Pin<Box<dyn Future<Output = ()> + Send>>>,
+    last: Option<PatcherState>,
+}
+
+#[cfg(feature = "ws")]
+impl PatchProgressStream<'_> {
+    #[must_use]
+    /// The last state this yielded, `None` before the first one
+    pub const fn last(&self) -> Option<&PatcherState> {
+        self.last.as_ref()
+    }
+
+    /// Yields `state` if it's a change from the last state
+    fn update(&mut self, state: PatcherState) -> Option<PatcherState> {
+        if self.last.as_ref() == Some(&state) {
+            return None;
+        }
+
+        self.last = Some(state.clone());
+        Some(state)
+    }
+}
+
+#[cfg(feature = "ws")]
+impl Stream for PatchProgressStream<'_> {
+    type Item = Result<PatcherState, Error>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+
+        loop {
+            if let Some(fetch) = &mut this.fetch {
+                if let Poll::Ready(result) = fetch.as_mut().poll(cx) {
+                    this.fetch = None;
+                    this.sleep = Some(Box::pin(runtime::sleep(this.poll_interval)));
+
+                    match result {
+                        Ok(state) => {
+                            if let Some(state) = this.update(state) {
+                                return Poll::Ready(Some(Ok(state)));
+                            }
+                        }
+                        Err(err) => return Poll::Ready(Some(Err(err))),
+                    }
+                    continue;
+                }
+            } else if let Some(sleep) = &mut this.sleep {
+                if sleep.as_mut().poll(cx).is_ready() {
+                    this.sleep = None;
+                    this.fetch = Some(Box::pin(this.client.patcher_state()));
+                    continue;
+                }
+            }
+
+            let event = match Pin::new(&mut this.events).poll_next(cx) {
+                Poll::Ready(Some(event)) => event,
+                Poll::Ready(None) => return Poll::Ready(None),
+                Poll::Pending => return Poll::Pending,
+            };
+
+            match event {
+                Ok(StreamEvent::Event(event)) => {
+                    if let Some(state) = this.update(event.data) {
+                        return Poll::Ready(Some(Ok(state)));
+                    }
+                }
+                Ok(StreamEvent::Reconnected) => {
+                    if this.fetch.is_none() {
+                        this.sleep = None;
+                        this.fetch = Some(Box::pin(this.client.patcher_state()));
+                    }
+                }
+                // Events that can't be decoded, such as when the state is deleted, carry no state
+                Ok(StreamEvent::ConnectionLost) | Err(_) => {}
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{BytesProgress, PatcherState};
+
+    const PATCHING: &str = r#"{
+        "action": "Patching",
+        "percentPatched": 40.0,
+        "components": [
+            {"id": "game_patcher", "progress": {"network": {"bytesComplete": 50, "bytesRequired": 100}, "total": {"bytesComplete": 25, "bytesRequired": 100}}},
+            {"id": "lcu_patcher", "progress": {"network": {"bytesComplete": 50, "bytesRequired": 100}, "total": {"bytesComplete": 75, "bytesRequired": 100}}},
+            {"id": "idle", "isUpToDate": true}
+        ]
+    }"#;
+
+    #[test]
+    fn patch_progress() {
+        let state: PatcherState = serde_json::from_str(PATCHING).unwrap();
+        let progress = state.progress();
+
+        assert!((progress.download - 50.0).abs() < f64::EPSILON);
+        assert!((progress.apply - 50.0).abs() < f64::EPSILON);
+        assert!((progress.total - 40.0).abs() < f64::EPSILON);
+
+        assert!((BytesProgress::default().percent() - 100.0).abs() < f64::EPSILON);
+    }
+
+    #[cfg(feature = "ws")]
+    #[tokio::test]
+    async fn watch_dedup() {
+        use super::{PatchProgressStream, PATCHER_STATE_ENDPOINT};
+        use crate::rest::unreachable_client;
+        use crate::ws::{test_util, types::EventKind, DefaultErrorHandler, LcuWebSocket};
+        use futures_util::StreamExt;
+        use std::time::Duration;
+
+        let (_listener, client) = unreachable_client();
+
+        let (stream, mut server) = test_util::mock_stream();
+        let mut ws = LcuWebSocket::from_stream(stream, DefaultErrorHandler);
+
+        let event_kind = EventKind::json_api_event_callback_str(PATCHER_STATE_ENDPOINT);
+        let (_, events) = ws.subscribe_stream(event_kind).unwrap();
+
+        // The fetched state is idle, so the first event is not a change, and the next poll is far away
+        let states = PatchProgressStream {
+            client: &client,
+            events,
+            poll_interval: Duration::from_secs(60),
+            fetch: Some(Box::pin(async {
+                Ok(serde_json::from_str(r#"{"action": "Idle"}"#).unwrap())
+            })),
+            sleep: None,
+            last: None,
+        };
+
+        while server.receive().unwrap().is_none() {
+            std::thread::sleep(Duration::from_millis(1));
+        }
+
+        for data in [r#"{"action": "Idle"}"#, PATCHING] {
+            server
+                .send_text(format!(
+                    r#"[8, "OnJsonApiEvent_patcher_v1_products_league_of_legends_state", {{"data": {data}, "eventType": "Update", "uri": "{PATCHER_STATE_ENDPOINT}"}}]"#
+                ))
+                .unwrap();
+        }
+
+        let states: Vec<_> = states.take(2).map(Result::unwrap).collect().await;
+        assert_eq!(states[0].action, "Idle");
+        assert_eq!(states[1].action, "Patching");
+
+        ws.abort().unwrap();
+    }
+}
@@ -0,0 +1,353 @@
+//! Typed wrappers around the `/lol-perks/v1` endpoints, which manage rune pages
+//!
+//! Only so many custom pages can be owned, [`LcuClient::upsert_rune_page`] makes room for a new one when they're all used
+
+use super::LcuClient;
+use crate::Error;
+use serde_derive::{Deserialize, Serialize};
+
+/// The endpoint every rune page is served from, pages are created by posting to it
+pub const PAGES_ENDPOINT: &str = "/lol-perks/v1/pages";
+
+/// The endpoint the selected rune page is served from
+pub const CURRENT_PAGE_ENDPOINT: &str = "/lol-perks/v1/currentpage";
+
+/// The endpoint the number of pages the player owns is served from
+pub const INVENTORY_ENDPOINT: &str = "/lol-perks/v1/inventory";
+
+/// A rune page, either one of the player's own, or one of the preset pages
+#[allow(clippy::struct_excessive_bools)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RunePage {
+    /// Assigned by the LCU, this is ignored when a page is created
+    #[serde(default)]
+    pub id: u64,
+    pub name: String,
+    pub primary_style_id: i32,
+    pub sub_style_id: i32,
+    /// The keystone and three primary runes, then two secondary runes, then the three stat shards
+    pub selected_perk_ids: Vec<i32>,
+    /// Whether this is the selected page, a page created with this set is selected right away
+    #[serde(default)]
+    pub current: bool,
+    #[serde(default)]
+    pub is_active: bool,
+    /// Preset pages can't be deleted, only the player's own pages can
+    #[serde(default)]
+    pub is_deletable: bool,
+    #[serde(default)]
+    pub is_editable: bool,
+}
+
+impl RunePage {
+    #[must_use]
+    /// Creates a builder for a page called `name`, see [`RunePageBuilder`]
+    pub fn builder(name: impl Into<String>) -> RunePageBuilder {
+        RunePageBuilder::new(name)
+    }
+}
+
+/// Builder for [`RunePage`], created with [`RunePage::builder`]
+///
+/// ```
+/// use irelia::rest::perks::RunePage;
+///
+/// // Conqueror, Triumph, Legend: Alacrity, Last Stand, then Resolve
+/// let page = RunePage::builder("Irelia")
+///     .primary(8000, [8010, 9111, 9104, 8299])
+///     .secondary(8400, [8444, 8453])
+///     .stat_shards([5005, 5008, 5011])
+///     .build();
+///
+/// assert_eq!(page.selected_perk_ids.len(), 9);
+/// ```
+#[derive(Debug, Clone)]
+pub struct RunePageBuilder {
+    name: String,
+    primary_style_id: i32,
+    primary_perk_ids: [i32; 4],
+    sub_style_id: i32,
+    sub_perk_ids: [i32; 2],
+    stat_shard_ids: [i32; 3],
+    current: bool,
+}
+
+impl RunePageBuilder {
+    #[must_use]
+    /// Creates a builder for a page called `name`, which is selected once it's created
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            primary_style_id: 0,
+            primary_perk_ids: [0; 4],
+            sub_style_id: 0,
+            sub_perk_ids: [0; 2],
+            stat_shard_ids: [0; 3],
+            current: true,
+        }
+    }
+
+    #[must_use]
+    /// Sets the primary path, such as `8000` for Precision, with its keystone first
+    pub const fn primary(mut self, style_id: i32, perk_ids: [i32; 4]) -> Self {
+        self.primary_style_id = style_id;
+        self.primary_perk_ids = perk_ids;
+        self
+    }
+
+    #[must_use]
+    /// Sets the secondary path, and the two runes taken from it
+    pub const fn secondary(mut self, style_id: i32, perk_ids: [i32; 2]) -> Self {
+        self.sub_style_id = style_id;
+        self.sub_perk_ids = perk_ids;
+        self
+    }
+
+    #[must_use]
+    /// Sets the offense, flex, and defense stat shards, in that order
+    pub const fn stat_shards(mut self, perk_ids: [i32; 3]) -> Self {
+        self.stat_shard_ids = perk_ids;
+        self
+    }
+
+    #[must_use]
+    /// Whether the page is selected once it's created, it is by default
+    pub const fn current(mut self, current: bool) -> Self {
+        self.current = current;
+        self
+    }
+
+    #[must_use]
+    pub fn build(self) -> RunePage {
+        let selected_perk_ids = self
+            .primary_perk_ids
+            .into_iter()
+            .chain(self.sub_perk_ids)
+            .chain(self.stat_shard_ids)
+            .collect();
+
+        RunePage {
+            id: 0,
+            name: self.name,
+            primary_style_id: self.primary_style_id,
+            sub_style_id: self.sub_style_id,
+            selected_perk_ids,
+            current: self.current,
+            is_active: false,
+            is_deletable: true,
+            is_editable: true,
+        }
+    }
+}
+
+/// How many rune pages the player owns
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PerkInventory {
+    /// How many custom pages can exist at once, preset pages don't count towards this
+    pub owned_page_count: usize,
+}
+
+impl LcuClient {
+    /// Gets every rune page, including the preset pages
+    ///
+    /// # Errors
+    /// This will return an error if the LCU API is not running
+    pub async fn rune_pages(&self) -> Result<Vec<RunePage>, Error> {
+        self.get(PAGES_ENDPOINT).await
+    }
+
+    /// Gets the selected rune page
+    ///
+    /// # Errors
+    /// This will return an error if the LCU API is not running
+    pub async fn current_rune_page(&self) -> Result<RunePage, Error> {
+        self.get(CURRENT_PAGE_ENDPOINT).await
+    }
+
+    /// Gets the page a new one should replace, the selected page if it's the player's own, or else the first of the player's pages
+    ///
+    /// Returns `None` if the player has no pages of their own
+    ///
+    /// # Errors
+    /// This will return an error if the LCU API is not running
+    pub async fn editable_rune_page(&self) -> Result<Option<RunePage>, Error> {
+        let pages = self.rune_pages().await?;
+        Ok(editable_page(&pages).cloned())
+    }
+
+    /// Gets how many rune pages the player owns
+    ///
+    /// # Errors
+    /// This will return an error if the LCU API is not running
+    pub async fn perk_inventory(&self) -> Result<PerkInventory, Error> {
+        self.get(INVENTORY_ENDPOINT).await
+    }
+
+    /// Creates `page`, returning it as it was created, with its ID
+    ///
+    /// # Errors
+    /// This will return an error if the LCU API is not running, the page is invalid, or the player has no room for another page
+    pub async fn create_rune_page(&self, page: &RunePage) -> Result<RunePage, Error> {
+        self.post(PAGES_ENDPOINT, page).await
+    }
+
+    /// Deletes the page with `id`
+    ///
+    /// # Errors
+    /// This will return an error if the LCU API is not running, or there is no page with `id` that can be deleted
+    pub async fn delete_rune_page(&self, id: u64) -> Result<(), Error> {
+        // The response is empty when the page was deleted
+        self.lcu_request_empty(&format!("{PAGES_ENDPOINT}/{id}"), "DELETE", None::<()>)
+            .await
+    }
+
+    /// Selects the page with `id`
+    ///
+    /// # Errors
+    /// This will return an error if the LCU API is not running, or there is no page with `id`
+    pub async fn set_current_rune_page(&self, id: u64) -> Result<(), Error> {
+        // The response is empty when the page was selected
+        self.lcu_request_empty(CURRENT_PAGE_ENDPOINT, "PUT", Some(id))
+            .await
+    }
+
+    /// Creates `page`, replacing the player's page with the same name, if there is one
+    ///
+    /// When every page the player owns is used, [`LcuClient::editable_rune_page`] is deleted to make room.
+    /// If creating the page fails after one was deleted, the deleted page is created again, so nothing is lost
+    ///
+    /// # Errors
+    /// This will return an error if the LCU API is not running, the page is invalid,
+    /// or the player has no pages of their own and no room for one
+    pub async fn upsert_rune_page(&self, page: &RunePage) -> Result<RunePage, Error> {
+        let pages = self.rune_pages().await?;
+        let inventory = self.perk_inventory().await?;
+
+        let Some(replaced) = page_to_replace(&pages, &page.name, inventory.owned_page_count) else {
+            return self.create_rune_page(page).await;
+        };
+
+        self.delete_rune_page(replaced.id).await?;
+
+        match self.create_rune_page(page).await {
+            Ok(page) => Ok(page),
+            Err(err) => {
+                // The original error is the one worth reporting, restoring is only a best effort
+                let _ = self.create_rune_page(replaced).await;
+                Err(err)
+            }
+        }
+    }
+}
+
+fn editable_page(pages: &[RunePage]) -> Option<&RunePage> {
+    let mut deletable = pages.iter().filter(|page| page.is_deletable);
+    let first = deletable.next()?;
+
+    if first.current {
+        return Some(first);
+    }
+
+    Some(deletable.find(|page| page.current).unwrap_or(first))
+}
+
+/// The page that has to be deleted before one called `name` can be created, if any
+fn page_to_replace<'a>(
+    pages: &'a [RunePage],
+    name: &str,
+    owned_page_count: usize,
+) -> Option<&'a RunePage> {
+    let same_name = pages
+        .iter()
+        .find(|page| page.is_deletable && page.name == name);
+
+    if same_name.is_some() {
+        return same_name;
+    }
+
+    let used = pages.iter().filter(|page| page.is_deletable).count();
+    if used < owned_page_count {
+        return None;
+    }
+
+    editable_page(pages)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{editable_page, page_to_replace, RunePage};
+    use crate::rest::LcuClient;
+
+    fn page(id: u64, name: &str, current: bool, is_deletable: bool) -> RunePage {
+        RunePage {
+            id,
+            current,
+            is_deletable,
+            ..RunePage::builder(name).build()
+        }
+    }
+
+    #[test]
+    fn builder_order() {
+        let page = RunePage::builder("test")
+            .primary(8000, [8010, 9111, 9104, 8299])
+            .secondary(8400, [8444, 8453])
+            .stat_shards([5005, 5008, 5011])
+            .current(false)
+            .build();
+
+        assert_eq!(
+            page.selected_perk_ids,
+            [8010, 9111, 9104, 8299, 8444, 8453, 5005, 5008, 5011]
+        );
+        assert_eq!((page.primary_style_id, page.sub_style_id), (8000, 8400));
+        assert!(!page.current);
+
+        let json = serde_json::to_value(&page).unwrap();
+        assert_eq!(json["primaryStyleId"], 8000);
+        assert_eq!(json["selectedPerkIds"][8], 5011);
+    }
+
+    #[test]
+    fn replace_order() {
+        let pages = [
+            page(1, "Preset", true, false),
+            page(2, "Top", false, true),
+            page(3, "Mid", false, true),
+        ];
+
+        // There's room for another page, so only a page with the same name is replaced
+        assert_eq!(
+            page_to_replace(&pages, "Mid", 3).map(|page| page.id),
+            Some(3)
+        );
+        assert_eq!(page_to_replace(&pages, "Jungle", 3), None);
+
+        // The selected page is a preset, so the first of the player's own pages makes room
+        assert_eq!(editable_page(&pages).map(|page| page.id), Some(2));
+        assert_eq!(
+            page_to_replace(&pages, "Jungle", 2).map(|page| page.id),
+            Some(2)
+        );
+
+        let pages = [page(1, "Top", false, true), page(2, "Mid", true, true)];
+        assert_eq!(
+            page_to_replace(&pages, "Jungle", 2).map(|page| page.id),
+            Some(2)
+        );
+
+        assert_eq!(editable_page(&[page(1, "Preset", true, false)]), None);
+    }
+
+    #[ignore = "This is only needed for testing, and doesn't need to be run all the time"]
+    #[tokio::test]
+    async fn test_rune_pages() {
+        let client = LcuClient::connect().unwrap();
+
+        println!("{:?}", client.rune_pages().await);
+        println!("{:?}", client.editable_rune_page().await);
+        println!("{:?}", client.perk_inventory().await);
+    }
+}
@@ -0,0 +1,81 @@
+//! Restarting and quitting the client through the LCU, rather than by killing its processes
+//!
+//! Restarting the UX only reloads the window, so the connection keeps working.
+//! Restarting the whole client starts a new LCU, with new credentials, so [`LcuClient::reconnect`] has to be called afterwards
+
+use super::LcuClient;
+use crate::Error;
+
+/// The endpoint that restarts the whole client
+pub const RESTART_ENDPOINT: &str = "/process-control/v1/process/restart";
+
+/// The endpoint that closes the client
+pub const QUIT_ENDPOINT: &str = "/process-control/v1/process/quit";
+
+/// The endpoint that restarts only the client's window
+pub const RESTART_UX_ENDPOINT: &str = "/riotclient/kill-and-restart-ux";
+
+/// What [`LcuClient::restart`] restarts
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Restart {
+    /// Only the window, which fixes most visual issues, the LCU keeps running
+    Ux,
+    /// The whole client, the LCU is started again with new credentials
+    Client,
+}
+
+impl Restart {
+    #[must_use]
+    /// The endpoint that performs the restart
+    pub const fn endpoint(self) -> &'static str {
+        match self {
+            Self::Ux => RESTART_UX_ENDPOINT,
+            Self::Client => RESTART_ENDPOINT,
+        }
+    }
+
+    #[must_use]
+    /// Whether the client has to be reconnected once the restart is done
+    pub const fn needs_reconnect(self) -> bool {
+        matches!(self, Self::Client)
+    }
+}
+
+impl LcuClient {
+    /// Restarts the window or the whole client, see [`Restart`]
+    ///
+    /// After [`Restart::Client`], requests fail until [`LcuClient::reconnect`] is called on the new client
+    ///
+    /// # Errors
+    /// This will return an error if the LCU API is not running
+    pub async fn restart(&self, restart: Restart) -> Result<(), Error> {
+        self.post_empty(restart.endpoint()).await
+    }
+
+    /// Closes the client, requests fail once it does
+    ///
+    /// # Errors
+    /// This will return an error if the LCU API is not running
+    pub async fn quit(&self) -> Result<(), Error> {
+        self.post_empty(QUIT_ENDPOINT).await
+    }
+
+    async fn post_empty(&self, endpoint: &str) -> Result<(), Error> {
+        // The response is empty when the request was accepted
+        self.lcu_request_empty(endpoint, "POST", None::<()>).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Restart, RESTART_ENDPOINT, RESTART_UX_ENDPOINT};
+
+    #[test]
+    fn restart_kinds() {
+        assert_eq!(Restart::Ux.endpoint(), RESTART_UX_ENDPOINT);
+        assert_eq!(Restart::Client.endpoint(), RESTART_ENDPOINT);
+
+        assert!(!Restart::Ux.needs_reconnect());
+        assert!(Restart::Client.needs_reconnect());
+    }
+}
@@ -0,0 +1,544 @@
+//! A local HTTP server that forwards requests to the LCU, with the auth header filled in
+//!
+//! Anything that can make plain HTTP requests, such as a dashboard in a browser, or a tool in another language,
+//! can use the LCU through this without handling its certificate or credentials.
+//! Only what the [`AllowList`] lets through is forwarded, which is nothing by default,
+//! and requests from a browser are only accepted from the origins set with [`LcuProxy::allow_origin`]
+//!
+//! Bodies are sent as JSON, or whatever their `Content-Type` says, and responses are always JSON.
+//! A successful response is `200`, or `204` if it has no body. Failed requests keep the LCU's status and error body,
+//! errors from the proxy itself are in the same format, with `PROXY_ERROR` as the error code
+//!
+//! ```no_run
+//! use irelia::rest::proxy::{AllowList, LcuProxy};
+//! use irelia::rest::LcuClient;
+//!
+//! # async fn run() -> std::io::Result<()> {
+//! let client = LcuClient::connect().unwrap();
+//! let allow = AllowList::new()
+//!     .allow_read_only("/lol-gameflow")
+//!     .allow("POST", "/lol-lobby/v2/lobby");
+//!
+//! let proxy = LcuProxy::new(client, allow)
+//!     .allow_origin("http://localhost:5173")
+//!     .bind(([127, 0, 0, 1], 3000))
+//!     .await?;
+//!
+//! println!("Forwarding requests from {}", proxy.addr());
+//! # Ok(())
+//! # }
+//! ```
+
+use super::LcuClient;
+use crate::utils::runtime;
+use crate::{Error, LcuError};
+use http_body_util::{BodyExt, Full, LengthLimitError, Limited};
+use hyper::body::{Bytes, Incoming};
+use hyper::header::{
+    HeaderValue, ACCESS_CONTROL_ALLOW_HEADERS, ACCESS_CONTROL_ALLOW_METHODS,
+    ACCESS_CONTROL_ALLOW_ORIGIN, CONTENT_TYPE, HOST, ORIGIN, VARY,
+};
+use hyper::server::conn::http1;
+use hyper::service::service_fn;
+use hyper::{Method, Request, Response, StatusCode};
+use hyper_util::rt::TokioIo;
+use std::convert::Infallible;
+use std::net::{IpAddr, SocketAddr};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::net::TcpListener;
+use tokio::task::JoinHandle;
+
+/// The largest body that's forwarded, anything bigger is rejected with `413`
+pub const MAX_BODY_SIZE: usize = 16 * 1024 * 1024;
+
+/// The error code of errors from the proxy itself, rather than from the LCU
+pub const PROXY_ERROR_CODE: &str = "PROXY_ERROR";
+
+const JSON: &str = "application/json";
+
+/// How long to wait after failing to accept a connection, before trying again
+const ACCEPT_BACKOFF: Duration = Duration::from_millis(100);
+
+/// Which requests are forwarded, by method and path
+///
+/// Paths are matched by prefix, a whole segment at a time, so `/lol-gameflow` allows `/lol-gameflow/v1/session`,
+/// but not `/lol-gameflow-evil`. Paths with `.` or `..` segments, or encoded slashes and dots, are never forwarded
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AllowList {
+    rules: Vec<AllowRule>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct AllowRule {
+    /// `None` allows any method
+    method: Option<String>,
+    prefix: String,
+}
+
+impl AllowList {
+    #[must_use]
+    /// An allow list that allows nothing
+    pub const fn new() -> Self {
+        Self { rules: Vec::new() }
+    }
+
+    #[must_use]
+    /// Allows requests with `method` to anything under `prefix`
+    pub fn allow(mut self, method: &str, prefix: impl Into<String>) -> Self {
+        self.rules.push(AllowRule {
+            method: Some(method.to_ascii_uppercase()),
+            prefix: prefix.into(),
+        });
+        self
+    }
+
+    #[must_use]
+    /// Allows `GET` and `HEAD` requests to anything under `prefix`
+    pub fn allow_read_only(self, prefix: impl Into<String>) -> Self {
+        let prefix = prefix.into();
+        self.allow("GET", prefix.clone()).allow("HEAD", prefix)
+    }
+
+    #[must_use]
+    /// Allows requests with any method to anything under `prefix`, `"/"` allows everything
+    pub fn allow_any_method(mut self, prefix: impl Into<String>) -> Self {
+        self.rules.push(AllowRule {
+            method: None,
+            prefix: prefix.into(),
+        });
+        self
+    }
+
+    #[must_use]
+    /// Whether a request with `method` to `path` is forwarded, `path` shouldn't include the query
+    pub fn allows(&self, method: &str, path: &str) -> bool {
+        is_plain_path(path)
+            && self.rules.iter().any(|rule| {
+                let method_allowed = rule
+                    .method
+                    .as_ref()
+                    .map_or(true, |allowed| allowed.eq_ignore_ascii_case(method));
+
+                method_allowed && is_under(path, &rule.prefix)
+            })
+    }
+}
+
+/// Whether `path` is `prefix`, or under it
+fn is_under(path: &str, prefix: &str) -> bool {
+    let prefix = prefix.trim_end_matches('/');
+
+    path.strip_prefix(prefix)
+        .is_some_and(|rest| rest.is_empty() || rest.starts_with('/'))
+}
+
+/// Whether `path` means the same thing however it's normalized, so it can't escape a prefix it matched
+fn is_plain_path(path: &str) -> bool {
+    const ENCODED: [&str; 3] = ["%2e", "%2f", "%5c"];
+
+    let lowercase = path.to_ascii_lowercase();
+
+    path.starts_with('/')
+        && !path.contains('\\')
+        && !ENCODED.iter().any(|encoded| lowercase.contains(encoded))
+        && !path
+            .split('/')
+            .any(|segment| segment == "." || segment == "..")
+}
+
+/// A proxy that hasn't started yet, see the [module docs](self)
+pub struct LcuProxy {
+    client: LcuClient,
+    allow: AllowList,
+    origins: Vec<String>,
+    hosts: Vec<String>,
+}
+
+impl LcuProxy {
+    #[must_use]
+    /// A proxy forwarding what `allow` lets through to `client`
+    pub const fn new(client: LcuClient, allow: AllowList) -> Self {
+        Self {
+            client,
+            allow,
+            origins: Vec::new(),
+            hosts: Vec::new(),
+        }
+    }
+
+    #[must_use]
+    /// Accepts requests from pages served from `origin`, such as `http://localhost:5173`
+    ///
+    /// Requests without an `Origin` header, such as ones that don't come from a browser, are always accepted
+    pub fn allow_origin(mut self, origin: impl Into<String>) -> Self {
+        self.origins.push(origin.into());
+        self
+    }
+
+    #[must_use]
+    /// Accepts requests sent to `host`, as well as to the loopback
+    ///
+    /// Requests are only accepted if the `Host` header names the loopback, so a page can't reach the proxy
+    /// by pointing its own domain at `127.0.0.1`. This is only needed if the proxy is bound to another address
+    pub fn allow_host(mut self, host: impl Into<String>) -> Self {
+        self.hosts.push(host.into());
+        self
+    }
+
+    /// Starts serving on `addr`, in the background on the current tokio runtime
+    ///
+    /// This should be a loopback address, such as `127.0.0.1:3000`, anything that can reach it can use the LCU
+    /// as far as the [`AllowList`] allows. Port `0` picks any free port, see [`ProxyServer::addr`]
+    ///
+    /// # Errors
+    /// This will return an error if `addr` cannot be bound
+    ///
+    /// # Panics
+    /// This will panic if called outside a tokio runtime
+    pub async fn bind(self, addr: impl Into<SocketAddr>) -> std::io::Result<ProxyServer> {
+        let listener = TcpListener::bind(addr.into()).await?;
+        let addr = listener.local_addr()?;
+
+        let proxy = Arc::new(self);
+        let task = tokio::spawn(async move {
+            loop {
+                // Failing to accept one connection, such as when out of file descriptors, doesn't stop the proxy
+                let Ok((stream, _)) = listener.accept().await else {
+                    runtime::sleep(ACCEPT_BACKOFF).await;
+                    continue;
+                };
+
+                let proxy = proxy.clone();
+                tokio::spawn(async move {
+                    let service = service_fn(|request| {
+                        let proxy = proxy.clone();
+                        async move { Ok::<_, Infallible>(proxy.handle(request).await) }
+                    });
+
+                    let _ = http1::Builder::new()
+                        .serve_connection(TokioIo::new(stream), service)
+                        .await;
+                });
+            }
+        });
+
+        Ok(ProxyServer { addr, task })
+    }
+
+    async fn handle(&self, request: Request<Incoming>) -> Response<Full<Bytes>> {
+        if !self.allows_host(request.headers().get(HOST)) {
+            return proxy_error(StatusCode::FORBIDDEN, "The host is not allowed");
+        }
+
+        let origin = request.headers().get(ORIGIN).cloned();
+
+        let mut response = match &origin {
+            Some(origin) if !self.allows_origin(origin) => {
+                return proxy_error(StatusCode::FORBIDDEN, "The origin is not allowed");
+            }
+            Some(_) if request.method() == Method::OPTIONS => preflight(),
+            _ => self.forward(request).await,
+        };
+
+        if let Some(origin) = origin {
+            let headers = response.headers_mut();
+            headers.insert(ACCESS_CONTROL_ALLOW_ORIGIN, origin);
+            headers.insert(VARY, HeaderValue::from_static("Origin"));
+        }
+
+        response
+    }
+
+    async fn forward(&self, request: Request<Incoming>) -> Response<Full<Bytes>> {
+        let method = request.method().as_str().to_owned();
+
+        if !self.allow.allows(&method, request.uri().path()) {
+            return proxy_error(
+                StatusCode::FORBIDDEN,
+                &format!("{method} {} is not allowed", request.uri().path()),
+            );
+        }
+
+        let endpoint = request
+            .uri()
+            .path_and_query()
+            .map_or("/", |path| path.as_str())
+            .to_owned();
+
+        let content_type = request
+            .headers()
+            .get(CONTENT_TYPE)
+            .cloned()
+            .unwrap_or_else(|| HeaderValue::from_static(JSON));
+
+        let body = match Limited::new(request.into_body(), MAX_BODY_SIZE)
+            .collect()
+            .await
+        {
+            Ok(body) => body.to_bytes(),
+            Err(err) if err.is::<LengthLimitError>() => {
+                return proxy_error(StatusCode::PAYLOAD_TOO_LARGE, "The body is too large");
+            }
+            Err(err) => return proxy_error(StatusCode::BAD_REQUEST, &err.to_string()),
+        };
+
+        let mut builder = self.client.request(&method, endpoint);
+        if !body.is_empty() {
+            builder = builder.body_raw(content_type, body);
+        }
+
+        match builder.send_bytes().await {
+            Ok(body) => json_response(&body),
+            Err(err) => error_response(err),
+        }
+    }
+
+    fn allows_host(&self, host: Option<&HeaderValue>) -> bool {
+        let Some(host) = host.and_then(|host| host.to_str().ok()) else {
+            return false;
+        };
+
+        let name = host_name(host);
+
+        name.eq_ignore_ascii_case("localhost")
+            || name
+                .trim_start_matches('[')
+                .trim_end_matches(']')
+                .parse::<IpAddr>()
+                .is_ok_and(|ip| ip.is_loopback())
+            || self
+                .hosts
+                .iter()
+                .any(|allowed| allowed.eq_ignore_ascii_case(name))
+    }
+
+    fn allows_origin(&self, origin: &HeaderValue) -> bool {
+        self.origins
+            .iter()
+            .any(|allowed| allowed.as_bytes() == origin.as_bytes())
+    }
+}
+
+/// A running proxy, created with [`LcuProxy::bind`], it stops when this is dropped
+///
+/// Connections that are already open are served until they close
+pub struct ProxyServer {
+    addr: SocketAddr,
+    task: JoinHandle<()>,
+}
+
+impl ProxyServer {
+    #[must_use]
+    /// The address the proxy is listening on
+    pub const fn addr(&self) -> SocketAddr {
+        self.addr
+    }
+
+    /// Stops accepting connections, the same as dropping this
+    pub fn shutdown(self) {}
+}
+
+impl Drop for ProxyServer {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+/// The `Host` header without the port, IPv6 addresses keep their brackets
+fn host_name(host: &str) -> &str {
+    match host.rfind(':') {
+        Some(index) if !host[index..].contains(']') => &host[..index],
+        _ => host,
+    }
+}
+
+/// Answers a CORS preflight, the request that follows is checked against the allow list as usual
+fn preflight() -> Response<Full<Bytes>> {
+    let mut response = Response::new(Full::default());
+    *response.status_mut() = StatusCode::NO_CONTENT;
+
+    let headers = response.headers_mut();
+    headers.insert(
+        ACCESS_CONTROL_ALLOW_METHODS,
+        HeaderValue::from_static("GET, HEAD, POST, PUT, PATCH, DELETE"),
+    );
+    headers.insert(
+        ACCESS_CONTROL_ALLOW_HEADERS,
+        HeaderValue::from_static("Content-Type"),
+    );
+
+    response
+}
+
+/// Converts a msgpack body from the LCU to JSON
+fn json_response(body: &[u8]) -> Response<Full<Bytes>> {
+    if body.is_empty() {
+        let mut response = Response::new(Full::default());
+        *response.status_mut() = StatusCode::NO_CONTENT;
+        return response;
+    }
+
+    match rmp_serde::from_slice::<serde_json::Value>(body) {
+        Ok(json) => with_json(StatusCode::OK, &json),
+        Err(err) => proxy_error(StatusCode::BAD_GATEWAY, &err.to_string()),
+    }
+}
+
+fn error_response(err: Error) -> Response<Full<Bytes>> {
+    match err {
+        Error::LcuError(err) => {
+            let status = StatusCode::from_u16(err.http_status).unwrap_or(StatusCode::BAD_GATEWAY);
+            with_json(status, &err)
+        }
+        Error::RequestError(status) => {
+            proxy_error(status, status.canonical_reason().unwrap_or_default())
+        }
+        Error::Timeout => proxy_error(StatusCode::GATEWAY_TIMEOUT, &err.to_string()),
+        err => proxy_error(StatusCode::BAD_GATEWAY, &err.to_string()),
+    }
+}
+
+/// An error from the proxy itself, in the same format as the LCU's errors
+fn proxy_error(status: StatusCode, message: &str) -> Response<Full<Bytes>> {
+    with_json(
+        status,
+        &LcuError {
+            error_code: PROXY_ERROR_CODE.to_owned(),
+            http_status: status.as_u16(),
+            message: message.to_owned(),
+            implementation_details: serde_json::Value::Null,
+        },
+    )
+}
+
+fn with_json(status: StatusCode, body: &impl serde::Serialize) -> Response<Full<Bytes>> {
+    // Values that came from JSON, or were built here, can always be written as JSON again
+    let body = serde_json::to_vec(body).unwrap_or_default();
+
+    let mut response = Response::new(Full::from(body));
+    *response.status_mut() = status;
+    response
+        .headers_mut()
+        .insert(CONTENT_TYPE, HeaderValue::from_static(JSON));
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{host_name, AllowList};
+
+    #[test]
+    fn allow_list() {
+        let allow = AllowList::new()
+            .allow_read_only("/lol-gameflow/")
+            .allow("post", "/lol-lobby/v2/lobby");
+
+        assert!(allow.allows("GET", "/lol-gameflow/v1/session"));
+        assert!(allow.allows("head", "/lol-gameflow"));
+        assert!(!allow.allows("DELETE", "/lol-gameflow/v1/session"));
+        assert!(!allow.allows("GET", "/lol-gameflow-evil"));
+        assert!(allow.allows("POST", "/lol-lobby/v2/lobby"));
+        assert!(!allow.allows("POST", "/lol-lobby/v2/lobby-evil"));
+
+        // Nothing can climb out of an allowed prefix
+        assert!(!allow.allows("GET", "/lol-gameflow/../riotclient/kill-ux"));
+        assert!(!allow.allows("GET", "/lol-gameflow/%2E%2E/riotclient/kill-ux"));
+        assert!(!allow.allows("GET", "/lol-gameflow/%2friotclient"));
+
+        assert!(!AllowList::new().allows("GET", "/"));
+        assert!(AllowList::new()
+            .allow_any_method("/")
+            .allows("DELETE", "/anything"));
+    }
+
+    #[test]
+    fn host_names() {
+        assert_eq!(host_name("localhost:3000"), "localhost");
+        assert_eq!(host_name("127.0.0.1"), "127.0.0.1");
+        assert_eq!(host_name("[::1]:3000"), "[::1]");
+        assert_eq!(host_name("[::1]"), "[::1]");
+    }
+
+    #[cfg(feature = "ws")]
+    #[tokio::test]
+    async fn forward() {
+        use super::LcuProxy;
+        use crate::mock::MockLcu;
+        use serde_json::Value;
+        use std::io::{Read, Write};
+        use std::net::{SocketAddr, TcpStream};
+
+        /// Sends a raw request, returning the status and the body
+        async fn send(addr: SocketAddr, request: String) -> (u16, Value) {
+            tokio::task::spawn_blocking(move || {
+                let mut stream = TcpStream::connect(addr).unwrap();
+                stream.write_all(request.as_bytes()).unwrap();
+
+                let mut response = String::new();
+                stream.read_to_string(&mut response).unwrap();
+
+                let status = response[9..12].parse().unwrap();
+                let (_, body) = response.split_once("\r\n\r\n").unwrap();
+                (status, serde_json::from_str(body).unwrap_or_default())
+            })
+            .await
+            .unwrap()
+        }
+
+        fn request(method: &str, path: &str, extra: &str, body: &str) -> String {
+            format!(
+                "{method} {path} HTTP/1.1\r\nHost: 127.0.0.1\r\nConnection: close\r\n{extra}Content-Length: {}\r\n\r\n{body}",
+                body.len()
+            )
+        }
+
+        let mock = MockLcu::start().unwrap();
+        mock.on_get("/lol-gameflow/v1/gameflow-phase")
+            .respond_json("Lobby");
+        mock.on_post("/lol-lobby/v2/lobby")
+            .respond_error(hyper::StatusCode::BAD_REQUEST, "Invalid queue");
+
+        let allow = AllowList::new()
+            .allow_read_only("/lol-gameflow")
+            .allow("POST", "/lol-lobby/v2/lobby");
+
+        let proxy = LcuProxy::new(mock.client(), allow)
+            .allow_origin("http://localhost:5173")
+            .bind(([127, 0, 0, 1], 0))
+            .await
+            .unwrap();
+        let addr = proxy.addr();
+
+        let phase = request("GET", "/lol-gameflow/v1/gameflow-phase", "", "");
+        assert_eq!(send(addr, phase).await, (200, Value::from("Lobby")));
+
+        let (status, body) = send(
+            addr,
+            request("POST", "/lol-lobby/v2/lobby", "", r#"{"queueId": 1}"#),
+        )
+        .await;
+        assert_eq!(status, 400);
+        assert_eq!(body["message"], "Invalid queue");
+        assert_eq!(mock.requests()[1].body, serde_json::json!({"queueId": 1}));
+
+        // Nothing outside the allow list, or from an unknown origin or host, reaches the client
+        let requests = mock.requests().len();
+
+        let (status, body) = send(addr, request("DELETE", "/lol-lobby/v2/lobby", "", "")).await;
+        assert_eq!(status, 403);
+        assert_eq!(body["errorCode"], "PROXY_ERROR");
+
+        let origin = "Origin: http://evil.example\r\n";
+        let phase = request("GET", "/lol-gameflow/v1/gameflow-phase", origin, "");
+        assert_eq!(send(addr, phase).await.0, 403);
+
+        let phase = request("GET", "/lol-gameflow/v1/gameflow-phase", "", "")
+            .replace("127.0.0.1", "evil.example");
+        assert_eq!(send(addr, phase).await.0, 403);
+
+        assert_eq!(mock.requests().len(), requests);
+
+        proxy.shutdown();
+    }
+}
@@ -0,0 +1,248 @@
+//! Typed wrappers around the `/lol-ranked/v1` endpoints
+//!
+//! Tiers and divisions are enums, and [`Tier`] is ordered from Iron to Challenger, so ranks can be compared directly
+
+use super::LcuClient;
+use crate::Error;
+use serde_derive::{Deserialize, Serialize};
+
+/// The endpoint the logged in summoner's ranked stats are served from, and the uri of its websocket event
+pub const CURRENT_RANKED_STATS_ENDPOINT: &str = "/lol-ranked/v1/current-ranked-stats";
+
+/// The queue type of ranked solo/duo
+pub const RANKED_SOLO: &str = "RANKED_SOLO_5x5";
+
+/// The queue type of ranked flex
+pub const RANKED_FLEX: &str = "RANKED_FLEX_SR";
+
+/// A player's ranked stats, in every ranked queue
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RankedStats {
+    #[serde(default)]
+    pub queues: Vec<RankedQueueStats>,
+    /// The highest rank across every queue
+    #[serde(default)]
+    pub highest_ranked_entry: Option<RankedQueueStats>,
+}
+
+impl RankedStats {
+    #[must_use]
+    /// The stats in `queue_type`, such as [`RANKED_SOLO`], `None` if the player has no entry for it
+    pub fn queue(&self, queue_type: &str) -> Option<&RankedQueueStats> {
+        self.queues
+            .iter()
+            .find(|queue| queue.queue_type == queue_type)
+    }
+}
+
+/// A player's ranked stats in a single queue
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RankedQueueStats {
+    /// Such as [`RANKED_SOLO`] or [`RANKED_FLEX`]
+    pub queue_type: String,
+    #[serde(default)]
+    pub tier: Tier,
+    /// [`Division::None`] for unranked players, and players in Master and above
+    #[serde(default)]
+    pub division: Division,
+    #[serde(default)]
+    pub league_points: i32,
+    #[serde(default)]
+    pub wins: u32,
+    #[serde(default)]
+    pub losses: u32,
+    /// Whether the player is still playing their placement games
+    #[serde(default)]
+    pub is_provisional: bool,
+    #[serde(default)]
+    pub highest_tier: Tier,
+    #[serde(default)]
+    pub highest_division: Division,
+    #[serde(default)]
+    pub previous_season_end_tier: Tier,
+    #[serde(default)]
+    pub previous_season_end_division: Division,
+    /// The color tier of queues that are rated rather than ranked, such as TFT Hyper Roll
+    #[serde(default)]
+    pub rated_tier: RatedTier,
+    /// The rating of queues that are rated rather than ranked
+    #[serde(default)]
+    pub rated_rating: i32,
+}
+
+impl RankedQueueStats {
+    #[must_use]
+    /// The tier and division, `None` if the player is unranked in this queue
+    pub fn rank(&self) -> Option<(Tier, Division)> {
+        (self.tier != Tier::Unranked).then_some((self.tier, self.division))
+    }
+}
+
+/// A ranked tier, ordered from lowest to highest
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
+)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum Tier {
+    /// The player has not finished their placement games, sent as `NONE` or an empty string
+    #[default]
+    #[serde(rename = "NONE", alias = "")]
+    Unranked,
+    Iron,
+    Bronze,
+    Silver,
+    Gold,
+    Platinum,
+    Emerald,
+    Diamond,
+    Master,
+    Grandmaster,
+    Challenger,
+    /// A tier this version of the crate does not know about, this is ordered above every other tier
+    #[serde(other)]
+    Unknown,
+}
+
+impl Tier {
+    #[must_use]
+    /// Whether this tier is split into divisions, which every tier below Master is
+    pub const fn has_divisions(self) -> bool {
+        matches!(
+            self,
+            Self::Iron
+                | Self::Bronze
+                | Self::Silver
+                | Self::Gold
+                | Self::Platinum
+                | Self::Emerald
+                | Self::Diamond
+        )
+    }
+}
+
+/// A division within a tier, ordered from lowest to highest
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
+)]
+pub enum Division {
+    /// There is no division, sent as `NA` or an empty string
+    #[default]
+    #[serde(rename = "NA", alias = "")]
+    None,
+    IV,
+    III,
+    II,
+    I,
+    /// A division this version of the crate does not know about
+    #[serde(other)]
+    Unknown,
+}
+
+/// The tier of a rated queue, such as TFT Hyper Roll, ordered from lowest to highest
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
+)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum RatedTier {
+    /// The player has not played the queue, sent as `NONE` or an empty string
+    #[default]
+    #[serde(rename = "NONE", alias = "")]
+    Unrated,
+    Gray,
+    Green,
+    Blue,
+    Purple,
+    /// The highest tier, also called Hyper
+    Orange,
+    /// A tier this version of the crate does not know about, this is ordered above every other tier
+    #[serde(other)]
+    Unknown,
+}
+
+impl LcuClient {
+    /// Gets the logged in summoner's ranked stats
+    ///
+    /// # Errors
+    /// This will return an error if the LCU API is not running
+    pub async fn current_ranked_stats(&self) -> Result<RankedStats, Error> {
+        self.get(CURRENT_RANKED_STATS_ENDPOINT).await
+    }
+
+    /// Gets the ranked stats of the player with `puuid`
+    ///
+    /// # Errors
+    /// This will return an error if the LCU API is not running, or there is no player with `puuid`
+    pub async fn ranked_stats(&self, puuid: &str) -> Result<RankedStats, Error> {
+        self.get(format!("/lol-ranked/v1/ranked-stats/{puuid}"))
+            .await
+    }
+
+    /// Gets the ranked stats of the player with `puuid` in `queue_type`, such as [`RANKED_SOLO`]
+    ///
+    /// Returns `None` if the player has no entry for the queue
+    ///
+    /// # Errors
+    /// This will return an error if the LCU API is not running, or there is no player with `puuid`
+    pub async fn ranked_queue_stats(
+        &self,
+        puuid: &str,
+        queue_type: &str,
+    ) -> Result<Option<RankedQueueStats>, Error> {
+        let stats = self.ranked_stats(puuid).await?;
+        Ok(stats.queue(queue_type).cloned())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Division, RankedStats, Tier, RANKED_FLEX, RANKED_SOLO};
+    use crate::rest::LcuClient;
+
+    #[test]
+    fn ranked_stats_deserialize() {
+        let stats: RankedStats = serde_json::from_str(
+            r#"{
+                "queues": [
+                    {"queueType": "RANKED_SOLO_5x5", "tier": "EMERALD", "division": "II", "leaguePoints": 45, "wins": 30, "losses": 25},
+                    {"queueType": "RANKED_FLEX_SR", "tier": "", "division": "NA", "isProvisional": true},
+                    {"queueType": "RANKED_TFT", "tier": "MASTER", "division": "NA", "previousSeasonEndTier": "SOMETHING_NEW"}
+                ]
+            }"#,
+        )
+        .unwrap();
+
+        let solo = stats.queue(RANKED_SOLO).unwrap();
+        assert_eq!(solo.rank(), Some((Tier::Emerald, Division::II)));
+        assert_eq!(solo.league_points, 45);
+
+        let flex = stats.queue(RANKED_FLEX).unwrap();
+        assert_eq!(flex.rank(), None);
+        assert_eq!(flex.division, Division::None);
+
+        let tft = stats.queue("RANKED_TFT").unwrap();
+        assert!(!tft.tier.has_divisions());
+        assert_eq!(tft.previous_season_end_tier, Tier::Unknown);
+    }
+
+    #[test]
+    fn rank_order() {
+        assert!(Tier::Iron < Tier::Challenger);
+        assert!(Tier::Unranked < Tier::Iron);
+        assert!((Tier::Gold, Division::I) > (Tier::Gold, Division::IV));
+
+        assert_eq!(
+            serde_json::to_string(&Tier::Grandmaster).unwrap(),
+            "\"GRANDMASTER\""
+        );
+    }
+
+    #[ignore = "This is only needed for testing, and doesn't need to be run all the time"]
+    #[tokio::test]
+    async fn test_ranked_stats() {
+        let client = LcuClient::connect().unwrap();
+
+        println!("{:?}", client.current_ranked_stats().await);
+    }
+}
@@ -0,0 +1,330 @@
+//! An [`LcuClient`] that reconnects on its own when the client restarts
+//!
+//! The port and auth token change every time the client starts, so a plain [`LcuClient`]
+//! starts failing once the client restarts, until it's reconnected manually
+
+use super::LcuClient;
+use crate::process_info::ErrorKind;
+use crate::utils::runtime;
+use crate::Error;
+use hyper::http::HeaderValue;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::sync::Arc;
+use tokio::sync::{RwLock, RwLockReadGuard};
+
+type ReconnectHook = Arc<dyn Fn(&LcuClient) + Send + Sync>;
+
+/// How a [`ReconnectingClient`] reacts to the connection being lost
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ReconnectPolicy {
+    /// Sends the failed request again after reconnecting, this is `true` by default
+    ///
+    /// Only idempotent requests (get, head, put and delete) are sent again, unless [`ReconnectPolicy::retry_all`] is set
+    pub retry: bool,
+    /// Also sends post and patch requests again, which can apply them twice if the client
+    /// handled them before the connection was lost, this is `false` by default
+    pub retry_all: bool,
+    /// Reads the lock file when reconnecting, see [`LcuClient::reconnect`]
+    pub force_lock_file: bool,
+}
+
+impl Default for ReconnectPolicy {
+    fn default() -> Self {
+        Self {
+            retry: true,
+            retry_all: false,
+            force_lock_file: false,
+        }
+    }
+}
+
+/// Wraps an [`LcuClient`], finding the client again whenever a request fails because it could not be reached
+///
+/// Requests that fail because the connection was refused, or because the client closed, cause the client
+/// to be found again, and the new credentials to be swapped in. If [`ReconnectPolicy::retry`] is set,
+/// idempotent requests are then sent again, once.
+///
+/// ```no_run
+/// use irelia::rest::{reconnect::ReconnectingClient, LcuClient};
+///
+/// # async fn run() -> Result<(), irelia::Error> {
+/// let client = ReconnectingClient::new(LcuClient::connect()?)
+///     .on_reconnect(|client| println!("Reconnected to {}", client.url()));
+///
+/// let phase: serde_json::Value = client.get("/lol-gameflow/v1/gameflow-phase").await?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct ReconnectingClient {
+    client: RwLock<LcuClient>,
+    policy: ReconnectPolicy,
+    hooks: Vec<ReconnectHook>,
+}
+
+impl ReconnectingClient {
+    #[must_use]
+    pub fn new(client: LcuClient) -> Self {
+        Self::new_with_policy(client, ReconnectPolicy::default())
+    }
+
+    #[must_use]
+    pub fn new_with_policy(client: LcuClient, policy: ReconnectPolicy) -> Self {
+        Self {
+            client: RwLock::new(client),
+            policy,
+            hooks: Vec::new(),
+        }
+    }
+
+    #[must_use]
+    /// Adds a hook that runs every time the client reconnects, with the newly connected client
+    pub fn on_reconnect(mut self, hook: impl Fn(&LcuClient) + Send + Sync + 'static) -> Self {
+        self.hooks.push(Arc::new(hook));
+        self
+    }
+
+    #[must_use]
+    pub const fn policy(&self) -> ReconnectPolicy {
+        self.policy
+    }
+
+    /// Locks the inner client, for anything that isn't a request, such as reading the url
+    ///
+    /// Reconnecting waits until the guard is dropped
+    pub async fn client(&self) -> RwLockReadGuard<'_, LcuClient> {
+        self.client.read().await
+    }
+
+    /// Finds the client again, swapping in the new credentials
+    ///
+    /// # Errors
+    /// This will return an error if the client is not running
+    pub async fn reconnect(&self) -> Result<(), Error> {
+        self.reconnect_from(None).await
+    }
+
+    /// Sends a delete request to the LCU, see [`LcuClient::delete`]
+    ///
+    /// # Errors
+    /// This will return an error if the LCU API is not running, even after reconnecting
+    pub async fn delete<R: DeserializeOwned>(
+        &self,
+        endpoint: impl AsRef<str> + Send,
+    ) -> Result<R, Error> {
+        self.lcu_request(endpoint.as_ref(), "DELETE", None::<()>)
+            .await
+    }
+
+    /// Sends a get request to the LCU, see [`LcuClient::get`]
+    ///
+    /// # Errors
+    /// This will return an error if the LCU API is not running, even after reconnecting
+    pub async fn get<R: DeserializeOwned>(
+        &self,
+        endpoint: impl AsRef<str> + Send,
+    ) -> Result<R, Error> {
+        self.lcu_request(endpoint.as_ref(), "GET", None::<()>).await
+    }
+
+    /// Sends a patch request to the LCU, see [`LcuClient::patch`]
+    ///
+    /// # Errors
+    /// This will return an error if the LCU API is not running, even after reconnecting
+    pub async fn patch<T: Serialize + Send + Sync, R: DeserializeOwned>(
+        &self,
+        endpoint: impl AsRef<str> + Send,
+        body: T,
+    ) -> Result<R, Error> {
+        self.lcu_request(endpoint.as_ref(), "PATCH", Some(body))
+            .await
+    }
+
+    /// Sends a post request to the LCU, see [`LcuClient::post`]
+    ///
+    /// # Errors
+    /// This will return an error if the LCU API is not running, even after reconnecting
+    pub async fn post<T: Serialize + Send + Sync, R: DeserializeOwned>(
+        &self,
+        endpoint: impl AsRef<str> + Send,
+        body: T,
+    ) -> Result<R, Error> {
+        self.lcu_request(endpoint.as_ref(), "POST", Some(body))
+            .await
+    }
+
+    /// Sends a put request to the LCU, see [`LcuClient::put`]
+    ///
+    /// # Errors
+    /// This will return an error if the LCU API is not running, even after reconnecting
+    pub async fn put<T: Serialize + Send + Sync, R: DeserializeOwned>(
+        &self,
+        endpoint: impl AsRef<str> + Send,
+        body: T,
+    ) -> Result<R, Error> {
+        self.lcu_request(endpoint.as_ref(), "PUT", Some(body)).await
+    }
+
+    /// Makes a request to the LCU, reconnecting if the client could not be reached, see [`LcuClient::lcu_request`]
+    ///
+    /// # Errors
+    /// This will return an error if the LCU API is not running, even after reconnecting,
+    /// or the provided type or body is invalid
+    pub async fn lcu_request<T: Serialize + Send + Sync, R: DeserializeOwned>(
+        &self,
+        endpoint: &str,
+        method: &str,
+        body: Option<T>,
+    ) -> Result<R, Error> {
+        let (result, auth_header) = {
+            let client = self.client.read().await;
+            let result = client.lcu_request(endpoint, method, body.as_ref()).await;
+            (result, client.auth_header().clone())
+        };
+
+        match &result {
+            Err(err) if is_disconnect(err) => {}
+            _ => return result,
+        }
+
+        self.reconnect_from(Some(&auth_header)).await?;
+
+        if !self.policy.retry || !(self.policy.retry_all || is_idempotent(method)) {
+            return result;
+        }
+
+        self.client
+            .read()
+            .await
+            .lcu_request(endpoint, method, body.as_ref())
+            .await
+    }
+
+    /// Finds the client, and swaps in the new credentials, unless they already changed from `auth_header`
+    ///
+    /// Searching the processes blocks, so it's done on a blocking thread, without holding the lock,
+    /// requests keep going through the old credentials in the meantime
+    async fn reconnect_from(&self, auth_header: Option<&HeaderValue>) -> Result<(), Error> {
+        let locator = {
+            let client = self.client.read().await;
+
+            // Another request may have already reconnected while this one was waiting
+            if auth_header.is_some_and(|auth_header| auth_header != client.auth_header()) {
+                return Ok(());
+            }
+
+            client.reconnect_locator(self.policy.force_lock_file)
+        };
+
+        let located = runtime::spawn_blocking(move || locator.locate()).await?;
+
+        let mut client = self.client.write().await;
+        if auth_header.is_some_and(|auth_header| auth_header != client.auth_header()) {
+            return Ok(());
+        }
+
+        client.reconnect_with_located(&located)?;
+
+        for hook in &self.hooks {
+            hook(&client);
+        }
+
+        Ok(())
+    }
+}
+
+/// Errors caused by the client not being reachable, rather than the request itself
+fn is_disconnect(err: &Error) -> bool {
+    match err {
+        Error::HyperClientError(_) | Error::HyperError(_) => true,
+        Error::ProcessInfoError(err) => err.kind() == ErrorKind::ClientClosed,
+        _ => false,
+    }
+}
+
+/// Methods that can be sent twice without changing the result, so they're safe to retry
+fn is_idempotent(method: &str) -> bool {
+    ["GET", "HEAD", "PUT", "DELETE"]
+        .iter()
+        .any(|idempotent| method.eq_ignore_ascii_case(idempotent))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{is_disconnect, is_idempotent};
+    use crate::process_info::CLIENT_CLOSED;
+    use crate::Error;
+
+    #[test]
+    fn test_is_disconnect() {
+        assert!(is_disconnect(&Error::ProcessInfoError(CLIENT_CLOSED)));
+        assert!(!is_disconnect(&Error::Timeout));
+    }
+
+    #[test]
+    fn test_is_idempotent() {
+        assert!(is_idempotent("GET"));
+        assert!(is_idempotent("delete"));
+        assert!(!is_idempotent("POST"));
+        assert!(!is_idempotent("PATCH"));
+    }
+
+    #[cfg(feature = "ws")]
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_reconnect_unlocked() {
+        use super::ReconnectingClient;
+        use crate::mock::{MockLcu, MOCK_TOKEN};
+        use crate::process_info::{Error, LocatedClient};
+        use crate::rest::LcuClient;
+        use crate::RequestClient;
+        use std::sync::atomic::{AtomicU32, Ordering};
+        use std::sync::Arc;
+        use std::time::Duration;
+
+        let mock = MockLcu::start().unwrap();
+        let addr = mock.addr();
+
+        let located = Arc::new(AtomicU32::new(0));
+        let counter = Arc::clone(&located);
+        let locator = move || -> Result<LocatedClient, Error> {
+            // Blocks like searching the processes would, after the first connection
+            if counter.fetch_add(1, Ordering::Relaxed) > 0 {
+                std::thread::sleep(Duration::from_millis(200));
+            }
+            LocatedClient::new(addr, MOCK_TOKEN)
+        };
+
+        let reconnected = Arc::new(AtomicU32::new(0));
+        let hook_counter = Arc::clone(&reconnected);
+        let client = Arc::new(
+            ReconnectingClient::new(
+                LcuClient::connect_with_locator(
+                    locator,
+                    &RequestClient::danger_accept_invalid_certs(),
+                )
+                .unwrap(),
+            )
+            .on_reconnect(move |_| {
+                hook_counter.fetch_add(1, Ordering::Relaxed);
+            }),
+        );
+
+        let reconnecting = tokio::spawn({
+            let client = Arc::clone(&client);
+            async move { client.reconnect().await }
+        });
+        tokio::time::sleep(Duration::from_millis(50)).await;
+
+        // The client can still be used while the locator blocks
+        let url = tokio::time::timeout(Duration::from_millis(100), client.client())
+            .await
+            .unwrap()
+            .url();
+        assert_eq!(url, addr);
+        assert_eq!(reconnected.load(Ordering::Relaxed), 0);
+
+        reconnecting.await.unwrap().unwrap();
+        assert_eq!(located.load(Ordering::Relaxed), 2);
+        assert_eq!(reconnected.load(Ordering::Relaxed), 1);
+    }
+}
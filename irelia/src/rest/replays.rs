@@ -0,0 +1,438 @@
+//! Typed wrappers around the `/lol-replays/v1` endpoints, which download `.rofl` files of past games
+//!
+//! Downloads are started through the LCU, which reports its progress as the replay's metadata changes
+
+use super::LcuClient;
+use crate::Error;
+use serde_derive::{Deserialize, Serialize};
+use std::io;
+use std::path::{Path, PathBuf};
+
+#[cfg(feature = "ws")]
+use crate::ws::{types::Event, LcuWebSocket, SubscriberID};
+#[cfg(feature = "ws")]
+use serde::Deserialize as _;
+#[cfg(feature = "ws")]
+use std::collections::HashMap;
+#[cfg(feature = "ws")]
+use std::sync::{Arc, Mutex, PoisonError};
+#[cfg(feature = "ws")]
+use tokio::sync::{mpsc, Semaphore};
+
+/// The endpoint the replay configuration is served from
+pub const CONFIGURATION_ENDPOINT: &str = "/lol-replays/v1/configuration";
+
+/// The glob matching the uri of every replay's metadata event, see [`crate::ws::Glob`]
+pub const METADATA_GLOB: &str = "/lol-replays/v1/metadata/*";
+
+/// Whether replays can be downloaded and watched right now
+#[allow(clippy::struct_excessive_bools)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReplayConfiguration {
+    #[serde(default)]
+    pub is_replays_enabled: bool,
+    #[serde(default)]
+    pub is_replays_for_end_of_game_enabled: bool,
+    #[serde(default)]
+    pub is_replays_for_match_history_enabled: bool,
+    /// Replays can't be downloaded while the client is patching
+    #[serde(default)]
+    pub is_patching: bool,
+    #[serde(default)]
+    pub is_playing_game: bool,
+    #[serde(default)]
+    pub is_playing_replay: bool,
+    /// The patch replays can be watched on, replays from other patches are `incompatible`
+    #[serde(default)]
+    pub game_version: String,
+}
+
+/// The download state of a single replay
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReplayMetadata {
+    pub game_id: u64,
+    pub state: ReplayState,
+    /// Between 0 and 100, only meaningful while `downloading`
+    #[serde(default)]
+    pub download_progress: u8,
+}
+
+/// Where a replay is at, as shown on its button in match history
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ReplayState {
+    /// The client is still working out whether the replay can be downloaded
+    Checking,
+    /// The replay can be downloaded
+    Download,
+    Downloading,
+    /// The replay is on disk, and can be watched
+    Watch,
+    /// The replay was recorded on another patch
+    Incompatible,
+    Lost,
+    MissingOrExpired,
+    /// The last download failed, and can be started again
+    RetryDownload,
+    Error,
+    Unsupported,
+    /// A state this version of the crate does not know about
+    #[serde(other)]
+    Unknown,
+}
+
+impl ReplayState {
+    #[must_use]
+    /// Whether the replay can never be downloaded from this state, such as when it expired
+    pub const fn is_unavailable(self) -> bool {
+        matches!(
+            self,
+            Self::Incompatible
+                | Self::Lost
+                | Self::MissingOrExpired
+                | Self::Error
+                | Self::Unsupported
+        )
+    }
+}
+
+impl LcuClient {
+    /// Gets whether replays can be downloaded and watched right now
+    ///
+    /// # Errors
+    /// This will return an error if the LCU API is not running
+    pub async fn replay_configuration(&self) -> Result<ReplayConfiguration, Error> {
+        self.get(CONFIGURATION_ENDPOINT).await
+    }
+
+    /// Gets the folder replays are downloaded to
+    ///
+    /// # Errors
+    /// This will return an error if the LCU API is not running
+    pub async fn replays_path(&self) -> Result<PathBuf, Error> {
+        self.get("/lol-replays/v1/rofls/path").await
+    }
+
+    /// Gets the download state of the replay for `game_id`
+    ///
+    /// # Errors
+    /// This will return an error if the LCU API is not running
+    pub async fn replay_metadata(&self, game_id: u64) -> Result<ReplayMetadata, Error> {
+        self.get(format!("/lol-replays/v1/metadata/{game_id}"))
+            .await
+    }
+
+    /// Starts downloading the replay for `game_id`, without waiting for it to finish
+    ///
+    /// The progress can be followed with [`LcuClient::replay_metadata`], see [`ReplayDownloader`]
+    /// for a download that resolves once the file is on disk
+    ///
+    /// # Errors
+    /// This will return an error if the LCU API is not running, or the replay can't be downloaded
+    pub async fn start_replay_download(&self, game_id: u64) -> Result<(), Error> {
+        #[derive(Serialize)]
+        #[serde(rename_all = "camelCase")]
+        struct Download {
+            /// The LCU rejects the request without saying where the download was started from
+            component_type: &'static str,
+        }
+
+        let body = Download {
+            component_type: "replay-button_match-history",
+        };
+
+        let endpoint = format!("/lol-replays/v1/rofls/{game_id}/download");
+        // The response is empty when the download was started
+        self.lcu_request_empty(&endpoint, "POST", Some(body)).await
+    }
+
+    /// Finds the `.rofl` file of `game_id` in the replays folder, if it was downloaded
+    ///
+    /// Files are named after the platform and game, such as `EUW1-1234567890.rofl`
+    ///
+    /// # Errors
+    /// This will return an error if the LCU API is not running, or the replays folder can't be read
+    pub async fn find_replay(&self, game_id: u64) -> Result<Option<PathBuf>, Error> {
+        let path = self.replays_path().await?;
+        Ok(find_rofl(&path, game_id)?)
+    }
+}
+
+fn find_rofl(dir: &Path, game_id: u64) -> io::Result<Option<PathBuf>> {
+    let suffix = format!("-{game_id}.rofl");
+
+    for entry in std::fs::read_dir(dir)? {
+        let path = entry?.path();
+
+        if path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .is_some_and(|name| name.ends_with(&suffix))
+        {
+            return Ok(Some(path));
+        }
+    }
+
+    Ok(None)
+}
+
+#[cfg(feature = "ws")]
+type Waiters = Arc<Mutex<HashMap<u64, Vec<mpsc::UnboundedSender<ReplayMetadata>>>>>;
+
+#[cfg(feature = "ws")]
+/// Downloads replays, resolving with the path of the `.rofl` file once it's on disk
+///
+/// Progress is read from the websocket, so [`ReplayDownloader::subscribe`] has to be called before downloading.
+/// At most `max_concurrent` downloads are started at once, any others wait for one of them to finish
+///
+/// ```no_run
+/// use irelia::rest::{replays::ReplayDownloader, LcuClient};
+/// use irelia::ws::LcuWebSocket;
+///
+/// # async fn download() -> Result<(), irelia::Error> {
+/// let client = LcuClient::connect()?;
+/// let mut websocket = LcuWebSocket::new();
+///
+/// let downloader = ReplayDownloader::new(2);
+/// downloader.subscribe(&mut websocket);
+///
+/// let path = downloader
+///     .download(&client, 1234567890, |metadata| {
+///         println!("{}%", metadata.download_progress);
+///     })
+///     .await?;
+///
+/// println!("Downloaded to {}", path.display());
+/// # Ok(())
+/// # }
+/// ```
+pub struct ReplayDownloader {
+    permits: Arc<Semaphore>,
+    waiters: Waiters,
+}
+
+#[cfg(feature = "ws")]
+impl ReplayDownloader {
+    #[must_use]
+    /// Creates a downloader that runs at most `max_concurrent` downloads at once
+    ///
+    /// # Panics
+    /// This panics if `max_concurrent` is zero
+    pub fn new(max_concurrent: usize) -> Self {
+        assert!(
+            max_concurrent > 0,
+            "At least one download has to be allowed"
+        );
+
+        Self {
+            permits: Arc::new(Semaphore::new(max_concurrent)),
+            waiters: Arc::default(),
+        }
+    }
+
+    /// Subscribes to the metadata events of every replay on `websocket`, passing them on to the downloads waiting on them
+    ///
+    /// Returns `None` if the websocket connection has already been closed previously
+    pub fn subscribe(&self, websocket: &mut LcuWebSocket) -> Option<SubscriberID> {
+        let waiters = Arc::clone(&self.waiters);
+
+        websocket.subscribe_filtered(METADATA_GLOB, move |event: &Event| {
+            if let Ok(metadata) = ReplayMetadata::deserialize(&event.2.data) {
+                notify(&waiters, &metadata);
+            }
+        })
+    }
+
+    /// Downloads the replay for `game_id`, calling `on_progress` every time its state changes,
+    /// and returning the path of the `.rofl` file once it's on disk
+    ///
+    /// If the replay was already downloaded, this returns its path without downloading it again
+    ///
+    /// # Errors
+    /// This will return an error if the LCU API is not running, the replay can't be downloaded,
+    /// such as when it expired, or the download failed
+    pub async fn download(
+        &self,
+        client: &LcuClient,
+        game_id: u64,
+        mut on_progress: impl FnMut(&ReplayMetadata) + Send,
+    ) -> Result<PathBuf, Error> {
+        // The semaphore is never closed, so this always holds a permit
+        let _permit = self.permits.acquire().await.ok();
+
+        let (sender, mut receiver) = mpsc::unbounded_channel();
+        let _waiting = Waiting::new(&self.waiters, game_id, sender);
+
+        // Listening before checking the state, so a change that happens between the two is not missed
+        let mut metadata = client.replay_metadata(game_id).await?;
+        on_progress(&metadata);
+
+        let mut started = false;
+
+        loop {
+            match metadata.state {
+                ReplayState::Watch => break,
+                ReplayState::Download | ReplayState::RetryDownload if !started => {
+                    client.start_replay_download(game_id).await?;
+                    started = true;
+                }
+                state if state.is_unavailable() => return Err(unavailable(game_id, state)),
+                ReplayState::RetryDownload => {
+                    return Err(unavailable(game_id, ReplayState::RetryDownload))
+                }
+                _ => {}
+            }
+
+            metadata = receiver.recv().await.ok_or(Error::WebSocketClosed)?;
+            on_progress(&metadata);
+        }
+
+        client
+            .find_replay(game_id)
+            .await?
+            .ok_or_else(|| unavailable(game_id, ReplayState::Lost))
+    }
+}
+
+#[cfg(feature = "ws")]
+/// A download waiting on its game's metadata, which stops waiting when dropped, however the download ends
+struct Waiting<'a> {
+    waiters: &'a Waiters,
+    game_id: u64,
+    sender: mpsc::UnboundedSender<ReplayMetadata>,
+}
+
+#[cfg(feature = "ws")]
+impl<'a> Waiting<'a> {
+    fn new(
+        waiters: &'a Waiters,
+        game_id: u64,
+        sender: mpsc::UnboundedSender<ReplayMetadata>,
+    ) -> Self {
+        waiters
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .entry(game_id)
+            .or_default()
+            .push(sender.clone());
+
+        Self {
+            waiters,
+            game_id,
+            sender,
+        }
+    }
+}
+
+#[cfg(feature = "ws")]
+impl Drop for Waiting<'_> {
+    fn drop(&mut self) {
+        let mut waiters = self.waiters.lock().unwrap_or_else(PoisonError::into_inner);
+
+        if let Some(senders) = waiters.get_mut(&self.game_id) {
+            senders.retain(|sender| !sender.same_channel(&self.sender));
+
+            if senders.is_empty() {
+                waiters.remove(&self.game_id);
+            }
+        }
+    }
+}
+
+#[cfg(feature = "ws")]
+/// Sends `metadata` to every download waiting on its game, forgetting the ones that stopped waiting
+fn notify(waiters: &Waiters, metadata: &ReplayMetadata) {
+    let mut waiters = waiters.lock().unwrap_or_else(PoisonError::into_inner);
+
+    if let Some(senders) = waiters.get_mut(&metadata.game_id) {
+        senders.retain(|sender| sender.send(metadata.clone()).is_ok());
+
+        if senders.is_empty() {
+            waiters.remove(&metadata.game_id);
+        }
+    }
+}
+
+#[cfg(feature = "ws")]
+fn unavailable(game_id: u64, state: ReplayState) -> Error {
+    Error::Unavailable(format!(
+        "the replay for game {game_id} could not be downloaded, it's {state:?}"
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{find_rofl, ReplayMetadata, ReplayState};
+
+    #[test]
+    fn metadata_deserialize() {
+        let metadata: ReplayMetadata = serde_json::from_str(
+            r#"{"gameId": 1234567890, "state": "downloading", "downloadProgress": 42}"#,
+        )
+        .unwrap();
+
+        assert_eq!(metadata.state, ReplayState::Downloading);
+        assert_eq!(metadata.download_progress, 42);
+
+        let state: ReplayState = serde_json::from_str("\"missingOrExpired\"").unwrap();
+        assert!(state.is_unavailable());
+
+        let state: ReplayState = serde_json::from_str("\"someNewState\"").unwrap();
+        assert_eq!(state, ReplayState::Unknown);
+    }
+
+    #[test]
+    fn find_downloaded_rofl() {
+        let dir = std::env::temp_dir().join(format!("irelia-replays-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("EUW1-1234567890.rofl"), []).unwrap();
+
+        let found = find_rofl(&dir, 1_234_567_890).unwrap();
+        let missing = find_rofl(&dir, 1_234_567).unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(found, Some(dir.join("EUW1-1234567890.rofl")));
+        assert_eq!(missing, None);
+    }
+
+    #[cfg(feature = "ws")]
+    #[tokio::test]
+    async fn notify_waiters() {
+        use super::{notify, ReplayDownloader};
+
+        let downloader = ReplayDownloader::new(1);
+        let (sender, mut receiver) = tokio::sync::mpsc::unbounded_channel();
+        downloader.waiters.lock().unwrap().insert(1, vec![sender]);
+
+        let metadata = ReplayMetadata {
+            game_id: 1,
+            state: ReplayState::Watch,
+            download_progress: 100,
+        };
+
+        notify(&downloader.waiters, &metadata);
+        assert_eq!(receiver.recv().await, Some(metadata.clone()));
+
+        // Downloads that stopped waiting are forgotten
+        drop(receiver);
+        notify(&downloader.waiters, &metadata);
+        assert!(downloader.waiters.lock().unwrap().is_empty());
+    }
+
+    #[cfg(feature = "ws")]
+    #[tokio::test]
+    async fn failed_download_stops_waiting() {
+        use super::ReplayDownloader;
+        use crate::rest::unreachable_client;
+
+        // Drop the listener, so the port refuses connections
+        let (_, client) = unreachable_client();
+        let downloader = ReplayDownloader::new(1);
+
+        let result = downloader.download(&client, 1, |_| {}).await;
+        assert!(result.is_err());
+        assert!(downloader.waiters.lock().unwrap().is_empty());
+    }
+}
@@ -0,0 +1,237 @@
+//! A builder for requests that need more than an endpoint and a body, such as query parameters or headers
+
+use super::body::Multipart;
+use super::LcuClient;
+use crate::utils::requests::{encode_query_value, MIME};
+use crate::Error;
+use http_body_util::Full;
+use hyper::body::Bytes;
+use hyper::header::{HeaderMap, HeaderName, HeaderValue};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::fmt::{Display, Write};
+use std::time::Duration;
+
+/// A request being built, created with [`LcuClient::request`], and sent with [`RequestBuilder::send`]
+///
+/// Any error while building, such as an invalid header, is returned when the request is sent
+///
+/// ```no_run
+/// use irelia::rest::LcuClient;
+///
+/// # async fn run(client: LcuClient) -> Result<(), irelia::Error> {
+/// let matches: serde_json::Value = client
+///     .request("GET", "/lol-match-history/v1/products/lol/current-summoner/matches")
+///     .query("begIndex", 0)
+///     .query("endIndex", 20)
+///     .send()
+///     .await?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct RequestBuilder<'a> {
+    client: &'a LcuClient,
+    method: String,
+    endpoint: String,
+    headers: HeaderMap,
+    body: Option<(HeaderValue, Bytes)>,
+    timeout: Option<Duration>,
+    error: Option<Error>,
+}
+
+impl RequestBuilder<'_> {
+    #[must_use]
+    /// Appends `key=value` to the query, both are percent encoded
+    pub fn query(mut self, key: &str, value: impl Display) -> Self {
+        let separator = if self.endpoint.contains('?') {
+            '&'
+        } else {
+            '?'
+        };
+
+        let _ = write!(
+            self.endpoint,
+            "{separator}{}={}",
+            encode_query_value(key),
+            encode_query_value(&value.to_string())
+        );
+
+        self
+    }
+
+    #[must_use]
+    /// Adds a header, these take priority over the request client's default headers
+    ///
+    /// `Content-Type`, `Accept`, and `Authorization` are always set by the client, and will replace these
+    pub fn header<K, V>(mut self, key: K, value: V) -> Self
+    where
+        HeaderName: TryFrom<K>,
+        <HeaderName as TryFrom<K>>::Error: Into<hyper::http::Error>,
+        HeaderValue: TryFrom<V>,
+        <HeaderValue as TryFrom<V>>::Error: Into<hyper::http::Error>,
+    {
+        let header = HeaderName::try_from(key)
+            .map_err(Into::into)
+            .and_then(|key| Ok((key, HeaderValue::try_from(value).map_err(Into::into)?)));
+
+        match header {
+            Ok((key, value)) => {
+                self.headers.append(key, value);
+            }
+            Err(err) => self.fail(err.into()),
+        }
+
+        self
+    }
+
+    #[must_use]
+    /// Sets the body, serialized as msgpack, the same as [`LcuClient::lcu_request`]
+    pub fn body<T: Serialize>(mut self, body: T) -> Self {
+        match rmp_serde::to_vec_named(&body) {
+            Ok(body) => self.body = Some((HeaderValue::from_static(MIME), body.into())),
+            Err(err) => self.fail(err.into()),
+        }
+
+        self
+    }
+
+    #[must_use]
+    /// Sets a body that's already encoded, `content_type` being its type, see [`LcuClient::request_raw`]
+    pub fn body_raw(mut self, content_type: HeaderValue, body: impl Into<Bytes>) -> Self {
+        self.body = Some((content_type, body.into()));
+        self
+    }
+
+    #[must_use]
+    /// Sets a `multipart/form-data` body, see [`Multipart`]
+    pub fn multipart(mut self, form: Multipart) -> Self {
+        match form.encode() {
+            Ok(body) => self.body = Some(body),
+            Err(err) => self.fail(err),
+        }
+
+        self
+    }
+
+    #[must_use]
+    /// Fails the request with [`Error::Timeout`] after `timeout`, instead of the timeout set on the client
+    pub const fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Sends the request, deserializing the response the same way as [`LcuClient::lcu_request`]
+    ///
+    /// # Errors
+    /// This will return an error if building the request failed, the LCU API is not running,
+    /// or the provided type is invalid
+    pub async fn send<R: DeserializeOwned>(self) -> Result<R, Error> {
+        let client = self.client;
+
+        let response = self
+            .send_inner()
+            .await
+            .and_then(|buf| Ok(rmp_serde::from_slice(&buf)?));
+
+        client.finish(response).await
+    }
+
+    /// Sends the request, returning the body as is, rather than deserializing it
+    ///
+    /// # Errors
+    /// This will return an error if building the request failed, or the LCU API is not running
+    pub async fn send_bytes(self) -> Result<Bytes, Error> {
+        let client = self.client;
+        let response = self.send_inner().await;
+        client.finish(response).await
+    }
+
+    async fn send_inner(self) -> Result<Bytes, Error> {
+        if let Some(err) = self.error {
+            return Err(err);
+        }
+
+        let client = self.client;
+
+        let (content_type, body) = match self.body {
+            Some((content_type, body)) => (content_type, Some(Full::new(body))),
+            None => (HeaderValue::from_static(MIME), None),
+        };
+
+        client
+            .retried(self.timeout.or(client.timeout), || {
+                client.request_client.bytes_request(
+                    client.url,
+                    &self.endpoint,
+                    &self.method,
+                    body.clone(),
+                    Some(&client.auth_header),
+                    content_type.clone(),
+                    self.headers.clone(),
+                )
+            })
+            .await
+    }
+
+    /// Keeps the first error, so it can be returned when the request is sent
+    fn fail(&mut self, err: Error) {
+        self.error.get_or_insert(err);
+    }
+}
+
+impl LcuClient {
+    #[must_use]
+    /// Starts building a request, for when query parameters, headers, or a non msgpack body are needed
+    pub fn request(&self, method: &str, endpoint: impl Into<String>) -> RequestBuilder<'_> {
+        RequestBuilder {
+            client: self,
+            method: method.to_string(),
+            endpoint: endpoint.into(),
+            headers: HeaderMap::new(),
+            body: None,
+            timeout: None,
+            error: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::rest::unreachable_client;
+    use crate::utils::requests::encode_query_value;
+    use crate::Error;
+
+    #[test]
+    fn test_encode_query_value() {
+        assert_eq!(encode_query_value("Faker"), "Faker");
+        assert_eq!(encode_query_value("Hide on bush"), "Hide%20on%20bush");
+        assert_eq!(encode_query_value("a+b&c=d"), "a%2Bb%26c%3Dd");
+        assert_eq!(encode_query_value("페이커"), "%ED%8E%98%EC%9D%B4%EC%BB%A4");
+        assert_eq!(
+            encode_query_value("Ünïcödé~_.-"),
+            "%C3%9Cn%C3%AFc%C3%B6d%C3%A9~_.-"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_request_builder() {
+        let (_, client) = unreachable_client();
+
+        let request = client
+            .request("GET", "/lol-match-history/v1/matches")
+            .query("begIndex", 0)
+            .query("name", "Hide on bush");
+        assert_eq!(
+            request.endpoint,
+            "/lol-match-history/v1/matches?begIndex=0&name=Hide%20on%20bush"
+        );
+
+        // Building errors are kept until the request is sent
+        let result = client
+            .request("GET", "/example")
+            .header("x-test", "line\nbreak")
+            .send::<serde_json::Value>()
+            .await;
+        assert!(matches!(result, Err(Error::HyperHttpError(_))));
+    }
+}
@@ -0,0 +1,181 @@
+//! Retrying requests that fail while the client is still starting up
+//!
+//! Right after logging in, the LCU answers with `503 Service Unavailable`, or an `RPC_ERROR`,
+//! until the plugin serving the endpoint has finished loading
+
+use crate::Error;
+use hyper::StatusCode;
+use std::fmt::{Debug, Formatter};
+use std::sync::Arc;
+use std::time::Duration;
+
+type RetryPredicate = Arc<dyn Fn(&Error) -> bool + Send + Sync>;
+
+/// How often, and how long apart, a failed request is sent again, set with [`super::LcuClientBuilder::retry`]
+///
+/// The wait between attempts starts at [`RetryPolicy::initial_backoff`], and is multiplied by
+/// [`RetryPolicy::multiplier`] after every attempt, up to [`RetryPolicy::max_backoff`].
+/// By default, only errors matching [`is_transient`] are retried
+///
+/// ```no_run
+/// use irelia::rest::{retry::RetryPolicy, LcuClient};
+/// use std::time::Duration;
+///
+/// let client = LcuClient::builder()
+///     .retry(
+///         RetryPolicy::new()
+///             .max_attempts(10)
+///             .initial_backoff(Duration::from_millis(500)),
+///     )
+///     .build()
+///     .unwrap();
+/// ```
+#[derive(Clone)]
+pub struct RetryPolicy {
+    max_attempts: u32,
+    initial_backoff: Duration,
+    max_backoff: Duration,
+    multiplier: u32,
+    retry_on: RetryPredicate,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 5,
+            initial_backoff: Duration::from_millis(250),
+            max_backoff: Duration::from_secs(5),
+            multiplier: 2,
+            retry_on: Arc::new(is_transient),
+        }
+    }
+}
+
+impl Debug for RetryPolicy {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RetryPolicy")
+            .field("max_attempts", &self.max_attempts)
+            .field("initial_backoff", &self.initial_backoff)
+            .field("max_backoff", &self.max_backoff)
+            .field("multiplier", &self.multiplier)
+            .finish_non_exhaustive()
+    }
+}
+
+impl RetryPolicy {
+    #[must_use]
+    /// Up to 5 attempts, waiting 250 milliseconds, doubling up to 5 seconds, between them
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    #[must_use]
+    /// How many times a request is sent in total, including the first, `1` never retries
+    pub fn max_attempts(mut self, max_attempts: u32) -> Self {
+        self.max_attempts = max_attempts.max(1);
+        self
+    }
+
+    #[must_use]
+    /// How long to wait before the first retry
+    pub fn initial_backoff(mut self, initial_backoff: Duration) -> Self {
+        self.initial_backoff = initial_backoff;
+        self
+    }
+
+    #[must_use]
+    /// The longest wait between two attempts
+    pub fn max_backoff(mut self, max_backoff: Duration) -> Self {
+        self.max_backoff = max_backoff;
+        self
+    }
+
+    #[must_use]
+    /// What the wait is multiplied by after every attempt, `1` waits the same amount every time
+    pub fn multiplier(mut self, multiplier: u32) -> Self {
+        self.multiplier = multiplier.max(1);
+        self
+    }
+
+    #[must_use]
+    /// Only retries errors `retry_on` returns `true` for, replacing [`is_transient`]
+    pub fn retry_on(mut self, retry_on: impl Fn(&Error) -> bool + Send + Sync + 'static) -> Self {
+        self.retry_on = Arc::new(retry_on);
+        self
+    }
+
+    #[must_use]
+    /// Whether a request that failed with `err` on attempt `attempt`, starting from `1`, should be sent again
+    pub fn should_retry(&self, attempt: u32, err: &Error) -> bool {
+        attempt < self.max_attempts && (self.retry_on)(err)
+    }
+
+    #[must_use]
+    /// How long to wait after attempt `attempt`, starting from `1`, before sending the request again
+    pub fn backoff(&self, attempt: u32) -> Duration {
+        let factor = self.multiplier.saturating_pow(attempt.saturating_sub(1));
+
+        self.initial_backoff
+            .saturating_mul(factor)
+            .min(self.max_backoff)
+    }
+}
+
+#[must_use]
+/// Whether `err` is one the LCU returns while it's still starting up, a `503`, or an `RPC_ERROR`
+pub fn is_transient(err: &Error) -> bool {
+    match err {
+        Error::LcuError(err) if err.error_code == "RPC_ERROR" => true,
+        _ => err.status() == Some(StatusCode::SERVICE_UNAVAILABLE),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{is_transient, RetryPolicy};
+    use crate::{Error, LcuError};
+    use hyper::StatusCode;
+    use std::time::Duration;
+
+    #[test]
+    fn test_backoff() {
+        let policy = RetryPolicy::new()
+            .initial_backoff(Duration::from_millis(100))
+            .max_backoff(Duration::from_secs(1));
+
+        assert_eq!(policy.backoff(1), Duration::from_millis(100));
+        assert_eq!(policy.backoff(2), Duration::from_millis(200));
+        assert_eq!(policy.backoff(4), Duration::from_millis(800));
+        assert_eq!(policy.backoff(5), Duration::from_secs(1));
+        assert_eq!(policy.backoff(u32::MAX), Duration::from_secs(1));
+
+        let policy = policy.multiplier(1);
+        assert_eq!(policy.backoff(10), Duration::from_millis(100));
+    }
+
+    #[test]
+    fn test_should_retry() {
+        let unavailable = Error::RequestError(StatusCode::SERVICE_UNAVAILABLE);
+        let not_found = Error::RequestError(StatusCode::NOT_FOUND);
+        let rpc_error = Error::LcuError(LcuError {
+            error_code: "RPC_ERROR".to_string(),
+            http_status: 500,
+            message: String::new(),
+            implementation_details: serde_json::Value::Null,
+        });
+
+        assert!(is_transient(&unavailable));
+        assert!(is_transient(&rpc_error));
+        assert!(!is_transient(&not_found));
+
+        let policy = RetryPolicy::new().max_attempts(3);
+        assert!(policy.should_retry(1, &unavailable));
+        assert!(policy.should_retry(2, &rpc_error));
+        assert!(!policy.should_retry(3, &unavailable));
+        assert!(!policy.should_retry(1, &not_found));
+
+        let policy = policy.retry_on(|err| matches!(err, Error::Timeout));
+        assert!(policy.should_retry(1, &Error::Timeout));
+        assert!(!policy.should_retry(1, &unavailable));
+    }
+}
@@ -0,0 +1,225 @@
+//! Typed wrappers around the Riot Client API, which launches and tracks every Riot game, not just League
+//!
+//! The Riot Client serves its API the same way the LCU does, so [`RiotClient`] sends its requests through an [`LcuClient`].
+//! TFT is part of League, so it's launched as [`LEAGUE_OF_LEGENDS`]
+
+use super::LcuClient;
+use crate::utils::process_info::{
+    get_running_riot_client, RiotClientConnection, CLIENT_PROCESS_NAME, RIOT_CLIENT_PROCESS_NAME,
+};
+use crate::{Error, RequestClient};
+use hyper::http::HeaderValue;
+use hyper::StatusCode;
+use serde_derive::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// The endpoint the installed products are served from
+pub const PRODUCTS_ENDPOINT: &str = "/product-launcher/v1/products";
+
+/// The endpoint the running products are served from
+pub const SESSIONS_ENDPOINT: &str = "/product-session/v1/external-sessions";
+
+/// The endpoint the logged in account is served from
+pub const AUTHORIZATION_ENDPOINT: &str = "/rso-auth/v1/authorization";
+
+/// The product ID of League of Legends, and TFT
+pub const LEAGUE_OF_LEGENDS: &str = "league_of_legends";
+
+/// The product ID of Valorant
+pub const VALORANT: &str = "valorant";
+
+/// The product ID of Legends of Runeterra
+pub const LEGENDS_OF_RUNETERRA: &str = "bacon";
+
+/// The patchline every product is released on
+pub const LIVE: &str = "live";
+
+/// A running product, as served by [`SESSIONS_ENDPOINT`]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProductSession {
+    /// Such as [`LEAGUE_OF_LEGENDS`]
+    pub product_id: String,
+    /// Such as `league_of_legends.live`
+    #[serde(default)]
+    pub patchline_full_name: String,
+    #[serde(default)]
+    pub version: String,
+    #[serde(default)]
+    pub launch_configuration: Option<LaunchConfiguration>,
+}
+
+/// How a running product was started
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LaunchConfiguration {
+    #[serde(default)]
+    pub arguments: Vec<String>,
+    #[serde(default)]
+    pub executable: String,
+    #[serde(default)]
+    pub locale: Option<String>,
+}
+
+/// The account the Riot Client is logged in to
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RsoAuthorization {
+    #[serde(default)]
+    pub current_account_id: u64,
+    /// Such as `NA1`
+    #[serde(default)]
+    pub current_platform_id: String,
+    /// The PUUID of the account
+    #[serde(default)]
+    pub subject: String,
+}
+
+/// A connection to the Riot Client API
+pub struct RiotClient {
+    client: LcuClient,
+}
+
+impl RiotClient {
+    /// Connects to the running Riot Client, see [`get_running_riot_client`]
+    ///
+    /// # Errors
+    /// This will return an error if neither the Riot Client or League client are running,
+    /// or their credentials could not be read
+    pub fn connect() -> Result<Self, Error> {
+        Self::connect_with_request_client(&RequestClient::new())
+    }
+
+    /// Connects to the running Riot Client, sending requests through `request_client`
+    ///
+    /// # Errors
+    /// This will return an error if neither the Riot Client or League client are running,
+    /// or their credentials could not be read
+    pub fn connect_with_request_client(request_client: &RequestClient) -> Result<Self, Error> {
+        let connection = get_running_riot_client(RIOT_CLIENT_PROCESS_NAME, CLIENT_PROCESS_NAME)?;
+        Self::new_with_connection(&connection, request_client)
+    }
+
+    /// Connects to the Riot Client that `connection` points to
+    ///
+    /// # Errors
+    /// This will return an error if the auth header in `connection` is not a valid header
+    pub fn new_with_connection(
+        connection: &RiotClientConnection,
+        request_client: &RequestClient,
+    ) -> Result<Self, Error> {
+        let auth_header = HeaderValue::from_str(connection.auth_header())?;
+
+        Ok(Self::new_with_client(
+            LcuClient::new_with_credentials_with_request_client(
+                connection.addr(),
+                auth_header,
+                request_client,
+            ),
+        ))
+    }
+
+    #[must_use]
+    /// Sends requests through `client`, which has to be connected to the Riot Client rather than the LCU
+    pub const fn new_with_client(client: LcuClient) -> Self {
+        Self { client }
+    }
+
+    #[must_use]
+    /// The client requests are sent through, for endpoints without a typed wrapper
+    pub const fn client(&self) -> &LcuClient {
+        &self.client
+    }
+
+    /// Gets the IDs of the installed products, such as [`LEAGUE_OF_LEGENDS`]
+    ///
+    /// # Errors
+    /// This will return an error if the Riot Client API is not running
+    pub async fn products(&self) -> Result<Vec<String>, Error> {
+        self.client.get(PRODUCTS_ENDPOINT).await
+    }
+
+    /// Launches `product_id` on `patchline`, which is usually [`LIVE`]
+    ///
+    /// # Errors
+    /// This will return an error if the Riot Client API is not running, or the product is not installed
+    pub async fn launch(&self, product_id: &str, patchline: &str) -> Result<(), Error> {
+        let endpoint = format!("{PRODUCTS_ENDPOINT}/{product_id}/patchlines/{patchline}");
+
+        // The response is empty when the product was launched
+        self.client
+            .lcu_request_empty(&endpoint, "POST", None::<()>)
+            .await
+    }
+
+    /// Gets every running product, keyed by their session ID
+    ///
+    /// # Errors
+    /// This will return an error if the Riot Client API is not running
+    pub async fn sessions(&self) -> Result<HashMap<String, ProductSession>, Error> {
+        self.client.get(SESSIONS_ENDPOINT).await
+    }
+
+    /// Whether `product_id` is running
+    ///
+    /// # Errors
+    /// This will return an error if the Riot Client API is not running
+    pub async fn is_running(&self, product_id: &str) -> Result<bool, Error> {
+        let sessions = self.sessions().await?;
+
+        Ok(sessions
+            .values()
+            .any(|session| session.product_id == product_id))
+    }
+
+    /// Gets the account the Riot Client is logged in to, `None` if it isn't logged in
+    ///
+    /// # Errors
+    /// This will return an error if the Riot Client API is not running
+    pub async fn authorization(&self) -> Result<Option<RsoAuthorization>, Error> {
+        match self.client.get(AUTHORIZATION_ENDPOINT).await {
+            Ok(authorization) => Ok(Some(authorization)),
+            Err(err) if err.status() == Some(StatusCode::NOT_FOUND) => Ok(None),
+            Err(err) => Err(err),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ProductSession, RiotClient, LEAGUE_OF_LEGENDS};
+    use std::collections::HashMap;
+
+    #[test]
+    fn sessions_deserialize() {
+        let sessions: HashMap<String, ProductSession> = serde_json::from_str(
+            r#"{
+                "host_app": {"productId": "riot_client", "version": "1.0"},
+                "1234": {
+                    "productId": "league_of_legends",
+                    "patchlineFullName": "league_of_legends.live",
+                    "launchConfiguration": {"arguments": ["--locale=en_US"], "executable": "LeagueClient.exe"}
+                }
+            }"#,
+        )
+        .unwrap();
+
+        let league = &sessions["1234"];
+        assert_eq!(league.product_id, LEAGUE_OF_LEGENDS);
+        assert_eq!(
+            league.launch_configuration.as_ref().unwrap().arguments,
+            ["--locale=en_US"]
+        );
+        assert_eq!(sessions["host_app"].launch_configuration, None);
+    }
+
+    #[ignore = "This is only needed for testing, and doesn't need to be run all the time"]
+    #[tokio::test]
+    async fn test_riot_client() {
+        let client = RiotClient::connect().unwrap();
+
+        println!("{:?}", client.products().await);
+        println!("{:?}", client.sessions().await);
+        println!("{:?}", client.authorization().await);
+    }
+}
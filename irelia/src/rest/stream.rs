@@ -0,0 +1,202 @@
+//! Responses that aren't msgpack, such as images from `/lol-game-data/assets`, read as raw bytes
+
+use super::LcuClient;
+use crate::utils::requests::status_error;
+use crate::Error;
+use futures_core::Stream;
+use http_body_util::{combinators::BoxBody, BodyExt};
+use hyper::body::{Body, Bytes};
+use std::pin::Pin;
+use std::task::{ready, Context, Poll};
+use tokio::io::{AsyncRead, ReadBuf};
+
+/// The body of a response, read as it arrives, created with [`LcuClient::get_stream`]
+///
+/// This is both a [`Stream`] of chunks, and an [`AsyncRead`], so it can be copied straight to a file
+/// with `tokio::io::copy`. Only one of the two should be used, as they read from the same body
+#[derive(Debug)]
+pub struct ResponseStream {
+    body: BoxBody<Bytes, hyper::Error>,
+    /// What's left of the last chunk, after a read that didn't have room for all of it
+    chunk: Bytes,
+}
+
+impl ResponseStream {
+    #[must_use]
+    /// The size of the body, if the LCU sent a `Content-Length`
+    pub fn content_length(&self) -> Option<u64> {
+        self.body.size_hint().exact()
+    }
+
+    fn poll_chunk(&mut self, cx: &mut Context<'_>) -> Poll<Option<Result<Bytes, Error>>> {
+        if !self.chunk.is_empty() {
+            return Poll::Ready(Some(Ok(std::mem::take(&mut self.chunk))));
+        }
+
+        loop {
+            let frame = match ready!(Pin::new(&mut self.body).poll_frame(cx)) {
+                Some(Ok(frame)) => frame,
+                Some(Err(err)) => return Poll::Ready(Some(Err(err.into()))),
+                None => return Poll::Ready(None),
+            };
+
+            // Trailers are skipped, only the data is part of the body, and empty data
+            // is skipped too, as a read of zero bytes would be taken as the end of the body
+            if let Ok(data) = frame.into_data() {
+                if !data.is_empty() {
+                    return Poll::Ready(Some(Ok(data)));
+                }
+            }
+        }
+    }
+}
+
+impl Stream for ResponseStream {
+    type Item = Result<Bytes, Error>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.get_mut().poll_chunk(cx)
+    }
+}
+
+impl AsyncRead for ResponseStream {
+    fn poll_read(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<std::io::Result<()>> {
+        let this = self.get_mut();
+
+        let mut chunk = match ready!(this.poll_chunk(cx)) {
+            Some(Ok(chunk)) => chunk,
+            Some(Err(err)) => return Poll::Ready(Err(std::io::Error::other(err))),
+            None => return Poll::Ready(Ok(())),
+        };
+
+        let len = chunk.len().min(buf.remaining());
+        buf.put_slice(&chunk.split_to(len));
+        this.chunk = chunk;
+
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl LcuClient {
+    /// Sends a get request to the LCU, returning the body as is, rather than deserializing it
+    ///
+    /// # Errors
+    /// This will return an error if the LCU API is not running, or the request fails
+    pub async fn get_bytes(&self, endpoint: impl AsRef<str> + Send) -> Result<Bytes, Error> {
+        let endpoint = endpoint.as_ref();
+        let cache = self.cache_for("GET");
+
+        if let Some(body) = cache.and_then(|cache| cache.get(endpoint)) {
+            return self.finish(Ok(body)).await;
+        }
+
+        let request = self.request_client.request_template(
+            self.url,
+            endpoint,
+            "GET",
+            None::<()>,
+            Some(&self.auth_header),
+        );
+
+        let response = self.timed(request).await.inspect(|body| {
+            if let Some(cache) = cache {
+                cache.insert(endpoint, body.clone());
+            }
+        });
+        self.finish(response).await
+    }
+
+    /// Sends a get request to the LCU, returning the body as a [`ResponseStream`], which is read as it arrives
+    ///
+    /// The client's timeout only applies until the response headers are received
+    ///
+    /// # Errors
+    /// This will return an error if the LCU API is not running, or the request fails
+    pub async fn get_stream(
+        &self,
+        endpoint: impl AsRef<str> + Send,
+    ) -> Result<ResponseStream, Error> {
+        let endpoint = endpoint.as_ref();
+
+        let request = async {
+            let response = self
+                .request_client
+                .raw_request_template(self.url, endpoint, "GET", None, Some(&self.auth_header))
+                .await?;
+
+            let status = response.status();
+
+            if status.is_success() {
+                return Ok(ResponseStream {
+                    body: response.into_body().boxed(),
+                    chunk: Bytes::new(),
+                });
+            }
+
+            let body = response.collect().await?.to_bytes();
+            Err(status_error(status, &body))
+        };
+
+        let response = self.timed(request).await;
+        self.finish(response).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ResponseStream;
+    use crate::rest::LcuClient;
+    use http_body_util::{BodyExt, StreamBody};
+    use hyper::body::{Bytes, Frame};
+    use std::pin::Pin;
+    use tokio::io::{AsyncRead, ReadBuf};
+
+    #[tokio::test]
+    async fn empty_frames_skipped() {
+        let frames =
+            ["ab", "", "cd", ""].map(|data| Ok::<_, hyper::Error>(Frame::data(Bytes::from(data))));
+        let mut stream = ResponseStream {
+            body: StreamBody::new(futures_util::stream::iter(frames)).boxed(),
+            chunk: Bytes::new(),
+        };
+
+        let mut body = Vec::new();
+        loop {
+            let mut data = [0; 8];
+            let mut buf = ReadBuf::new(&mut data);
+            std::future::poll_fn(|cx| Pin::new(&mut stream).poll_read(cx, &mut buf))
+                .await
+                .unwrap();
+
+            if buf.filled().is_empty() {
+                break;
+            }
+            body.extend_from_slice(buf.filled());
+        }
+
+        assert_eq!(body, b"abcd");
+    }
+
+    #[ignore = "This is only needed for testing, and doesn't need to be run all the time"]
+    #[tokio::test]
+    async fn test_get_stream() {
+        use futures_util::StreamExt;
+
+        let client = LcuClient::connect().unwrap();
+        let endpoint = "/lol-game-data/assets/v1/profile-icons/1.jpg";
+
+        let bytes = client.get_bytes(endpoint).await.unwrap();
+
+        let mut stream = client.get_stream(endpoint).await.unwrap();
+        let mut streamed = Vec::new();
+        while let Some(chunk) = stream.next().await {
+            streamed.extend_from_slice(&chunk.unwrap());
+        }
+
+        assert_eq!(bytes, streamed);
+    }
+}
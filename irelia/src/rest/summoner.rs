@@ -0,0 +1,152 @@
+//! Typed wrappers around the `/lol-summoner/v1` endpoints
+//!
+//! The logged in summoner is cached by [`LcuClient::current_summoner`], since it only changes when the client restarts
+
+use super::LcuClient;
+use crate::utils::requests::encode_query_value;
+use crate::Error;
+use hyper::StatusCode;
+use serde_derive::{Deserialize, Serialize};
+
+/// The endpoint the logged in summoner is served from
+pub const CURRENT_SUMMONER_ENDPOINT: &str = "/lol-summoner/v1/current-summoner";
+
+/// A summoner, as returned by the summoner endpoints
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Summoner {
+    pub account_id: u64,
+    pub summoner_id: u64,
+    pub puuid: String,
+    /// The name shown in the client, this is the riot ID game name on newer accounts
+    #[serde(default)]
+    pub display_name: String,
+    #[serde(default)]
+    pub game_name: String,
+    #[serde(default)]
+    pub tag_line: String,
+    #[serde(default)]
+    pub internal_name: String,
+    #[serde(default)]
+    pub profile_icon_id: i32,
+    #[serde(default)]
+    pub summoner_level: u32,
+    #[serde(default)]
+    pub percent_complete_for_next_level: u32,
+}
+
+impl LcuClient {
+    /// Gets the logged in summoner
+    ///
+    /// This is only fetched once, and is cached until the client is reconnected
+    ///
+    /// # Errors
+    /// This will return an error if the LCU API is not running, or no one is logged in
+    pub async fn current_summoner(&self) -> Result<Summoner, Error> {
+        if let Some(summoner) = self.current_summoner.get() {
+            return Ok(summoner.clone());
+        }
+
+        let summoner = self.get(CURRENT_SUMMONER_ENDPOINT).await?;
+        Ok(self.current_summoner.get_or_init(|| summoner).clone())
+    }
+
+    /// Looks up a summoner by their summoner ID, returning `None` if no summoner has that ID
+    ///
+    /// # Errors
+    /// This will return an error if the LCU API is not running
+    pub async fn summoner_by_id(&self, summoner_id: u64) -> Result<Option<Summoner>, Error> {
+        self.optional_summoner(&format!("/lol-summoner/v1/summoners/{summoner_id}"))
+            .await
+    }
+
+    /// Looks up a summoner by their PUUID, returning `None` if no summoner has that PUUID
+    ///
+    /// # Errors
+    /// This will return an error if the LCU API is not running
+    pub async fn summoner_by_puuid(&self, puuid: &str) -> Result<Option<Summoner>, Error> {
+        let endpoint = format!(
+            "/lol-summoner/v2/summoners/puuid/{}",
+            encode_query_value(puuid)
+        );
+
+        self.optional_summoner(&endpoint).await
+    }
+
+    /// Looks up a summoner by name, returning `None` if no summoner has that name
+    ///
+    /// `name` is encoded by this function, so it should be passed exactly as shown in the client,
+    /// spaces, `+`, and non-ASCII characters included
+    ///
+    /// # Errors
+    /// This will return an error if the LCU API is not running
+    pub async fn summoner_by_name(&self, name: &str) -> Result<Option<Summoner>, Error> {
+        let endpoint = format!(
+            "/lol-summoner/v1/summoners?name={}",
+            encode_query_value(name)
+        );
+
+        self.optional_summoner(&endpoint).await
+    }
+
+    /// Gets the summoner at `endpoint`, which responds with not found, or an empty body, if there is none
+    async fn optional_summoner(&self, endpoint: &str) -> Result<Option<Summoner>, Error> {
+        let request = self.request_client.request_template(
+            self.url,
+            endpoint,
+            "GET",
+            None::<()>,
+            Some(&self.auth_header),
+        );
+
+        let summoner = match self.timed(request).await {
+            Ok(body) if body.is_empty() => Ok(None),
+            Ok(body) => rmp_serde::from_slice(&body).map_err(Error::from),
+            Err(err) if err.status() == Some(StatusCode::NOT_FOUND) => Ok(None),
+            Err(err) => Err(err),
+        };
+
+        self.finish(summoner).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Summoner;
+    use crate::rest::{unreachable_client, LcuClient};
+    use crate::Error;
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn test_current_summoner_cache() {
+        // Nothing is ever accepted, so anything that isn't cached times out
+        let (_listener, mut client) = unreachable_client();
+        let (addr, auth) = (client.url(), client.auth_header().clone());
+        client.timeout = Some(Duration::from_millis(20));
+
+        let summoner: Summoner = serde_json::from_str(
+            r#"{"accountId": 1, "summonerId": 2, "puuid": "abc", "gameName": "Sylv", "tagLine": "NA1"}"#,
+        )
+        .unwrap();
+        client.current_summoner.set(summoner.clone()).unwrap();
+
+        assert_eq!(client.current_summoner().await.unwrap(), summoner);
+
+        // The logged in summoner can be different after reconnecting, so it's fetched again
+        client.reconnect_with_credentials(addr, auth);
+        assert!(matches!(
+            client.current_summoner().await,
+            Err(Error::Timeout)
+        ));
+    }
+
+    #[ignore = "This is only needed for testing, and doesn't need to be run all the time"]
+    #[tokio::test]
+    async fn test_summoner_lookup() {
+        let client = LcuClient::connect().unwrap();
+        let summoner = client.current_summoner().await.unwrap();
+
+        println!("{:?}", client.summoner_by_id(summoner.summoner_id).await);
+        println!("{:?}", client.summoner_by_puuid(&summoner.puuid).await);
+    }
+}
@@ -0,0 +1,237 @@
+//! What version of the client is running, and which endpoints it has, for gating features on the patch
+//!
+//! Endpoints are added and removed between patches, so anything relying on a newer endpoint can check first
+
+use super::LcuClient;
+use crate::Error;
+use serde_derive::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+/// The build of the running client, from `/system/v1/builds`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SystemBuild {
+    /// Such as `LoL-releases/14.20`
+    #[serde(default)]
+    pub branch: String,
+    /// The full version of the client, such as `14.20.622.5356`
+    pub version: String,
+    /// The game's patch, such as `14.20`
+    #[serde(default)]
+    pub game_branch: String,
+    #[serde(default)]
+    pub game_branch_full: String,
+}
+
+impl SystemBuild {
+    /// Parses [`SystemBuild::version`]
+    ///
+    /// # Errors
+    /// This will return an error if the version is not made of numbers separated by `.`
+    pub fn client_version(&self) -> Result<ClientVersion, ParseVersionError> {
+        self.version.parse()
+    }
+}
+
+/// A client version, such as `14.20.622.5356`, compared number by number
+///
+/// Missing parts are treated as `0`, so `14.20` is equal to `14.20.0.0`, and ordered before `14.20.622`.
+/// Anything after a `+` or `-`, such as the branch in `14.20.622.5356+branch.releases-14-20`, is ignored
+///
+/// ```
+/// use irelia::rest::system::ClientVersion;
+///
+/// let version: ClientVersion = "14.20.622.5356".parse().unwrap();
+/// assert!(version >= ClientVersion::new(14, 20));
+/// assert!(version < "14.21".parse().unwrap());
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ClientVersion {
+    pub major: u32,
+    pub minor: u32,
+    pub patch: u32,
+    pub build: u32,
+}
+
+impl ClientVersion {
+    #[must_use]
+    /// A version for a patch, such as `ClientVersion::new(14, 20)`, the patch and build are `0`
+    pub const fn new(major: u32, minor: u32) -> Self {
+        Self {
+            major,
+            minor,
+            patch: 0,
+            build: 0,
+        }
+    }
+}
+
+impl Display for ClientVersion {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}.{}.{}.{}",
+            self.major, self.minor, self.patch, self.build
+        )
+    }
+}
+
+/// The version was not made of up to four numbers separated by `.`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseVersionError(String);
+
+impl Display for ParseVersionError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Invalid client version: {}", self.0)
+    }
+}
+
+impl std::error::Error for ParseVersionError {}
+
+impl FromStr for ClientVersion {
+    type Err = ParseVersionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || ParseVersionError(s.to_string());
+
+        // The client appends the branch it was built from, which isn't part of the version
+        let numbers = s.trim().split(['+', '-']).next().unwrap_or_default();
+
+        let mut parts = [0; 4];
+        let mut split = numbers.split('.');
+
+        for part in &mut parts {
+            match split.next() {
+                Some(value) => *part = value.parse().map_err(|_| invalid())?,
+                None => break,
+            }
+        }
+
+        if split.next().is_some() {
+            return Err(invalid());
+        }
+
+        let [major, minor, patch, build] = parts;
+        Ok(Self {
+            major,
+            minor,
+            patch,
+            build,
+        })
+    }
+}
+
+/// Every function, type, and event the client has, from `/help`, mapped to their descriptions
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HelpSchema {
+    #[serde(default)]
+    pub functions: HashMap<String, String>,
+    #[serde(default)]
+    pub types: HashMap<String, String>,
+    #[serde(default)]
+    pub events: HashMap<String, String>,
+}
+
+impl HelpSchema {
+    #[must_use]
+    /// Whether the client has a function, such as `GetLolSummonerV1CurrentSummoner`
+    pub fn has_function(&self, name: &str) -> bool {
+        self.functions.contains_key(name)
+    }
+
+    #[must_use]
+    /// Whether the client has a type, such as `LolSummonerSummoner`
+    pub fn has_type(&self, name: &str) -> bool {
+        self.types.contains_key(name)
+    }
+
+    #[must_use]
+    /// Whether the client has an event, such as `OnJsonApiEvent_lol-gameflow_v1_gameflow-phase`
+    pub fn has_event(&self, name: &str) -> bool {
+        self.events.contains_key(name)
+    }
+}
+
+impl LcuClient {
+    /// Gets the build of the running client, see [`SystemBuild::client_version`] to compare it
+    ///
+    /// # Errors
+    /// This will return an error if the LCU API is not running
+    pub async fn system_build(&self) -> Result<SystemBuild, Error> {
+        self.get("/system/v1/builds").await
+    }
+
+    /// Gets every function, type, and event the running client has, unlike `rest::schema` this comes from the client itself
+    ///
+    /// # Errors
+    /// This will return an error if the LCU API is not running
+    pub async fn help(&self) -> Result<HelpSchema, Error> {
+        self.get("/help").await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ClientVersion, HelpSchema, SystemBuild};
+
+    #[test]
+    fn test_client_version() {
+        let version: ClientVersion = "14.20.622.5356".parse().unwrap();
+        assert_eq!(version.major, 14);
+        assert_eq!(version.build, 5356);
+        assert_eq!(version.to_string(), "14.20.622.5356");
+
+        assert!(version > ClientVersion::new(14, 20));
+        assert!(version < ClientVersion::new(14, 21));
+        assert!(version > "9.24".parse().unwrap());
+        assert_eq!(
+            "14.20".parse::<ClientVersion>(),
+            Ok(ClientVersion::new(14, 20))
+        );
+
+        assert_eq!(
+            "14.20.622.5356+branch.releases-14-20.code.public.content.release"
+                .parse::<ClientVersion>(),
+            Ok(version)
+        );
+        assert_eq!(
+            "14.20-pbe".parse::<ClientVersion>(),
+            Ok(ClientVersion::new(14, 20))
+        );
+
+        assert!("".parse::<ClientVersion>().is_err());
+        assert!("+14.20".parse::<ClientVersion>().is_err());
+        assert!("14.x".parse::<ClientVersion>().is_err());
+        assert!("1.2.3.4.5".parse::<ClientVersion>().is_err());
+    }
+
+    #[test]
+    fn test_deserialize() {
+        let build: SystemBuild = serde_json::from_str(
+            r#"{"branch":"LoL-releases/14.20","gameBranch":"14.20","gameBranchFull":"14.20.622.5356","version":"14.20.622.5356"}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            build.client_version().unwrap(),
+            "14.20.622.5356".parse().unwrap()
+        );
+
+        let help: HelpSchema = serde_json::from_str(
+            r#"{"events":{"OnJsonApiEvent":""},"functions":{"GetLolSummonerV1CurrentSummoner":"Gets the current summoner"},"types":{}}"#,
+        )
+        .unwrap();
+        assert!(help.has_function("GetLolSummonerV1CurrentSummoner"));
+        assert!(help.has_event("OnJsonApiEvent"));
+        assert!(!help.has_type("LolSummonerSummoner"));
+    }
+
+    #[ignore = "This is only needed for testing, and doesn't need to be run all the time"]
+    #[tokio::test]
+    async fn test_system_build() {
+        let client = crate::rest::LcuClient::connect().unwrap();
+        let build = client.system_build().await.unwrap();
+        println!("{build:?} {}", build.client_version().unwrap());
+    }
+}
@@ -0,0 +1,182 @@
+//! Helpers for Teamfight Tactics, which shares the LCU with League, but has its own queues and ranks
+//!
+//! TFT lobbies are created like any other, with the queue IDs in [`TftQueue`].
+//! Hyper Roll is rated rather than ranked, so its rank is a [`RatedTier`] and a rating, rather than a tier and division
+
+use super::lobby::Lobby;
+use super::ranked::{RankedQueueStats, RatedTier};
+use super::LcuClient;
+use crate::Error;
+use serde_derive::{Deserialize, Serialize};
+
+/// The queue type of ranked TFT
+pub const RANKED_TFT: &str = "RANKED_TFT";
+
+/// The queue type of Hyper Roll
+pub const RANKED_TFT_TURBO: &str = "RANKED_TFT_TURBO";
+
+/// The queue type of ranked Double Up
+pub const RANKED_TFT_DOUBLE_UP: &str = "RANKED_TFT_DOUBLE_UP";
+
+/// The endpoint the Treasure Realms banners are served from
+pub const TROVES_BANNERS_ENDPOINT: &str = "/lol-tft-troves/v1/banners";
+
+/// A TFT queue that a lobby can be created for
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TftQueue {
+    Normal,
+    Ranked,
+    HyperRoll,
+    DoubleUp,
+    Tutorial,
+}
+
+impl TftQueue {
+    #[must_use]
+    /// The ID of the queue, as passed to [`LcuClient::create_lobby`]
+    pub const fn queue_id(self) -> i32 {
+        match self {
+            Self::Normal => 1090,
+            Self::Ranked => 1100,
+            Self::Tutorial => 1110,
+            Self::HyperRoll => 1130,
+            Self::DoubleUp => 1160,
+        }
+    }
+
+    #[must_use]
+    /// The queue with `queue_id`, `None` if it's not a TFT queue
+    pub const fn from_queue_id(queue_id: i32) -> Option<Self> {
+        match queue_id {
+            1090 => Some(Self::Normal),
+            1100 => Some(Self::Ranked),
+            1110 => Some(Self::Tutorial),
+            1130 => Some(Self::HyperRoll),
+            1160 => Some(Self::DoubleUp),
+            _ => None,
+        }
+    }
+}
+
+/// A player's Hyper Roll rank
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct HyperRollRating {
+    pub tier: RatedTier,
+    pub rating: i32,
+}
+
+impl From<&RankedQueueStats> for HyperRollRating {
+    fn from(stats: &RankedQueueStats) -> Self {
+        Self {
+            tier: stats.rated_tier,
+            rating: stats.rated_rating,
+        }
+    }
+}
+
+/// A Treasure Realms banner, which pulls from a pool of cosmetics
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TrovesBanner {
+    pub id: String,
+    #[serde(default)]
+    pub name: String,
+    /// The ID of the pool the banner pulls from
+    #[serde(default)]
+    pub pity_counter_id: String,
+    /// How many pulls are left until the banner's guaranteed reward
+    #[serde(default)]
+    pub pity_threshold: u32,
+    /// Such as `2024-01-01T00:00:00Z`
+    #[serde(default)]
+    pub start_date: String,
+    #[serde(default)]
+    pub end_date: String,
+    #[serde(default)]
+    pub is_collectible: bool,
+}
+
+impl LcuClient {
+    /// Creates a lobby for `queue`, leaving the current one
+    ///
+    /// # Errors
+    /// This will return an error if the LCU API is not running, or the queue is not available
+    pub async fn create_tft_lobby(&self, queue: TftQueue) -> Result<Lobby, Error> {
+        self.create_lobby(queue.queue_id()).await
+    }
+
+    /// Gets the ranked TFT stats of the player with `puuid`, `None` if the player has no entry for it
+    ///
+    /// # Errors
+    /// This will return an error if the LCU API is not running, or there is no player with `puuid`
+    pub async fn tft_ranked_stats(&self, puuid: &str) -> Result<Option<RankedQueueStats>, Error> {
+        self.ranked_queue_stats(puuid, RANKED_TFT).await
+    }
+
+    /// Gets the Hyper Roll rank of the player with `puuid`, `None` if the player has no entry for it
+    ///
+    /// # Errors
+    /// This will return an error if the LCU API is not running, or there is no player with `puuid`
+    pub async fn hyper_roll_rating(&self, puuid: &str) -> Result<Option<HyperRollRating>, Error> {
+        let stats = self.ranked_queue_stats(puuid, RANKED_TFT_TURBO).await?;
+        Ok(stats.as_ref().map(HyperRollRating::from))
+    }
+
+    /// Gets the Treasure Realms banners that are active
+    ///
+    /// # Errors
+    /// This will return an error if the LCU API is not running
+    pub async fn troves_banners(&self) -> Result<Vec<TrovesBanner>, Error> {
+        self.get(TROVES_BANNERS_ENDPOINT).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{HyperRollRating, TftQueue, RANKED_TFT_TURBO};
+    use crate::rest::ranked::{RankedStats, RatedTier};
+    use crate::rest::LcuClient;
+
+    #[test]
+    fn queue_ids() {
+        for queue in [
+            TftQueue::Normal,
+            TftQueue::Ranked,
+            TftQueue::HyperRoll,
+            TftQueue::DoubleUp,
+            TftQueue::Tutorial,
+        ] {
+            assert_eq!(TftQueue::from_queue_id(queue.queue_id()), Some(queue));
+        }
+
+        assert_eq!(TftQueue::from_queue_id(420), None);
+    }
+
+    #[test]
+    fn hyper_roll() {
+        let stats: RankedStats = serde_json::from_str(
+            r#"{
+                "queues": [
+                    {"queueType": "RANKED_TFT_TURBO", "tier": "NONE", "ratedTier": "PURPLE", "ratedRating": 3400},
+                    {"queueType": "RANKED_TFT", "tier": "GOLD", "division": "I"}
+                ]
+            }"#,
+        )
+        .unwrap();
+
+        let rating = HyperRollRating::from(stats.queue(RANKED_TFT_TURBO).unwrap());
+        assert_eq!(rating.tier, RatedTier::Purple);
+        assert_eq!(rating.rating, 3400);
+        assert!(RatedTier::Orange > RatedTier::Purple);
+    }
+
+    #[ignore = "This is only needed for testing, and doesn't need to be run all the time"]
+    #[tokio::test]
+    async fn test_tft() {
+        let client = LcuClient::connect().unwrap();
+        let summoner = client.current_summoner().await.unwrap();
+
+        println!("{:?}", client.hyper_roll_rating(&summoner.puuid).await);
+        println!("{:?}", client.troves_banners().await);
+    }
+}
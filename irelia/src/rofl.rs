@@ -0,0 +1,611 @@
+//! Parser for `.rofl` replay files, as downloaded by the client, see [`crate::rest::replays`]
+//!
+//! A `.rofl` file starts with a fixed size header, followed by the game's metadata as JSON,
+//! and then the payload, an index of chunks and keyframes followed by their data.
+//! The chunks themselves are encrypted with the key in the payload header, and are exposed as is
+//!
+//! Replays recorded since the format was changed in 2024 only keep the metadata, at the end of the file,
+//! so only [`Rofl::metadata`] is available for them
+
+use serde_derive::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::fmt::{Display, Formatter};
+use std::path::Path;
+use std::time::Duration;
+
+/// The magic bytes the first version of the format starts with
+const MAGIC: &[u8; 6] = b"RIOT\0\0";
+
+/// The magic bytes the newer version of the format starts with
+const MAGIC_V2: &[u8; 4] = b"RIOT";
+
+/// Length of the signature that comes right after the magic bytes
+const SIGNATURE_LEN: usize = 256;
+
+/// Length of the header, from the start of the file to the end of the offsets
+const HEADER_LEN: usize = MAGIC.len() + SIGNATURE_LEN + 26;
+
+/// Length of each entry in the chunk index
+const CHUNK_HEADER_LEN: usize = 17;
+
+#[derive(Debug)]
+/// Errors that can happen while reading a `.rofl` file
+pub enum Error {
+    Io(std::io::Error),
+    /// The file does not start with the magic bytes of either version of the format
+    InvalidMagic,
+    /// The file ended before a section it points to, such as after a download was cut short
+    Truncated,
+    /// The metadata, or the stats within it, were not valid JSON
+    Json(serde_json::Error),
+    /// The encryption key was not valid UTF-8
+    Utf8(std::str::Utf8Error),
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Io(e) => e.fmt(f),
+            Self::InvalidMagic => f.write_str("The file is not a rofl file"),
+            Self::Truncated => f.write_str("The rofl file ended before it was expected to"),
+            Self::Json(e) => e.fmt(f),
+            Self::Utf8(e) => e.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl From<std::io::Error> for Error {
+    fn from(value: std::io::Error) -> Self {
+        Self::Io(value)
+    }
+}
+
+impl From<serde_json::Error> for Error {
+    fn from(value: serde_json::Error) -> Self {
+        Self::Json(value)
+    }
+}
+
+impl From<std::str::Utf8Error> for Error {
+    fn from(value: std::str::Utf8Error) -> Self {
+        Self::Utf8(value)
+    }
+}
+
+/// A parsed `.rofl` file
+///
+/// ```no_run
+/// use irelia::rofl::Rofl;
+///
+/// let rofl = Rofl::open("EUW1-1234567890.rofl").unwrap();
+///
+/// println!("Patch {}", rofl.metadata().game_version);
+/// for player in rofl.metadata().players() {
+///     println!("{:?} played {:?}", player.name(), player.champion());
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct Rofl {
+    data: Vec<u8>,
+    header: Option<Header>,
+    metadata: Metadata,
+    payload: Option<PayloadHeader>,
+    chunks: Vec<Chunk>,
+}
+
+impl Rofl {
+    /// Reads and parses the file at `path`
+    ///
+    /// # Errors
+    /// This will return an error if the file can't be read, or is not a valid `.rofl` file
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, Error> {
+        Self::parse(std::fs::read(path)?)
+    }
+
+    /// Parses a whole `.rofl` file
+    ///
+    /// # Errors
+    /// This will return an error if `data` is not a valid `.rofl` file
+    pub fn parse(data: Vec<u8>) -> Result<Self, Error> {
+        if !data.starts_with(MAGIC) {
+            return Self::parse_v2(data);
+        }
+
+        let header = Header::parse(&data)?;
+        let metadata = Metadata::parse(section(
+            &data,
+            header.metadata_offset,
+            header.metadata_length,
+        )?)?;
+
+        let payload = PayloadHeader::parse(section(
+            &data,
+            header.payload_header_offset,
+            header.payload_header_length,
+        )?)?;
+
+        let count = payload.chunk_count as usize + payload.keyframe_count as usize;
+        let index = section(
+            &data,
+            header.payload_offset,
+            u32::try_from(count * CHUNK_HEADER_LEN).map_err(|_| Error::Truncated)?,
+        )?;
+
+        let chunks = index
+            .chunks_exact(CHUNK_HEADER_LEN)
+            .map(Chunk::parse)
+            .collect::<Result<_, _>>()?;
+
+        Ok(Self {
+            data,
+            header: Some(header),
+            metadata,
+            payload: Some(payload),
+            chunks,
+        })
+    }
+
+    /// The newer format only has the metadata, at the end of the file, followed by its length
+    fn parse_v2(data: Vec<u8>) -> Result<Self, Error> {
+        if !data.starts_with(MAGIC_V2) {
+            return Err(Error::InvalidMagic);
+        }
+
+        let (rest, length) = data.split_last_chunk::<4>().ok_or(Error::Truncated)?;
+        let length = u32::from_le_bytes(*length) as usize;
+        let start = rest.len().checked_sub(length).ok_or(Error::Truncated)?;
+        let metadata = Metadata::parse(&rest[start..])?;
+
+        Ok(Self {
+            data,
+            header: None,
+            metadata,
+            payload: None,
+            chunks: Vec::new(),
+        })
+    }
+
+    #[must_use]
+    /// The header of the file, `None` for the newer format
+    pub const fn header(&self) -> Option<&Header> {
+        self.header.as_ref()
+    }
+
+    #[must_use]
+    /// The metadata of the game, such as its length and the stats of every player
+    pub const fn metadata(&self) -> &Metadata {
+        &self.metadata
+    }
+
+    #[must_use]
+    /// The payload header, such as the game ID and the encryption key, `None` for the newer format
+    pub const fn payload(&self) -> Option<&PayloadHeader> {
+        self.payload.as_ref()
+    }
+
+    #[must_use]
+    /// Every chunk and keyframe in the file, in the order they're stored in
+    pub fn chunks(&self) -> &[Chunk] {
+        &self.chunks
+    }
+
+    /// Only the keyframes, which hold the whole state of the game at a point in time
+    pub fn keyframes(&self) -> impl Iterator<Item = &Chunk> {
+        self.chunks
+            .iter()
+            .filter(|chunk| chunk.kind == ChunkKind::KeyFrame)
+    }
+
+    #[must_use]
+    /// The encrypted data of `chunk`, or `None` if it's outside the file
+    pub fn chunk_data(&self, chunk: &Chunk) -> Option<&[u8]> {
+        let header = self.header.as_ref()?;
+        let payload = self.payload.as_ref()?;
+
+        // Offsets are relative to the end of the chunk index
+        let start = header.payload_offset as usize
+            + (payload.chunk_count as usize + payload.keyframe_count as usize) * CHUNK_HEADER_LEN
+            + chunk.offset as usize;
+
+        self.data.get(start..start + chunk.length as usize)
+    }
+
+    #[must_use]
+    /// The whole file, as it was read
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.data
+    }
+}
+
+/// Returns `length` bytes from `offset`, or an error if they aren't all in `data`
+fn section(data: &[u8], offset: u32, length: u32) -> Result<&[u8], Error> {
+    let start = offset as usize;
+    data.get(start..start + length as usize)
+        .ok_or(Error::Truncated)
+}
+
+/// Reads little endian integers one after another
+struct Reader<'a>(&'a [u8]);
+
+impl<'a> Reader<'a> {
+    fn take<const N: usize>(&mut self) -> Result<[u8; N], Error> {
+        let (bytes, rest) = self.0.split_first_chunk::<N>().ok_or(Error::Truncated)?;
+        self.0 = rest;
+        Ok(*bytes)
+    }
+
+    fn bytes(&mut self, len: usize) -> Result<&'a [u8], Error> {
+        if self.0.len() < len {
+            return Err(Error::Truncated);
+        }
+
+        let (bytes, rest) = self.0.split_at(len);
+        self.0 = rest;
+        Ok(bytes)
+    }
+
+    fn u8(&mut self) -> Result<u8, Error> {
+        self.take::<1>().map(|[byte]| byte)
+    }
+
+    fn u16(&mut self) -> Result<u16, Error> {
+        self.take().map(u16::from_le_bytes)
+    }
+
+    fn u32(&mut self) -> Result<u32, Error> {
+        self.take().map(u32::from_le_bytes)
+    }
+
+    fn u64(&mut self) -> Result<u64, Error> {
+        self.take().map(u64::from_le_bytes)
+    }
+}
+
+/// Where each section of the file is, all offsets are from the start of the file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Header {
+    pub signature: Box<[u8; SIGNATURE_LEN]>,
+    pub header_length: u16,
+    pub file_length: u32,
+    pub metadata_offset: u32,
+    pub metadata_length: u32,
+    pub payload_header_offset: u32,
+    pub payload_header_length: u32,
+    pub payload_offset: u32,
+}
+
+impl Header {
+    fn parse(data: &[u8]) -> Result<Self, Error> {
+        let mut reader = Reader(data.get(..HEADER_LEN).ok_or(Error::Truncated)?);
+        reader.take::<6>()?;
+
+        Ok(Self {
+            signature: Box::new(reader.take()?),
+            header_length: reader.u16()?,
+            file_length: reader.u32()?,
+            metadata_offset: reader.u32()?,
+            metadata_length: reader.u32()?,
+            payload_header_offset: reader.u32()?,
+            payload_header_length: reader.u32()?,
+            payload_offset: reader.u32()?,
+        })
+    }
+}
+
+/// Metadata of the game the replay is of
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Metadata {
+    /// How long the game lasted, in milliseconds
+    pub game_length: u64,
+    /// Such as `14.3.558.2873`
+    pub game_version: String,
+    #[serde(default)]
+    pub last_game_chunk_id: u32,
+    #[serde(default)]
+    pub last_key_frame_id: u32,
+    /// The stats of every player, this is a JSON array within a string
+    #[serde(rename = "statsJson")]
+    stats_json: String,
+    #[serde(skip)]
+    players: Vec<PlayerStats>,
+}
+
+impl Metadata {
+    fn parse(json: &[u8]) -> Result<Self, Error> {
+        let mut metadata: Self = serde_json::from_slice(json)?;
+        metadata.players = serde_json::from_str(&metadata.stats_json)?;
+        Ok(metadata)
+    }
+
+    #[must_use]
+    /// How long the game lasted
+    pub const fn duration(&self) -> Duration {
+        Duration::from_millis(self.game_length)
+    }
+
+    #[must_use]
+    /// The stats of every player, in the order of the scoreboard
+    pub fn players(&self) -> &[PlayerStats] {
+        &self.players
+    }
+}
+
+/// The end of game stats of a single player
+///
+/// Every value is stored as a string, and which stats exist changes between patches and game modes,
+/// so they're left untyped, with accessors for the common ones
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct PlayerStats {
+    pub stats: Map<String, Value>,
+}
+
+impl PlayerStats {
+    #[must_use]
+    /// Reads a stat as a string, such as `SKIN` or `WIN`
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.stats.get(name)?.as_str()
+    }
+
+    #[must_use]
+    /// Reads a numeric stat, such as `CHAMPIONS_KILLED`, returning `None` if it's missing or not a number
+    pub fn stat(&self, name: &str) -> Option<i64> {
+        match self.stats.get(name)? {
+            Value::String(value) => value.parse().ok(),
+            value => value.as_i64(),
+        }
+    }
+
+    #[must_use]
+    /// The player's name, their Riot ID game name in newer replays
+    pub fn name(&self) -> Option<&str> {
+        self.get("RIOT_ID_GAME_NAME").or_else(|| self.get("NAME"))
+    }
+
+    #[must_use]
+    /// The name of the champion played, such as `Ahri`
+    pub fn champion(&self) -> Option<&str> {
+        self.get("SKIN")
+    }
+
+    #[must_use]
+    /// `100` for blue side, and `200` for red side
+    pub fn team(&self) -> Option<i64> {
+        self.stat("TEAM")
+    }
+
+    #[must_use]
+    /// Whether the player's team won
+    pub fn won(&self) -> Option<bool> {
+        match self.get("WIN")? {
+            "Win" => Some(true),
+            "Fail" => Some(false),
+            _ => None,
+        }
+    }
+}
+
+/// Information about the chunks, and the key they're encrypted with
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PayloadHeader {
+    pub game_id: u64,
+    /// How long the game lasted, in milliseconds
+    pub game_length: u32,
+    pub keyframe_count: u32,
+    pub chunk_count: u32,
+    /// The last chunk of the loading screen
+    pub end_startup_chunk_id: u32,
+    /// The first chunk once the game started
+    pub start_game_chunk_id: u32,
+    /// Time between keyframes, in milliseconds
+    pub keyframe_interval: u32,
+    /// Base64 encoded key the chunks are encrypted with
+    pub encryption_key: String,
+}
+
+impl PayloadHeader {
+    fn parse(data: &[u8]) -> Result<Self, Error> {
+        let mut reader = Reader(data);
+
+        let game_id = reader.u64()?;
+        let game_length = reader.u32()?;
+        let keyframe_count = reader.u32()?;
+        let chunk_count = reader.u32()?;
+        let end_startup_chunk_id = reader.u32()?;
+        let start_game_chunk_id = reader.u32()?;
+        let keyframe_interval = reader.u32()?;
+
+        let key_length = reader.u16()?;
+        let encryption_key = std::str::from_utf8(reader.bytes(key_length.into())?)?.to_owned();
+
+        Ok(Self {
+            game_id,
+            game_length,
+            keyframe_count,
+            chunk_count,
+            end_startup_chunk_id,
+            start_game_chunk_id,
+            keyframe_interval,
+            encryption_key,
+        })
+    }
+}
+
+/// Whether an entry in the index is a chunk, or a keyframe
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChunkKind {
+    /// The events between two points in time
+    Chunk,
+    /// The whole state of the game at a point in time
+    KeyFrame,
+    /// A kind this version of the crate does not know about
+    Unknown(u8),
+}
+
+/// An entry in the chunk index, see [`Rofl::chunk_data`] for its data
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Chunk {
+    pub id: u32,
+    pub kind: ChunkKind,
+    pub length: u32,
+    /// The chunk that comes after this one, for keyframes, this is the first chunk after it
+    pub next_chunk_id: u32,
+    /// Offset of the data from the end of the chunk index
+    pub offset: u32,
+}
+
+impl Chunk {
+    fn parse(data: &[u8]) -> Result<Self, Error> {
+        let mut reader = Reader(data);
+
+        Ok(Self {
+            id: reader.u32()?,
+            kind: match reader.u8()? {
+                1 => ChunkKind::Chunk,
+                2 => ChunkKind::KeyFrame,
+                kind => ChunkKind::Unknown(kind),
+            },
+            length: reader.u32()?,
+            next_chunk_id: reader.u32()?,
+            offset: reader.u32()?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ChunkKind, Error, Rofl, CHUNK_HEADER_LEN, HEADER_LEN, MAGIC, SIGNATURE_LEN};
+    use serde_json::json;
+
+    fn metadata() -> Vec<u8> {
+        let stats = json!([
+            {"NAME": "Faker", "SKIN": "Ahri", "TEAM": "100", "WIN": "Win", "CHAMPIONS_KILLED": "7"},
+            {"RIOT_ID_GAME_NAME": "Caps", "SKIN": "Sylas", "TEAM": "200", "WIN": "Fail"},
+        ]);
+
+        serde_json::to_vec(&json!({
+            "gameLength": 1_800_000,
+            "gameVersion": "13.1.481.1234",
+            "lastGameChunkId": 2,
+            "lastKeyFrameId": 1,
+            "statsJson": stats.to_string(),
+        }))
+        .unwrap()
+    }
+
+    fn rofl() -> Vec<u8> {
+        let metadata = metadata();
+        let key = b"c2VjcmV0";
+
+        let mut payload_header = Vec::new();
+        payload_header.extend(1_234_567_890_u64.to_le_bytes());
+        for value in [1_800_000_u32, 1, 2, 1, 2, 60_000] {
+            payload_header.extend(value.to_le_bytes());
+        }
+        payload_header.extend(u16::try_from(key.len()).unwrap().to_le_bytes());
+        payload_header.extend(key);
+
+        let mut index = Vec::new();
+        let mut chunk_data: Vec<u8> = Vec::new();
+        for (id, kind, data, next) in [
+            (1_u32, 1_u8, &b"chunk one"[..], 2_u32),
+            (2, 1, b"chunk two", 0),
+            (1, 2, b"keyframe", 2),
+        ] {
+            index.extend(id.to_le_bytes());
+            index.push(kind);
+            index.extend(u32::try_from(data.len()).unwrap().to_le_bytes());
+            index.extend(next.to_le_bytes());
+            index.extend(u32::try_from(chunk_data.len()).unwrap().to_le_bytes());
+            chunk_data.extend(data);
+        }
+        assert_eq!(index.len(), 3 * CHUNK_HEADER_LEN);
+
+        let metadata_offset = HEADER_LEN;
+        let payload_header_offset = metadata_offset + metadata.len();
+        let payload_offset = payload_header_offset + payload_header.len();
+        let file_length = payload_offset + index.len() + chunk_data.len();
+
+        let mut file = Vec::new();
+        file.extend(MAGIC);
+        file.extend([0; SIGNATURE_LEN]);
+        file.extend(u16::try_from(HEADER_LEN).unwrap().to_le_bytes());
+        for value in [
+            file_length,
+            metadata_offset,
+            metadata.len(),
+            payload_header_offset,
+            payload_header.len(),
+            payload_offset,
+        ] {
+            file.extend(u32::try_from(value).unwrap().to_le_bytes());
+        }
+        assert_eq!(file.len(), HEADER_LEN);
+
+        file.extend(metadata);
+        file.extend(payload_header);
+        file.extend(index);
+        file.extend(chunk_data);
+        file
+    }
+
+    #[test]
+    fn parse_rofl() {
+        let rofl = Rofl::parse(rofl()).unwrap();
+
+        let metadata = rofl.metadata();
+        assert_eq!(metadata.game_version, "13.1.481.1234");
+        assert_eq!(metadata.duration().as_secs(), 1800);
+
+        let [faker, caps] = metadata.players() else {
+            panic!("Expected two players, found {:?}", metadata.players());
+        };
+        assert_eq!(faker.name(), Some("Faker"));
+        assert_eq!(faker.champion(), Some("Ahri"));
+        assert_eq!(faker.team(), Some(100));
+        assert_eq!(faker.won(), Some(true));
+        assert_eq!(faker.stat("CHAMPIONS_KILLED"), Some(7));
+        assert_eq!(caps.name(), Some("Caps"));
+        assert_eq!(caps.won(), Some(false));
+
+        let payload = rofl.payload().unwrap();
+        assert_eq!(payload.game_id, 1_234_567_890);
+        assert_eq!(payload.encryption_key, "c2VjcmV0");
+
+        assert_eq!(rofl.chunks().len(), 3);
+        let keyframe = rofl.keyframes().next().unwrap();
+        assert_eq!(keyframe.kind, ChunkKind::KeyFrame);
+        assert_eq!(rofl.chunk_data(keyframe), Some(&b"keyframe"[..]));
+        assert_eq!(rofl.chunk_data(&rofl.chunks()[1]), Some(&b"chunk two"[..]));
+    }
+
+    #[test]
+    fn parse_rofl_v2() {
+        let metadata = metadata();
+
+        let mut file = b"RIOT\x02\x00".to_vec();
+        file.extend([0xAB; 64]);
+        file.extend(&metadata);
+        file.extend(u32::try_from(metadata.len()).unwrap().to_le_bytes());
+
+        let rofl = Rofl::parse(file).unwrap();
+        assert_eq!(rofl.metadata().players().len(), 2);
+        assert!(rofl.payload().is_none());
+        assert!(rofl.chunks().is_empty());
+    }
+
+    #[test]
+    fn parse_invalid() {
+        assert!(matches!(
+            Rofl::parse(b"PK\x03\x04".to_vec()),
+            Err(Error::InvalidMagic)
+        ));
+
+        let mut truncated = rofl();
+        truncated.truncate(HEADER_LEN + 10);
+        assert!(matches!(Rofl::parse(truncated), Err(Error::Truncated)));
+    }
+}
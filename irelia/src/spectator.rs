@@ -0,0 +1,529 @@
+//! Client for the spectator servers, which serve the chunks and keyframes of games in progress
+//!
+//! These are the same endpoints the game uses when spectating, under `/observer-mode/rest/consumer`.
+//! Unlike the other APIs, they're served by riot's servers over plain HTTP, so the address of the
+//! spectator server for the game's platform has to be passed in, such as `http://spectator.euw1.lol.pvp.net:8080`
+//!
+//! Chunks are only published a while after they happen, so anything that isn't available yet is retried,
+//! see [`SpectatorClient::retry`]
+
+use crate::utils::runtime::{self, HttpConnector};
+use crate::Error;
+use futures_core::Stream;
+use http_body_util::{BodyExt, Full};
+use hyper::body::Bytes;
+use hyper::{Request, StatusCode, Uri};
+use hyper_util::client::legacy::Client;
+use serde::de::DeserializeOwned;
+use serde_derive::{Deserialize, Serialize};
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use std::time::Duration;
+
+/// The game the spectator data is for
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GameKey {
+    pub game_id: u64,
+    /// Such as `EUW1`
+    pub platform_id: String,
+}
+
+/// Information about a game being spectated, from `getGameMetaData`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GameMetadata {
+    pub game_key: GameKey,
+    /// Base64 encoded key the chunks and keyframes are encrypted with
+    #[serde(default)]
+    pub encryption_key: String,
+    /// Time between chunks, in milliseconds
+    #[serde(default)]
+    pub chunk_time_interval: u32,
+    /// Time between keyframes, in milliseconds
+    #[serde(default)]
+    pub key_frame_time_interval: u32,
+    /// How far behind the live game spectators are, in seconds
+    #[serde(default)]
+    pub delay_time: u32,
+    #[serde(default)]
+    pub game_ended: bool,
+    #[serde(default)]
+    pub last_chunk_id: u32,
+    #[serde(default)]
+    pub last_keyframe_id: u32,
+    /// The last chunk of the loading screen
+    #[serde(default)]
+    pub end_startup_chunk_id: u32,
+    /// The first chunk once the game started
+    #[serde(default)]
+    pub start_game_chunk_id: u32,
+    /// The last chunk of the game, `0` until it ended
+    #[serde(default)]
+    pub end_game_chunk_id: u32,
+}
+
+/// Which chunk was published last, and when the next one will be, from `getLastChunkInfo`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LastChunkInfo {
+    pub chunk_id: u32,
+    /// The keyframe for the chunk, or the one before it
+    pub key_frame_id: u32,
+    /// The chunk that will be published next
+    #[serde(default)]
+    pub next_chunk_id: u32,
+    /// How long until the next chunk is published, in milliseconds
+    #[serde(default)]
+    pub next_available_chunk: u64,
+    #[serde(default)]
+    pub end_startup_chunk_id: u32,
+    #[serde(default)]
+    pub start_game_chunk_id: u32,
+    /// The last chunk of the game, `0` until it ended
+    #[serde(default)]
+    pub end_game_chunk_id: u32,
+    /// Length of the chunk, in milliseconds
+    #[serde(default)]
+    pub duration: u32,
+}
+
+impl LastChunkInfo {
+    #[must_use]
+    /// How long until the next chunk is published
+    pub const fn next_available(&self) -> Duration {
+        Duration::from_millis(self.next_available_chunk)
+    }
+}
+
+/// A chunk of a game, as downloaded, still encrypted
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SpectatorChunk {
+    pub id: u32,
+    pub data: Bytes,
+}
+
+/// Client for a single game on a spectator server
+///
+/// ```no_run
+/// use futures_util::StreamExt;
+/// use irelia::spectator::SpectatorClient;
+///
+/// # async fn spectate() -> Result<(), irelia::Error> {
+/// let client = SpectatorClient::new("http://spectator.euw1.lol.pvp.net:8080", "EUW1", 1234567890)?;
+///
+/// let metadata = client.game_metadata().await?;
+/// let mut chunks = client.chunks(metadata.start_game_chunk_id);
+///
+/// while let Some(chunk) = chunks.next().await {
+///     let chunk = chunk?;
+///     println!("Chunk {} is {} bytes", chunk.id, chunk.data.len());
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct SpectatorClient {
+    client: Client<HttpConnector, Full<Bytes>>,
+    base_url: String,
+    platform_id: String,
+    game_id: u64,
+    retry_interval: Duration,
+    max_retries: u32,
+}
+
+impl SpectatorClient {
+    /// Creates a client for the game `game_id` on `platform_id`, such as `EUW1`, served from `base_url`
+    ///
+    /// Chunks that aren't available yet are retried every second, up to 10 times
+    ///
+    /// # Errors
+    /// This will return an error if `base_url` is not a valid URL
+    pub fn new(
+        base_url: impl Into<String>,
+        platform_id: impl Into<String>,
+        game_id: u64,
+    ) -> Result<Self, Error> {
+        let mut base_url = base_url.into();
+        base_url.truncate(base_url.trim_end_matches('/').len());
+
+        // Checked here, so building the URL of each request can't fail later
+        base_url.parse::<Uri>().map_err(hyper::http::Error::from)?;
+
+        Ok(Self {
+            client: Client::builder(runtime::Executor::new()).build(runtime::http_connector(None)),
+            base_url,
+            platform_id: platform_id.into(),
+            game_id,
+            retry_interval: Duration::from_secs(1),
+            max_retries: 10,
+        })
+    }
+
+    #[must_use]
+    /// Sets how often, and how many times, a chunk or keyframe that isn't available yet is retried
+    pub fn retry(mut self, interval: Duration, max_retries: u32) -> Self {
+        self.retry_interval = interval;
+        self.max_retries = max_retries;
+        self
+    }
+
+    #[must_use]
+    pub const fn game_id(&self) -> u64 {
+        self.game_id
+    }
+
+    #[must_use]
+    pub fn platform_id(&self) -> &str {
+        &self.platform_id
+    }
+
+    /// Gets the metadata of the game, such as the encryption key, and which chunks exist
+    ///
+    /// # Errors
+    /// This will return an error if the spectator server can't be reached, or the game is not being spectated
+    pub async fn game_metadata(&self) -> Result<GameMetadata, Error> {
+        self.json("getGameMetaData", 1).await
+    }
+
+    /// Gets the last chunk that was published, and when the next one will be
+    ///
+    /// # Errors
+    /// This will return an error if the spectator server can't be reached, or the game is not being spectated
+    pub async fn last_chunk_info(&self) -> Result<LastChunkInfo, Error> {
+        // The ID is ignored, but has to be there
+        self.json("getLastChunkInfo", 0).await
+    }
+
+    /// Downloads the chunk `chunk_id`, retrying while it isn't available yet
+    ///
+    /// # Errors
+    /// This will return an error if the spectator server can't be reached,
+    /// or the chunk was still not available after every retry
+    pub async fn chunk(&self, chunk_id: u32) -> Result<Bytes, Error> {
+        self.retried("getGameDataChunk", chunk_id).await
+    }
+
+    /// Downloads the keyframe `keyframe_id`, retrying while it isn't available yet
+    ///
+    /// # Errors
+    /// This will return an error if the spectator server can't be reached,
+    /// or the keyframe was still not available after every retry
+    pub async fn keyframe(&self, keyframe_id: u32) -> Result<Bytes, Error> {
+        self.retried("getKeyFrame", keyframe_id).await
+    }
+
+    #[must_use]
+    /// Streams every chunk from `start`, in order, waiting for each one to be published
+    ///
+    /// The stream ends after the last chunk of the game, or after an error, as the chunks after it would be out of order
+    pub fn chunks(&self, start: u32) -> ChunkStream {
+        ChunkStream {
+            state: Some(Sequencer {
+                client: self.clone(),
+                next_id: start,
+                last_available: None,
+                end_id: None,
+            }),
+            future: None,
+        }
+    }
+
+    fn endpoint(&self, method: &str, id: u32) -> String {
+        format!(
+            "{}/observer-mode/rest/consumer/{method}/{}/{}/{id}/token",
+            self.base_url, self.platform_id, self.game_id
+        )
+    }
+
+    /// Requests `method`, returning the status, and the body if it succeeded
+    async fn get(&self, method: &str, id: u32) -> Result<Result<Bytes, StatusCode>, Error> {
+        let request = Request::get(self.endpoint(method, id)).body(Full::default())?;
+        let response = self.client.request(request).await?;
+
+        let status = response.status();
+        if !status.is_success() {
+            return Ok(Err(status));
+        }
+
+        Ok(Ok(response.collect().await?.to_bytes()))
+    }
+
+    async fn json<R: DeserializeOwned>(&self, method: &str, id: u32) -> Result<R, Error> {
+        let body = self.get(method, id).await?.map_err(Error::RequestError)?;
+        Ok(serde_json::from_slice(&body)?)
+    }
+
+    async fn retried(&self, method: &str, id: u32) -> Result<Bytes, Error> {
+        let mut retries = 0;
+
+        loop {
+            match self.get(method, id).await? {
+                Ok(body) => return Ok(body),
+                // Chunks that haven't been published yet are not found
+                Err(StatusCode::NOT_FOUND) if retries < self.max_retries => retries += 1,
+                Err(status) => return Err(Error::RequestError(status)),
+            }
+
+            runtime::sleep(self.retry_interval).await;
+        }
+    }
+}
+
+/// Where a [`ChunkStream`] is at
+struct Sequencer {
+    client: SpectatorClient,
+    next_id: u32,
+    /// The last chunk known to be published
+    last_available: Option<u32>,
+    /// The last chunk of the game, once it ended
+    end_id: Option<u32>,
+}
+
+impl Sequencer {
+    /// Waits for the next chunk to be published, and downloads it
+    async fn next(mut self) -> (Option<Self>, Option<Result<SpectatorChunk, Error>>) {
+        let id = self.next_id;
+
+        loop {
+            if self.end_id.is_some_and(|end_id| id > end_id) {
+                return (None, None);
+            }
+
+            if self.last_available.is_some_and(|last| id <= last) {
+                break;
+            }
+
+            let info = match self.client.last_chunk_info().await {
+                Ok(info) => info,
+                Err(err) => return (None, Some(Err(err))),
+            };
+
+            self.last_available = Some(info.chunk_id);
+            self.end_id = (info.end_game_chunk_id > 0).then_some(info.end_game_chunk_id);
+
+            if id > info.chunk_id {
+                // Sleeping until this chunk should be out, at least a little, so a server that says it's due now
+                // isn't asked in a tight loop, the game having ended doesn't mean the last chunks are published
+                let chunks_after_next = id - info.chunk_id - 1;
+                let wait = info
+                    .next_available()
+                    .saturating_add(
+                        Duration::from_millis(u64::from(info.duration)) * chunks_after_next,
+                    )
+                    .max(Duration::from_millis(100));
+                runtime::sleep(wait).await;
+            }
+        }
+
+        match self.client.chunk(id).await {
+            Ok(data) => {
+                self.next_id += 1;
+                (Some(self), Some(Ok(SpectatorChunk { id, data })))
+            }
+            Err(err) => (None, Some(Err(err))),
+        }
+    }
+}
+
+type SequencerFuture = Pin<
+    Box<dyn Future<Output = (Option<Sequencer>, Option<Result<SpectatorChunk, Error>>)> + Send>,
+>;
+
+/// Stream of the chunks of a game, in order, see [`SpectatorClient::chunks`]
+pub struct ChunkStream {
+    state: Option<Sequencer>,
+    future: Option<SequencerFuture>,
+}
+
+impl ChunkStream {
+    #[must_use]
+    /// The ID of the next chunk to be yielded, `None` once the stream ended
+    pub fn next_chunk_id(&self) -> Option<u32> {
+        self.state.as_ref().map(|state| state.next_id)
+    }
+}
+
+impl Stream for ChunkStream {
+    type Item = Result<SpectatorChunk, Error>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        if self.future.is_none() {
+            let Some(state) = self.state.take() else {
+                return Poll::Ready(None);
+            };
+
+            self.future = Some(Box::pin(state.next()));
+        }
+
+        let Some(future) = self.future.as_mut() else {
+            return Poll::Ready(None);
+        };
+
+        let Poll::Ready((state, item)) = future.as_mut().poll(cx) else {
+            return Poll::Pending;
+        };
+
+        self.future = None;
+        self.state = state;
+
+        Poll::Ready(item)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{LastChunkInfo, SpectatorClient};
+    use crate::Error;
+    use futures_util::StreamExt;
+    use hyper::StatusCode;
+    use std::io::{BufRead, BufReader, Write};
+    use std::net::TcpListener;
+    use std::sync::atomic::{AtomicU32, Ordering};
+    use std::sync::Arc;
+    use std::time::{Duration, Instant};
+
+    /// Serves chunks 1 to 3 from a game that already ended, with chunk 2 only being found on the second try
+    fn serve() -> (String, Arc<AtomicU32>) {
+        serve_with(|| r#"{"chunkId": 3, "keyFrameId": 1, "endGameChunkId": 3}"#.to_string())
+    }
+
+    /// Same as [`serve`], with the last chunk info coming from `last_chunk_info`
+    fn serve_with(
+        last_chunk_info: impl Fn() -> String + Send + 'static,
+    ) -> (String, Arc<AtomicU32>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let requests = Arc::new(AtomicU32::new(0));
+
+        let counter = Arc::clone(&requests);
+        std::thread::spawn(move || {
+            let mut missed = false;
+
+            for stream in listener.incoming() {
+                let mut stream = stream.unwrap();
+                let mut line = String::new();
+                let mut reader = BufReader::new(&stream);
+                reader.read_line(&mut line).unwrap();
+
+                // Skip the rest of the request
+                let mut header = String::new();
+                while reader.read_line(&mut header).unwrap() > 2 {
+                    header.clear();
+                }
+
+                counter.fetch_add(1, Ordering::SeqCst);
+                let path = line.split(' ').nth(1).unwrap();
+
+                let (status, body) = if path.contains("getLastChunkInfo") {
+                    ("200 OK", last_chunk_info())
+                } else if path.contains("getGameDataChunk/EUW1/1/2/") && !missed {
+                    missed = true;
+                    ("404 Not Found", String::new())
+                } else if let Some(id) = path.strip_suffix("/token").and_then(|path| {
+                    path.rsplit('/')
+                        .next()
+                        .filter(|_| path.contains("getGameDataChunk"))
+                }) {
+                    ("200 OK", format!("chunk {id}"))
+                } else {
+                    ("404 Not Found", String::new())
+                };
+
+                let _ = write!(
+                    stream,
+                    "HTTP/1.1 {status}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                    body.len()
+                );
+            }
+        });
+
+        (url, requests)
+    }
+
+    #[test]
+    fn chunk_info_deserialize() {
+        let info: LastChunkInfo = serde_json::from_str(
+            r#"{
+                "chunkId": 12,
+                "availableSince": 5000,
+                "nextAvailableChunk": 25000,
+                "keyFrameId": 5,
+                "nextChunkId": 13,
+                "endStartupChunkId": 2,
+                "startGameChunkId": 4,
+                "endGameChunkId": 0,
+                "duration": 30000
+            }"#,
+        )
+        .unwrap();
+
+        assert_eq!(info.chunk_id, 12);
+        assert_eq!(info.next_available(), Duration::from_secs(25));
+    }
+
+    #[tokio::test]
+    async fn chunk_sequence() {
+        let (url, _) = serve();
+        let client = SpectatorClient::new(url, "EUW1", 1)
+            .unwrap()
+            .retry(Duration::from_millis(10), 3);
+
+        let chunks: Vec<_> = client
+            .chunks(1)
+            .map(|chunk| {
+                let chunk = chunk.unwrap();
+                (chunk.id, chunk.data)
+            })
+            .collect()
+            .await;
+
+        assert_eq!(
+            chunks,
+            [
+                (1, "chunk 1".into()),
+                (2, "chunk 2".into()),
+                (3, "chunk 3".into())
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn missing_keyframe() {
+        let (url, requests) = serve();
+        let client = SpectatorClient::new(url, "EUW1", 1)
+            .unwrap()
+            .retry(Duration::from_millis(10), 2);
+
+        let result = client.keyframe(1).await;
+        assert!(matches!(
+            result,
+            Err(Error::RequestError(StatusCode::NOT_FOUND))
+        ));
+        // The first try, and both retries
+        assert_eq!(requests.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn waits_for_unpublished_chunk() {
+        let start = Instant::now();
+        // The game already ended, but its last chunk is only published after a while
+        let (url, requests) = serve_with(move || {
+            let chunk_id = if start.elapsed() < Duration::from_millis(300) {
+                2
+            } else {
+                3
+            };
+            format!(
+                r#"{{"chunkId": {chunk_id}, "keyFrameId": 1, "endGameChunkId": 3, "nextAvailableChunk": 300}}"#
+            )
+        });
+        let client = SpectatorClient::new(url, "EUW1", 1).unwrap();
+
+        let chunk = client.chunks(3).next().await.unwrap().unwrap();
+        assert_eq!(chunk.id, 3);
+        // Two checks of the last chunk, and the chunk itself, rather than asking again until it's out
+        assert_eq!(requests.load(Ordering::SeqCst), 3);
+    }
+}
@@ -1,8 +1,12 @@
 #[cfg(all(feature = "nativetls", feature = "rustls"))]
-compile_error!("NativeTls and Rustls cannot be used together");
+compile_error!(
+    "NativeTls and Rustls cannot be used together, disable default features to use `nativetls`"
+);
 
 #[cfg(not(any(feature = "nativetls", feature = "rustls")))]
-compile_error!("You need to either enable the `nativetls` or `rustls` feature");
+compile_error!(
+    "You need to either enable the `nativetls` (`native-tls`) or `rustls` (`rustls-tls`) feature"
+);
 
 #[cfg(feature = "nativetls")]
 mod nativetls;
@@ -13,3 +17,28 @@ mod rustls;
 pub use nativetls::*;
 #[cfg(feature = "rustls")]
 pub use rustls::*;
+
+#[cfg(any(feature = "rest", feature = "in_game"))]
+const HANDSHAKE_TIMEOUT: std::time::Duration = std::time::Duration::from_millis(500);
+
+#[cfg(any(feature = "rest", feature = "in_game"))]
+/// Connects to `addr`, returning the SHA-256 fingerprint of the certificate it presents
+///
+/// The certificate is not verified, so this can be used to check what a running client
+/// is actually serving, before deciding whether to trust it
+///
+/// This blocks for up to a second while connecting and doing the handshake,
+/// so from async code it should be run with something like `tokio::task::spawn_blocking`
+///
+/// # Errors
+/// This will return an error if nothing is listening on `addr`, the TLS handshake fails, or either times out
+pub fn peer_certificate_fingerprint(
+    addr: impl Into<std::net::SocketAddr>,
+) -> Result<[u8; 32], crate::Error> {
+    let certificate = peer_certificate(addr.into())?;
+    let digest = ring::digest::digest(&ring::digest::SHA256, &certificate);
+
+    let mut fingerprint = [0; 32];
+    fingerprint.copy_from_slice(digest.as_ref());
+    Ok(fingerprint)
+}
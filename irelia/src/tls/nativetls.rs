@@ -1,5 +1,7 @@
 use std::sync::LazyLock;
 
+use crate::process_info::TlsOptions;
+
 include!(concat!(env!("OUT_DIR"), "/riot_games_const_pem.rs"));
 
 static NATIVE_TLS_CERTIFICATE: LazyLock<native_tls::TlsConnector> =
@@ -9,21 +11,42 @@ fn connector_internal() -> native_tls::TlsConnector {
     // This was turned into a der in the build script, so if this fails something is horribly wrong
     let cert = native_tls::Certificate::from_der(cert::PEM_FILE).unwrap();
 
+    // The client is only reached over the loopback, so the name isn't checked, the certificate still has to be signed by riot
     let connector = native_tls::TlsConnector::builder()
         .add_root_certificate(cert)
         .disable_built_in_roots(true)
+        .danger_accept_invalid_hostnames(true)
         .build()
         .unwrap();
 
     connector
 }
 
+/// A connector for a client that isn't on the loopback, see [`TlsOptions`]
+///
+/// The certificate name can't be checked against anything but the name the connection is opened with,
+/// so it's checked against the host for REST requests, and [`TlsOptions::connection_name`] for websockets
+fn remote_connector(options: &TlsOptions) -> Result<native_tls::TlsConnector, native_tls::Error> {
+    let cert = native_tls::Certificate::from_der(cert::PEM_FILE)?;
+
+    native_tls::TlsConnector::builder()
+        .add_root_certificate(cert)
+        .disable_built_in_roots(true)
+        .use_sni(options.sni.is_some())
+        .danger_accept_invalid_certs(options.accept_invalid_certs)
+        .build()
+}
+
 #[cfg(feature = "ws")]
 pub use ws::*;
 
 #[cfg(feature = "ws")]
 mod ws {
-    use super::NATIVE_TLS_CERTIFICATE;
+    use std::net::TcpStream;
+    use tungstenite::error::TlsError;
+    use tungstenite::stream::MaybeTlsStream;
+
+    use super::{remote_connector, TlsOptions, NATIVE_TLS_CERTIFICATE};
 
     pub type TlsType = native_tls::TlsConnector;
 
@@ -34,6 +57,44 @@ mod ws {
     pub fn connector() -> TlsType {
         NATIVE_TLS_CERTIFICATE.clone()
     }
+
+    #[cfg(any(test, feature = "mock"))]
+    /// A connector that accepts any certificate, with any name, for the mock and tests
+    pub fn accept_any_cert_connector() -> Result<tungstenite::Connector, TlsError> {
+        native_tls::TlsConnector::builder()
+            .danger_accept_invalid_certs(true)
+            .danger_accept_invalid_hostnames(true)
+            .build()
+            .map(tungstenite::Connector::NativeTls)
+            .map_err(TlsError::Native)
+    }
+
+    /// Wraps `tcp` for a client that isn't on the loopback, doing the handshake right away
+    pub fn remote_stream(
+        options: &TlsOptions,
+        tcp: TcpStream,
+    ) -> Result<MaybeTlsStream<TcpStream>, tungstenite::Error> {
+        let connector = remote_connector(options)
+            .map_err(|err| tungstenite::Error::Tls(TlsError::Native(err)))?;
+
+        let stream =
+            connector
+                .connect(options.connection_name(), tcp)
+                .map_err(|err| match err {
+                    native_tls::HandshakeError::Failure(err) => {
+                        tungstenite::Error::Tls(TlsError::Native(err))
+                    }
+                    // The stream is blocking, so this only happens if it was given a timeout that ran out
+                    native_tls::HandshakeError::WouldBlock(_) => {
+                        tungstenite::Error::Io(std::io::Error::new(
+                            std::io::ErrorKind::TimedOut,
+                            "the TLS handshake timed out",
+                        ))
+                    }
+                })?;
+
+        Ok(MaybeTlsStream::NativeTls(stream))
+    }
 }
 
 #[cfg(any(feature = "rest", feature = "in_game", feature = "replay"))]
@@ -41,18 +102,81 @@ pub use http::*;
 
 #[cfg(any(feature = "rest", feature = "in_game", feature = "replay"))]
 mod http {
-    use hyper_util::client::legacy::connect;
+    use std::net::{SocketAddr, TcpStream};
+    use std::time::Duration;
 
-    use super::NATIVE_TLS_CERTIFICATE;
+    use crate::utils::runtime::{http_connector, HttpConnector};
 
-    pub type Connector = hyper_tls::HttpsConnector<connect::HttpConnector>;
+    use super::{remote_connector, TlsOptions, NATIVE_TLS_CERTIFICATE};
 
-    pub fn https_connector() -> Connector {
+    pub type Connector = hyper_tls::HttpsConnector<HttpConnector>;
+
+    pub fn https_connector(connect_timeout: Option<Duration>) -> Connector {
         let connector = NATIVE_TLS_CERTIFICATE.clone();
-        let mut http = connect::HttpConnector::new();
-        http.enforce_http(false);
+        let http = http_connector(connect_timeout);
         let mut https = hyper_tls::HttpsConnector::from((http, connector.clone().into()));
         https.https_only(true);
         https
     }
+
+    /// The same as [`https_connector`], but accepts any certificate, instead of only the one signed by riot
+    pub fn https_connector_accept_invalid_certs(connect_timeout: Option<Duration>) -> Connector {
+        let connector = accept_any_cert_connector()
+            .expect("The TLS backend should always be able to build a connector");
+
+        let http = http_connector(connect_timeout);
+        let mut https = hyper_tls::HttpsConnector::from((http, connector.into()));
+        https.https_only(true);
+        https
+    }
+
+    /// The same as [`https_connector`], but for a client that isn't on the loopback, see [`TlsOptions`]
+    pub fn https_connector_remote(
+        connect_timeout: Option<Duration>,
+        options: &TlsOptions,
+    ) -> Connector {
+        let connector = remote_connector(options)
+            .expect("The TLS backend should always be able to build a connector");
+
+        let http = http_connector(connect_timeout);
+        let mut https = hyper_tls::HttpsConnector::from((http, connector.into()));
+        https.https_only(true);
+        https
+    }
+
+    fn accept_any_cert_connector() -> Result<native_tls::TlsConnector, native_tls::Error> {
+        native_tls::TlsConnector::builder()
+            .danger_accept_invalid_certs(true)
+            .danger_accept_invalid_hostnames(true)
+            .build()
+    }
+
+    /// Does a TLS handshake with `addr`, returning the DER of the leaf certificate without verifying it
+    pub fn peer_certificate(addr: SocketAddr) -> std::io::Result<Vec<u8>> {
+        let connector = accept_any_cert_connector().map_err(std::io::Error::other)?;
+
+        let stream = TcpStream::connect_timeout(&addr, super::super::HANDSHAKE_TIMEOUT)?;
+        // Something that accepts the connection but never answers would otherwise block forever
+        stream.set_read_timeout(Some(super::super::HANDSHAKE_TIMEOUT))?;
+        stream.set_write_timeout(Some(super::super::HANDSHAKE_TIMEOUT))?;
+
+        let stream =
+            connector
+                .connect(&addr.ip().to_string(), stream)
+                .map_err(|err| match err {
+                    native_tls::HandshakeError::Failure(err) => std::io::Error::other(err),
+                    // The stream is blocking, so this only happens when a timeout runs out
+                    native_tls::HandshakeError::WouldBlock(_) => std::io::Error::new(
+                        std::io::ErrorKind::TimedOut,
+                        "the TLS handshake timed out",
+                    ),
+                })?;
+
+        stream
+            .peer_certificate()
+            .map_err(std::io::Error::other)?
+            .ok_or_else(|| std::io::Error::other("the server did not present a certificate"))?
+            .to_der()
+            .map_err(std::io::Error::other)
+    }
 }
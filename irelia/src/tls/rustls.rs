@@ -1,4 +1,11 @@
-use std::sync::LazyLock;
+use rustls::client::danger::{HandshakeSignatureValid, ServerCertVerified, ServerCertVerifier};
+use rustls::client::WebPkiServerVerifier;
+use rustls::crypto::{verify_tls12_signature, verify_tls13_signature, CryptoProvider};
+use rustls::pki_types::{CertificateDer, ServerName, UnixTime};
+use rustls::{DigitallySignedStruct, SignatureScheme};
+use std::sync::{Arc, LazyLock};
+
+use crate::process_info::TlsOptions;
 
 static RUSTLS_CLIENT_CONFIG: LazyLock<rustls::ClientConfig> = LazyLock::new(connector_internal);
 
@@ -18,14 +25,161 @@ fn connector_internal() -> rustls::ClientConfig {
         .with_no_client_auth()
 }
 
+/// A client config that accepts any certificate, see [`AcceptAnyCert`]
+pub fn accept_any_cert_config() -> Result<rustls::ClientConfig, rustls::Error> {
+    let provider = Arc::new(rustls::crypto::ring::default_provider());
+
+    Ok(
+        rustls::ClientConfig::builder_with_provider(provider.clone())
+            .with_safe_default_protocol_versions()?
+            .dangerous()
+            .with_custom_certificate_verifier(Arc::new(AcceptAnyCert(provider)))
+            .with_no_client_auth(),
+    )
+}
+
+/// A client config for a client that isn't on the loopback, see [`TlsOptions`]
+///
+/// Connections have to be opened with [`TlsOptions::connection_name`], the certificate is checked
+/// against [`TlsOptions::certificate_name`] regardless
+fn remote_config(options: &TlsOptions) -> Result<rustls::ClientConfig, rustls::Error> {
+    let mut config = if options.accept_invalid_certs {
+        accept_any_cert_config()?
+    } else {
+        let provider = Arc::new(rustls::crypto::ring::default_provider());
+        let roots = rustls::RootCertStore {
+            roots: vec![cert::DECODED_CERT],
+        };
+
+        let inner = WebPkiServerVerifier::builder_with_provider(Arc::new(roots), provider.clone())
+            .build()
+            .map_err(|err| rustls::Error::General(err.to_string()))?;
+
+        rustls::ClientConfig::builder_with_provider(provider)
+            .with_safe_default_protocol_versions()?
+            .dangerous()
+            .with_custom_certificate_verifier(Arc::new(FixedNameVerifier {
+                inner,
+                name: options.certificate_name.clone(),
+            }))
+            .with_no_client_auth()
+    };
+
+    config.enable_sni = options.sni.is_some();
+    Ok(config)
+}
+
+/// Checks the certificate against a fixed name, rather than the name the connection was opened with,
+/// as the LCU's certificate is always issued for the loopback
+#[derive(Debug)]
+struct FixedNameVerifier {
+    inner: Arc<WebPkiServerVerifier>,
+    name: String,
+}
+
+impl ServerCertVerifier for FixedNameVerifier {
+    fn verify_server_cert(
+        &self,
+        end_entity: &CertificateDer<'_>,
+        intermediates: &[CertificateDer<'_>],
+        _server_name: &ServerName<'_>,
+        ocsp_response: &[u8],
+        now: UnixTime,
+    ) -> Result<ServerCertVerified, rustls::Error> {
+        let name = ServerName::try_from(self.name.as_str())
+            .map_err(|err| rustls::Error::General(err.to_string()))?;
+
+        self.inner
+            .verify_server_cert(end_entity, intermediates, &name, ocsp_response, now)
+    }
+
+    fn verify_tls12_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+        self.inner.verify_tls12_signature(message, cert, dss)
+    }
+
+    fn verify_tls13_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+        self.inner.verify_tls13_signature(message, cert, dss)
+    }
+
+    fn supported_verify_schemes(&self) -> Vec<SignatureScheme> {
+        self.inner.supported_verify_schemes()
+    }
+}
+
+/// Accepts whatever certificate the server presents, only the handshake signatures are checked
+#[derive(Debug)]
+struct AcceptAnyCert(Arc<CryptoProvider>);
+
+impl ServerCertVerifier for AcceptAnyCert {
+    fn verify_server_cert(
+        &self,
+        _end_entity: &CertificateDer<'_>,
+        _intermediates: &[CertificateDer<'_>],
+        _server_name: &ServerName<'_>,
+        _ocsp_response: &[u8],
+        _now: UnixTime,
+    ) -> Result<ServerCertVerified, rustls::Error> {
+        Ok(ServerCertVerified::assertion())
+    }
+
+    fn verify_tls12_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+        verify_tls12_signature(
+            message,
+            cert,
+            dss,
+            &self.0.signature_verification_algorithms,
+        )
+    }
+
+    fn verify_tls13_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, rustls::Error> {
+        verify_tls13_signature(
+            message,
+            cert,
+            dss,
+            &self.0.signature_verification_algorithms,
+        )
+    }
+
+    fn supported_verify_schemes(&self) -> Vec<SignatureScheme> {
+        self.0.signature_verification_algorithms.supported_schemes()
+    }
+}
+
 #[cfg(feature = "ws")]
 pub use ws::*;
 
 #[cfg(feature = "ws")]
 mod ws {
+    use rustls::pki_types::ServerName;
+    use std::net::TcpStream;
     use std::sync::Arc;
+    #[cfg(any(test, feature = "mock"))]
+    use tungstenite::error::TlsError;
+    use tungstenite::stream::MaybeTlsStream;
 
-    use super::RUSTLS_CLIENT_CONFIG;
+    #[cfg(any(test, feature = "mock"))]
+    use super::accept_any_cert_config;
+    use super::{remote_config, TlsOptions, RUSTLS_CLIENT_CONFIG};
 
     pub type TlsType = Arc<rustls::ClientConfig>;
 
@@ -36,6 +190,32 @@ mod ws {
     pub fn connector() -> TlsType {
         Arc::new(RUSTLS_CLIENT_CONFIG.clone())
     }
+
+    #[cfg(any(test, feature = "mock"))]
+    /// A connector that accepts any certificate, for the mock and tests
+    pub fn accept_any_cert_connector() -> Result<tungstenite::Connector, TlsError> {
+        let config = accept_any_cert_config()?;
+        Ok(tungstenite::Connector::Rustls(Arc::new(config)))
+    }
+
+    /// Wraps `tcp` for a client that isn't on the loopback, the handshake happens on the first read or write
+    pub fn remote_stream(
+        options: &TlsOptions,
+        tcp: TcpStream,
+    ) -> Result<MaybeTlsStream<TcpStream>, tungstenite::Error> {
+        let config = remote_config(options).map_err(|err| tungstenite::Error::Tls(err.into()))?;
+
+        let name = ServerName::try_from(options.connection_name().to_owned()).map_err(|err| {
+            tungstenite::Error::Tls(rustls::Error::General(err.to_string()).into())
+        })?;
+
+        let connection = rustls::ClientConnection::new(Arc::new(config), name)
+            .map_err(|err| tungstenite::Error::Tls(err.into()))?;
+
+        Ok(MaybeTlsStream::Rustls(rustls::StreamOwned::new(
+            connection, tcp,
+        )))
+    }
 }
 
 #[cfg(any(feature = "rest", feature = "in_game"))]
@@ -43,20 +223,94 @@ pub use http::*;
 
 #[cfg(any(feature = "rest", feature = "in_game"))]
 mod http {
-    use hyper_util::client::legacy::connect;
+    use hyper::Uri;
+    use hyper_rustls::builderstates::WantsProtocols1;
+    use hyper_rustls::HttpsConnectorBuilder;
+    use rustls::pki_types::ServerName;
+    use std::net::{SocketAddr, TcpStream};
+    use std::sync::Arc;
+    use std::time::Duration;
 
-    use super::RUSTLS_CLIENT_CONFIG;
+    use crate::utils::runtime::{http_connector, HttpConnector};
 
-    pub type Connector = hyper_rustls::HttpsConnector<connect::HttpConnector>;
+    use super::{accept_any_cert_config, remote_config, TlsOptions, RUSTLS_CLIENT_CONFIG};
 
-    pub fn https_connector() -> Connector {
+    pub type Connector = hyper_rustls::HttpsConnector<HttpConnector>;
+
+    pub fn https_connector(connect_timeout: Option<Duration>) -> Connector {
         // Get a client config using the riotgames.pem file
         let tls = RUSTLS_CLIENT_CONFIG.clone();
         // Set up an HTTPS only client, with just the client config
-        hyper_rustls::HttpsConnectorBuilder::new()
+        let builder = HttpsConnectorBuilder::new()
             .with_tls_config(tls.clone())
+            .https_only();
+
+        with_versions(builder, connect_timeout)
+    }
+
+    /// The same as [`https_connector`], but accepts any certificate, instead of only the one signed by riot
+    pub fn https_connector_accept_invalid_certs(connect_timeout: Option<Duration>) -> Connector {
+        let tls = accept_any_cert_config()
+            .expect("The default protocol versions are always supported by ring");
+
+        let builder = HttpsConnectorBuilder::new()
+            .with_tls_config(tls)
+            .https_only();
+
+        with_versions(builder, connect_timeout)
+    }
+
+    /// The same as [`https_connector`], but for a client that isn't on the loopback, see [`TlsOptions`]
+    pub fn https_connector_remote(
+        connect_timeout: Option<Duration>,
+        options: &TlsOptions,
+    ) -> Connector {
+        let tls = remote_config(options)
+            .expect("The default protocol versions are always supported by ring");
+        let name = options.connection_name().to_owned();
+
+        let builder = HttpsConnectorBuilder::new()
+            .with_tls_config(tls)
             .https_only()
-            .enable_http1()
-            .build()
+            .with_server_name_resolver(move |_: &Uri| ServerName::try_from(name.clone()));
+
+        with_versions(builder, connect_timeout)
+    }
+
+    /// Enables HTTP/1.1, and with the `http2` feature HTTP/2, picked with ALPN during the handshake
+    fn with_versions(
+        builder: HttpsConnectorBuilder<WantsProtocols1>,
+        connect_timeout: Option<Duration>,
+    ) -> Connector {
+        #[cfg(feature = "http2")]
+        let builder = builder.enable_all_versions();
+        #[cfg(not(feature = "http2"))]
+        let builder = builder.enable_http1();
+
+        builder.wrap_connector(http_connector(connect_timeout))
+    }
+
+    /// Does a TLS handshake with `addr`, returning the DER of the leaf certificate without verifying it
+    pub fn peer_certificate(addr: SocketAddr) -> std::io::Result<Vec<u8>> {
+        let config = accept_any_cert_config().map_err(std::io::Error::other)?;
+
+        let server_name = ServerName::IpAddress(addr.ip().into());
+        let mut connection = rustls::ClientConnection::new(Arc::new(config), server_name)
+            .map_err(std::io::Error::other)?;
+
+        let mut stream = TcpStream::connect_timeout(&addr, super::super::HANDSHAKE_TIMEOUT)?;
+        // Something that accepts the connection but never answers would otherwise block forever
+        stream.set_read_timeout(Some(super::super::HANDSHAKE_TIMEOUT))?;
+        stream.set_write_timeout(Some(super::super::HANDSHAKE_TIMEOUT))?;
+
+        while connection.is_handshaking() {
+            connection.complete_io(&mut stream)?;
+        }
+
+        connection
+            .peer_certificates()
+            .and_then(|certificates| certificates.first())
+            .map(|certificate| certificate.to_vec())
+            .ok_or_else(|| std::io::Error::other("the server did not present a certificate"))
     }
 }
@@ -1,4 +1,8 @@
-#[cfg(any(feature = "ws", feature = "rest"))]
+#[cfg(any(feature = "ws", feature = "rest", feature = "in_game"))]
 pub mod process_info;
 #[cfg(any(feature = "in_game", feature = "rest"))]
 pub mod requests;
+#[cfg(any(feature = "rest", feature = "in_game"))]
+pub(crate) mod runtime;
+#[cfg(feature = "tracing")]
+pub(crate) mod trace;
@@ -3,45 +3,97 @@
 
 //! This module also contains a list of constants for the different names
 //! of the processes for `OSX`, and `Windows`
+//!
+//! On Linux, the Windows client is expected to be running under Wine, such as through Lutris or Proton,
+//! so the Windows process names are used, and paths are translated from the Wine prefix
+//!
+//! On Windows, the `windows-native` feature lists processes through WMI instead of sysinfo,
+//! which only reads the processes with the names being looked for
 
 use irelia_encoder::Encoder;
+#[cfg(all(target_os = "windows", feature = "windows-native"))]
+use native::{Pid, Process, System};
 use std::fmt::{Display, Formatter};
 use std::io::Read;
 use std::net::{Ipv4Addr, SocketAddrV4};
 use std::num::ParseIntError;
+use std::path::{Path, PathBuf};
 use std::str::FromStr;
-use sysinfo::{ProcessRefreshKind, RefreshKind, System};
+use std::time::{Duration, Instant};
+#[cfg(not(all(target_os = "windows", feature = "windows-native")))]
+use sysinfo::{Pid, Process, ProcessRefreshKind, ProcessesToUpdate, System};
 
-// Linux is unplayable, the constants here are only defined so the docs build
-#[cfg(target_os = "windows")]
+mod locator;
+#[cfg(all(target_os = "windows", feature = "windows-native"))]
+mod native;
+mod remote;
+#[cfg(feature = "rest")]
+mod watcher;
+pub use locator::{ClientLocator, LocatedClient, ProcessLocator};
+pub use remote::{RemoteLcu, TlsOptions, LCU_CERTIFICATE_NAME};
+#[cfg(feature = "rest")]
+pub use watcher::{ClientEvent, ClientWatcher};
+
+#[cfg(any(target_os = "windows", target_os = "linux"))]
 pub const CLIENT_PROCESS_NAME: &str = "LeagueClientUx.exe";
 #[cfg(target_os = "macos")]
 pub const CLIENT_PROCESS_NAME: &str = "LeagueClientUx";
 
-#[cfg(target_os = "windows")]
+#[cfg(any(target_os = "windows", target_os = "linux"))]
 pub const GAME_PROCESS_NAME: &str = "League of Legends.exe";
 #[cfg(target_os = "macos")]
 pub const GAME_PROCESS_NAME: &str = "League of Legends";
 
+#[cfg(any(target_os = "windows", target_os = "linux"))]
+pub const RIOT_CLIENT_PROCESS_NAME: &str = "RiotClientUx.exe";
+#[cfg(target_os = "macos")]
+pub const RIOT_CLIENT_PROCESS_NAME: &str = "Riot Client";
+
 /// const copy of the encoder
 pub(crate) const ENCODER: Encoder = Encoder::new();
 
-#[cfg(all(docsrs, target_os = "linux"))]
-/// Constant for the game process name, `League of Legends.exe` on Windows and `League of Legends` on MacOS
-pub const GAME_PROCESS_NAME: &str = "";
-#[cfg(all(docsrs, target_os = "linux"))]
-/// Constant for the client process name, `LeagueClientUx.exe` on Windows and `LeagueClientUx` on MacOS
-pub const CLIENT_PROCESS_NAME: &str = "";
-
 const NOT_RUNNING: Error = Error::new(
     ErrorKind::NotRunning,
     "neither the game or client process were running",
 );
 
+const RIOT_CLIENT_NOT_RUNNING: Error = Error::new(
+    ErrorKind::NotRunning,
+    "neither the riot client or league client process were running",
+);
+
+#[cfg(feature = "rest")]
+pub(crate) const CLIENT_CLOSED: Error = Error::new(
+    ErrorKind::ClientClosed,
+    "the client closed while the request was in flight",
+);
+
 const PORT_NOT_FOUND: Error = Error::new(ErrorKind::PortNotFound, "port was not found");
 
 const AUTH_NOT_FOUND: Error = Error::new(ErrorKind::AuthTokenNotFound, "auth token was not found");
 
+const INVALID_AUTH_HEADER: Error = Error::new(
+    ErrorKind::InvalidAuthHeader,
+    "the encoded auth header was not valid UTF-8",
+);
+
+const LOCK_FILE_STALE: Error = Error::new(
+    ErrorKind::LockFileStale,
+    "the lock file was written by a client that is no longer running",
+)
+.set_lockfile_error(true);
+
+const WAIT_TIMED_OUT: Error = Error::new(
+    ErrorKind::TimedOut,
+    "the client did not start before the timeout",
+);
+
+const LOCK_FILE_PID_INVALID: Error = Error::new(
+    ErrorKind::LockFilePidInvalid,
+    "the PID in the lock file was not a number",
+)
+.set_lockfile_error(true);
+
 const LOCK_FILE_NOT_FOUND: Error = Error::new(
     ErrorKind::LockFileNotFound,
     "Did not follow the typical install structure",
@@ -69,27 +121,62 @@ pub fn get_running_client<T>(
 where
     T: FromStr,
 {
-    const RIOT_PREFIX: &[u8] = b"riot:";
-    const BASIC_PREFIX: &[u8] = b"Basic ";
+    running_client(client_process_name, game_process_name, force_lock_file)
+        .map(|(_, addr, auth)| (addr, auth))
+}
 
-    // If we always read the lock file, we never need to get the command line of the process
-    let cmd = if force_lock_file {
-        sysinfo::UpdateKind::Never
-    } else {
-        sysinfo::UpdateKind::OnlyIfNotSet
+/// The PID, address, and auth header of a running client
+pub(crate) type RunningClient<T> = (u32, SocketAddrV4, Result<T, <T as FromStr>::Err>);
+
+/// The same as [`get_running_client`], but also returns the PID of the process the credentials came from
+pub(crate) fn running_client<T>(
+    client_process_name: &str,
+    game_process_name: &str,
+    force_lock_file: bool,
+) -> Result<RunningClient<T>, Error>
+where
+    T: FromStr,
+{
+    let discover = || {
+        let system = running_processes(&[client_process_name, game_process_name], force_lock_file)?;
+        let (process, client) = find_client(&system, client_process_name, game_process_name)?;
+
+        let (addr, auth) = process_credentials(process, client, force_lock_file, |credentials| {
+            T::from_str(credentials.auth_header)
+        })?;
+        Ok((process.pid().as_u32(), addr, auth))
     };
-    // No matter what, the path to the process is required
-    let refresh_kind = ProcessRefreshKind::nothing()
-        .with_exe(sysinfo::UpdateKind::OnlyIfNotSet)
-        .with_cmd(cmd);
 
-    // Get the current list of processes
-    let system = System::new_with_specifics(
-        // This creates a new instance of `system` every time, so this only
-        //  needs to be updated if it's not set
-        RefreshKind::nothing().with_processes(refresh_kind),
-    );
+    #[cfg(feature = "tracing")]
+    let discover = || crate::utils::trace::discovery(force_lock_file, discover);
+
+    discover()
+}
 
+/// The same as [`get_running_client`], but returns everything known about the connection
+///
+/// This includes the raw auth token, for use with other HTTP clients, and the install directory of the client
+///
+/// # Errors
+/// This will return an error if the LCU is not running, or the lock file is inaccessible
+pub fn get_connection_info(
+    client_process_name: &str,
+    game_process_name: &str,
+    force_lock_file: bool,
+) -> Result<ConnectionInfo, Error> {
+    // The command line is always needed here, as it's used to resolve the region
+    let system = running_processes(&[client_process_name, game_process_name], false)?;
+    let (process, client) = find_client(&system, client_process_name, game_process_name)?;
+
+    connection_info(process, client, force_lock_file)
+}
+
+/// Finds the first client or game process, returning it, and whether it's the client
+fn find_client<'a>(
+    system: &'a System,
+    client_process_name: &str,
+    game_process_name: &str,
+) -> Result<(&'a Process, bool), Error> {
     // Is the client running, or is it the game?
     let mut client = false;
 
@@ -100,89 +187,996 @@ where
         .processes()
         .values()
         .find(|process| {
-            client = process.name() == client_process_name;
-            client || (process.name() == game_process_name)
+            client = is_named(process, client_process_name);
+            client || is_named(process, game_process_name)
         })
         .ok_or(NOT_RUNNING)?;
 
-    // The size of the lock file is typically 53kb, but I am overallocating to stay cautious
-    let mut lock_file = [0; 60];
-    let [port, auth] = if client && !force_lock_file {
-        // The port and auth should always be ASCII, as they are a number and a B64 buffer
-        let cmd = process.cmd().iter().filter_map(|os_str| os_str.to_str());
-        // Use a variable in a higher scope to make sure that port and auth get initialized
-        let mut scoped_auth = None;
-        let mut scoped_port = None;
-
-        // Iterate through the command args, updating the scoped values as we go
-        for s in cmd {
-            if scoped_auth.is_some() && scoped_port.is_some() {
-                break;
-            }
+    Ok((process, client))
+}
 
-            if scoped_auth.is_none() {
-                scoped_auth = s.strip_prefix("--remoting-auth-token=");
+/// Finds the client, keeping the list of processes between calls
+///
+/// [`get_connection_info`] and the other functions here list every process from scratch each time,
+/// this only refreshes the processes, reading the path and command line of new ones,
+/// which is much cheaper when checking for the client every few seconds
+///
+/// ```no_run
+/// use irelia::process_info::ProcessInfoProvider;
+///
+/// let mut provider = ProcessInfoProvider::new();
+///
+/// loop {
+///     println!("{:?}", provider.current_client().map(|info| info.addr()));
+///     std::thread::sleep(std::time::Duration::from_secs(5));
+/// }
+/// ```
+#[derive(Debug)]
+pub struct ProcessInfoProvider {
+    system: System,
+}
+
+impl ProcessInfoProvider {
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            system: System::new(),
+        }
+    }
+
+    /// Refreshes the list of processes, and returns the connection info for the client, see [`get_connection_info`]
+    ///
+    /// # Errors
+    /// This will return an error if the LCU is not running, or the lock file is inaccessible
+    pub fn current_client(&mut self) -> Result<ConnectionInfo, Error> {
+        // The command line is always needed here, as it's used to resolve the region
+        refresh_processes(
+            &mut self.system,
+            &[CLIENT_PROCESS_NAME, GAME_PROCESS_NAME],
+            false,
+        )?;
+
+        let (process, client) = find_client(&self.system, CLIENT_PROCESS_NAME, GAME_PROCESS_NAME)?;
+        connection_info(process, client, false)
+    }
+
+    #[must_use]
+    /// Refreshes the list of processes, and checks whether the game itself is running
+    ///
+    /// The game process starts with the loading screen, so the in game API may not be available yet,
+    /// if the processes can't be listed, this is `false`
+    pub fn is_game_running(&mut self) -> bool {
+        if refresh_processes(&mut self.system, &[GAME_PROCESS_NAME], false).is_err() {
+            return false;
+        }
+
+        self.system
+            .processes()
+            .values()
+            .any(|process| is_named(process, GAME_PROCESS_NAME))
+    }
+}
+
+impl Default for ProcessInfoProvider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Blocks until the client is running, polling every `poll_interval`, and returns its connection info
+///
+/// The client not running, and lock file errors while it's still starting, are retried.
+/// Any other error is returned immediately
+///
+/// # Errors
+/// This will return an error of kind [`ErrorKind::TimedOut`] if the client did not start within `timeout`,
+/// or any error that can't be retried
+pub fn wait_for_client(
+    timeout: Duration,
+    poll_interval: Duration,
+) -> Result<ConnectionInfo, Error> {
+    let deadline = Instant::now() + timeout;
+
+    loop {
+        match get_connection_info(CLIENT_PROCESS_NAME, GAME_PROCESS_NAME, false) {
+            Err(err) if is_retryable(&err) => {}
+            result => return result,
+        }
+
+        let now = Instant::now();
+        if now >= deadline {
+            return Err(WAIT_TIMED_OUT);
+        }
+
+        std::thread::sleep(poll_interval.min(deadline - now));
+    }
+}
+
+#[cfg(feature = "rest")]
+/// The same as [`wait_for_client`], but waits asynchronously between polls
+///
+/// Finding the process blocks, so it's done on the runtime's blocking thread pool
+///
+/// # Errors
+/// This will return an error of kind [`ErrorKind::TimedOut`] if the client did not start within `timeout`,
+/// or any error that can't be retried
+pub async fn wait_for_client_async(
+    timeout: Duration,
+    poll_interval: Duration,
+) -> Result<ConnectionInfo, Error> {
+    let wait = async {
+        loop {
+            let located = crate::utils::runtime::spawn_blocking(|| {
+                get_connection_info(CLIENT_PROCESS_NAME, GAME_PROCESS_NAME, false)
+            })
+            .await;
+
+            match located {
+                Err(err) if is_retryable(&err) => {}
+                result => return result,
             }
 
-            if scoped_port.is_none() {
-                scoped_port = s.strip_prefix("--app-port=");
+            crate::utils::runtime::sleep(poll_interval).await;
+        }
+    };
+
+    crate::utils::runtime::timeout(timeout, wait)
+        .await
+        .unwrap_or(Err(WAIT_TIMED_OUT))
+}
+
+/// Errors that happen while the client is starting up, such as the lock file not being written yet
+fn is_retryable(err: &Error) -> bool {
+    err.kind == ErrorKind::NotRunning || err.is_lockfile_error()
+}
+
+#[must_use]
+/// Checks whether a process with the given PID is still running
+///
+/// With `windows-native`, if the processes can't be listed through WMI, the process is assumed to still be running
+pub fn is_process_running(pid: u32) -> bool {
+    #[cfg(all(target_os = "windows", feature = "windows-native"))]
+    {
+        native::is_process_running(pid).unwrap_or(true)
+    }
+
+    #[cfg(not(all(target_os = "windows", feature = "windows-native")))]
+    {
+        let mut system = System::new();
+        system.refresh_processes_specifics(
+            ProcessesToUpdate::Some(&[Pid::from_u32(pid)]),
+            true,
+            ProcessRefreshKind::nothing(),
+        ) == 1
+    }
+}
+
+/// Gets the port, auth, and region of every running client
+///
+/// This is the same as [`get_running_client`], except it does not stop at the first process found.
+/// If a game is running alongside its own client, only the client is returned, as they share the same API.
+/// This includes both the live client and the PBE if they're running side by side, see [`ConnectionInfo::kind`].
+/// Each process is read on its own thread, so a slow lock file or install directory doesn't hold up the others.
+///
+/// The region is resolved on a best effort basis, first from the `--region=` launch argument,
+/// and then from the `LeagueClientSettings.yaml` file in the install directory. If neither is
+/// available, the region will be `None`, this never causes the connection itself to error.
+///
+/// If nothing is running, the returned `Vec` will be empty.
+#[must_use]
+pub fn get_running_clients(
+    client_process_name: &str,
+    game_process_name: &str,
+    force_lock_file: bool,
+) -> Vec<Result<ConnectionInfo, Error>> {
+    // The command line is always needed here, as it's used to resolve the region
+    let system = match running_processes(&[client_process_name, game_process_name], false) {
+        Ok(system) => system,
+        Err(err) => return vec![Err(err)],
+    };
+
+    let (clients, games): (Vec<_>, Vec<_>) = system
+        .processes()
+        .values()
+        .filter(|process| {
+            is_named(process, client_process_name) || is_named(process, game_process_name)
+        })
+        .partition(|process| is_named(process, client_process_name));
+
+    // A game shares the LCU of the client that launched it, so it only counts if its client isn't running
+    let games = games.into_iter().filter(|game| {
+        let game_dir = install_dir(game, false);
+        game_dir.is_none()
+            || !clients
+                .iter()
+                .any(|client| install_dir(client, true) == game_dir)
+    });
+
+    let processes: Vec<_> = clients
+        .iter()
+        .map(|process| (*process, true))
+        .chain(games.map(|process| (process, false)))
+        .collect();
+
+    std::thread::scope(|scope| {
+        let handles: Vec<_> = processes
+            .into_iter()
+            .map(|(process, client)| {
+                scope.spawn(move || connection_info(process, client, force_lock_file))
+            })
+            .collect();
+
+        handles
+            .into_iter()
+            .map(|handle| {
+                handle
+                    .join()
+                    .unwrap_or_else(|panic| std::panic::resume_unwind(panic))
+            })
+            .collect()
+    })
+}
+
+/// The environment variable read by [`get_client_from_env`], this can be the path to the lock file, or the directory it's in
+pub const LOCK_FILE_PATH_ENV: &str = "IRELIA_LOCKFILE_PATH";
+
+/// Reads the connection from the lock file at `path`, without looking for the client process
+///
+/// `path` can either be the lock file itself, or the install directory containing it.
+/// This is useful where processes can't be listed, such as in containers or sandboxes,
+/// but nothing checks that the process that wrote the lock file is still running.
+///
+/// # Errors
+/// This will return an error if the lock file can't be read, or is malformed
+pub fn get_client_from_lock_file(path: impl AsRef<Path>) -> Result<ConnectionInfo, Error> {
+    lock_file_connection_info(path.as_ref(), DetectionSource::LockFile)
+}
+
+/// The same as [`get_client_from_lock_file`], using the path in the [`LOCK_FILE_PATH_ENV`] environment variable
+///
+/// Returns `None` if the environment variable is not set
+///
+/// # Errors
+/// This will return an error if the lock file can't be read, or is malformed
+#[must_use]
+pub fn get_client_from_env() -> Option<Result<ConnectionInfo, Error>> {
+    let path = std::env::var_os(LOCK_FILE_PATH_ENV)?;
+    Some(lock_file_connection_info(
+        Path::new(&path),
+        DetectionSource::Environment,
+    ))
+}
+
+fn lock_file_connection_info(
+    path: &Path,
+    source: DetectionSource,
+) -> Result<ConnectionInfo, Error> {
+    let path = if path.is_dir() {
+        path.join("lockfile")
+    } else {
+        path.to_path_buf()
+    };
+
+    let lock_file = std::fs::read_to_string(&path)?;
+    let lock_file = LockFile::parse(&lock_file)?;
+
+    let (addr, auth_header) = encode_credentials(lock_file.port, lock_file.auth, |auth_header| {
+        Box::from(auth_header)
+    })?;
+
+    let install_dir = path.parent().map(Path::to_path_buf);
+    let region = install_dir.as_deref().and_then(settings_region);
+
+    Ok(ConnectionInfo {
+        addr,
+        auth_header,
+        auth_token: lock_file.auth.into(),
+        protocol: lock_file.protocol.map(Box::from),
+        pid: lock_file.pid,
+        process_name: lock_file.name.into(),
+        kind: ClientKind::new(region.as_deref(), install_dir.as_deref()),
+        install_dir,
+        region,
+        locale: None,
+        rso_platform_id: None,
+        source,
+    })
+}
+
+/// Reads the credentials from the process, along with everything else in [`ConnectionInfo`]
+fn connection_info(
+    process: &Process,
+    client: bool,
+    force_lock_file: bool,
+) -> Result<ConnectionInfo, Error> {
+    let (addr, (auth_header, auth_token, protocol)) =
+        process_credentials(process, client, force_lock_file, |credentials| {
+            (
+                Box::from(credentials.auth_header),
+                Box::from(credentials.auth_token),
+                credentials.protocol.map(Box::from),
+            )
+        })?;
+
+    let install_dir = install_dir(process, client);
+    let region = process_region(process, client);
+
+    Ok(ConnectionInfo {
+        addr,
+        auth_header,
+        auth_token,
+        protocol,
+        pid: Some(process.pid().as_u32()),
+        process_name: process.name().to_string_lossy().into(),
+        kind: ClientKind::new(region.as_deref(), install_dir.as_deref()),
+        install_dir,
+        region,
+        locale: process_arg(process, "--locale=").map(Box::from),
+        rso_platform_id: process_arg(process, "--rso_platform_id=").map(Box::from),
+        source: DetectionSource::new(client, force_lock_file),
+    })
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// Connection data for a single running client, returned by [`get_running_clients`] and [`get_connection_info`]
+pub struct ConnectionInfo {
+    addr: SocketAddrV4,
+    auth_header: Box<str>,
+    auth_token: Box<str>,
+    protocol: Option<Box<str>>,
+    pid: Option<u32>,
+    process_name: Box<str>,
+    install_dir: Option<PathBuf>,
+    region: Option<Box<str>>,
+    locale: Option<Box<str>>,
+    rso_platform_id: Option<Box<str>>,
+    kind: ClientKind,
+    source: DetectionSource,
+}
+
+impl ConnectionInfo {
+    #[must_use]
+    /// The address the LCU API is being served on
+    pub const fn addr(&self) -> SocketAddrV4 {
+        self.addr
+    }
+
+    #[must_use]
+    /// The port the LCU API is being served on
+    pub const fn port(&self) -> u16 {
+        self.addr.port()
+    }
+
+    #[must_use]
+    /// The pre-encoded `Authorization` header, in the format `Basic <base64>`
+    pub fn auth_header(&self) -> &str {
+        &self.auth_header
+    }
+
+    #[must_use]
+    /// The raw auth token, the password for the `riot` user with basic auth
+    pub fn auth_token(&self) -> &str {
+        &self.auth_token
+    }
+
+    #[must_use]
+    /// The protocol from the lock file, which is always `https` in practice
+    ///
+    /// This is `None` if the credentials were read from the command line, as it's only stored in the lock file
+    pub fn protocol(&self) -> Option<&str> {
+        self.protocol.as_deref()
+    }
+
+    #[must_use]
+    /// The PID of the process the credentials were read from
+    ///
+    /// This is `None` if they were read straight from a lock file that didn't have one, see [`get_client_from_lock_file`]
+    pub const fn pid(&self) -> Option<u32> {
+        self.pid
+    }
+
+    #[must_use]
+    /// The name of the process the credentials were read from, either the client or the game
+    pub fn process_name(&self) -> &str {
+        &self.process_name
+    }
+
+    #[must_use]
+    /// The directory the client is installed in, this is where the lock file and the client's config are stored
+    pub fn install_dir(&self) -> Option<&Path> {
+        self.install_dir.as_deref()
+    }
+
+    #[must_use]
+    /// The region the client belongs to, such as `EUW` or `NA`, if it could be determined
+    pub fn region(&self) -> Option<&str> {
+        self.region.as_deref()
+    }
+
+    #[must_use]
+    /// The locale the client is running in, such as `en_GB`, from the `--locale=` launch argument
+    ///
+    /// This is `None` if the launch arguments couldn't be read
+    pub fn locale(&self) -> Option<&str> {
+        self.locale.as_deref()
+    }
+
+    #[must_use]
+    /// The platform ID the account belongs to, such as `EUW1` or `NA1`, from the `--rso_platform_id=` launch argument
+    ///
+    /// This is the routing value used by the Riot API. It's `None` if the launch arguments couldn't be read
+    pub fn rso_platform_id(&self) -> Option<&str> {
+        self.rso_platform_id.as_deref()
+    }
+
+    #[must_use]
+    /// Whether this is the live client or the PBE
+    pub const fn kind(&self) -> ClientKind {
+        self.kind
+    }
+
+    #[must_use]
+    /// Who distributed the client, guessed from the install directory, this is [`ClientDistribution::Riot`] if it's unknown
+    pub fn distribution(&self) -> ClientDistribution {
+        self.install_dir
+            .as_deref()
+            .map_or(ClientDistribution::Riot, ClientDistribution::from_path)
+    }
+
+    #[must_use]
+    /// Where the port and auth token were read from
+    pub const fn source(&self) -> DetectionSource {
+        self.source
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+/// Who distributed the client, as the Garena and Tencent installs are laid out differently
+///
+/// Every distribution uses the same process names, but the Garena and Tencent installs keep the client
+/// in a `LeagueClient` directory next to the `Game` directory, instead of in the root of the install.
+/// This is detected from the install path, so nothing needs to be configured to find them
+pub enum ClientDistribution {
+    Riot,
+    Garena,
+    /// Tencent, launched through `WeGame` in China
+    Tencent,
+}
+
+impl ClientDistribution {
+    /// Guesses the distribution from the directories in the path, defaulting to Riot
+    fn from_path(path: &Path) -> Self {
+        let mut components = path
+            .components()
+            .filter_map(|component| component.as_os_str().to_str());
+
+        components
+            .find_map(|component| {
+                if component.contains("Garena") {
+                    Some(Self::Garena)
+                } else if ["英雄联盟", "腾讯游戏", "WeGame", "Tencent"]
+                    .iter()
+                    .any(|name| component.contains(name))
+                {
+                    Some(Self::Tencent)
+                } else {
+                    None
+                }
+            })
+            .unwrap_or(Self::Riot)
+    }
+
+    /// Gets the directory the lock file is in, from the root of the install the game is in
+    fn game_lock_file_dir(self, root: &Path) -> PathBuf {
+        match self {
+            Self::Riot => root.to_path_buf(),
+            Self::Garena | Self::Tencent => root.join("LeagueClient"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+/// Which client a connection is for, as the live client and the PBE can be installed, and running, side by side
+pub enum ClientKind {
+    Live,
+    /// The Public Beta Environment
+    Pbe,
+}
+
+impl ClientKind {
+    /// The PBE is its own region, and is installed to `League of Legends (PBE)` by default
+    fn new(region: Option<&str>, install_dir: Option<&Path>) -> Self {
+        let pbe_region = region.is_some_and(|region| region.eq_ignore_ascii_case("PBE"));
+        let pbe_dir = install_dir
+            .and_then(Path::file_name)
+            .and_then(|name| name.to_str())
+            .is_some_and(|name| name.to_ascii_uppercase().contains("PBE"));
+
+        if pbe_region || pbe_dir {
+            Self::Pbe
+        } else {
+            Self::Live
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+/// Where the credentials for a connection were found
+pub enum DetectionSource {
+    /// The launch arguments of the client process
+    CommandLine,
+    /// The `lockfile` in the install directory, used for the game process, or when the lock file is forced
+    LockFile,
+    /// The lock file at the path in [`LOCK_FILE_PATH_ENV`], rather than found through the running process
+    Environment,
+}
+
+impl DetectionSource {
+    /// Only the client has the credentials in its launch arguments, the game always needs the lock file
+    const fn new(client: bool, force_lock_file: bool) -> Self {
+        if client && !force_lock_file {
+            Self::CommandLine
+        } else {
+            Self::LockFile
+        }
+    }
+}
+
+/// Gets the port and auth for the Riot Client's own remoting API
+///
+/// This is a separate API from the LCU, and is used for things like launching products or account info.
+/// The Riot Client process is checked first, using its `--app-port=` and `--remoting-auth-token=` args.
+/// The League client is also launched with `--riotclient-app-port=` and `--riotclient-auth-token=`,
+/// so it's used as a fallback when only the League client can be found.
+///
+/// If the Riot Client is running, but its command line can't be read, its own lock file is read instead,
+/// this is stored in `Riot Games/Riot Client/Config` in the local app data directory.
+///
+/// This never affects the detection done by [`get_running_client`].
+///
+/// # Errors
+/// This will return an error if neither the Riot Client or League client are running,
+/// or if their command line and the lock file could not be read.
+pub fn get_running_riot_client(
+    riot_client_process_name: &str,
+    client_process_name: &str,
+) -> Result<RiotClientConnection, Error> {
+    let system = running_processes(&[riot_client_process_name, client_process_name], false)?;
+
+    let mut processes = system.processes().values();
+    // Declared here so the credentials can borrow from it
+    let lock_file;
+
+    let credentials = if let Some(process) = processes
+        .clone()
+        .find(|process| is_named(process, riot_client_process_name))
+    {
+        match args_credentials(process, "--app-port=", "--remoting-auth-token=") {
+            Ok(credentials) => credentials,
+            Err(err) => {
+                let path = riot_client_lock_file().ok_or(err)?;
+                lock_file = std::fs::read_to_string(path)?;
+
+                let lock_file = LockFile::parse(&lock_file)?;
+                [lock_file.port, lock_file.auth]
             }
         }
+    } else {
+        let process = processes
+            .find(|process| is_named(process, client_process_name))
+            .ok_or(RIOT_CLIENT_NOT_RUNNING)?;
+
+        args_credentials(
+            process,
+            "--riotclient-app-port=",
+            "--riotclient-auth-token=",
+        )?
+    };
+
+    let [port, auth] = credentials;
+    let (addr, auth_header) = encode_credentials(port, auth, |auth: &str| Box::from(auth))?;
 
-        // Check that we found a port and auth key, otherwise error
-        [
-            scoped_port.ok_or(PORT_NOT_FOUND)?,
-            scoped_auth.ok_or(AUTH_NOT_FOUND)?,
-        ]
+    Ok(RiotClientConnection { addr, auth_header })
+}
+
+/// Gets the path to the Riot Client's lock file, `None` if the platform doesn't have a known location for it
+fn riot_client_lock_file() -> Option<PathBuf> {
+    #[cfg(target_os = "windows")]
+    let dir = std::env::var_os("LOCALAPPDATA").map(PathBuf::from);
+    #[cfg(target_os = "macos")]
+    let dir = std::env::var_os("HOME").map(|home| {
+        PathBuf::from(home)
+            .join("Library")
+            .join("Application Support")
+    });
+    // Under Wine, the app data directory is inside the prefix, which isn't known without the process
+    #[cfg(not(any(target_os = "windows", target_os = "macos")))]
+    let dir: Option<PathBuf> = None;
+
+    dir.map(|dir| {
+        dir.join("Riot Games")
+            .join("Riot Client")
+            .join("Config")
+            .join("lockfile")
+    })
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// Connection data for the Riot Client API, returned by [`get_running_riot_client`]
+pub struct RiotClientConnection {
+    addr: SocketAddrV4,
+    auth_header: Box<str>,
+}
+
+impl RiotClientConnection {
+    #[must_use]
+    /// The address the Riot Client API is being served on
+    pub const fn addr(&self) -> SocketAddrV4 {
+        self.addr
+    }
+
+    #[must_use]
+    /// The pre-encoded `Authorization` header, in the format `Basic <base64>`
+    pub fn auth_header(&self) -> &str {
+        &self.auth_header
+    }
+}
+
+/// Gets the list of processes, only refreshing what's needed to read the credentials
+fn running_processes(names: &[&str], force_lock_file: bool) -> Result<System, Error> {
+    let mut system = System::new();
+    refresh_processes(&mut system, names, force_lock_file)?;
+    Ok(system)
+}
+
+#[cfg(all(target_os = "windows", feature = "windows-native"))]
+/// Refreshes the list of processes, only listing those named one of `names`
+///
+/// WMI always reads the path and command line, so `force_lock_file` makes no difference
+fn refresh_processes(
+    system: &mut System,
+    names: &[&str],
+    _force_lock_file: bool,
+) -> Result<(), Error> {
+    system.refresh(names)
+}
+
+#[cfg(not(all(target_os = "windows", feature = "windows-native")))]
+/// Refreshes the list of processes, only reading the path and command line of those that could have one of `names`
+///
+/// Listing the processes by name is cheap, it's reading the details of every process that's slow.
+/// This never fails, it returns a `Result` to match the `windows-native` backend
+#[allow(clippy::unnecessary_wraps)]
+fn refresh_processes(
+    system: &mut System,
+    names: &[&str],
+    force_lock_file: bool,
+) -> Result<(), Error> {
+    system.refresh_processes_specifics(ProcessesToUpdate::All, true, ProcessRefreshKind::nothing());
+
+    let candidates: Vec<Pid> = system
+        .processes()
+        .values()
+        .filter(|process| names.iter().any(|name| may_be_named(process, name)))
+        .map(Process::pid)
+        .collect();
+
+    if !candidates.is_empty() {
+        system.refresh_processes_specifics(
+            ProcessesToUpdate::Some(&candidates),
+            false,
+            process_refresh_kind(force_lock_file),
+        );
+    }
+
+    Ok(())
+}
+
+#[cfg(not(all(target_os = "windows", feature = "windows-native")))]
+/// Checks if the process could have the given name, before its command line has been read, see [`is_named`]
+fn may_be_named(process: &Process, name: &str) -> bool {
+    #[cfg(target_os = "linux")]
+    if let Some(short_name) = process.name().to_str() {
+        if is_truncated_name(short_name, name) {
+            return true;
+        }
+    }
+
+    process.name() == name
+}
+
+#[cfg(not(all(target_os = "windows", feature = "windows-native")))]
+/// What needs to be refreshed on each process to read the credentials
+fn process_refresh_kind(force_lock_file: bool) -> ProcessRefreshKind {
+    // If we always read the lock file, we never need to get the command line of the process
+    let cmd = if force_lock_file {
+        sysinfo::UpdateKind::Never
+    } else {
+        sysinfo::UpdateKind::OnlyIfNotSet
+    };
+    // No matter what, the path to the process is required
+    let refresh_kind = ProcessRefreshKind::nothing()
+        .with_exe(sysinfo::UpdateKind::OnlyIfNotSet)
+        .with_cmd(cmd);
+
+    // Under Wine, the prefix the process is running in is needed to find the lock file
+    #[cfg(target_os = "linux")]
+    let refresh_kind = refresh_kind
+        .with_cmd(sysinfo::UpdateKind::OnlyIfNotSet)
+        .with_environ(sysinfo::UpdateKind::OnlyIfNotSet);
+
+    refresh_kind
+}
+
+/// Checks if the process has the given name
+///
+/// On Linux, the name is truncated to 15 bytes by the kernel, so any name that's cut short is compared against
+/// the executable in the command line instead, which under Wine is the Windows path of the executable
+fn is_named(process: &Process, name: &str) -> bool {
+    if process.name() == name {
+        return true;
+    }
+
+    #[cfg(target_os = "linux")]
+    if let Some(short_name) = process.name().to_str() {
+        return is_truncated_name(short_name, name)
+            && process
+                .cmd()
+                .first()
+                .and_then(|exe| exe.to_str())
+                .is_some_and(|exe| exe.rsplit(['\\', '/']).next() == Some(name));
+    }
+
+    false
+}
+
+#[cfg(target_os = "linux")]
+/// The length Linux truncates process names to, excluding the null terminator
+const TASK_COMM_LEN: usize = 15;
+
+#[cfg(target_os = "linux")]
+fn is_truncated_name(short_name: &str, name: &str) -> bool {
+    short_name.len() == TASK_COMM_LEN && name.starts_with(short_name)
+}
+
+/// Gets the directory the client is installed in, this is where the lock file is stored
+fn install_dir(process: &Process, client: bool) -> Option<PathBuf> {
+    // We have to walk back twice to get the path of the lock file relative to the path of the game
+    // This can only be None on Linux according to the docs, so we should be fine everywhere else
+    #[cfg(target_os = "linux")]
+    let path = wine_exe(process).or_else(|| process.exe().map(Path::to_path_buf))?;
+    #[cfg(not(target_os = "linux"))]
+    let path = process.exe()?;
+
+    let dir = path.parent()?;
+    // Sadly, we're relying on how the client structures things here
+    // Walking back a whole folder in order to get the lock file
+    if client {
+        Some(dir.to_path_buf())
     } else {
-        // We have to walk back twice to get the path of the lock file relative to the path of the game
-        // This can only be None on Linux according to the docs, so we should be fine everywhere else
-        let path = process.exe().ok_or(LOCK_FILE_NOT_FOUND)?;
-
-        let mut dir = path.parent().ok_or(LOCK_FILE_NOT_FOUND)?;
-        // Sadly, we're relying on how the client structures things here
-        // Walking back a whole folder in order to get the lock file
-        if !client {
-            // If we're looking at the game and not the client, we need to walk back once more
-            dir = dir.parent().ok_or(LOCK_FILE_NOT_FOUND)?;
+        // If we're looking at the game and not the client, we need to walk back once more
+        let root = dir.parent()?;
+        Some(ClientDistribution::from_path(root).game_lock_file_dir(root))
+    }
+}
+
+#[cfg(target_os = "linux")]
+/// Gets the host path of a process running under Wine, from the Windows path in its command line
+///
+/// The exe of a Wine process is Wine itself, so it can't be used to find the install directory
+fn wine_exe(process: &Process) -> Option<PathBuf> {
+    let exe = process.cmd().first()?.to_str()?;
+
+    let prefix = process
+        .environ()
+        .iter()
+        .filter_map(|var| var.to_str())
+        .find_map(|var| var.strip_prefix("WINEPREFIX="))
+        .map(PathBuf::from)
+        .or_else(|| Some(PathBuf::from(std::env::var_os("HOME")?).join(".wine")))?;
+
+    wine_to_host(&prefix, exe)
+}
+
+#[cfg(target_os = "linux")]
+/// Translates a Windows path, such as `C:\Riot Games\League of Legends`, to a path on the host
+///
+/// Every drive is a symlink in `dosdevices`, `C:` to `drive_c` and `Z:` to `/` by default,
+/// so this works for any drive mapping the prefix has
+fn wine_to_host(prefix: &Path, path: &str) -> Option<PathBuf> {
+    let (drive, rest) = path.split_once(':')?;
+
+    if drive.len() != 1 || !drive.chars().all(|drive| drive.is_ascii_alphabetic()) {
+        return None;
+    }
+
+    let mut host = prefix
+        .join("dosdevices")
+        .join(format!("{}:", drive.to_ascii_lowercase()));
+    host.extend(rest.split('\\').filter(|part| !part.is_empty()));
+
+    Some(host)
+}
+
+/// The credentials passed to the closure given to [`process_credentials`]
+struct Credentials<'a> {
+    /// The encoded auth header, in the format `Basic <base64>`
+    auth_header: &'a str,
+    auth_token: &'a str,
+    protocol: Option<&'a str>,
+}
+
+/// Reads the port and auth from the process, passing the encoded auth header to `f`
+///
+/// This takes a closure so the auth header never has to leave the stack unless the caller wants it to
+fn process_credentials<R>(
+    process: &Process,
+    client: bool,
+    force_lock_file: bool,
+    f: impl FnOnce(Credentials<'_>) -> R,
+) -> Result<(SocketAddrV4, R), Error> {
+    // The size of the lock file is typically 53kb, but I am overallocating to stay cautious
+    let mut lock_file = [0; 60];
+    let ([port, auth], protocol) =
+        if DetectionSource::new(client, force_lock_file) == DetectionSource::CommandLine {
+            (
+                args_credentials(process, "--app-port=", "--remoting-auth-token=")?,
+                None,
+            )
+        } else {
+            let dir = install_dir(process, client).ok_or(LOCK_FILE_NOT_FOUND)?;
+
+            let mut file = std::fs::File::open(dir.join("lockfile"))?;
+            // This len shouldn't be more than a few bytes
+            let len = file
+                .metadata()?
+                .len()
+                .try_into()
+                .expect("This file is always ~60 bytes");
+
+            // Read the file initially
+            let mut read = file.read(&mut lock_file)?;
+
+            // Make sure the entire file was read, though it is so small I can't imagine it wouldn't be
+            while read != len {
+                read += file.read(&mut lock_file[read..])?;
+            }
+
+            // Make sure that we're not over reading into 0's
+            let lock_file = std::str::from_utf8(&lock_file[..len])?;
+
+            let lock_file = LockFile::parse(lock_file)?;
+
+            // Only the client writes its own PID to the lock file, the game's PID never matches
+            if client {
+                check_lock_file_owner(&lock_file, &lock_file_owners(process))?;
+            }
+
+            ([lock_file.port, lock_file.auth], lock_file.protocol)
         };
 
-        let mut file = std::fs::File::open(dir.join("lockfile"))?;
-        // This len shouldn't be more than a few bytes
-        let len = file
-            .metadata()?
-            .len()
-            .try_into()
-            .expect("This file is always ~60 bytes");
+    encode_credentials(port, auth, |auth_header| {
+        f(Credentials {
+            auth_header,
+            auth_token: auth,
+            protocol,
+        })
+    })
+}
 
-        // Read the file initially
-        let mut read = file.read(&mut lock_file)?;
+/// The PIDs that can have written the lock file for `process`
+///
+/// The lock file is written by `LeagueClient`, while the process found is usually `LeagueClientUx`,
+/// which `LeagueClient` starts, passing its own PID as `--app-pid`
+fn lock_file_owners(process: &Process) -> [Option<u32>; 3] {
+    [
+        Some(process.pid().as_u32()),
+        process.parent().map(Pid::as_u32),
+        process_arg(process, "--app-pid=").and_then(|pid| pid.parse().ok()),
+    ]
+}
 
-        // Make sure the entire file was read, though it is so small I can't imagine it wouldn't be
-        while read != len {
-            read += file.read(&mut lock_file[read..])?;
-        }
+/// Fails with [`ErrorKind::LockFileStale`] if the lock file was written by none of `owners`
+///
+/// A lock file without a PID is not checked
+fn check_lock_file_owner(lock_file: &LockFile<'_>, owners: &[Option<u32>]) -> Result<(), Error> {
+    match lock_file.pid {
+        Some(pid) if !owners.contains(&Some(pid)) => Err(LOCK_FILE_STALE),
+        _ => Ok(()),
+    }
+}
 
-        // Make sure that we're not over reading into 0's
-        let lock_file = std::str::from_utf8(&lock_file[..len])?;
+/// The fields of the lock file, which is in the format `name:pid:port:auth:protocol`
+struct LockFile<'a> {
+    /// The name of the process that wrote the file, such as `LeagueClient`
+    name: &'a str,
+    /// The PID of the client that wrote the file, `None` if the file doesn't have one
+    pid: Option<u32>,
+    port: &'a str,
+    auth: &'a str,
+    protocol: Option<&'a str>,
+}
 
+impl<'a> LockFile<'a> {
+    fn parse(lock_file: &'a str) -> Result<Self, Error> {
         // Split the lock file on `:` which separates the different fields
         // Because lock_file is from a higher scope, we can split the string here
-        // and return two string references later on
+        // and return string references later on
         let mut split = lock_file.split(':');
 
-        [
-            // Get the 3rd field, which should be the port
-            split
-                .nth(2)
+        Ok(Self {
+            name: split.next().unwrap_or_default(),
+            pid: match split.next() {
+                None | Some("") => None,
+                Some(pid) => Some(pid.parse().map_err(|_| LOCK_FILE_PID_INVALID)?),
+            },
+            port: split
+                .next()
                 .ok_or(PORT_NOT_FOUND.set_lockfile_error(true))?,
-            // We moved the cursor, so the fourth element is the very next one
-            // Which should be the auth string
-            split
+            auth: split
                 .next()
                 .ok_or(AUTH_NOT_FOUND.set_lockfile_error(true))?,
-        ]
-    };
+            protocol: split
+                .next()
+                .map(str::trim)
+                .filter(|protocol| !protocol.is_empty()),
+        })
+    }
+}
+
+/// Reads the port and auth from the command line of the process, using the given argument prefixes
+fn args_credentials<'a>(
+    process: &'a Process,
+    port_prefix: &str,
+    auth_prefix: &str,
+) -> Result<[&'a str; 2], Error> {
+    // The port and auth should always be ASCII, as they are a number and a B64 buffer
+    let cmd = process.cmd().iter().filter_map(|os_str| os_str.to_str());
+    // Use a variable in a higher scope to make sure that port and auth get initialized
+    let mut scoped_auth = None;
+    let mut scoped_port = None;
+
+    // Iterate through the command args, updating the scoped values as we go
+    for s in cmd {
+        if scoped_auth.is_some() && scoped_port.is_some() {
+            break;
+        }
+
+        if scoped_auth.is_none() {
+            scoped_auth = s.strip_prefix(auth_prefix);
+        }
+
+        if scoped_port.is_none() {
+            scoped_port = s.strip_prefix(port_prefix);
+        }
+    }
+
+    // Check that we found a port and auth key, otherwise error
+    Ok([
+        scoped_port.ok_or(PORT_NOT_FOUND)?,
+        scoped_auth.ok_or(AUTH_NOT_FOUND)?,
+    ])
+}
+
+/// Parses the port, and encodes the auth into a basic auth header, passing it to `f`
+pub(crate) fn encode_credentials<R>(
+    port: &str,
+    auth: &str,
+    f: impl FnOnce(&str) -> R,
+) -> Result<(SocketAddrV4, R), Error> {
+    let res = encode_auth_header(auth, f)?;
+
+    let port: u16 = port.parse().map_err(|err: ParseIntError| {
+        Error::new_string(ErrorKind::PortNotFound, err.to_string())
+    })?;
+
+    Ok((SocketAddrV4::new(Ipv4Addr::LOCALHOST, port), res))
+}
+
+/// Encodes the auth into a basic auth header for the `riot` user, passing it to `f`
+fn encode_auth_header<R>(auth: &str, f: impl FnOnce(&str) -> R) -> Result<R, Error> {
+    const RIOT_PREFIX: &[u8] = b"riot:";
+    const BASIC_PREFIX: &[u8] = b"Basic ";
 
     // Prevent the pre-encoded base64 string from allocating
     let pre_encoded_buffer_len = auth.len() + RIOT_PREFIX.len();
@@ -193,6 +1187,9 @@ where
         &mut [0; 22 + RIOT_PREFIX.len()]
     };
 
+    // Tokens shorter than usual would otherwise encode the unused end of the stack buffers
+    let buffer = &mut buffer[..pre_encoded_buffer_len];
+
     buffer[..RIOT_PREFIX.len()].copy_from_slice(RIOT_PREFIX);
     buffer[RIOT_PREFIX.len()..auth.len() + RIOT_PREFIX.len()].copy_from_slice(auth.as_bytes());
 
@@ -204,23 +1201,59 @@ where
         &mut [b'='; 36 + BASIC_PREFIX.len()]
     };
 
+    let auth_header_buffer = &mut auth_header_buffer[..auth_header_len + BASIC_PREFIX.len()];
+
     auth_header_buffer[..BASIC_PREFIX.len()].copy_from_slice(BASIC_PREFIX);
 
     // The auth header has to be base64 encoded, so that's happens here
     ENCODER.internal_encode(buffer, &mut auth_header_buffer[BASIC_PREFIX.len()..]);
 
-    let port: u16 = port.parse().map_err(|err: ParseIntError| {
-        Error::new_string(ErrorKind::PortNotFound, err.to_string())
-    })?;
+    // Base64 is always ASCII, but if the encoder ever breaks that, it shouldn't be reported as a lock file error
+    let auth_header_buffer =
+        std::str::from_utf8(auth_header_buffer).map_err(|_| INVALID_AUTH_HEADER)?;
 
-    let addr = SocketAddrV4::new(Ipv4Addr::LOCALHOST, port);
+    // Format the header so that it can be used as a header
+    // For the LCU API
+    Ok(f(auth_header_buffer))
+}
 
-    let auth_header_buffer = std::str::from_utf8(auth_header_buffer)?;
+/// Best effort attempt at getting the region of the process, this returns `None` instead of erroring
+fn process_region(process: &Process, client: bool) -> Option<Box<str>> {
+    if let Some(region) = process_arg(process, "--region=") {
+        return Some(region.into());
+    }
 
-    // Format the port and header so that they can be used as headers
-    // For the LCU API
-    let res = T::from_str(auth_header_buffer);
-    Ok((addr, res))
+    settings_region(&install_dir(process, client)?)
+}
+
+/// Reads the region from the client settings in the install directory
+fn settings_region(install_dir: &Path) -> Option<Box<str>> {
+    let settings =
+        std::fs::read_to_string(install_dir.join("Config").join("LeagueClientSettings.yaml"))
+            .ok()?;
+
+    region_from_settings(&settings).map(Box::from)
+}
+
+/// Finds the value of the launch argument starting with `prefix`
+fn process_arg<'a>(process: &'a Process, prefix: &str) -> Option<&'a str> {
+    process
+        .cmd()
+        .iter()
+        .filter_map(|os_str| os_str.to_str())
+        .find_map(|arg| arg.strip_prefix(prefix))
+}
+
+/// Finds the `region: "XX"` entry in the client settings, without pulling in a YAML parser
+fn region_from_settings(settings: &str) -> Option<&str> {
+    settings.lines().find_map(|line| {
+        let region = line
+            .trim()
+            .strip_prefix("region:")?
+            .trim()
+            .trim_matches('"');
+        (!region.is_empty()).then_some(region)
+    })
 }
 
 #[derive(Debug, Clone)]
@@ -288,9 +1321,21 @@ impl Error {
 pub enum ErrorKind {
     Io(std::io::ErrorKind),
     LockFileNotFound,
+    /// The PID in the lock file does not match the running client, so it was left behind by a previous session
+    LockFileStale,
+    /// The PID in the lock file is not a number, lock files without a PID are still read
+    LockFilePidInvalid,
     AuthTokenNotFound,
     PortNotFound,
     NotRunning,
+    /// The auth header could not be encoded, this is always a bug in the encoder
+    InvalidAuthHeader,
+    /// The process the connection was made to is no longer running
+    ClientClosed,
+    /// The client did not start in time, returned by [`wait_for_client`]
+    TimedOut,
+    /// The running processes could not be listed, this only happens with the `windows-native` backend
+    ProcessListFailed,
 }
 
 impl From<std::io::Error> for Error {
@@ -317,7 +1362,12 @@ impl From<std::str::Utf8Error> for Error {
 
 #[cfg(test)]
 mod tests {
-    use super::{get_running_client, CLIENT_PROCESS_NAME, GAME_PROCESS_NAME};
+    use super::{
+        check_lock_file_owner, encode_credentials, get_running_client, get_running_clients,
+        get_running_riot_client, is_process_running, region_from_settings, ClientDistribution,
+        ClientKind, ErrorKind, LockFile, CLIENT_PROCESS_NAME, GAME_PROCESS_NAME,
+        RIOT_CLIENT_PROCESS_NAME,
+    };
     use hyper::http::HeaderValue;
     use sysinfo::{ProcessRefreshKind, RefreshKind, System};
 
@@ -329,6 +1379,72 @@ mod tests {
         println!("{port} {pass:?}");
     }
 
+    #[ignore = "This is only needed for testing, and doesn't need to be run all the time"]
+    #[test]
+    fn test_running_clients() {
+        for client in get_running_clients(CLIENT_PROCESS_NAME, GAME_PROCESS_NAME, false) {
+            println!("{client:?}");
+        }
+    }
+
+    #[ignore = "This is only needed for testing, and doesn't need to be run all the time"]
+    #[test]
+    fn test_riot_client() {
+        let riot_client =
+            get_running_riot_client(RIOT_CLIENT_PROCESS_NAME, CLIENT_PROCESS_NAME).unwrap();
+        println!("{riot_client:?}");
+    }
+
+    #[test]
+    fn test_region_from_settings() {
+        const SETTINGS: &str = "install:\n  globals:\n    locale: \"en_GB\"\n    region: \"EUW\"\n";
+
+        assert_eq!(region_from_settings(SETTINGS), Some("EUW"));
+        assert_eq!(region_from_settings("region: \"\"\n"), None);
+        assert_eq!(region_from_settings("locale: \"en_GB\""), None);
+    }
+
+    #[test]
+    fn test_client_distribution() {
+        use std::path::Path;
+
+        let riot = Path::new("C:/Riot Games/League of Legends");
+        let garena = Path::new("C:/Garena/Games/32775");
+        let tencent = Path::new("D:/WeGameApps/英雄联盟");
+
+        assert_eq!(
+            ClientDistribution::from_path(riot),
+            ClientDistribution::Riot
+        );
+        assert_eq!(
+            ClientDistribution::from_path(garena),
+            ClientDistribution::Garena
+        );
+        assert_eq!(
+            ClientDistribution::from_path(tencent),
+            ClientDistribution::Tencent
+        );
+
+        assert_eq!(ClientDistribution::Riot.game_lock_file_dir(riot), riot);
+        assert_eq!(
+            ClientDistribution::Tencent.game_lock_file_dir(tencent),
+            tencent.join("LeagueClient")
+        );
+    }
+
+    #[test]
+    fn test_client_kind() {
+        use std::path::Path;
+
+        let live = Path::new("C:/Riot Games/League of Legends");
+        let pbe = Path::new("C:/Riot Games/League of Legends (PBE)");
+
+        assert_eq!(ClientKind::new(Some("EUW"), Some(live)), ClientKind::Live);
+        assert_eq!(ClientKind::new(Some("PBE"), Some(live)), ClientKind::Pbe);
+        assert_eq!(ClientKind::new(None, Some(pbe)), ClientKind::Pbe);
+        assert_eq!(ClientKind::new(None, None), ClientKind::Live);
+    }
+
     #[ignore = "This is only needed for testing, and doesn't need to be run all the time"]
     #[test]
     fn test_process_args() {
@@ -364,4 +1480,172 @@ mod tests {
 
         println!("{process:?}");
     }
+
+    #[test]
+    fn test_encode_credentials() {
+        let (addr, header) = encode_credentials("2999", "test", str::to_owned).unwrap();
+        assert_eq!(addr.port(), 2999);
+        assert_eq!(header, "Basic cmlvdDp0ZXN0");
+
+        assert!(encode_credentials("port", "test", str::to_owned).is_err());
+    }
+
+    #[test]
+    fn test_is_process_running() {
+        assert!(is_process_running(std::process::id()));
+    }
+
+    #[test]
+    fn test_client_from_lock_file() {
+        use super::{get_client_from_lock_file, DetectionSource};
+
+        let dir = std::env::temp_dir().join(format!("irelia-lock-file-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("lockfile"), "LeagueClient:12345:52437:test:https").unwrap();
+
+        let info = get_client_from_lock_file(&dir).unwrap();
+        assert_eq!(info.port(), 52437);
+        assert_eq!(info.pid(), Some(12345));
+        assert_eq!(info.auth_token(), "test");
+        assert_eq!(info.auth_header(), "Basic cmlvdDp0ZXN0");
+        assert_eq!(info.process_name(), "LeagueClient");
+        assert_eq!(info.install_dir(), Some(dir.as_path()));
+        assert_eq!(info.source(), DetectionSource::LockFile);
+
+        assert_eq!(
+            get_client_from_lock_file(dir.join("lockfile")).unwrap(),
+            info
+        );
+
+        // A lock file without a PID is still read
+        std::fs::write(dir.join("lockfile"), "LeagueClient::52437:test:https").unwrap();
+        let info = get_client_from_lock_file(&dir).unwrap();
+        assert_eq!(info.pid(), None);
+        assert_eq!(info.port(), 52437);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+        assert!(get_client_from_lock_file(&dir).is_err());
+    }
+
+    #[ignore = "This is only needed for testing, and doesn't need to be run all the time"]
+    #[test]
+    fn test_wait_for_client() {
+        use super::wait_for_client;
+        use std::time::Duration;
+
+        let info = wait_for_client(Duration::from_secs(30), Duration::from_secs(1)).unwrap();
+        println!("{info:?}");
+    }
+
+    #[ignore = "This is only needed for testing, and doesn't need to be run all the time"]
+    #[test]
+    fn test_process_info_provider() {
+        let mut provider = super::ProcessInfoProvider::new();
+
+        for _ in 0..3 {
+            println!("{:?}", provider.current_client());
+        }
+    }
+
+    #[test]
+    fn test_running_processes() {
+        use super::{is_named, running_processes};
+
+        let exe = std::env::current_exe().unwrap();
+        let name = exe.file_name().unwrap().to_str().unwrap();
+
+        let system = running_processes(&[name], false).unwrap();
+        let process = system
+            .processes()
+            .values()
+            .find(|process| is_named(process, name))
+            .unwrap();
+
+        assert_eq!(process.pid().as_u32(), std::process::id());
+        assert!(!process.cmd().is_empty());
+    }
+
+    #[test]
+    fn test_lock_file() {
+        let lock_file = LockFile::parse("LeagueClient:12345:52437:AbCdEf123:https").unwrap();
+        assert_eq!(lock_file.name, "LeagueClient");
+        assert_eq!(lock_file.pid, Some(12345));
+        assert_eq!([lock_file.port, lock_file.auth], ["52437", "AbCdEf123"]);
+        assert_eq!(lock_file.protocol, Some("https"));
+
+        // A lock file left behind by a client that's since closed is stale
+        assert!(check_lock_file_owner(&lock_file, &[Some(12345)]).is_ok());
+        assert_eq!(
+            check_lock_file_owner(&lock_file, &[Some(54321)])
+                .err()
+                .map(|err| err.kind()),
+            Some(ErrorKind::LockFileStale)
+        );
+
+        // Without a PID, there's nothing to tell a stale lock file apart by
+        let lock_file = LockFile::parse("LeagueClient::52437:AbCdEf123").unwrap();
+        assert_eq!(lock_file.pid, None);
+        assert_eq!(lock_file.protocol, None);
+        assert!(check_lock_file_owner(&lock_file, &[Some(54321)]).is_ok());
+
+        assert_eq!(
+            LockFile::parse("LeagueClient:unknown:52437:AbCdEf123")
+                .err()
+                .map(|err| err.kind()),
+            Some(ErrorKind::LockFilePidInvalid)
+        );
+
+        assert_eq!(
+            LockFile::parse("LeagueClient:12345:52437")
+                .err()
+                .map(|err| err.kind()),
+            Some(ErrorKind::AuthTokenNotFound)
+        );
+    }
+
+    #[test]
+    fn test_lock_file_owner() {
+        let lock_file = LockFile::parse("LeagueClient:12345:52437:AbCdEf123:https").unwrap();
+
+        // The lock file is written by `LeagueClient`, which is the parent of the `LeagueClientUx` that's found
+        let ux = Some(23456);
+        assert!(check_lock_file_owner(&lock_file, &[ux, Some(12345), None]).is_ok());
+        assert!(check_lock_file_owner(&lock_file, &[ux, None, Some(12345)]).is_ok());
+
+        assert_eq!(
+            check_lock_file_owner(&lock_file, &[ux, Some(1), Some(2)])
+                .err()
+                .map(|err| err.kind()),
+            Some(ErrorKind::LockFileStale)
+        );
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_wine_paths() {
+        use super::{is_truncated_name, wine_to_host};
+        use std::path::Path;
+
+        let prefix = Path::new("/home/user/Games/league-of-legends");
+        assert_eq!(
+            wine_to_host(
+                prefix,
+                "C:\\Riot Games\\League of Legends\\LeagueClientUx.exe"
+            )
+            .unwrap(),
+            prefix.join("dosdevices/c:/Riot Games/League of Legends/LeagueClientUx.exe")
+        );
+        assert_eq!(
+            wine_to_host(prefix, "z:\\opt\\league").unwrap(),
+            prefix.join("dosdevices/z:/opt/league")
+        );
+        assert_eq!(wine_to_host(prefix, "/usr/bin/wine64"), None);
+
+        assert!(is_truncated_name("LeagueClientUx.", "LeagueClientUx.exe"));
+        assert!(is_truncated_name(
+            "League of Legen",
+            "League of Legends.exe"
+        ));
+        assert!(!is_truncated_name("LeagueClient", "LeagueClientUx.exe"));
+    }
 }
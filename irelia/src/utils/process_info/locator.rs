@@ -0,0 +1,101 @@
+use std::net::SocketAddr;
+
+use super::{encode_auth_header, running_client, Error, CLIENT_PROCESS_NAME, GAME_PROCESS_NAME};
+
+/// Finds the client to connect to, and the credentials to connect with
+///
+/// [`ProcessLocator`] is used by default, which looks through the running processes, and the lock file.
+/// Anything else can be passed to [`LcuClient::connect_with_locator`] and [`LcuWebSocket::new_with_locator`],
+/// such as fake credentials in tests, or a fixed address. A [`LocatedClient`] always locates itself,
+/// and any `Fn() -> Result<LocatedClient, Error>` can be used as a locator
+///
+/// [`LcuClient::connect_with_locator`]: crate::rest::LcuClient::connect_with_locator
+/// [`LcuWebSocket::new_with_locator`]: crate::ws::LcuWebSocket::new_with_locator
+pub trait ClientLocator: Send + Sync {
+    /// Finds the client, this is called when connecting, and again whenever the connection is established again
+    ///
+    /// # Errors
+    /// This should return an error if the client cannot be found, such as when it isn't running
+    fn locate(&self) -> Result<LocatedClient, Error>;
+}
+
+/// The address and credentials of a client, as found by a [`ClientLocator`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LocatedClient {
+    pub addr: SocketAddr,
+    /// The `Authorization` header, in the format `Basic <base64>`
+    pub auth_header: String,
+    /// The PID of the client process, `None` if it isn't known, such as for a client on another machine
+    pub pid: Option<u32>,
+}
+
+impl LocatedClient {
+    /// A client at `addr`, encoding `token` into the auth header the same way the client expects
+    ///
+    /// # Errors
+    /// This will return an error if the encoded header is not valid UTF-8, which should never happen
+    pub fn new(addr: impl Into<SocketAddr>, token: &str) -> Result<Self, Error> {
+        Ok(Self {
+            addr: addr.into(),
+            auth_header: encode_auth_header(token, str::to_owned)?,
+            pid: None,
+        })
+    }
+}
+
+impl ClientLocator for LocatedClient {
+    fn locate(&self) -> Result<LocatedClient, Error> {
+        Ok(self.clone())
+    }
+}
+
+impl<F> ClientLocator for F
+where
+    F: Fn() -> Result<LocatedClient, Error> + Send + Sync,
+{
+    fn locate(&self) -> Result<LocatedClient, Error> {
+        self()
+    }
+}
+
+/// Finds the client running on this machine, see [`get_running_client`]
+///
+/// [`get_running_client`]: super::get_running_client
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ProcessLocator {
+    /// Read the lock file regardless of whether the client or the game is running
+    pub force_lock_file: bool,
+}
+
+impl ClientLocator for ProcessLocator {
+    fn locate(&self) -> Result<LocatedClient, Error> {
+        let (pid, addr, auth_header) =
+            running_client::<String>(CLIENT_PROCESS_NAME, GAME_PROCESS_NAME, self.force_lock_file)?;
+
+        Ok(LocatedClient {
+            addr: addr.into(),
+            auth_header: auth_header.unwrap_or_else(|never| match never {}),
+            pid: Some(pid),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ClientLocator, LocatedClient};
+    use crate::process_info::Error;
+    use std::net::{Ipv4Addr, SocketAddr};
+
+    #[test]
+    fn fixed_locators() {
+        let located = LocatedClient::new((Ipv4Addr::LOCALHOST, 2999), "test").unwrap();
+        // `riot:test`
+        assert_eq!(located.auth_header, "Basic cmlvdDp0ZXN0");
+        assert_eq!(located.locate().unwrap(), located);
+
+        let remote = || -> Result<LocatedClient, Error> {
+            LocatedClient::new("192.168.1.2:2999".parse::<SocketAddr>().unwrap(), "test")
+        };
+        assert_eq!(remote.locate().unwrap().addr.port(), 2999);
+    }
+}
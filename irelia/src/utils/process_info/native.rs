@@ -0,0 +1,239 @@
+//! Lists processes through WMI instead of sysinfo, used on Windows with the `windows-native` feature
+//!
+//! WMI filters the processes by name itself, so only the processes that could be the client are read,
+//! rather than listing every process on the system first
+
+use super::{Error, ErrorKind};
+use serde::de::DeserializeOwned;
+use serde_derive::Deserialize;
+use std::collections::HashMap;
+use std::ffi::{OsStr, OsString};
+use std::path::{Path, PathBuf};
+use wmi::{COMLibrary, WMIConnection};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+/// A process ID, the same as `sysinfo::Pid`
+pub(super) struct Pid(u32);
+
+impl Pid {
+    pub(super) const fn as_u32(self) -> u32 {
+        self.0
+    }
+}
+
+#[derive(Debug)]
+/// The parts of a process that are needed to read the credentials, the same as `sysinfo::Process`
+pub(super) struct Process {
+    pid: Pid,
+    parent: Option<Pid>,
+    name: OsString,
+    exe: Option<PathBuf>,
+    cmd: Vec<OsString>,
+}
+
+impl Process {
+    pub(super) const fn pid(&self) -> Pid {
+        self.pid
+    }
+
+    pub(super) const fn parent(&self) -> Option<Pid> {
+        self.parent
+    }
+
+    pub(super) fn name(&self) -> &OsStr {
+        &self.name
+    }
+
+    pub(super) fn exe(&self) -> Option<&Path> {
+        self.exe.as_deref()
+    }
+
+    pub(super) fn cmd(&self) -> &[OsString] {
+        &self.cmd
+    }
+}
+
+#[derive(Debug, Default)]
+/// The processes found by the last refresh, the same as `sysinfo::System`
+pub(super) struct System {
+    processes: HashMap<Pid, Process>,
+}
+
+impl System {
+    pub(super) fn new() -> Self {
+        Self::default()
+    }
+
+    pub(super) const fn processes(&self) -> &HashMap<Pid, Process> {
+        &self.processes
+    }
+
+    /// Replaces the processes with those named one of `names`
+    pub(super) fn refresh(&mut self, names: &[&str]) -> Result<(), Error> {
+        if names.is_empty() {
+            self.processes.clear();
+            return Ok(());
+        }
+
+        let filter = names
+            .iter()
+            .map(|name| format!("Name = '{}'", escape(name)))
+            .collect::<Vec<_>>()
+            .join(" OR ");
+
+        let processes: Vec<Win32Process> = query(format!(
+            "SELECT ProcessId, ParentProcessId, Name, ExecutablePath, CommandLine FROM Win32_Process WHERE {filter}"
+        ))?;
+
+        self.processes = processes
+            .into_iter()
+            .map(|process| (Pid(process.process_id), Process::from(process)))
+            .collect();
+
+        Ok(())
+    }
+}
+
+/// Checks whether a process with the given PID is still running
+pub(super) fn is_process_running(pid: u32) -> Result<bool, Error> {
+    let processes: Vec<Win32ProcessId> = query(format!(
+        "SELECT ProcessId FROM Win32_Process WHERE ProcessId = {pid}"
+    ))?;
+
+    Ok(processes.iter().any(|process| process.process_id == pid))
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct Win32Process {
+    process_id: u32,
+    parent_process_id: u32,
+    name: String,
+    executable_path: Option<String>,
+    /// `None` when the process belongs to another user, and this isn't elevated
+    command_line: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct Win32ProcessId {
+    process_id: u32,
+}
+
+impl From<Win32Process> for Process {
+    fn from(process: Win32Process) -> Self {
+        Self {
+            pid: Pid(process.process_id),
+            // The idle process is the parent of processes whose parent is unknown
+            parent: (process.parent_process_id != 0).then_some(Pid(process.parent_process_id)),
+            name: process.name.into(),
+            exe: process.executable_path.map(PathBuf::from),
+            cmd: process
+                .command_line
+                .as_deref()
+                .map(split_command_line)
+                .unwrap_or_default(),
+        }
+    }
+}
+
+/// Runs a WQL query, on its own thread
+///
+/// COM has to be initialized on the thread WMI is used from, and the calling thread may already have it
+/// initialized in a way that can't be changed, such as the UI thread of a GUI
+fn query<T: DeserializeOwned + Send + 'static>(query: String) -> Result<Vec<T>, Error> {
+    let result = std::thread::spawn(move || {
+        let connection = WMIConnection::new(COMLibrary::new()?)?;
+        connection.raw_query(query)
+    })
+    .join()
+    .unwrap_or_else(|panic| std::panic::resume_unwind(panic));
+
+    result.map_err(|err| Error::new_string(ErrorKind::ProcessListFailed, err.to_string()))
+}
+
+/// Escapes a string to be used in a quoted WQL string
+fn escape(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('\'', "\\'")
+}
+
+/// Splits a command line into its arguments, following the same rules as `CommandLineToArgvW`
+///
+/// Arguments are split on whitespace outside of quotes, and backslashes are only special before a quote
+fn split_command_line(command_line: &str) -> Vec<OsString> {
+    let mut args = Vec::new();
+    let mut arg = String::new();
+    // Whether an argument has been started, as `""` is an empty argument
+    let mut started = false;
+    let mut quoted = false;
+    let mut backslashes = 0;
+
+    for c in command_line.chars() {
+        match c {
+            '\\' => {
+                backslashes += 1;
+                started = true;
+            }
+            '"' => {
+                // Pairs of backslashes before a quote are halved, and an odd one out escapes the quote
+                arg.push_str(&"\\".repeat(backslashes / 2));
+                if backslashes % 2 == 1 {
+                    arg.push('"');
+                } else {
+                    quoted = !quoted;
+                }
+                backslashes = 0;
+                started = true;
+            }
+            ' ' | '\t' if !quoted => {
+                arg.push_str(&"\\".repeat(backslashes));
+                backslashes = 0;
+
+                if started {
+                    args.push(std::mem::take(&mut arg).into());
+                    started = false;
+                }
+            }
+            c => {
+                arg.push_str(&"\\".repeat(backslashes));
+                backslashes = 0;
+                arg.push(c);
+                started = true;
+            }
+        }
+    }
+
+    arg.push_str(&"\\".repeat(backslashes));
+    if started {
+        args.push(arg.into());
+    }
+
+    args
+}
+
+#[cfg(test)]
+mod tests {
+    use super::split_command_line;
+
+    #[test]
+    fn test_split_command_line() {
+        let args = split_command_line(
+            r#""C:\Riot Games\League of Legends\LeagueClientUx.exe" "--install-directory=C:\Riot Games\League of Legends" --app-port=52437  --remoting-auth-token=AbC"#,
+        );
+
+        assert_eq!(
+            args,
+            [
+                r"C:\Riot Games\League of Legends\LeagueClientUx.exe",
+                r"--install-directory=C:\Riot Games\League of Legends",
+                "--app-port=52437",
+                "--remoting-auth-token=AbC",
+            ]
+        );
+
+        assert_eq!(
+            split_command_line(r#"a\\\"b "c\\" "" d\e"#),
+            [r#"a\"b"#, r"c\", "", r"d\e"]
+        );
+    }
+}
@@ -0,0 +1,164 @@
+use std::net::{SocketAddr, ToSocketAddrs};
+
+use super::{encode_auth_header, ClientLocator, Error, ErrorKind, LocatedClient};
+
+/// The name the LCU's certificate is issued for, it only ever listens on the loopback
+pub const LCU_CERTIFICATE_NAME: &str = "127.0.0.1";
+
+/// A client that isn't on this machine's loopback, such as one forwarded over SSH, or running on another PC
+///
+/// The LCU only listens on `127.0.0.1`, so it has to be forwarded to be reached from anywhere else,
+/// such as with `ssh -L 2999:127.0.0.1:<port> <host>`, or a TCP proxy on the machine running it.
+/// The host is resolved every time the client is located, so it can be a name that changes address
+///
+/// ```no_run
+/// use irelia::process_info::RemoteLcu;
+/// use irelia::rest::LcuClient;
+///
+/// let remote = RemoteLcu::new("streaming-pc.local", 2999, "token");
+/// let client = LcuClient::connect_remote(remote).unwrap();
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RemoteLcu {
+    host: String,
+    port: u16,
+    token: String,
+    tls: TlsOptions,
+}
+
+/// How the TLS connection to a [`RemoteLcu`] is made
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TlsOptions {
+    /// The name the certificate is checked against, rather than the host connected to,
+    /// this is [`LCU_CERTIFICATE_NAME`] by default
+    ///
+    /// With `nativetls`, the name can only be checked against the name the connection is opened with,
+    /// so REST requests check it against the host connected to, and websockets against [`TlsOptions::connection_name`]
+    pub certificate_name: String,
+    /// The name sent with SNI, none is sent by default, as the LCU doesn't need one, but a proxy routing by name might
+    ///
+    /// With `nativetls`, the host connected to is sent instead for REST requests
+    pub sni: Option<String>,
+    /// Accepts any certificate, such as from a proxy that terminates TLS with its own,
+    /// this allows anything along the way to impersonate the client
+    pub accept_invalid_certs: bool,
+}
+
+impl Default for TlsOptions {
+    fn default() -> Self {
+        Self {
+            certificate_name: LCU_CERTIFICATE_NAME.to_owned(),
+            sni: None,
+            accept_invalid_certs: false,
+        }
+    }
+}
+
+impl TlsOptions {
+    #[must_use]
+    /// The name connections are opened with, which is sent with SNI when it's enabled
+    pub fn connection_name(&self) -> &str {
+        self.sni.as_deref().unwrap_or(&self.certificate_name)
+    }
+}
+
+impl RemoteLcu {
+    #[must_use]
+    /// A client served on `host` and `port`, with `token` as the password for the `riot` user
+    pub fn new(host: impl Into<String>, port: u16, token: impl Into<String>) -> Self {
+        Self {
+            host: host.into(),
+            port,
+            token: token.into(),
+            tls: TlsOptions::default(),
+        }
+    }
+
+    #[must_use]
+    /// Checks the certificate against `name`, see [`TlsOptions::certificate_name`]
+    pub fn certificate_name(mut self, name: impl Into<String>) -> Self {
+        self.tls.certificate_name = name.into();
+        self
+    }
+
+    #[must_use]
+    /// Sends `name` with SNI, see [`TlsOptions::sni`]
+    pub fn sni(mut self, name: impl Into<String>) -> Self {
+        self.tls.sni = Some(name.into());
+        self
+    }
+
+    #[must_use]
+    /// Accepts any certificate, see [`TlsOptions::accept_invalid_certs`]
+    pub const fn danger_accept_invalid_certs(mut self) -> Self {
+        self.tls.accept_invalid_certs = true;
+        self
+    }
+
+    #[must_use]
+    pub fn host(&self) -> &str {
+        &self.host
+    }
+
+    #[must_use]
+    pub const fn port(&self) -> u16 {
+        self.port
+    }
+
+    #[must_use]
+    pub const fn tls(&self) -> &TlsOptions {
+        &self.tls
+    }
+
+    /// Resolves the host to the address connections are made to
+    ///
+    /// # Errors
+    /// This will return an error if the host cannot be resolved
+    pub fn resolve(&self) -> Result<SocketAddr, Error> {
+        let io_error = |kind, message| Error::new_string(ErrorKind::Io(kind), message);
+
+        (self.host.as_str(), self.port)
+            .to_socket_addrs()
+            .map_err(|err| io_error(err.kind(), format!("{}: {err}", self.host)))?
+            .next()
+            .ok_or_else(|| {
+                io_error(
+                    std::io::ErrorKind::NotFound,
+                    format!("{} did not resolve to any address", self.host),
+                )
+            })
+    }
+}
+
+impl ClientLocator for RemoteLcu {
+    fn locate(&self) -> Result<LocatedClient, Error> {
+        Ok(LocatedClient {
+            addr: self.resolve()?,
+            auth_header: encode_auth_header(&self.token, str::to_owned)?,
+            pid: None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{RemoteLcu, LCU_CERTIFICATE_NAME};
+    use crate::process_info::ClientLocator;
+
+    #[test]
+    fn remote_locate() {
+        let remote = RemoteLcu::new("127.0.0.1", 2999, "test");
+        let located = remote.locate().unwrap();
+
+        assert_eq!(located.addr, "127.0.0.1:2999".parse().unwrap());
+        assert_eq!(located.auth_header, "Basic cmlvdDp0ZXN0");
+        assert_eq!(located.pid, None);
+
+        assert_eq!(remote.tls().connection_name(), LCU_CERTIFICATE_NAME);
+        let remote = remote.sni("lcu.example");
+        assert_eq!(remote.tls().connection_name(), "lcu.example");
+        assert_eq!(remote.tls().certificate_name, LCU_CERTIFICATE_NAME);
+
+        assert!(RemoteLcu::new("", 2999, "test").locate().is_err());
+    }
+}
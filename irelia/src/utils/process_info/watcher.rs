@@ -0,0 +1,321 @@
+use std::net::SocketAddrV4;
+#[cfg(feature = "notify")]
+use std::path::Path;
+use std::path::PathBuf;
+use std::pin::Pin;
+#[cfg(feature = "notify")]
+use std::sync::{Arc, Mutex, PoisonError};
+#[cfg(feature = "notify")]
+use std::task::Waker;
+use std::task::{Context, Poll};
+use std::time::Duration;
+
+use futures_core::Stream;
+
+use super::{
+    get_client_from_lock_file, get_running_client, CLIENT_PROCESS_NAME, GAME_PROCESS_NAME,
+};
+use crate::utils::runtime::Interval;
+
+/// A change in whether the client is running, yielded by [`ClientWatcher`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ClientEvent {
+    /// The client started, with the address and auth header it can be reached with
+    Started(SocketAddrV4, String),
+    /// The client that was last started is no longer running
+    Stopped,
+}
+
+/// Stream that polls for the client, yielding a [`ClientEvent`] whenever it starts or stops
+///
+/// The first poll happens immediately, so if the client is already running, `Started` is yielded first.
+/// If the client restarts between polls, with new credentials, `Stopped` is yielded before the new `Started`.
+///
+/// Finding the process is done on the task polling the stream, this is usually quick, but it does block
+///
+/// ```no_run
+/// use futures_util::StreamExt;
+/// use irelia::process_info::{ClientEvent, ClientWatcher};
+/// use std::time::Duration;
+///
+/// # async fn watch() {
+/// let mut watcher = ClientWatcher::new(Duration::from_secs(1));
+///
+/// while let Some(event) = watcher.next().await {
+///     match event {
+///         ClientEvent::Started(addr, auth_header) => println!("Started on {addr}"),
+///         ClientEvent::Stopped => println!("Stopped"),
+///     }
+/// }
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct ClientWatcher {
+    interval: Interval,
+    force_lock_file: bool,
+    /// Set when watching a lock file directly, instead of looking for the process
+    lock_file: Option<PathBuf>,
+    /// Set when the lock file's directory could be watched for changes
+    #[cfg(feature = "notify")]
+    events: Option<LockFileEvents>,
+    running: Option<(SocketAddrV4, String)>,
+    pending: Option<ClientEvent>,
+}
+
+impl ClientWatcher {
+    #[must_use]
+    /// Creates a watcher that checks for the client every `interval`
+    ///
+    /// # Panics
+    /// With `runtime-tokio`, this panics if `interval` is zero, or if it's not called from within a tokio runtime with the timer enabled
+    pub fn new(interval: Duration) -> Self {
+        Self {
+            interval: Interval::new(interval),
+            force_lock_file: false,
+            lock_file: None,
+            #[cfg(feature = "notify")]
+            events: None,
+            running: None,
+            pending: None,
+        }
+    }
+
+    #[must_use]
+    /// Creates a watcher that reads the lock file at `path` every `interval`, instead of looking for the client process
+    ///
+    /// `path` can either be the lock file itself, or the install directory containing it, see [`get_client_from_lock_file`].
+    /// `Started` is yielded whenever the lock file is created, or rewritten with new credentials, and `Stopped` when it's removed
+    ///
+    /// The lock file is polled every `interval`. With the `notify` feature, the directory it's in is also watched,
+    /// so it's read again as soon as it changes. Polling is kept as the fallback, as file events aren't reliable
+    /// on the network and wine mounted drives the lock file is often read from, and the directory can't be watched
+    /// if it doesn't exist yet
+    ///
+    /// # Panics
+    /// With `runtime-tokio`, this panics if `interval` is zero, or if it's not called from within a tokio runtime with the timer enabled
+    pub fn from_lock_file(path: impl Into<PathBuf>, interval: Duration) -> Self {
+        let path = path.into();
+
+        Self {
+            #[cfg(feature = "notify")]
+            events: LockFileEvents::new(&path),
+            lock_file: Some(path),
+            ..Self::new(interval)
+        }
+    }
+
+    #[must_use]
+    /// Reads the lock file regardless of whether the client or the game is running, see [`get_running_client`]
+    ///
+    /// This has no effect on watchers created with [`ClientWatcher::from_lock_file`]
+    pub fn force_lock_file(mut self, force_lock_file: bool) -> Self {
+        self.force_lock_file = force_lock_file;
+        self
+    }
+
+    /// Stores what is currently running, returning the first event caused by the change, if any
+    fn update(&mut self, current: Option<(SocketAddrV4, String)>) -> Option<ClientEvent> {
+        if current == self.running {
+            return None;
+        }
+
+        let previous = std::mem::replace(&mut self.running, current.clone());
+        let started = current.map(|(addr, auth_header)| ClientEvent::Started(addr, auth_header));
+
+        if previous.is_some() {
+            self.pending = started;
+            Some(ClientEvent::Stopped)
+        } else {
+            started
+        }
+    }
+}
+
+impl Stream for ClientWatcher {
+    type Item = ClientEvent;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        if let Some(event) = self.pending.take() {
+            return Poll::Ready(Some(event));
+        }
+
+        loop {
+            #[cfg(feature = "notify")]
+            let changed = self
+                .events
+                .as_ref()
+                .is_some_and(|events| events.poll_changed(cx));
+            #[cfg(not(feature = "notify"))]
+            let changed = false;
+
+            if !changed && self.interval.poll_tick(cx).is_pending() {
+                return Poll::Pending;
+            }
+
+            // Any error means there is nothing that can be connected to yet, such as when the lock file hasn't been written
+            let current = match &self.lock_file {
+                Some(path) => get_client_from_lock_file(path)
+                    .ok()
+                    .map(|info| (info.addr(), info.auth_header().to_owned())),
+                None => get_running_client::<String>(
+                    CLIENT_PROCESS_NAME,
+                    GAME_PROCESS_NAME,
+                    self.force_lock_file,
+                )
+                .ok()
+                .map(|(addr, auth_header)| {
+                    (addr, auth_header.unwrap_or_else(|never| match never {}))
+                }),
+            };
+
+            if let Some(event) = self.update(current) {
+                return Poll::Ready(Some(event));
+            }
+        }
+    }
+}
+
+#[cfg(feature = "notify")]
+/// Wakes the watcher whenever the lock file changes, instead of waiting for the next poll
+struct LockFileEvents {
+    /// Events stop once this is dropped
+    _watcher: notify::RecommendedWatcher,
+    signal: Arc<Mutex<Signal>>,
+}
+
+#[cfg(feature = "notify")]
+#[derive(Default)]
+struct Signal {
+    changed: bool,
+    waker: Option<Waker>,
+}
+
+#[cfg(feature = "notify")]
+impl LockFileEvents {
+    /// Watches the directory of the lock file at `path`, or `path` itself if it's the install directory,
+    /// returning `None` if it can't be watched
+    fn new(path: &Path) -> Option<Self> {
+        use notify::{RecursiveMode, Watcher};
+
+        let (dir, name) = if path.is_dir() {
+            (path, std::ffi::OsStr::new("lockfile"))
+        } else {
+            let dir = path.parent().filter(|dir| !dir.as_os_str().is_empty());
+            (dir.unwrap_or(Path::new(".")), path.file_name()?)
+        };
+
+        let name = name.to_owned();
+        let signal = Arc::new(Mutex::new(Signal::default()));
+        let sender = Arc::clone(&signal);
+
+        let mut watcher =
+            notify::recommended_watcher(move |event: notify::Result<notify::Event>| {
+                // Anything else in the directory, such as the client's logs, is ignored
+                let Ok(event) = event else { return };
+                if !event
+                    .paths
+                    .iter()
+                    .any(|path| path.file_name() == Some(&name))
+                {
+                    return;
+                }
+
+                let mut signal = sender.lock().unwrap_or_else(PoisonError::into_inner);
+                signal.changed = true;
+                if let Some(waker) = signal.waker.take() {
+                    waker.wake();
+                }
+            })
+            .ok()?;
+
+        watcher.watch(dir, RecursiveMode::NonRecursive).ok()?;
+
+        Some(Self {
+            _watcher: watcher,
+            signal,
+        })
+    }
+
+    /// Whether the lock file changed since this was last called, if it hasn't, `cx` is woken when it does
+    fn poll_changed(&self, cx: &Context<'_>) -> bool {
+        let mut signal = self.signal.lock().unwrap_or_else(PoisonError::into_inner);
+
+        if std::mem::take(&mut signal.changed) {
+            return true;
+        }
+
+        signal.waker = Some(cx.waker().clone());
+        false
+    }
+}
+
+#[cfg(feature = "notify")]
+impl std::fmt::Debug for LockFileEvents {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("LockFileEvents").finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ClientEvent, ClientWatcher};
+    use std::net::{Ipv4Addr, SocketAddrV4};
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn test_watcher_update() {
+        let mut watcher = ClientWatcher::new(Duration::from_secs(1));
+        let first = (
+            SocketAddrV4::new(Ipv4Addr::LOCALHOST, 52437),
+            "Basic a".to_string(),
+        );
+        let second = (
+            SocketAddrV4::new(Ipv4Addr::LOCALHOST, 61234),
+            "Basic b".to_string(),
+        );
+
+        assert_eq!(watcher.update(None), None);
+        assert_eq!(
+            watcher.update(Some(first.clone())),
+            Some(ClientEvent::Started(first.0, first.1.clone()))
+        );
+        assert_eq!(watcher.update(Some(first)), None);
+
+        // A restart between polls stops the old client before starting the new one
+        assert_eq!(
+            watcher.update(Some(second.clone())),
+            Some(ClientEvent::Stopped)
+        );
+        assert_eq!(
+            watcher.pending.take(),
+            Some(ClientEvent::Started(second.0, second.1))
+        );
+
+        assert_eq!(watcher.update(None), Some(ClientEvent::Stopped));
+        assert_eq!(watcher.pending, None);
+    }
+
+    #[cfg(feature = "notify")]
+    #[tokio::test]
+    async fn test_watcher_lock_file_events() {
+        use futures_util::StreamExt;
+
+        let dir = std::env::temp_dir().join(format!("irelia-watcher-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let _ = std::fs::remove_file(dir.join("lockfile"));
+
+        // Only the first poll happens within the test, so the change has to be seen through the event
+        let mut watcher = ClientWatcher::from_lock_file(&dir, Duration::from_secs(60));
+        assert!(watcher.events.is_some());
+        let pending = tokio::time::timeout(Duration::from_millis(50), watcher.next()).await;
+        assert!(pending.is_err());
+
+        std::fs::write(dir.join("lockfile"), "LeagueClient:1:52437:password:https").unwrap();
+        let event = tokio::time::timeout(Duration::from_secs(5), watcher.next())
+            .await
+            .unwrap();
+
+        std::fs::remove_dir_all(&dir).unwrap();
+        assert!(matches!(event, Some(ClientEvent::Started(addr, _)) if addr.port() == 52437));
+    }
+}
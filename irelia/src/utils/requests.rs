@@ -1,20 +1,30 @@
+use crate::process_info::TlsOptions;
+#[cfg(feature = "rest")]
+use crate::rest::fixture::{FixtureRequest, Interaction, Replay};
+#[cfg(feature = "rest")]
+use crate::rest::middleware::{Middleware, Stack};
+use crate::utils::runtime;
 use crate::Error;
 use std::fmt::Debug;
 use std::future::Future;
 use std::io::BufWriter;
 use std::io::Write;
-use std::net::SocketAddrV4;
+use std::net::SocketAddr;
 use std::pin::Pin;
+#[cfg(feature = "rest")]
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::Duration;
 
-use http_body_util::{BodyExt, Collected, Full};
+use http_body_util::{BodyExt, Full};
 use hyper::body::{Bytes, Incoming};
-use hyper::header::{ACCEPT, AUTHORIZATION, CONTENT_TYPE};
+use hyper::header::{HeaderMap, ACCEPT, AUTHORIZATION, CONTENT_TYPE, USER_AGENT};
 use hyper::http::uri::Scheme;
 use hyper::http::HeaderValue;
 use hyper::rt::Executor;
+#[cfg(feature = "rest")]
+use hyper::StatusCode;
 use hyper::{Request, Response, Uri};
 use hyper_util::client::legacy::Client;
-use hyper_util::rt::TokioExecutor;
 use serde::Serialize;
 
 /// Struct that represents any connection to the in game or rest APIs, this client has to be constructed and then passed to the clients
@@ -32,26 +42,148 @@ use serde::Serialize;
 #[derive(Clone, Debug)]
 pub struct RequestClient {
     client: Client<crate::tls::Connector, Full<Bytes>>,
+    default_headers: HeaderMap,
+    #[cfg(feature = "rest")]
+    recorder: Option<Recorder>,
+    /// Set when requests are answered from a fixture, rather than sent
+    #[cfg(feature = "rest")]
+    pub(crate) fixture: Option<Arc<Mutex<Replay>>>,
+    #[cfg(feature = "rest")]
+    middleware: Stack,
 }
 
 type BoxFuture = Pin<Box<dyn Future<Output = ()> + Send>>;
 
+/// Bodies are sent, and responses requested, as msgpack unless stated otherwise
+pub(crate) const MIME: &str = "application/x-msgpack";
+
+/// How a [`RequestClient`] opens and keeps connections, these are set through [`crate::rest::LcuClientBuilder`]
+///
+/// Connections are kept alive and reused by default, `None` leaves hyper's default in place
+#[derive(Debug, Clone, Default)]
+pub(crate) struct ConnectionOptions {
+    pub(crate) accept_invalid_certs: bool,
+    /// Set for a client that isn't on the loopback, see [`TlsOptions`]
+    pub(crate) remote: Option<TlsOptions>,
+    pub(crate) connect_timeout: Option<Duration>,
+    /// How long an unused connection is kept open
+    pub(crate) pool_idle_timeout: Option<Duration>,
+    /// How many unused connections are kept open to each address
+    pub(crate) pool_max_idle_per_host: Option<usize>,
+}
+
 impl RequestClient {
     #[must_use]
     /// Creates a client to be passed to the LCU and in game structs
     pub fn new() -> Self {
-        Self::new_with_executor(TokioExecutor::new())
+        Self::new_with_executor(runtime::Executor::new())
     }
 
     #[must_use]
     pub fn new_with_executor<E: Executor<BoxFuture> + Clone + Send + Sync + 'static>(
         exec: E,
     ) -> Self {
-        let https = crate::tls::https_connector();
-        // Make the new client
-        let client = Client::builder(exec).build(https);
+        Self::from_client(Client::builder(exec).build(crate::tls::https_connector(None)))
+    }
+
+    #[must_use]
+    /// Creates a client where opening a connection fails after `connect_timeout`,
+    /// instead of waiting for the OS to give up
+    ///
+    /// This only bounds connecting, use [`crate::rest::LcuClientBuilder::timeout`] to bound the whole request
+    pub fn new_with_connect_timeout(connect_timeout: Duration) -> Self {
+        Self::new_with_options(ConnectionOptions {
+            connect_timeout: Some(connect_timeout),
+            ..ConnectionOptions::default()
+        })
+    }
+
+    #[must_use]
+    /// Creates a client that accepts any certificate, instead of only the one signed by riot's root certificate
+    ///
+    /// This should only be used if the bundled certificate stops matching what the client serves,
+    /// as it allows anything listening on the port to impersonate the client
+    pub fn danger_accept_invalid_certs() -> Self {
+        Self::new_with_options(ConnectionOptions {
+            accept_invalid_certs: true,
+            ..ConnectionOptions::default()
+        })
+    }
 
-        Self { client }
+    #[must_use]
+    /// Creates a client for a client that isn't on the loopback, such as one forwarded from another machine,
+    /// see [`TlsOptions`]
+    pub fn new_remote(tls: TlsOptions) -> Self {
+        Self::new_with_options(ConnectionOptions {
+            remote: Some(tls),
+            ..ConnectionOptions::default()
+        })
+    }
+
+    pub(crate) fn new_with_options(options: ConnectionOptions) -> Self {
+        let https = match options.remote {
+            Some(mut tls) => {
+                tls.accept_invalid_certs |= options.accept_invalid_certs;
+                crate::tls::https_connector_remote(options.connect_timeout, &tls)
+            }
+            None if options.accept_invalid_certs => {
+                crate::tls::https_connector_accept_invalid_certs(options.connect_timeout)
+            }
+            None => crate::tls::https_connector(options.connect_timeout),
+        };
+
+        let mut builder = Client::builder(runtime::Executor::new());
+        // Without a timer, idle connections are only closed once they're checked out again, never after the idle timeout
+        builder.pool_timer(runtime::Timer::new());
+
+        if let Some(idle_timeout) = options.pool_idle_timeout {
+            builder.pool_idle_timeout(idle_timeout);
+        }
+
+        if let Some(max_idle) = options.pool_max_idle_per_host {
+            builder.pool_max_idle_per_host(max_idle);
+        }
+
+        Self::from_client(builder.build(https))
+    }
+
+    fn from_client(client: Client<crate::tls::Connector, Full<Bytes>>) -> Self {
+        let mut default_headers = HeaderMap::new();
+        default_headers.insert(
+            USER_AGENT,
+            HeaderValue::from_static(concat!("irelia/", env!("CARGO_PKG_VERSION"))),
+        );
+
+        Self {
+            client,
+            default_headers,
+            #[cfg(feature = "rest")]
+            recorder: None,
+            #[cfg(feature = "rest")]
+            fixture: None,
+            #[cfg(feature = "rest")]
+            middleware: Stack::default(),
+        }
+    }
+
+    #[must_use]
+    /// Sets the `User-Agent` sent with every request, by default this is `irelia/<version>`
+    pub fn with_user_agent(mut self, user_agent: HeaderValue) -> Self {
+        self.default_headers.insert(USER_AGENT, user_agent);
+        self
+    }
+
+    #[must_use]
+    /// Returns the headers sent with every request
+    pub fn default_headers(&self) -> &HeaderMap {
+        &self.default_headers
+    }
+
+    /// Returns a mutable reference to the headers sent with every request
+    ///
+    /// `Content-Type`, `Accept`, and `Authorization` are always set by the client, and will replace any defaults
+    pub fn default_headers_mut(&mut self) -> &mut HeaderMap {
+        &mut self.default_headers
     }
 
     /// returns a raw hyper response, URIs always use HTTPS,
@@ -60,34 +192,45 @@ impl RequestClient {
     /// if the body is invalid JSON, otherwise in any way hyper would normally
     pub(crate) async fn raw_request_template(
         &self,
-        url: SocketAddrV4,
+        url: impl Into<SocketAddr>,
         endpoint: &str,
         method: &str,
         body: Option<Full<Bytes>>,
         auth_header: Option<&HeaderValue>,
     ) -> Result<Response<Incoming>, Error> {
-        const MINE: &str = "application/x-msgpack";
-        const LONGEST_SOCKET_ADDR: usize = "255.255.255.255:65535".len();
-
-        let mut buffer = [0; LONGEST_SOCKET_ADDR];
-        let mut buf_writer = BufWriter::new(buffer.as_mut_slice());
-
-        // The socket addr is IpV4, so this is guaranteed to fit by the type system
-        let _ = write!(&mut buf_writer, "{url}");
+        self.raw_request_with_headers(
+            url,
+            endpoint,
+            method,
+            body,
+            auth_header,
+            HeaderValue::from_static(MIME),
+            HeaderMap::new(),
+        )
+        .await
+    }
 
-        // Build the URI, always in https format
-        let built_uri = Uri::builder()
-            .scheme(Scheme::HTTPS)
-            .authority(buf_writer.buffer())
-            .path_and_query(endpoint)
-            .build()?;
+    /// The same as [`Self::raw_request_template`], but the body is sent as `content_type` rather than msgpack,
+    /// and `headers` are sent with the request, taking priority over the defaults
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) async fn raw_request_with_headers(
+        &self,
+        url: impl Into<SocketAddr>,
+        endpoint: &str,
+        method: &str,
+        body: Option<Full<Bytes>>,
+        auth_header: Option<&HeaderValue>,
+        content_type: HeaderValue,
+        headers: HeaderMap,
+    ) -> Result<Response<Incoming>, Error> {
+        let built_uri = build_uri(url.into(), endpoint)?;
 
         // Build the new request
         let mut builder = Request::builder()
             .method(method)
             .uri(built_uri)
-            .header(CONTENT_TYPE, MINE)
-            .header(ACCEPT, MINE);
+            .header(CONTENT_TYPE, content_type)
+            .header(ACCEPT, MIME);
 
         // Add the auth header, if provided
         if let Some(header) = auth_header {
@@ -97,41 +240,469 @@ impl RequestClient {
         let body = body.unwrap_or_default();
 
         // Add the body to finalize
-        let request = builder.body(body)?;
+        let mut request = builder.body(body)?;
+
+        // Fill in the defaults, letting the request's own headers, then the headers set above take priority
+        let mut defaults = self.default_headers.clone();
+        defaults.extend(headers);
+        defaults.extend(std::mem::take(request.headers_mut()));
+        *request.headers_mut() = defaults;
+
+        let response = async {
+            #[cfg(feature = "rest")]
+            if !self.middleware.0.is_empty() {
+                return self.request_with_middleware(request).await;
+            }
+
+            // Return the incoming request
+            Ok(self.client.request(request).await?)
+        };
+
+        #[cfg(feature = "tracing")]
+        let response = crate::utils::trace::request(method, endpoint, response);
+
+        response.await
+    }
+
+    #[cfg(feature = "rest")]
+    /// Sends the request, running it through every [`Middleware`] first, and every response through them after
+    async fn request_with_middleware(
+        &self,
+        mut request: Request<Full<Bytes>>,
+    ) -> Result<Response<Incoming>, Error> {
+        for middleware in &self.middleware.0 {
+            middleware.before_request(&mut request);
+        }
+
+        let mut sent = Request::new(());
+        *sent.method_mut() = request.method().clone();
+        *sent.uri_mut() = request.uri().clone();
+        *sent.version_mut() = request.version();
+        *sent.headers_mut() = request.headers().clone();
+
+        let response = self.client.request(request).await.map_err(Error::from);
 
-        // Return the incoming request
-        Ok(self.client.request(request).await?)
+        for middleware in &self.middleware.0 {
+            middleware.after_response(&sent, response.as_ref());
+        }
+
+        response
     }
 
     /// Makes a request, collects the bytes, and returns the buf
     pub(crate) async fn request_template<T: Serialize + Send>(
         &self,
-        url: SocketAddrV4,
+        url: impl Into<SocketAddr> + Send,
         endpoint: &str,
         method: &str,
         body: Option<T>,
         auth_header: Option<&HeaderValue>,
-    ) -> Result<Collected<Bytes>, Error> {
+    ) -> Result<Bytes, Error> {
         let body = body
             .map(|body| rmp_serde::to_vec_named(&body).map(Full::from))
             .transpose()?;
 
+        self.bytes_request(
+            url,
+            endpoint,
+            method,
+            body,
+            auth_header,
+            HeaderValue::from_static(MIME),
+            HeaderMap::new(),
+        )
+        .await
+    }
+
+    /// Makes a request with a body that's already encoded as `content_type`, collects the bytes, and returns the buf
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) async fn bytes_request(
+        &self,
+        url: impl Into<SocketAddr> + Send,
+        endpoint: &str,
+        method: &str,
+        body: Option<Full<Bytes>>,
+        auth_header: Option<&HeaderValue>,
+        content_type: HeaderValue,
+        headers: HeaderMap,
+    ) -> Result<Bytes, Error> {
+        // The request is only captured when it's replayed, or recorded as a fixture
+        #[cfg(feature = "rest")]
+        let captured = if self.fixture.is_some()
+            || self
+                .recorder
+                .as_ref()
+                .is_some_and(|recorder| recorder.format == RecordFormat::Fixture)
+        {
+            Some(
+                FixtureRequest::capture(
+                    method,
+                    endpoint,
+                    body.as_ref(),
+                    auth_header,
+                    &content_type,
+                    (&self.default_headers, &headers),
+                )
+                .await,
+            )
+        } else {
+            None
+        };
+
+        #[cfg(feature = "rest")]
+        if let (Some(fixture), Some(request)) = (&self.fixture, &captured) {
+            return fixture
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner)
+                .respond(request);
+        }
+
         let response = self
-            .raw_request_template(url, endpoint, method, body, auth_header)
+            .raw_request_with_headers(
+                url,
+                endpoint,
+                method,
+                body,
+                auth_header,
+                content_type,
+                headers,
+            )
             .await?;
 
-        if !response.status().is_success() {
-            return Err(Error::RequestError(response.status()));
+        let status = response.status();
+        let body = response.collect().await?.to_bytes();
+
+        #[cfg(feature = "rest")]
+        if let Some(recorder) = &self.recorder {
+            recorder.record(method, endpoint, status, &body, captured);
         }
 
-        let body = response.collect().await?;
+        if status.is_success() {
+            return Ok(body);
+        }
+
+        // Only the LCU explains what went wrong, and it's the only API requests are authenticated to,
+        // so requests to every other API fail with `RequestError`, whichever features are enabled
+        #[cfg(feature = "rest")]
+        if auth_header.is_some() {
+            return Err(status_error(status, &body));
+        }
+
+        Err(Error::RequestError(status))
+    }
+}
+
+#[cfg(feature = "rest")]
+impl RequestClient {
+    #[must_use]
+    /// Records every request made through this client to `sink`, as one line of JSON per request
+    ///
+    /// Each line is a [`RecordedRequest`], and can be read back with `serde_json`.
+    /// Recording is best effort, if writing to `sink` fails the request still succeeds.
+    /// Clones of this client share the same sink
+    ///
+    /// ```no_run
+    /// use irelia::RequestClient;
+    ///
+    /// let file = std::fs::File::create("session.jsonl").unwrap();
+    /// let request_client = RequestClient::new().record_to(file);
+    /// ```
+    pub fn record_to(self, sink: impl Write + Send + 'static) -> Self {
+        self.record_to_as(sink, RecordFormat::Requests)
+    }
+
+    #[must_use]
+    /// The same as [`RequestClient::record_to`], but each line is written as `format`
+    ///
+    /// This replaces any sink that was already set, including one set by [`RequestClient::record_fixture`]
+    pub fn record_to_as(mut self, sink: impl Write + Send + 'static, format: RecordFormat) -> Self {
+        self.recorder = Some(Recorder {
+            sink: Arc::new(Mutex::new(sink)),
+            format,
+        });
+        self
+    }
 
-        Ok(body)
+    #[must_use]
+    /// Runs `middleware` around every request made through this client, after any middleware already added
+    ///
+    /// Clones made afterwards run the same middleware, but clones made before, such as
+    /// ones already given to an [`LcuClient`](crate::rest::LcuClient), are left unchanged
+    pub fn with_middleware(mut self, middleware: Arc<dyn Middleware>) -> Self {
+        self.middleware.0.push(middleware);
+        self
     }
 }
 
+#[cfg(feature = "rest")]
+#[derive(Debug, Clone, PartialEq, serde_derive::Serialize, serde_derive::Deserialize)]
+/// A single request made while recording, see [`RequestClient::record_to`]
+pub struct RecordedRequest {
+    pub method: String,
+    /// The endpoint requested, including the query
+    pub path: String,
+    pub status: u16,
+    /// The response body, converted from msgpack, this is `null` if the request failed, or had no body
+    pub body: serde_json::Value,
+}
+
+#[cfg(feature = "rest")]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+/// What each line written by [`RequestClient::record_to_as`] is
+pub enum RecordFormat {
+    #[default]
+    /// A [`RecordedRequest`], with the body of each successful response
+    Requests,
+    /// An [`Interaction`], with the request that was sent and its response, which can be replayed,
+    /// see [`crate::rest::fixture`]
+    Fixture,
+}
+
+#[cfg(feature = "rest")]
+#[derive(Clone)]
+struct Recorder {
+    sink: Arc<Mutex<dyn Write + Send>>,
+    format: RecordFormat,
+}
+
+#[cfg(feature = "rest")]
+impl Recorder {
+    /// Writes a line for the request, `request` is always captured when recording a fixture
+    fn record(
+        &self,
+        method: &str,
+        endpoint: &str,
+        status: StatusCode,
+        body: &[u8],
+        request: Option<FixtureRequest>,
+    ) {
+        let mut sink = self.sink.lock().unwrap_or_else(PoisonError::into_inner);
+
+        let written = if let (RecordFormat::Fixture, Some(request)) = (self.format, request) {
+            serde_json::to_writer(&mut *sink, &Interaction::recorded(request, status, body))
+        } else {
+            let request = RecordedRequest {
+                method: method.to_string(),
+                path: endpoint.to_string(),
+                status: status.as_u16(),
+                body: status
+                    .is_success()
+                    .then(|| rmp_serde::from_slice(body).ok())
+                    .flatten()
+                    .unwrap_or_default(),
+            };
+            serde_json::to_writer(&mut *sink, &request)
+        };
+
+        if written.is_ok() {
+            let _ = sink.write_all(b"\n");
+            let _ = sink.flush();
+        }
+    }
+}
+
+#[cfg(feature = "rest")]
+impl Debug for Recorder {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("Recorder")
+    }
+}
+
+#[cfg(feature = "rest")]
+/// The error for a response from the LCU that wasn't successful, using its explanation if the body has one
+pub(crate) fn status_error(status: StatusCode, body: &[u8]) -> Error {
+    crate::LcuError::from_body(body).map_or(Error::RequestError(status), Error::LcuError)
+}
+
+/// Builds an HTTPS URI for the endpoint, IPv6 addresses are written in brackets, such as `https://[::1]:2999/`
+fn build_uri(url: SocketAddr, endpoint: &str) -> Result<Uri, hyper::http::Error> {
+    const LONGEST_SOCKET_ADDR: usize =
+        "[ffff:ffff:ffff:ffff:ffff:ffff:255.255.255.255%4294967295]:65535".len();
+
+    let mut buffer = [0; LONGEST_SOCKET_ADDR];
+    let mut buf_writer = BufWriter::new(buffer.as_mut_slice());
+
+    // The scope ID is only meaningful to the local machine, and isn't valid in a URI
+    let url = match url {
+        SocketAddr::V6(mut v6) => {
+            v6.set_scope_id(0);
+            SocketAddr::V6(v6)
+        }
+        v4 @ SocketAddr::V4(_) => v4,
+    };
+
+    // The `Display` impl for `SocketAddr` adds the brackets for IPv6, and the longest address is guaranteed to fit
+    let _ = write!(&mut buf_writer, "{url}");
+
+    // Build the URI, always in https format
+    Uri::builder()
+        .scheme(Scheme::HTTPS)
+        .authority(buf_writer.buffer())
+        .path_and_query(endpoint)
+        .build()
+}
+
 impl Default for RequestClient {
     fn default() -> Self {
         Self::new()
     }
 }
+
+/// Percent encodes everything in `value` other than the unreserved characters from RFC 3986,
+/// so it can be used as a single query value
+pub(crate) fn encode_query_value(value: &str) -> String {
+    use std::fmt::Write as _;
+
+    let mut encoded = String::with_capacity(value.len());
+
+    for byte in value.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                encoded.push(char::from(byte));
+            }
+            _ => {
+                let _ = write!(encoded, "%{byte:02X}");
+            }
+        }
+    }
+
+    encoded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{build_uri, RequestClient};
+    use hyper::header::{HeaderValue, USER_AGENT};
+
+    #[tokio::test]
+    async fn test_user_agent() {
+        let client = RequestClient::new();
+        assert_eq!(
+            client.default_headers()[USER_AGENT],
+            concat!("irelia/", env!("CARGO_PKG_VERSION"))
+        );
+
+        let client = client.with_user_agent(HeaderValue::from_static("my-app/1.0"));
+        assert_eq!(client.default_headers()[USER_AGENT], "my-app/1.0");
+    }
+
+    #[cfg(feature = "ws")]
+    #[tokio::test]
+    async fn test_status_error() {
+        use crate::mock::MockLcu;
+        use crate::Error;
+        use hyper::header::AUTHORIZATION;
+        use hyper::StatusCode;
+
+        let mock = MockLcu::start().unwrap();
+        mock.on_get("/liveclientdata/allgamedata")
+            .respond_error(StatusCode::NOT_FOUND, "No active game");
+
+        // The LCU's explanation is only read for requests made to the LCU
+        let err = mock
+            .client()
+            .get::<()>("/liveclientdata/allgamedata")
+            .await
+            .unwrap_err();
+        assert!(matches!(err, Error::LcuError(_)));
+
+        // Anything else keeps failing with the status alone, even when the body has an explanation
+        let mut client = RequestClient::danger_accept_invalid_certs();
+        client
+            .default_headers_mut()
+            .insert(AUTHORIZATION, mock.auth_header().clone());
+
+        let err = client
+            .request_template(
+                mock.addr(),
+                "/liveclientdata/allgamedata",
+                "GET",
+                None::<()>,
+                None,
+            )
+            .await
+            .unwrap_err();
+        assert!(matches!(err, Error::RequestError(StatusCode::NOT_FOUND)));
+    }
+
+    #[test]
+    fn test_build_uri() {
+        let v4 = build_uri(
+            "127.0.0.1:2999".parse().unwrap(),
+            "/liveclientdata/allgamedata",
+        );
+        assert_eq!(
+            v4.unwrap(),
+            "https://127.0.0.1:2999/liveclientdata/allgamedata"
+        );
+
+        let v6 = build_uri(
+            "[::1]:52437".parse().unwrap(),
+            "/lol-gameflow/v1/gameflow-phase",
+        );
+        assert_eq!(
+            v6.unwrap(),
+            "https://[::1]:52437/lol-gameflow/v1/gameflow-phase"
+        );
+
+        let scoped = build_uri("[fe80::1%3]:52437".parse().unwrap(), "/");
+        assert_eq!(scoped.unwrap(), "https://[fe80::1]:52437/");
+    }
+
+    #[cfg(feature = "rest")]
+    #[test]
+    fn test_lcu_error() {
+        use crate::{Error, LcuError};
+        use hyper::StatusCode;
+
+        let json = br#"{"errorCode":"RPC_ERROR","httpStatus":404,"implementationDetails":{},"message":"No active delegate"}"#;
+        let err = LcuError::from_body(json).unwrap();
+        assert_eq!(err.error_code, "RPC_ERROR");
+        assert_eq!(err.http_status, 404);
+        assert_eq!(err.message, "No active delegate");
+
+        let msgpack = rmp_serde::to_vec_named(&err).unwrap();
+        assert_eq!(LcuError::from_body(&msgpack), Some(err.clone()));
+
+        assert_eq!(LcuError::from_body(b""), None);
+        assert_eq!(LcuError::from_body(b"Not Found"), None);
+
+        assert_eq!(Error::LcuError(err).status(), Some(StatusCode::NOT_FOUND));
+    }
+
+    #[cfg(feature = "rest")]
+    #[test]
+    fn test_recorder() {
+        use super::{RecordFormat, RecordedRequest, Recorder};
+        use hyper::StatusCode;
+        use std::sync::{Arc, Mutex};
+
+        let sink = Arc::new(Mutex::new(Vec::new()));
+        let requests_recorder = Recorder {
+            sink: sink.clone(),
+            format: RecordFormat::Requests,
+        };
+
+        let body = rmp_serde::to_vec(&"Lobby").unwrap();
+        requests_recorder.record(
+            "GET",
+            "/lol-gameflow/v1/gameflow-phase",
+            StatusCode::OK,
+            &body,
+            None,
+        );
+        requests_recorder.record("GET", "/missing", StatusCode::NOT_FOUND, &body, None);
+
+        let sink = sink.lock().unwrap();
+        let requests: Vec<RecordedRequest> = serde_json::Deserializer::from_slice(&sink)
+            .into_iter()
+            .collect::<Result<_, _>>()
+            .unwrap();
+
+        assert_eq!(requests.len(), 2);
+        assert_eq!(requests[0].path, "/lol-gameflow/v1/gameflow-phase");
+        assert_eq!(requests[0].body, "Lobby");
+        assert_eq!(requests[1].status, 404);
+        assert!(requests[1].body.is_null());
+    }
+}
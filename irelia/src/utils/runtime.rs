@@ -0,0 +1,62 @@
+//! The async runtime used by the clients, picked with one of the `runtime-tokio`, `runtime-async-std` or `runtime-smol` features
+//!
+//! Everything that sleeps, ticks, spawns, or opens a connection goes through here, rather than calling a runtime directly.
+//! async-std and smol both drive their IO and timers with `async-io`, so they share everything but spawning
+
+#[cfg(any(
+    all(feature = "runtime-tokio", feature = "runtime-async-std"),
+    all(feature = "runtime-tokio", feature = "runtime-smol"),
+    all(feature = "runtime-async-std", feature = "runtime-smol"),
+))]
+compile_error!(
+    "Only one of the `runtime-tokio`, `runtime-async-std` and `runtime-smol` features can be enabled, disable default features to use `runtime-async-std` or `runtime-smol`"
+);
+
+#[cfg(not(any(
+    feature = "runtime-tokio",
+    feature = "runtime-async-std",
+    feature = "runtime-smol"
+)))]
+compile_error!(
+    "You need to enable one of the `runtime-tokio`, `runtime-async-std` or `runtime-smol` features"
+);
+
+#[cfg(any(feature = "runtime-async-std", feature = "runtime-smol"))]
+mod async_io;
+#[cfg(feature = "runtime-tokio")]
+mod tokio;
+
+#[cfg(any(feature = "runtime-async-std", feature = "runtime-smol"))]
+pub(crate) use async_io::*;
+#[cfg(feature = "runtime-tokio")]
+pub(crate) use tokio::*;
+
+#[cfg(test)]
+mod tests {
+    use super::{sleep, timeout, Interval};
+    use std::future::poll_fn;
+    use std::time::{Duration, Instant};
+
+    #[tokio::test]
+    async fn test_timeout() {
+        assert_eq!(timeout(Duration::from_secs(1), async { 1 }).await, Some(1));
+        assert_eq!(
+            timeout(Duration::from_millis(10), sleep(Duration::from_secs(1))).await,
+            None
+        );
+    }
+
+    #[tokio::test]
+    async fn test_interval() {
+        let start = Instant::now();
+        let mut interval = Interval::new(Duration::from_millis(20));
+
+        // The first tick is immediate, the ones after wait out the period
+        poll_fn(|cx| interval.poll_tick(cx)).await;
+        assert!(start.elapsed() < Duration::from_millis(20));
+
+        poll_fn(|cx| interval.poll_tick(cx)).await;
+        poll_fn(|cx| interval.poll_tick(cx)).await;
+        assert!(start.elapsed() >= Duration::from_millis(40));
+    }
+}
@@ -0,0 +1,246 @@
+//! The async-std and smol runtimes, used with the `runtime-async-std` and `runtime-smol` features
+//!
+//! Both use `async-io` for their timers and sockets, so only spawning and blocking differ between them
+
+use std::future::{poll_fn, Future};
+use std::io;
+use std::net::{IpAddr, SocketAddr, ToSocketAddrs};
+use std::pin::{pin, Pin};
+use std::task::{Context, Poll};
+use std::time::{Duration, Instant};
+
+use async_io::Async;
+use hyper::http::uri::Scheme;
+use hyper::rt::ReadBufCursor;
+use hyper::Uri;
+use hyper_util::client::legacy::connect::{Connected, Connection};
+use smol_hyper::rt::FuturesIo;
+
+#[cfg(feature = "runtime-async-std")]
+use async_std::task::spawn_blocking as unblock;
+#[cfg(feature = "runtime-smol")]
+use smol::unblock;
+
+/// Lets the hyper client close idle connections
+pub(crate) type Timer = smol_hyper::rt::SmolTimer;
+
+/// Runs the background tasks of the hyper client, such as driving its connections, on the global executor
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct Executor;
+
+impl Executor {
+    pub(crate) const fn new() -> Self {
+        Self
+    }
+}
+
+impl<F> hyper::rt::Executor<F> for Executor
+where
+    F: Future + Send + 'static,
+    F::Output: Send + 'static,
+{
+    fn execute(&self, future: F) {
+        #[cfg(feature = "runtime-async-std")]
+        async_std::task::spawn(future);
+        #[cfg(feature = "runtime-smol")]
+        smol::spawn(future).detach();
+    }
+}
+
+/// Waits for `duration` to elapse
+pub(crate) async fn sleep(duration: Duration) {
+    async_io::Timer::after(duration).await;
+}
+
+/// Runs `future` to completion, returning `None` if it does not finish within `duration`
+pub(crate) async fn timeout<F: Future>(duration: Duration, future: F) -> Option<F::Output> {
+    let mut future = pin!(future);
+    let mut timer = async_io::Timer::after(duration);
+
+    poll_fn(|cx| {
+        if let Poll::Ready(output) = future.as_mut().poll(cx) {
+            return Poll::Ready(Some(output));
+        }
+
+        Pin::new(&mut timer).poll(cx).map(|_| None)
+    })
+    .await
+}
+
+/// Runs `f` on a thread where blocking is fine, returning what it returns
+///
+/// # Panics
+/// This resumes the panic if `f` panics
+pub(crate) async fn spawn_blocking<T: Send + 'static>(f: impl FnOnce() -> T + Send + 'static) -> T {
+    unblock(f).await
+}
+
+/// A TCP connector that allows any scheme, `connect_timeout` bounds how long opening a connection can take
+pub(crate) fn http_connector(connect_timeout: Option<Duration>) -> HttpConnector {
+    HttpConnector { connect_timeout }
+}
+
+/// The TCP connector under TLS, see [`http_connector`]
+#[derive(Debug, Clone, Default)]
+pub(crate) struct HttpConnector {
+    connect_timeout: Option<Duration>,
+}
+
+impl tower_service::Service<Uri> for HttpConnector {
+    type Response = TcpStream;
+    type Error = io::Error;
+    type Future = Pin<Box<dyn Future<Output = io::Result<TcpStream>> + Send>>;
+
+    fn poll_ready(&mut self, _: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, uri: Uri) -> Self::Future {
+        let connect_timeout = self.connect_timeout;
+
+        Box::pin(async move {
+            match connect_timeout {
+                Some(duration) => timeout(duration, connect(uri)).await.unwrap_or_else(|| {
+                    Err(io::Error::new(
+                        io::ErrorKind::TimedOut,
+                        "Connecting timed out",
+                    ))
+                }),
+                None => connect(uri).await,
+            }
+        })
+    }
+}
+
+/// Connects to the host of `uri`, trying each address it resolves to in order
+async fn connect(uri: Uri) -> io::Result<TcpStream> {
+    let host = uri
+        .host()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "The URI has no host"))?;
+    let host = host
+        .trim_start_matches('[')
+        .trim_end_matches(']')
+        .to_owned();
+    let port = uri
+        .port_u16()
+        .unwrap_or(if uri.scheme() == Some(&Scheme::HTTPS) {
+            443
+        } else {
+            80
+        });
+
+    // The clients are almost always reached by IP, resolving a name blocks, so it's only done when needed
+    let addrs: Vec<SocketAddr> = match host.parse::<IpAddr>() {
+        Ok(ip) => vec![SocketAddr::new(ip, port)],
+        Err(_) => {
+            spawn_blocking(move || {
+                (host.as_str(), port)
+                    .to_socket_addrs()
+                    .map(Iterator::collect)
+            })
+            .await?
+        }
+    };
+
+    let mut last_err = None;
+    for addr in addrs {
+        match Async::<std::net::TcpStream>::connect(addr).await {
+            Ok(stream) => return Ok(TcpStream(FuturesIo::new(stream))),
+            Err(err) => last_err = Some(err),
+        }
+    }
+
+    Err(last_err.unwrap_or_else(|| {
+        io::Error::new(io::ErrorKind::NotFound, "The host resolved to no addresses")
+    }))
+}
+
+/// A connection opened by [`HttpConnector`]
+#[derive(Debug)]
+pub(crate) struct TcpStream(FuturesIo<Async<std::net::TcpStream>>);
+
+impl Connection for TcpStream {
+    fn connected(&self) -> Connected {
+        Connected::new()
+    }
+}
+
+impl hyper::rt::Read for TcpStream {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: ReadBufCursor<'_>,
+    ) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.0).poll_read(cx, buf)
+    }
+}
+
+impl hyper::rt::Write for TcpStream {
+    fn poll_write(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        Pin::new(&mut self.0).poll_write(cx, buf)
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.0).poll_flush(cx)
+    }
+
+    fn poll_shutdown(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.0).poll_shutdown(cx)
+    }
+}
+
+/// Ticks every period, the first tick is immediate
+///
+/// Missed ticks are not caught up on, the next tick is a period after the late one
+#[derive(Debug)]
+pub(crate) struct Interval {
+    period: Duration,
+    timer: async_io::Timer,
+}
+
+impl Interval {
+    pub(crate) fn new(period: Duration) -> Self {
+        Self {
+            period,
+            timer: async_io::Timer::at(Instant::now()),
+        }
+    }
+
+    pub(crate) fn poll_tick(&mut self, cx: &mut Context<'_>) -> Poll<()> {
+        if Pin::new(&mut self.timer).poll(cx).is_pending() {
+            return Poll::Pending;
+        }
+
+        self.timer.set_after(self.period);
+        Poll::Ready(())
+    }
+}
+
+#[cfg(any(feature = "blocking", all(feature = "ws", feature = "rest")))]
+/// Runs futures from synchronous code, on the calling thread
+///
+/// This must not be used from within an async runtime, or it will block it
+#[derive(Debug)]
+pub(crate) struct Runtime;
+
+#[cfg(any(feature = "blocking", all(feature = "ws", feature = "rest")))]
+impl Runtime {
+    #[allow(clippy::unnecessary_wraps)]
+    pub(crate) fn new() -> io::Result<Self> {
+        Ok(Self)
+    }
+
+    #[allow(clippy::unused_self)]
+    pub(crate) fn block_on<F: Future>(&self, future: F) -> F::Output {
+        #[cfg(feature = "runtime-async-std")]
+        use async_std::task::block_on;
+        #[cfg(feature = "runtime-smol")]
+        use smol::block_on;
+
+        block_on(future)
+    }
+}
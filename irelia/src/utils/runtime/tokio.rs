@@ -0,0 +1,87 @@
+//! The tokio runtime, used with the `runtime-tokio` feature
+
+use std::future::Future;
+use std::task::{Context, Poll};
+use std::time::Duration;
+
+use hyper_util::client::legacy::connect;
+use tokio::time::MissedTickBehavior;
+
+/// Runs the background tasks of the hyper client, such as driving its connections
+pub(crate) type Executor = hyper_util::rt::TokioExecutor;
+/// Lets the hyper client close idle connections
+pub(crate) type Timer = hyper_util::rt::TokioTimer;
+/// The TCP connector under TLS
+pub(crate) type HttpConnector = connect::HttpConnector;
+
+/// Waits for `duration` to elapse
+pub(crate) async fn sleep(duration: Duration) {
+    tokio::time::sleep(duration).await;
+}
+
+/// Runs `future` to completion, returning `None` if it does not finish within `duration`
+pub(crate) async fn timeout<F: Future>(duration: Duration, future: F) -> Option<F::Output> {
+    tokio::time::timeout(duration, future).await.ok()
+}
+
+#[cfg(feature = "rest")]
+/// Runs `f` on a thread where blocking is fine, returning what it returns
+///
+/// # Panics
+/// This resumes the panic if `f` panics, or panics if the runtime shuts down before `f` is run
+pub(crate) async fn spawn_blocking<T: Send + 'static>(f: impl FnOnce() -> T + Send + 'static) -> T {
+    match tokio::task::spawn_blocking(f).await {
+        Ok(output) => output,
+        Err(err) if err.is_panic() => std::panic::resume_unwind(err.into_panic()),
+        Err(err) => panic!("The blocking task was cancelled: {err}"),
+    }
+}
+
+/// A TCP connector that allows any scheme, `connect_timeout` bounds how long opening a connection can take
+pub(crate) fn http_connector(connect_timeout: Option<Duration>) -> HttpConnector {
+    let mut http = connect::HttpConnector::new();
+    http.enforce_http(false);
+    http.set_connect_timeout(connect_timeout);
+    http
+}
+
+/// Ticks every period, the first tick is immediate
+///
+/// Missed ticks are not caught up on, the next tick is a period after the late one
+#[derive(Debug)]
+pub(crate) struct Interval(tokio::time::Interval);
+
+impl Interval {
+    /// # Panics
+    /// This panics if `period` is zero, or if it's not called from within a tokio runtime with the timer enabled
+    pub(crate) fn new(period: Duration) -> Self {
+        let mut interval = tokio::time::interval(period);
+        interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        Self(interval)
+    }
+
+    pub(crate) fn poll_tick(&mut self, cx: &mut Context<'_>) -> Poll<()> {
+        self.0.poll_tick(cx).map(drop)
+    }
+}
+
+#[cfg(any(feature = "blocking", all(feature = "ws", feature = "rest")))]
+/// Runs futures from synchronous code, on a single threaded runtime owned by this
+///
+/// This must not be used from within an async runtime, or it will panic
+#[derive(Debug)]
+pub(crate) struct Runtime(tokio::runtime::Runtime);
+
+#[cfg(any(feature = "blocking", all(feature = "ws", feature = "rest")))]
+impl Runtime {
+    pub(crate) fn new() -> std::io::Result<Self> {
+        tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .map(Self)
+    }
+
+    pub(crate) fn block_on<F: Future>(&self, future: F) -> F::Output {
+        self.0.block_on(future)
+    }
+}
@@ -0,0 +1,145 @@
+//! Spans and events emitted with the `tracing` feature
+//!
+//! Only addresses, PIDs, methods, paths, statuses, and timings are recorded, never a header or the lock file,
+//! so the auth token can't end up in a log
+
+#[cfg(any(feature = "ws", feature = "rest", feature = "in_game"))]
+use std::net::SocketAddrV4;
+use std::time::Instant;
+
+#[cfg(any(feature = "ws", feature = "rest", feature = "in_game"))]
+/// Runs `discover`, recording how long finding the client took, and where it was found
+///
+/// Only the PID and the address are recorded, the rest of what's found is left out, as it contains the token
+pub(crate) fn discovery<T>(
+    force_lock_file: bool,
+    discover: impl FnOnce() -> Result<(u32, SocketAddrV4, T), crate::process_info::Error>,
+) -> Result<(u32, SocketAddrV4, T), crate::process_info::Error> {
+    let _span = tracing::debug_span!("discovery", force_lock_file).entered();
+    let started = Instant::now();
+
+    let result = discover();
+
+    match &result {
+        Ok((pid, addr, _)) => {
+            tracing::debug!(pid, %addr, elapsed = ?started.elapsed(), "found the client");
+        }
+        Err(err) => {
+            tracing::debug!(error = %err, elapsed = ?started.elapsed(), "the client was not found");
+        }
+    }
+
+    result
+}
+
+#[cfg(any(feature = "rest", feature = "in_game"))]
+/// Wraps `request` in a span with its method and path, recording the status and how long it took
+///
+/// The request is boxed, so tracing doesn't grow every future a request is awaited in
+pub(crate) fn request<F>(
+    method: &str,
+    path: &str,
+    request: F,
+) -> impl std::future::Future<Output = F::Output>
+where
+    F: std::future::Future<Output = Result<hyper::Response<hyper::body::Incoming>, crate::Error>>,
+{
+    use tracing::Instrument;
+
+    let span = tracing::debug_span!("request", method, path);
+
+    async move {
+        let started = Instant::now();
+        let response = Box::pin(request).await;
+
+        match &response {
+            Ok(response) => {
+                let status = response.status().as_u16();
+                tracing::debug!(status, latency = ?started.elapsed(), "received a response");
+            }
+            Err(err) => {
+                tracing::debug!(error = %err, latency = ?started.elapsed(), "the request failed");
+            }
+        }
+
+        response
+    }
+    .instrument(span)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicU64, Ordering};
+    use std::sync::{Arc, Mutex};
+    use tracing::field::{Field, Visit};
+    use tracing::span::{Attributes, Id, Record};
+    use tracing::{Event, Metadata, Subscriber};
+
+    /// Keeps every field of every span and event as `name=value`
+    #[derive(Clone, Default)]
+    struct Recorder {
+        fields: Arc<Mutex<Vec<String>>>,
+        next_id: Arc<AtomicU64>,
+    }
+
+    impl Visit for Recorder {
+        fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+            self.fields
+                .lock()
+                .unwrap()
+                .push(format!("{}={value:?}", field.name()));
+        }
+    }
+
+    impl Subscriber for Recorder {
+        fn enabled(&self, _: &Metadata<'_>) -> bool {
+            true
+        }
+
+        fn new_span(&self, span: &Attributes<'_>) -> Id {
+            span.record(&mut self.clone());
+            Id::from_u64(self.next_id.fetch_add(1, Ordering::Relaxed) + 1)
+        }
+
+        fn record(&self, _: &Id, values: &Record<'_>) {
+            values.record(&mut self.clone());
+        }
+
+        fn record_follows_from(&self, _: &Id, _: &Id) {}
+
+        fn event(&self, event: &Event<'_>) {
+            event.record(&mut self.clone());
+        }
+
+        fn enter(&self, _: &Id) {}
+
+        fn exit(&self, _: &Id) {}
+    }
+
+    #[cfg(all(feature = "rest", feature = "ws"))]
+    #[tokio::test]
+    async fn token_redacted() {
+        use crate::mock::{MockLcu, MOCK_TOKEN};
+
+        let recorder = Recorder::default();
+        let _guard = tracing::subscriber::set_default(recorder.clone());
+
+        let mock = MockLcu::start().unwrap();
+        mock.on_get("/lol-gameflow/v1/gameflow-phase")
+            .respond_json("Lobby");
+
+        let client = mock.client();
+        let _: String = client.get("/lol-gameflow/v1/gameflow-phase").await.unwrap();
+
+        let fields = recorder.fields.lock().unwrap();
+        assert!(fields.contains(&"method=\"GET\"".to_owned()));
+        assert!(fields.contains(&"path=\"/lol-gameflow/v1/gameflow-phase\"".to_owned()));
+        assert!(fields.contains(&"status=200".to_owned()));
+
+        let auth_header = mock.auth_header();
+        let encoded = auth_header.to_str().unwrap().trim_start_matches("Basic ");
+        assert!(!fields
+            .iter()
+            .any(|field| field.contains(MOCK_TOKEN) || field.contains(encoded)));
+    }
+}
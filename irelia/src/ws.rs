@@ -1,37 +1,69 @@
 //! Module containing all the data on the websocket LCU bindings
 
+mod debounce;
+mod dedup;
 mod error;
+#[cfg(feature = "rest")]
+pub mod events;
+mod filter;
 mod impls;
+#[cfg(feature = "rest")]
+mod polling;
+mod record;
+mod router;
+mod stream;
+#[cfg(any(test, feature = "test_util"))]
+pub mod test_util;
 pub mod types;
 mod utils;
 
 use impls::Returns;
-use std::net::{SocketAddr, TcpStream};
+use serde::de::DeserializeOwned;
+use std::collections::VecDeque;
+use std::io::{Read, Write};
+use std::net::TcpStream;
 use std::sync::mpsc::{Receiver, Sender};
 use std::thread::JoinHandle;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 use std::{ops::ControlFlow, thread};
+use tungstenite::handshake::HandshakeError;
 use tungstenite::stream::MaybeTlsStream;
 use tungstenite::util::NonBlockingResult;
-use tungstenite::{client::IntoClientRequest, Message, WebSocket};
+use tungstenite::{client::IntoClientRequest, Bytes, Message, WebSocket};
 
-use crate::utils::process_info::get_running_client;
-use crate::utils::process_info::{CLIENT_PROCESS_NAME, GAME_PROCESS_NAME};
+use crate::utils::process_info::{ClientLocator, ProcessLocator, RemoteLcu};
 use crate::ws::types::{Event, EventKind, RequestType};
 use crate::ws::utils::EventMap;
 
+pub use debounce::Debounce;
+pub use dedup::{diff, Change, Dedup};
 pub use error::Error as WebSocketError;
+pub use filter::{Filtered, Glob};
+pub use record::{RecordedEvent, Recorder, Replay};
+pub use router::{RouteId, Router};
+pub use stream::{DecodedEvent, EventStream, Overflow, StreamEvent};
 
 /// Type alias for the websocket stream type
 pub type WebSocketStream = WebSocket<MaybeTlsStream<TcpStream>>;
 
 /// Struct representing a connection to the LCU websocket
 pub struct LcuWebSocket {
+    writer: LcuWebSocketWriter,
+    reader: LcuWebSocketReader,
+}
+
+/// The half of an [`LcuWebSocket`] that subscribes and unsubscribes, created with [`LcuWebSocket::split`]
+pub struct LcuWebSocketWriter {
     ws_sender: Sender<ChannelMessage>,
-    handle: JoinHandle<()>,
     id_free_list: EventMap<(usize, Vec<usize>)>,
 }
 
+/// The half of an [`LcuWebSocket`] that owns the thread reading from the websocket, created with [`LcuWebSocket::split`]
+pub struct LcuWebSocketReader {
+    ws_sender: Sender<ChannelMessage>,
+    handle: JoinHandle<()>,
+}
+
 #[derive(Clone, Copy)]
 #[repr(transparent)]
 /// This is the ID of the subscriber when it's inserted into the list, corresponding to the index it's stored at
@@ -41,6 +73,7 @@ enum ChannelMessage {
     Subscribe(RequestType, EventKind, Box<dyn Subscriber + Send>),
     Unsubscribe(SubscriberID, EventKind),
     Abort,
+    Shutdown,
 }
 
 #[derive(PartialEq, Eq)]
@@ -85,6 +118,38 @@ pub trait Subscriber {
     /// Callback run when the subscriber is removed
     /// Default behavior is to do nothing
     fn on_unsubscribe(&mut self, _event_kind: &EventKind) {}
+
+    /// Callback run when the connection is established again, after the subscription has been sent again
+    /// Events that happened while disconnected are lost, so this is where any state should be fetched again
+    /// Default behavior is to do nothing
+    fn on_reconnect(&mut self, _event_kind: &EventKind) {}
+
+    /// Callback run when the connection is lost, before the error handler decides whether to reconnect
+    /// Default behavior is to do nothing
+    fn on_connection_lost(&mut self, _event_kind: &EventKind) {}
+}
+
+/// Keep-alive settings for the websocket, see [`ErrorHandler::heartbeat`]
+///
+/// The LCU stops sending anything when it hangs, or the machine sleeps, without closing the connection,
+/// so a ping is sent whenever the connection is quiet, and the connection is treated as lost
+/// if nothing, not even the pong, is received in time
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Heartbeat {
+    /// How long the connection can be quiet before a ping is sent
+    pub interval: Duration,
+    /// How long the connection can be quiet before it's treated as lost, and
+    /// [`WebSocketError::ConnectionLost`] is passed to the error handler
+    pub timeout: Duration,
+}
+
+impl Default for Heartbeat {
+    fn default() -> Self {
+        Self {
+            interval: Duration::from_secs(10),
+            timeout: Duration::from_secs(30),
+        }
+    }
 }
 
 /// Error handler trait, called when the websocket connection errors in an unexpected way
@@ -123,6 +188,12 @@ pub trait ErrorHandler: Send {
     fn on_timeout(&mut self) {
         thread::sleep(Duration::from_millis(500));
     }
+
+    /// Keep-alive settings used for every connection, this is only read once, when the event loop starts
+    /// Default behavior is to not send pings, and never treat a quiet connection as lost
+    fn heartbeat(&self) -> Option<Heartbeat> {
+        None
+    }
 }
 
 /// This is a zero sized struct which calls `eprintln!()` and then breaks on error
@@ -135,6 +206,42 @@ impl ErrorHandler for DefaultErrorHandler {
     }
 }
 
+/// Error handler that reconnects whenever the connection is lost, or the client can't be found,
+/// waiting `delay` between attempts
+///
+/// Reconnecting looks for the client again, so this follows the client restarting, with new credentials.
+/// Subscriptions are sent again once reconnected, see [`Subscriber::on_reconnect`].
+/// Messages that can't be parsed are skipped.
+/// A [`Heartbeat`] is used by default, so a client that stops responding is reconnected to as well
+pub struct AutoReconnect {
+    pub delay: Duration,
+    pub heartbeat: Option<Heartbeat>,
+}
+
+impl Default for AutoReconnect {
+    fn default() -> Self {
+        Self {
+            delay: Duration::from_secs(1),
+            heartbeat: Some(Heartbeat::default()),
+        }
+    }
+}
+
+impl ErrorHandler for AutoReconnect {
+    fn on_error(&mut self, error: WebSocketError) -> ControlFlow<(), Flow> {
+        if let WebSocketError::SerdeJson(_) = error {
+            return ControlFlow::Continue(Flow::Continue);
+        }
+
+        thread::sleep(self.delay);
+        ControlFlow::Continue(Flow::TryReconnect)
+    }
+
+    fn heartbeat(&self) -> Option<Heartbeat> {
+        self.heartbeat
+    }
+}
+
 impl Default for LcuWebSocket {
     #[must_use]
     /// Creates a new connection to the LCU websocket using the default error handler
@@ -153,6 +260,68 @@ impl LcuWebSocket {
     #[must_use]
     /// Creates a new connection to the LCU websocket
     pub fn new_with_error_handler(error_handler: impl ErrorHandler + 'static) -> Self {
+        Self::new_with_locator(error_handler, ProcessLocator::default())
+    }
+
+    #[must_use]
+    /// Creates a new connection to the client found by `locator`, instead of searching the running processes
+    ///
+    /// `locator` is asked again every time the connection is established, so it can return new credentials after a restart
+    pub fn new_with_locator(
+        error_handler: impl ErrorHandler + 'static,
+        locator: impl ClientLocator + 'static,
+    ) -> Self {
+        let (ws_sender, ws_receiver) = std::sync::mpsc::channel::<ChannelMessage>();
+
+        let handle = thread::spawn(move || {
+            let tls = crate::tls::connector();
+
+            let mut error_handler = error_handler;
+            let ws_receiver = ws_receiver;
+
+            event_loop(&mut error_handler, &ws_receiver, |error_handler| {
+                connect(&tls, error_handler, &locator)
+            });
+        });
+
+        Self::from_parts(ws_sender, handle)
+    }
+
+    #[must_use]
+    /// Creates a new connection to `remote`, a client that isn't on this machine's loopback, see [`RemoteLcu`]
+    ///
+    /// The host is resolved again every time the connection is established
+    pub fn new_remote(error_handler: impl ErrorHandler + 'static, remote: RemoteLcu) -> Self {
+        let (ws_sender, ws_receiver) = std::sync::mpsc::channel::<ChannelMessage>();
+
+        let handle = thread::spawn(move || {
+            let mut error_handler = error_handler;
+            let ws_receiver = ws_receiver;
+
+            event_loop(&mut error_handler, &ws_receiver, |error_handler| {
+                connect_remote(&remote, error_handler)
+            });
+        });
+
+        Self::from_parts(ws_sender, handle)
+    }
+
+    #[cfg(feature = "rest")]
+    #[must_use]
+    /// Creates a new connection to the LCU websocket, polling the REST API instead if the websocket upgrade is rejected
+    ///
+    /// This is an opt-in fallback for setups where security software blocks the websocket, but not the REST API.
+    /// It only approximates the websocket, every `interval` each subscribed endpoint is requested,
+    /// and an event is synthesized if the response changed since the last request.
+    ///
+    /// Only `JsonApiEvent` subscriptions with a callback can be polled, other subscribers never receive an event.
+    /// Changes that are reverted within a single `interval` are missed entirely.
+    ///
+    /// The decision is only made for the first connection, after that the connection behaves like [`LcuWebSocket::new_with_error_handler`]
+    pub fn new_with_polling_fallback(
+        error_handler: impl ErrorHandler + 'static,
+        interval: Duration,
+    ) -> Self {
         let (ws_sender, ws_receiver) = std::sync::mpsc::channel::<ChannelMessage>();
 
         let handle = thread::spawn(move || {
@@ -161,16 +330,185 @@ impl LcuWebSocket {
             let mut error_handler = error_handler;
             let ws_receiver = ws_receiver;
 
-            event_loop(&mut error_handler, &ws_receiver, &tls);
+            let locator = ProcessLocator::default();
+            let mut first = Some(connect(&tls, &mut error_handler, &locator));
+
+            // Only a failed handshake is retried over REST, anything else means the client isn't reachable at all
+            if let Some(Err(WebSocketError::Tungstenite(_))) = first {
+                polling::poll_loop(&mut error_handler, &ws_receiver, interval);
+                return;
+            }
+
+            event_loop(&mut error_handler, &ws_receiver, |error_handler| {
+                first
+                    .take()
+                    .unwrap_or_else(|| connect(&tls, error_handler, &locator))
+            });
+        });
+
+        Self::from_parts(ws_sender, handle)
+    }
+
+    #[cfg(any(test, feature = "test_util"))]
+    #[must_use]
+    /// Creates a connection over an already established websocket, such as the one from [`test_util::mock_stream`]
+    ///
+    /// The stream is expected to be nonblocking, and `on_connect` is never called for it.
+    /// Since there is no way to reconnect, asking to reconnect will pass a `ConnectionClosed` error to the handler
+    pub fn from_stream<S>(stream: WebSocket<S>, error_handler: impl ErrorHandler + 'static) -> Self
+    where
+        S: Read + Write + Send + 'static,
+    {
+        let (ws_sender, ws_receiver) = std::sync::mpsc::channel::<ChannelMessage>();
+
+        let handle = thread::spawn(move || {
+            let mut error_handler = error_handler;
+            let ws_receiver = ws_receiver;
+            let mut stream = Some(stream);
+
+            event_loop(&mut error_handler, &ws_receiver, |_| {
+                stream
+                    .take()
+                    .ok_or(tungstenite::Error::ConnectionClosed.into())
+            });
         });
 
+        Self::from_parts(ws_sender, handle)
+    }
+
+    fn from_parts(ws_sender: Sender<ChannelMessage>, handle: JoinHandle<()>) -> Self {
         Self {
-            ws_sender,
-            handle,
-            id_free_list: EventMap::new(),
+            writer: LcuWebSocketWriter {
+                ws_sender: ws_sender.clone(),
+                id_free_list: EventMap::new(),
+            },
+            reader: LcuWebSocketReader { ws_sender, handle },
         }
     }
 
+    /// Subscribes to a specific event kind using the subscriber
+    ///
+    /// Returns `None` is the websocket connection has already been closed previously
+    pub fn subscribe(
+        &mut self,
+        event_kind: EventKind,
+        subscriber: impl Subscriber + Send + 'static,
+    ) -> Option<SubscriberID> {
+        self.writer.subscribe(event_kind, subscriber)
+    }
+
+    pub fn subscribe_closure<R: Returns>(
+        &mut self,
+        event_kind: EventKind,
+        subscribe_closure: impl Fn(&Event) -> R + Send + 'static,
+    ) -> Option<SubscriberID> {
+        self.writer.subscribe_closure(event_kind, subscribe_closure)
+    }
+
+    /// Subscribes to a specific event kind, returning its events as a [`EventStream`],
+    /// with their data deserialized into `T`
+    ///
+    /// The stream ends once it's unsubscribed with the returned ID, or the connection is closed for good
+    ///
+    /// Returns `None` is the websocket connection has already been closed previously
+    pub fn subscribe_stream<T: DeserializeOwned + Send + 'static>(
+        &mut self,
+        event_kind: EventKind,
+    ) -> Option<(SubscriberID, EventStream<T>)> {
+        self.writer.subscribe_stream(event_kind)
+    }
+
+    /// The same as [`LcuWebSocket::subscribe_stream`], but the stream holds at most `capacity` events,
+    /// with `overflow` deciding what happens to events that arrive while it's full
+    ///
+    /// Returns `None` is the websocket connection has already been closed previously
+    pub fn subscribe_stream_bounded<T: DeserializeOwned + Send + 'static>(
+        &mut self,
+        event_kind: EventKind,
+        capacity: usize,
+        overflow: Overflow,
+    ) -> Option<(SubscriberID, EventStream<T>)> {
+        self.writer
+            .subscribe_stream_bounded(event_kind, capacity, overflow)
+    }
+
+    /// Subscribes to every `OnJsonApiEvent`, only passing on events whose uri matches `pattern`, see [`Glob`]
+    ///
+    /// Unlike subscribing to a single endpoint, this covers every endpoint under a path, such as `/lol-lobby/**`
+    ///
+    /// Returns `None` is the websocket connection has already been closed previously
+    pub fn subscribe_filtered(
+        &mut self,
+        pattern: &str,
+        subscriber: impl Subscriber + Send + 'static,
+    ) -> Option<SubscriberID> {
+        self.writer.subscribe_filtered(pattern, subscriber)
+    }
+
+    /// The same as [`LcuWebSocket::subscribe_stream`], but for every event whose uri matches `pattern`, see [`Glob`]
+    ///
+    /// Returns `None` is the websocket connection has already been closed previously
+    pub fn subscribe_stream_filtered<T: DeserializeOwned + Send + 'static>(
+        &mut self,
+        pattern: &str,
+    ) -> Option<(SubscriberID, EventStream<T>)> {
+        self.writer.subscribe_stream_filtered(pattern)
+    }
+
+    /// Subscribes a clone of `subscriber` to each of `event_kinds`, returning their IDs in the same order
+    ///
+    /// To share state between them, use an `Arc<Mutex<_>>` as the subscriber
+    ///
+    /// Returns `None` is the websocket connection has already been closed previously
+    pub fn subscribe_many<S: Subscriber + Clone + Send + 'static>(
+        &mut self,
+        event_kinds: impl IntoIterator<Item = EventKind>,
+        subscriber: &S,
+    ) -> Option<Vec<SubscriberID>> {
+        self.writer.subscribe_many(event_kinds, subscriber)
+    }
+
+    /// Unsubscribe to a new API event
+    ///
+    /// If all subscribers have been removed, this will unsubscribe from the event as a whole
+    ///
+    /// Returns `None` if the connection to the websocket was already closed
+    pub fn unsubscribe(&mut self, event_kind: EventKind, id: SubscriberID) -> Option<()> {
+        self.writer.unsubscribe(event_kind, id)
+    }
+
+    #[must_use]
+    /// Terminate the event loop
+    pub fn abort(self) -> Option<()> {
+        self.reader.abort()
+    }
+
+    /// Unsubscribes from every event, closes the websocket, and waits for the event loop to finish,
+    /// see [`LcuWebSocketReader::shutdown`]
+    ///
+    /// # Errors
+    /// This returns the panic payload if the event loop panicked
+    pub fn shutdown(self) -> thread::Result<()> {
+        self.reader.shutdown()
+    }
+
+    #[must_use]
+    /// Checks whether the underlying thread is finished or not
+    pub fn is_finished(&self) -> bool {
+        self.reader.is_finished()
+    }
+
+    #[must_use]
+    /// Splits the connection into the half that subscribes, and the half that owns the event loop,
+    /// so they can be moved to different tasks
+    ///
+    /// Dropping the writer doesn't end the event loop, it has to be shut down through the reader
+    pub fn split(self) -> (LcuWebSocketWriter, LcuWebSocketReader) {
+        (self.writer, self.reader)
+    }
+}
+
+impl LcuWebSocketWriter {
     /// Subscribes to a specific event kind using the subscriber
     ///
     /// Returns `None` is the websocket connection has already been closed previously
@@ -226,6 +564,78 @@ impl LcuWebSocket {
         Some(SubscriberID(id))
     }
 
+    /// Subscribes to a specific event kind, returning its events as a [`EventStream`],
+    /// with their data deserialized into `T`
+    ///
+    /// The stream ends once it's unsubscribed with the returned ID, or the connection is closed for good
+    ///
+    /// Returns `None` is the websocket connection has already been closed previously
+    pub fn subscribe_stream<T: DeserializeOwned + Send + 'static>(
+        &mut self,
+        event_kind: EventKind,
+    ) -> Option<(SubscriberID, EventStream<T>)> {
+        let (subscriber, stream) = stream::StreamSubscriber::new();
+        let id = self.subscribe(event_kind, subscriber)?;
+        Some((id, stream))
+    }
+
+    /// The same as [`LcuWebSocketWriter::subscribe_stream`], but the stream holds at most `capacity` events,
+    /// with `overflow` deciding what happens to events that arrive while it's full
+    ///
+    /// Returns `None` is the websocket connection has already been closed previously
+    pub fn subscribe_stream_bounded<T: DeserializeOwned + Send + 'static>(
+        &mut self,
+        event_kind: EventKind,
+        capacity: usize,
+        overflow: Overflow,
+    ) -> Option<(SubscriberID, EventStream<T>)> {
+        let (subscriber, stream) = stream::StreamSubscriber::bounded(capacity, overflow);
+        let id = self.subscribe(event_kind, subscriber)?;
+        Some((id, stream))
+    }
+
+    /// Subscribes to every `OnJsonApiEvent`, only passing on events whose uri matches `pattern`, see [`Glob`]
+    ///
+    /// Unlike subscribing to a single endpoint, this covers every endpoint under a path, such as `/lol-lobby/**`
+    ///
+    /// Returns `None` is the websocket connection has already been closed previously
+    pub fn subscribe_filtered(
+        &mut self,
+        pattern: &str,
+        subscriber: impl Subscriber + Send + 'static,
+    ) -> Option<SubscriberID> {
+        let filtered = Filtered::new(Glob::new(pattern), subscriber);
+        self.subscribe(EventKind::json_api_event(), filtered)
+    }
+
+    /// The same as [`LcuWebSocketWriter::subscribe_stream`], but for every event whose uri matches `pattern`, see [`Glob`]
+    ///
+    /// Returns `None` is the websocket connection has already been closed previously
+    pub fn subscribe_stream_filtered<T: DeserializeOwned + Send + 'static>(
+        &mut self,
+        pattern: &str,
+    ) -> Option<(SubscriberID, EventStream<T>)> {
+        let (subscriber, stream) = stream::StreamSubscriber::new();
+        let id = self.subscribe_filtered(pattern, subscriber)?;
+        Some((id, stream))
+    }
+
+    /// Subscribes a clone of `subscriber` to each of `event_kinds`, returning their IDs in the same order
+    ///
+    /// To share state between them, use an `Arc<Mutex<_>>` as the subscriber
+    ///
+    /// Returns `None` is the websocket connection has already been closed previously
+    pub fn subscribe_many<S: Subscriber + Clone + Send + 'static>(
+        &mut self,
+        event_kinds: impl IntoIterator<Item = EventKind>,
+        subscriber: &S,
+    ) -> Option<Vec<SubscriberID>> {
+        event_kinds
+            .into_iter()
+            .map(|event_kind| self.subscribe(event_kind, subscriber.clone()))
+            .collect()
+    }
+
     /// Unsubscribe to a new API event
     ///
     /// If all subscribers have been removed, this will unsubscribe from the event as a whole
@@ -242,13 +652,28 @@ impl LcuWebSocket {
 
         Some(())
     }
+}
 
+impl LcuWebSocketReader {
     #[must_use]
     /// Terminate the event loop
     pub fn abort(self) -> Option<()> {
         self.ws_sender.send(ChannelMessage::Abort).ok()
     }
 
+    /// Unsubscribes from every event, closes the websocket, and waits for the event loop to finish
+    ///
+    /// Every subscriber has `on_unsubscribe` called, and every [`EventStream`] ends.
+    /// The LCU is given a short time to acknowledge the close, so it doesn't keep a half open connection
+    ///
+    /// # Errors
+    /// This returns the panic payload if the event loop panicked
+    pub fn shutdown(self) -> thread::Result<()> {
+        // If this fails, the event loop already ended on its own
+        let _ = self.ws_sender.send(ChannelMessage::Shutdown);
+        self.handle.join()
+    }
+
     #[must_use]
     /// Checks whether the underlying thread is finished or not
     pub fn is_finished(&self) -> bool {
@@ -263,71 +688,40 @@ pub fn force<R: Returns, F: FnMut(&Event) -> R + Send>(f: F) -> F {
 
 type SubscriberMap = EventMap<Vec<Option<Box<dyn Subscriber>>>>;
 
-fn event_loop(
-    error_handler: &mut impl ErrorHandler,
+/// How long [`LcuWebSocketReader::shutdown`] waits for the LCU to acknowledge the close
+const SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(1);
+
+fn event_loop<H: ErrorHandler, S: Read + Write>(
+    error_handler: &mut H,
     receiver: &Receiver<ChannelMessage>,
-    tls: &crate::tls::TlsType,
+    mut connect: impl FnMut(&mut H) -> Result<WebSocket<S>, WebSocketError>,
 ) {
     // The stare of the websocket
-    let mut maybe_stream: Option<WebSocketStream> = None;
+    let mut maybe_stream: Option<WebSocket<S>> = None;
     let mut subscribers = SubscriberMap::new();
     let mut control_flow = ControlFlow::Continue(Flow::Continue);
-    let mut abort = false;
+    let heartbeat = error_handler.heartbeat();
+    // When anything was last received from the socket, and when the last ping was sent
+    let mut last_received = Instant::now();
+    let mut last_ping = Instant::now();
+    // Messages received while disconnected, applied once connected again
+    let mut pending = VecDeque::new();
+
+    #[cfg(feature = "tracing")]
+    let _span = tracing::debug_span!("websocket").entered();
 
     while control_flow.is_continue() {
         if let Some(stream) = &mut maybe_stream {
-            if let Ok(message) = receiver.try_recv() {
-                // Variable to determine if a message should be sent to the websocket, only one can be sent at a time
-                let mut ws_message = None;
-
-                match message {
-                    ChannelMessage::Subscribe(code, event_kind, mut subscriber) => {
-                        let subscribers = subscribers.get_mut(&event_kind);
-
-                        // If the map is empty, we are not taking messages for this endpoint, so we have to subscribe
-                        if subscribers.is_empty() {
-                            let endpoint_str = event_kind.to_string();
-
-                            let command = format!("[{}, \"{endpoint_str}\"]", code as u8).into();
-
-                            ws_message = Some(Message::Text(command));
-                        }
-
-                        // Let the subscriber update its own state
-                        subscriber.on_subscribe(&event_kind, &code);
-
-                        if let Some(idx) = subscribers.iter().position(Option::is_none) {
-                            subscribers[idx] = Some(subscriber);
-                        } else {
-                            subscribers.push(Some(subscriber));
-                        }
-                    }
-                    ChannelMessage::Unsubscribe(subscriber_id, event_kind) => {
-                        let subscribers = subscribers.get_mut(&event_kind);
-                        let subscriber = &mut subscribers[subscriber_id.0];
-                        if let Some(subscriber) = subscriber {
-                            subscriber.on_unsubscribe(&event_kind);
-                        }
-
-                        *subscriber = None;
-
-                        if subscribers.iter().flatten().count() == 0 {
-                            let unsub = format!(
-                                "[{}, \"{}\"]",
-                                RequestType::Unsubscribe as u8,
-                                event_kind.to_string()
-                            )
-                            .into();
-
-                            ws_message = Some(Message::Text(unsub));
-                        }
-                    }
-                    ChannelMessage::Abort => {
-                        abort = true;
-                        ws_message = Some(Message::Close(None));
-                    }
+            if let Some(message) = pending.pop_front().or_else(|| receiver.try_recv().ok()) {
+                if let ChannelMessage::Shutdown = message {
+                    shutdown(stream, &mut subscribers, error_handler);
+                    return;
                 }
 
+                // Only one message can be sent to the websocket at a time
+                let ws_message = apply_message(message, &mut subscribers);
+                let abort = matches!(ws_message, Some(Message::Close(_)));
+
                 if let Some(Err(e)) = ws_message.map(|m| stream.send(m)) {
                     control_flow = error_handler.on_error(e.into());
                 }
@@ -340,19 +734,72 @@ fn event_loop(
 
             // Else if the `control_flow` is still to continue, we take out next message
             if control_flow == ControlFlow::Continue(Flow::Continue) {
-                control_flow = receive_message(stream, &mut subscribers, error_handler)
-                    .unwrap_or_else(|e| error_handler.on_error(e));
+                control_flow =
+                    receive_message(stream, &mut subscribers, error_handler, &mut last_received)
+                        .unwrap_or_else(|e| error_handler.on_error(e));
+            }
+
+            if let (Some(heartbeat), ControlFlow::Continue(Flow::Continue)) =
+                (heartbeat, &control_flow)
+            {
+                if last_received.elapsed() >= heartbeat.timeout {
+                    control_flow = error_handler.on_error(WebSocketError::ConnectionLost);
+                } else if last_received.elapsed() >= heartbeat.interval
+                    && last_ping.elapsed() >= heartbeat.interval
+                {
+                    last_ping = Instant::now();
+                    if let Err(e) = stream.send(Message::Ping(Bytes::new())) {
+                        control_flow = error_handler.on_error(e.into());
+                    }
+                }
             }
         } else {
-            connect(tls, error_handler).map_or_else(
-                |e| control_flow = error_handler.on_error(e),
-                |stream| maybe_stream = Some(stream),
-            );
+            // Without a connection, only ending the loop can be acted on, everything else waits until connected
+            while let Ok(message) = receiver.try_recv() {
+                match message {
+                    ChannelMessage::Abort | ChannelMessage::Shutdown => {
+                        unsubscribe_all(&mut subscribers);
+                        return;
+                    }
+                    message => pending.push_back(message),
+                }
+            }
+
+            match connect(error_handler) {
+                Ok(mut stream) => match resubscribe(&mut stream, &mut subscribers) {
+                    Ok(()) => {
+                        #[cfg(feature = "tracing")]
+                        tracing::info!("connected to the client");
+
+                        maybe_stream = Some(stream);
+                        last_received = Instant::now();
+                        last_ping = Instant::now();
+                        // Otherwise a reconnect would be attempted again, dropping the new stream
+                        control_flow = ControlFlow::Continue(Flow::Continue);
+                    }
+                    Err(e) => control_flow = error_handler.on_error(e),
+                },
+                Err(e) => {
+                    #[cfg(feature = "tracing")]
+                    tracing::debug!(error = %e, "connecting failed");
+
+                    control_flow = error_handler.on_error(e);
+                }
+            }
         }
 
         // If the `control_flow` is to try and reconnect, we make the stream `None` before the start of the next run
-        if control_flow == ControlFlow::Continue(Flow::TryReconnect) {
-            maybe_stream = None;
+        if control_flow == ControlFlow::Continue(Flow::TryReconnect)
+            && maybe_stream.take().is_some()
+        {
+            #[cfg(feature = "tracing")]
+            tracing::info!("the connection was lost, reconnecting");
+
+            for (event_kind, subscribers) in subscribers.iter_mut() {
+                for subscriber in subscribers.iter_mut().flatten() {
+                    subscriber.on_connection_lost(&event_kind);
+                }
+            }
         }
     }
 
@@ -365,52 +812,237 @@ fn event_loop(
     }
 }
 
-fn receive_message(
-    stream: &mut WebSocketStream,
+/// Removes every subscriber, returning the event kinds that had any, which need to be unsubscribed from
+fn unsubscribe_all(subscribers: &mut SubscriberMap) -> Vec<EventKind> {
+    let mut active = Vec::new();
+
+    for (event_kind, subscribers) in subscribers.iter_mut() {
+        let mut removed = std::mem::take(subscribers).into_iter().flatten().peekable();
+
+        if removed.peek().is_some() {
+            for mut subscriber in removed {
+                subscriber.on_unsubscribe(&event_kind);
+            }
+
+            active.push(event_kind);
+        }
+    }
+
+    active
+}
+
+/// Unsubscribes from every event, then closes the connection, waiting up to [`SHUTDOWN_TIMEOUT`] for the LCU to acknowledge it
+fn shutdown<S: Read + Write>(
+    stream: &mut WebSocket<S>,
+    subscribers: &mut SubscriberMap,
+    error_handler: &mut impl ErrorHandler,
+) {
+    let unsubscribes = unsubscribe_all(subscribers).into_iter().map(|event_kind| {
+        #[cfg(feature = "tracing")]
+        tracing::debug!(event = %event_kind.to_string(), "unsubscribing");
+
+        let command = format!(
+            "[{}, \"{}\"]",
+            RequestType::Unsubscribe as u8,
+            event_kind.to_string()
+        );
+        Message::Text(command.into())
+    });
+
+    let mut error = None;
+    for message in unsubscribes.chain([Message::Close(None)]) {
+        if let Err(e) = stream.send(message) {
+            error = Some(e);
+            break;
+        }
+    }
+
+    let started = Instant::now();
+    while error.is_none() {
+        match stream.read().no_block() {
+            Ok(None) if started.elapsed() >= SHUTDOWN_TIMEOUT => break,
+            Ok(None) => thread::sleep(Duration::from_millis(10)),
+            Ok(Some(_)) => {}
+            Err(e) => error = Some(e),
+        }
+    }
+
+    match error {
+        // The LCU acknowledged the close
+        None | Some(tungstenite::Error::ConnectionClosed) => {}
+        Some(e) => {
+            let _ = error_handler.on_error(e.into());
+        }
+    }
+}
+
+fn receive_message<S: Read + Write>(
+    stream: &mut WebSocket<S>,
     subscribers: &mut SubscriberMap,
     error_handler: &mut impl ErrorHandler,
+    last_received: &mut Instant,
 ) -> Result<ControlFlow<(), Flow>, WebSocketError> {
-    let read = stream
-        .read()
-        .no_block()?
-        .filter(|msg| !msg.is_empty())
-        .map(Message::into_data);
+    let read = stream.read().no_block()?;
+
+    // Pings and pongs count too, they're how a quiet connection is known to still be alive
+    if read.is_some() {
+        *last_received = Instant::now();
+    }
+
+    // The client closing the connection is passed to the error handler, so it can decide whether to reconnect
+    if let Some(Message::Close(_)) = read {
+        return Err(tungstenite::Error::ConnectionClosed.into());
+    }
+
+    let read = read.filter(|msg| !msg.is_empty()).map(Message::into_data);
 
     if let Some(data) = read {
         let json = serde_json::from_slice::<Event>(&data)?;
-        let subscribers = subscribers.get_mut(&json.1);
+        return Ok(dispatch(&json, subscribers));
+    }
+
+    error_handler.on_timeout();
+
+    Ok(ControlFlow::Continue(Flow::Continue))
+}
+
+/// Sends the subscription for every event kind that still has subscribers, after reconnecting,
+/// then lets the subscribers know they may have missed events
+///
+/// Nothing is subscribed on the first connection, so this only does anything on a reconnect
+fn resubscribe<S: Read + Write>(
+    stream: &mut WebSocket<S>,
+    subscribers: &mut SubscriberMap,
+) -> Result<(), WebSocketError> {
+    let mut active = Vec::new();
+
+    for (event_kind, subscribers) in subscribers.iter_mut() {
+        if subscribers.iter().flatten().count() == 0 {
+            continue;
+        }
+
+        let command = format!(
+            "[{}, \"{}\"]",
+            RequestType::Subscribe as u8,
+            event_kind.to_string()
+        );
+
+        #[cfg(feature = "tracing")]
+        tracing::debug!(event = %event_kind.to_string(), "subscribing again");
+
+        stream.send(Message::Text(command.into()))?;
+        active.push((event_kind, subscribers));
+    }
 
+    for (event_kind, subscribers) in active {
         for subscriber in subscribers.iter_mut().flatten() {
-            let mut continues = true;
+            subscriber.on_reconnect(&event_kind);
+        }
+    }
+
+    Ok(())
+}
+
+/// Updates the subscribers according to the message, returning the message that needs to be sent to the websocket, if any
+fn apply_message(message: ChannelMessage, subscribers: &mut SubscriberMap) -> Option<Message> {
+    // Variable to determine if a message should be sent to the websocket
+    let mut ws_message = None;
 
-            subscriber.on_event(&json, &mut continues);
+    match message {
+        ChannelMessage::Subscribe(code, event_kind, mut subscriber) => {
+            let subscribers = subscribers.get_mut(&event_kind);
 
-            if !continues {
-                return Ok(ControlFlow::Break(()));
+            // If the map is empty, we are not taking messages for this endpoint, so we have to subscribe
+            if subscribers.is_empty() {
+                let endpoint_str = event_kind.to_string();
+
+                #[cfg(feature = "tracing")]
+                tracing::debug!(event = %endpoint_str, "subscribing");
+
+                let command = format!("[{}, \"{endpoint_str}\"]", code as u8).into();
+
+                ws_message = Some(Message::Text(command));
+            }
+
+            // Let the subscriber update its own state
+            subscriber.on_subscribe(&event_kind, &code);
+
+            if let Some(idx) = subscribers.iter().position(Option::is_none) {
+                subscribers[idx] = Some(subscriber);
+            } else {
+                subscribers.push(Some(subscriber));
             }
         }
-    } else {
-        error_handler.on_timeout();
+        ChannelMessage::Unsubscribe(subscriber_id, event_kind) => {
+            let subscribers = subscribers.get_mut(&event_kind);
+            let subscriber = &mut subscribers[subscriber_id.0];
+            if let Some(subscriber) = subscriber {
+                subscriber.on_unsubscribe(&event_kind);
+            }
+
+            *subscriber = None;
+
+            if subscribers.iter().flatten().count() == 0 {
+                #[cfg(feature = "tracing")]
+                tracing::debug!(event = %event_kind.to_string(), "unsubscribing");
+
+                let unsub = format!(
+                    "[{}, \"{}\"]",
+                    RequestType::Unsubscribe as u8,
+                    event_kind.to_string()
+                )
+                .into();
+
+                ws_message = Some(Message::Text(unsub));
+            }
+        }
+        ChannelMessage::Abort => {
+            ws_message = Some(Message::Close(None));
+        }
+        ChannelMessage::Shutdown => {
+            unsubscribe_all(subscribers);
+            ws_message = Some(Message::Close(None));
+        }
     }
 
-    Ok(ControlFlow::Continue(Flow::Continue))
+    ws_message
+}
+
+/// Passes the event to every subscriber of its kind, stopping early if one of them breaks the loop
+fn dispatch(event: &Event, subscribers: &mut SubscriberMap) -> ControlFlow<(), Flow> {
+    let subscribers = subscribers.get_mut(&event.1);
+
+    for subscriber in subscribers.iter_mut().flatten() {
+        let mut continues = true;
+
+        subscriber.on_event(event, &mut continues);
+
+        if !continues {
+            return ControlFlow::Break(());
+        }
+    }
+
+    ControlFlow::Continue(Flow::Continue)
 }
 
 fn connect(
     tls: &crate::tls::TlsType,
     error_handler: &mut impl ErrorHandler,
+    locator: &impl ClientLocator,
 ) -> Result<WebSocketStream, WebSocketError> {
     const TIMEOUT: Duration = Duration::from_millis(100);
 
-    let (addr, auth) = get_running_client(CLIENT_PROCESS_NAME, GAME_PROCESS_NAME, false)?;
+    let located = locator.locate()?;
 
-    let str_req = format!("wss://{addr}");
+    let str_req = format!("wss://{}", located.addr);
 
     let mut request = str_req.into_client_request()?;
 
-    request.headers_mut().insert("Authorization", auth?);
+    request
+        .headers_mut()
+        .insert("Authorization", located.auth_header.parse()?);
 
-    let tcp_stream = TcpStream::connect_timeout(&SocketAddr::V4(addr), TIMEOUT)?;
+    let tcp_stream = TcpStream::connect_timeout(&located.addr, TIMEOUT)?;
 
     let (mut stream, _) = tungstenite::client_tls_with_config(
         request.clone(),
@@ -418,9 +1050,368 @@ fn connect(
         None,
         Some(crate::tls::wrap_connector(tls)),
     )
-    .expect("The TLS handshake should never fail");
+    .map_err(|err| match err {
+        // The upgrade was rejected, or the connection was cut during it
+        HandshakeError::Failure(err) => WebSocketError::from(err),
+        HandshakeError::Interrupted(_) => unreachable!("The TCP stream is still blocking here"),
+    })?;
+
+    error_handler.on_connect(&mut stream)?;
+
+    Ok(stream)
+}
+
+fn connect_remote(
+    remote: &RemoteLcu,
+    error_handler: &mut impl ErrorHandler,
+) -> Result<WebSocketStream, WebSocketError> {
+    // A forwarded client is further away than the loopback, so this is more lenient than locally
+    const TIMEOUT: Duration = Duration::from_secs(5);
+
+    let located = remote.locate()?;
+
+    let mut request = format!("wss://{}", located.addr).into_client_request()?;
+
+    request
+        .headers_mut()
+        .insert("Authorization", located.auth_header.parse()?);
+
+    let tcp_stream = TcpStream::connect_timeout(&located.addr, TIMEOUT)?;
+    let stream = crate::tls::remote_stream(remote.tls(), tcp_stream)?;
+
+    let (mut stream, _) = tungstenite::client(request, stream).map_err(|err| match err {
+        HandshakeError::Failure(err) => WebSocketError::from(err),
+        HandshakeError::Interrupted(_) => unreachable!("The TCP stream is still blocking here"),
+    })?;
 
     error_handler.on_connect(&mut stream)?;
 
     Ok(stream)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::types::{Event, EventData, EventKind, RequestType};
+    use super::{test_util, ErrorHandler, Flow, LcuWebSocket, WebSocketError};
+    use std::ops::ControlFlow;
+    use std::sync::mpsc;
+    use std::thread;
+    use std::time::Duration;
+    use tungstenite::Message;
+
+    struct TestErrorHandler;
+
+    impl ErrorHandler for TestErrorHandler {
+        fn on_error(&mut self, error: WebSocketError) -> ControlFlow<(), Flow> {
+            panic!("{error}");
+        }
+
+        fn on_timeout(&mut self) {
+            thread::sleep(Duration::from_millis(1));
+        }
+    }
+
+    #[test]
+    fn test_mock_stream() {
+        let (stream, mut server) = test_util::mock_stream();
+        let mut ws_client = LcuWebSocket::from_stream(stream, TestErrorHandler);
+
+        let (sender, receiver) = mpsc::channel();
+
+        ws_client
+            .subscribe_closure(EventKind::json_api_event(), move |event: &Event| {
+                sender.send(event.clone()).is_ok()
+            })
+            .unwrap();
+
+        // Wait for the subscription to reach the server, so the event isn't sent before it's processed
+        let subscribe = loop {
+            if let Some(message) = server.receive().unwrap() {
+                break message;
+            }
+            thread::sleep(Duration::from_millis(1));
+        };
+
+        assert_eq!(subscribe, Message::text("[5, \"OnJsonApiEvent\"]"));
+
+        server
+            .send_text(
+                r#"[8, "OnJsonApiEvent", {"data": 1, "eventType": "Update", "uri": "/example"}]"#,
+            )
+            .unwrap();
+
+        let event = receiver.recv_timeout(Duration::from_secs(1)).unwrap();
+
+        assert_eq!(
+            event,
+            Event(
+                RequestType::Event,
+                EventKind::json_api_event(),
+                EventData {
+                    data: 1.into(),
+                    event_type: "Update".into(),
+                    uri: "/example".into(),
+                }
+            )
+        );
+
+        ws_client.abort().unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_subscribe_stream() {
+        use super::{DecodedEvent, StreamEvent};
+        use futures_util::StreamExt;
+
+        let (stream, mut server) = test_util::mock_stream();
+        let mut ws_client = LcuWebSocket::from_stream(stream, TestErrorHandler);
+
+        let event_kind = EventKind::json_api_event_callback_str("/lol-gameflow/v1/gameflow-phase");
+        let (id, mut events) = ws_client
+            .subscribe_stream::<String>(event_kind.clone())
+            .unwrap();
+
+        let subscribe = loop {
+            if let Some(message) = server.receive().unwrap() {
+                break message;
+            }
+            thread::sleep(Duration::from_millis(1));
+        };
+
+        assert_eq!(
+            subscribe,
+            Message::text("[5, \"OnJsonApiEvent_lol-gameflow_v1_gameflow-phase\"]")
+        );
+
+        server
+            .send_text(
+                r#"[8, "OnJsonApiEvent_lol-gameflow_v1_gameflow-phase", {"data": "Lobby", "eventType": "Update", "uri": "/lol-gameflow/v1/gameflow-phase"}]"#,
+            )
+            .unwrap();
+        server
+            .send_text(
+                r#"[8, "OnJsonApiEvent_lol-gameflow_v1_gameflow-phase", {"data": 1, "eventType": "Update", "uri": "/lol-gameflow/v1/gameflow-phase"}]"#,
+            )
+            .unwrap();
+
+        assert_eq!(
+            events.next().await.unwrap().unwrap(),
+            StreamEvent::Event(DecodedEvent {
+                event_type: "Update".into(),
+                uri: "/lol-gameflow/v1/gameflow-phase".into(),
+                data: "Lobby".to_string(),
+            })
+        );
+
+        // Data in the wrong shape is an error, but doesn't end the stream
+        assert!(matches!(
+            events.next().await,
+            Some(Err(WebSocketError::SerdeJson(_)))
+        ));
+
+        ws_client.unsubscribe(event_kind, id).unwrap();
+        assert!(events.next().await.is_none());
+
+        ws_client.abort().unwrap();
+    }
+
+    fn next_message(server: &mut test_util::MockServer) -> Message {
+        loop {
+            if let Some(message) = server.receive().unwrap() {
+                break message;
+            }
+            thread::sleep(Duration::from_millis(1));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_resubscribe() {
+        use super::{event_loop, AutoReconnect, StreamEvent};
+        use futures_util::StreamExt;
+
+        let (first, mut first_server) = test_util::mock_stream();
+        let (second, mut second_server) = test_util::mock_stream();
+        let mut streams = vec![second, first];
+
+        let (ws_sender, ws_receiver) = mpsc::channel();
+        let handle = thread::spawn(move || {
+            let mut error_handler = AutoReconnect {
+                delay: Duration::from_millis(1),
+                heartbeat: None,
+            };
+
+            event_loop(&mut error_handler, &ws_receiver, |_| {
+                streams
+                    .pop()
+                    .ok_or(tungstenite::Error::ConnectionClosed.into())
+            });
+        });
+
+        let mut ws_client = LcuWebSocket::from_parts(ws_sender, handle);
+
+        let (_, mut events) = ws_client
+            .subscribe_stream::<String>(EventKind::json_api_event_callback_str(
+                "/lol-gameflow/v1/gameflow-phase",
+            ))
+            .unwrap();
+
+        let subscribe = Message::text("[5, \"OnJsonApiEvent_lol-gameflow_v1_gameflow-phase\"]");
+        assert_eq!(next_message(&mut first_server), subscribe);
+
+        // The client exits, so the subscription has to be sent again on the new connection
+        first_server.close().unwrap();
+        assert_eq!(next_message(&mut second_server), subscribe);
+        assert_eq!(
+            events.next().await.unwrap().unwrap(),
+            StreamEvent::ConnectionLost
+        );
+        assert_eq!(
+            events.next().await.unwrap().unwrap(),
+            StreamEvent::Reconnected
+        );
+
+        second_server
+            .send_text(
+                r#"[8, "OnJsonApiEvent_lol-gameflow_v1_gameflow-phase", {"data": "Lobby", "eventType": "Update", "uri": "/lol-gameflow/v1/gameflow-phase"}]"#,
+            )
+            .unwrap();
+        assert!(matches!(
+            events.next().await,
+            Some(Ok(StreamEvent::Event(event))) if event.data == "Lobby"
+        ));
+
+        ws_client.abort().unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_heartbeat() {
+        use super::{event_loop, AutoReconnect, Heartbeat, StreamEvent};
+        use futures_util::StreamExt;
+
+        let (first, mut first_server) = test_util::mock_stream();
+        let (second, mut second_server) = test_util::mock_stream();
+        let mut streams = vec![second, first];
+
+        let (ws_sender, ws_receiver) = mpsc::channel();
+        let handle = thread::spawn(move || {
+            let mut error_handler = AutoReconnect {
+                delay: Duration::from_millis(1),
+                heartbeat: Some(Heartbeat {
+                    interval: Duration::from_millis(10),
+                    timeout: Duration::from_secs(1),
+                }),
+            };
+
+            event_loop(&mut error_handler, &ws_receiver, |_| {
+                streams
+                    .pop()
+                    .ok_or(tungstenite::Error::ConnectionClosed.into())
+            });
+        });
+
+        let mut ws_client = LcuWebSocket::from_parts(ws_sender, handle);
+
+        let (_, mut events) = ws_client
+            .subscribe_stream::<String>(EventKind::json_api_event_callback_str(
+                "/lol-gameflow/v1/gameflow-phase",
+            ))
+            .unwrap();
+
+        let subscribe = Message::text("[5, \"OnJsonApiEvent_lol-gameflow_v1_gameflow-phase\"]");
+        assert_eq!(next_message(&mut first_server), subscribe);
+
+        // A quiet connection is pinged
+        assert!(matches!(next_message(&mut first_server), Message::Ping(_)));
+
+        // The client never answers, so the connection is lost, and a new one is made
+        assert_eq!(next_message(&mut second_server), subscribe);
+        assert_eq!(
+            events.next().await.unwrap().unwrap(),
+            StreamEvent::ConnectionLost
+        );
+        assert_eq!(
+            events.next().await.unwrap().unwrap(),
+            StreamEvent::Reconnected
+        );
+
+        ws_client.abort().unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_shutdown() {
+        use futures_util::StreamExt;
+
+        let (client, mut server) = test_util::mock_stream();
+        let ws_client = LcuWebSocket::from_stream(client, super::DefaultErrorHandler);
+        let (mut writer, reader) = ws_client.split();
+
+        let (_, mut events) = writer
+            .subscribe_stream::<String>(EventKind::json_api_event_callback_str(
+                "/lol-gameflow/v1/gameflow-phase",
+            ))
+            .unwrap();
+
+        assert_eq!(
+            next_message(&mut server),
+            Message::text("[5, \"OnJsonApiEvent_lol-gameflow_v1_gameflow-phase\"]")
+        );
+
+        // Acts as the LCU, acknowledging the close
+        let server = thread::spawn(move || {
+            let mut received = Vec::new();
+            loop {
+                let message = next_message(&mut server);
+                if let Message::Close(_) = message {
+                    let _ = server.receive();
+                    break received;
+                }
+                received.push(message);
+            }
+        });
+
+        reader.shutdown().unwrap();
+
+        assert_eq!(
+            server.join().unwrap(),
+            vec![Message::text(
+                "[6, \"OnJsonApiEvent_lol-gameflow_v1_gameflow-phase\"]"
+            )]
+        );
+        assert!(events.next().await.is_none());
+
+        // The event loop is gone, so nothing can be subscribed to anymore
+        assert!(writer
+            .subscribe_closure(EventKind::json_api_event(), |_| {})
+            .is_none());
+    }
+
+    #[cfg(feature = "rest")]
+    #[tokio::test]
+    async fn test_remote() {
+        use super::{DefaultErrorHandler, StreamEvent};
+        use crate::mock::{MockLcu, MOCK_TOKEN};
+        use crate::process_info::RemoteLcu;
+        use futures_util::StreamExt;
+
+        const PHASE: &str = "/lol-gameflow/v1/gameflow-phase";
+
+        let mock = MockLcu::start().unwrap();
+        let remote = RemoteLcu::new("localhost", mock.addr().port(), MOCK_TOKEN)
+            .danger_accept_invalid_certs();
+        let mut ws = LcuWebSocket::new_remote(DefaultErrorHandler, remote);
+
+        let (_, mut phases) = ws
+            .subscribe_stream::<String>(EventKind::json_api_event_callback_str(PHASE))
+            .unwrap();
+
+        assert!(mock.wait_for_subscription(PHASE, Duration::from_secs(5)));
+        assert_eq!(mock.emit(PHASE, "Update", "ChampSelect").unwrap(), 1);
+
+        let Some(Ok(StreamEvent::Event(event))) = phases.next().await else {
+            panic!("The event should be received");
+        };
+        assert_eq!(event.data, "ChampSelect");
+
+        ws.abort().unwrap();
+    }
+}
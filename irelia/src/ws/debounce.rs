@@ -0,0 +1,123 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, RecvTimeoutError, Sender};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+use super::types::Event;
+use super::Subscriber;
+
+/// Subscriber that coalesces bursts of events, only passing the latest one on once they settle
+///
+/// An event is forwarded once `window` has passed without a newer one arriving,
+/// and the last pending event is always forwarded when the subscriber is removed.
+///
+/// The wrapped subscriber runs on its own thread, so breaking the loop from it
+/// takes effect on the next event received. `on_subscribe` and `on_unsubscribe` are not forwarded
+///
+/// ```no_run
+/// use irelia::ws::{types::EventKind, Debounce, LcuWebSocket};
+/// use std::time::Duration;
+///
+/// let mut ws = LcuWebSocket::new();
+///
+/// let subscriber = Debounce::new(Duration::from_millis(250), |event: &irelia::ws::types::Event| {
+///     println!("{:?}", event.2.data);
+/// });
+///
+/// ws.subscribe(EventKind::json_api_event_callback_str("/lol-champ-select/v1/session"), subscriber);
+/// ```
+pub struct Debounce {
+    sender: Sender<Event>,
+    continues: Arc<AtomicBool>,
+}
+
+impl Debounce {
+    #[must_use]
+    /// Wraps `subscriber`, forwarding only the latest event of each burst
+    pub fn new(window: Duration, subscriber: impl Subscriber + Send + 'static) -> Self {
+        let (sender, receiver) = mpsc::channel::<Event>();
+        let continues = Arc::new(AtomicBool::new(true));
+        let worker_continues = continues.clone();
+
+        thread::spawn(move || {
+            let mut subscriber = subscriber;
+
+            while let Ok(mut latest) = receiver.recv() {
+                let disconnected = loop {
+                    match receiver.recv_timeout(window) {
+                        Ok(event) => latest = event,
+                        Err(RecvTimeoutError::Timeout) => break false,
+                        Err(RecvTimeoutError::Disconnected) => break true,
+                    }
+                };
+
+                let mut continues = true;
+                subscriber.on_event(&latest, &mut continues);
+
+                if !continues {
+                    worker_continues.store(false, Ordering::Relaxed);
+                }
+
+                if disconnected {
+                    break;
+                }
+            }
+        });
+
+        Self { sender, continues }
+    }
+}
+
+impl Subscriber for Debounce {
+    fn on_event(&mut self, event: &Event, continues: &mut bool) {
+        *continues =
+            self.continues.load(Ordering::Relaxed) && self.sender.send(event.clone()).is_ok();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Debounce;
+    use crate::ws::types::{Event, EventData, EventKind, RequestType};
+    use crate::ws::Subscriber;
+    use std::sync::mpsc;
+    use std::time::Duration;
+
+    fn event(data: u32) -> Event {
+        Event(
+            RequestType::Event,
+            EventKind::json_api_event(),
+            EventData {
+                data: data.into(),
+                event_type: "Update".into(),
+                uri: "/example".into(),
+            },
+        )
+    }
+
+    #[test]
+    fn test_debounce() {
+        let (sender, receiver) = mpsc::channel();
+
+        let mut debounce = Debounce::new(Duration::from_millis(50), move |event: &Event| {
+            sender.send(event.2.data.clone()).is_ok()
+        });
+
+        let mut continues = true;
+        for i in 0..5 {
+            debounce.on_event(&event(i), &mut continues);
+        }
+
+        assert_eq!(receiver.recv_timeout(Duration::from_secs(1)).unwrap(), 4);
+
+        debounce.on_event(&event(5), &mut continues);
+        debounce.on_event(&event(6), &mut continues);
+        assert!(continues);
+
+        // Dropping flushes the final state immediately
+        drop(debounce);
+        assert_eq!(receiver.recv().unwrap(), 6);
+        assert!(receiver.recv().is_err());
+    }
+}
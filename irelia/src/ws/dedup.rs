@@ -0,0 +1,329 @@
+use serde_derive::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+
+use super::types::{Event, EventData, EventKind, RequestType};
+use super::{PoisonBehavior, Subscriber};
+
+/// A single operation of a JSON Patch (RFC 6902), as produced by [`diff`]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "op", rename_all = "lowercase")]
+pub enum Change {
+    Add { path: String, value: Value },
+    Remove { path: String },
+    Replace { path: String, value: Value },
+}
+
+/// Lists the changes from `old` to `new`, as a JSON Patch (RFC 6902) with paths as JSON pointers
+///
+/// Objects are compared key by key, and arrays index by index, anything else that differs is replaced as a whole.
+/// Applying the changes in order to `old` results in `new`
+#[must_use]
+pub fn diff(old: &Value, new: &Value) -> Vec<Change> {
+    let mut changes = Vec::new();
+    diff_into(&mut String::new(), old, new, &mut changes);
+    changes
+}
+
+fn diff_into(path: &mut String, old: &Value, new: &Value, changes: &mut Vec<Change>) {
+    match (old, new) {
+        (Value::Object(old), Value::Object(new)) => {
+            for (key, old_value) in old {
+                let len = push_segment(path, key);
+
+                match new.get(key) {
+                    Some(new_value) => diff_into(path, old_value, new_value, changes),
+                    None => changes.push(Change::Remove { path: path.clone() }),
+                }
+
+                path.truncate(len);
+            }
+
+            for (key, new_value) in new {
+                if !old.contains_key(key) {
+                    let len = push_segment(path, key);
+                    changes.push(Change::Add {
+                        path: path.clone(),
+                        value: new_value.clone(),
+                    });
+                    path.truncate(len);
+                }
+            }
+        }
+        (Value::Array(old), Value::Array(new)) => {
+            for (idx, (old_value, new_value)) in old.iter().zip(new).enumerate() {
+                let len = push_segment(path, &idx.to_string());
+                diff_into(path, old_value, new_value, changes);
+                path.truncate(len);
+            }
+
+            // Removed from the back, so the indexes of the elements before stay the same
+            for idx in (new.len()..old.len()).rev() {
+                let len = push_segment(path, &idx.to_string());
+                changes.push(Change::Remove { path: path.clone() });
+                path.truncate(len);
+            }
+
+            for (idx, new_value) in new.iter().enumerate().skip(old.len()) {
+                let len = push_segment(path, &idx.to_string());
+                changes.push(Change::Add {
+                    path: path.clone(),
+                    value: new_value.clone(),
+                });
+                path.truncate(len);
+            }
+        }
+        (old, new) if old != new => changes.push(Change::Replace {
+            path: path.clone(),
+            value: new.clone(),
+        }),
+        _ => {}
+    }
+}
+
+/// Appends `/segment` to the pointer, escaped, returning the length to truncate back to
+fn push_segment(path: &mut String, segment: &str) -> usize {
+    let len = path.len();
+    path.push('/');
+    path.push_str(&segment.replace('~', "~0").replace('/', "~1"));
+    len
+}
+
+/// Subscriber that only passes on events whose payload changed since the last event for the same uri
+///
+/// The LCU often sends the same payload again, such as the gameflow phase being broadcast again unchanged,
+/// so this cuts down on the work done for each event. Payloads are compared per uri,
+/// so this can wrap subscribers of every `OnJsonApiEvent`. `Delete` events are always passed on,
+/// as long as there was something to delete.
+///
+/// With [`Dedup::with_diff`], the data of the event passed on is the list of changes since the last payload,
+/// as a JSON Patch, see [`diff`]. The first event for a uri replaces the whole document
+///
+/// ```no_run
+/// use irelia::ws::{types::EventKind, Dedup, LcuWebSocket};
+///
+/// let mut ws = LcuWebSocket::new();
+///
+/// let subscriber = Dedup::new(|event: &irelia::ws::types::Event| {
+///     println!("{:?}", event.2.data);
+/// });
+///
+/// ws.subscribe(EventKind::json_api_event_callback_str("/lol-gameflow/v1/gameflow-phase"), subscriber);
+/// ```
+pub struct Dedup<S> {
+    subscriber: S,
+    last: HashMap<String, Value>,
+    emit_diff: bool,
+}
+
+impl<S: Subscriber> Dedup<S> {
+    #[must_use]
+    /// Wraps `subscriber`, passing on events unchanged, but only if their payload changed
+    pub fn new(subscriber: S) -> Self {
+        Self {
+            subscriber,
+            last: HashMap::new(),
+            emit_diff: false,
+        }
+    }
+
+    #[must_use]
+    /// Wraps `subscriber`, passing on the changes to the payload as a JSON Patch, in place of the payload
+    pub fn with_diff(subscriber: S) -> Self {
+        Self {
+            emit_diff: true,
+            ..Self::new(subscriber)
+        }
+    }
+
+    #[must_use]
+    /// The last payload passed on for `uri`
+    pub fn last(&self, uri: &str) -> Option<&Value> {
+        self.last.get(uri)
+    }
+}
+
+impl<S: Subscriber> Subscriber for Dedup<S> {
+    fn on_poison(&self) -> PoisonBehavior {
+        self.subscriber.on_poison()
+    }
+
+    fn on_subscribe(&mut self, event_kind: &EventKind, request_code: &RequestType) {
+        self.subscriber.on_subscribe(event_kind, request_code);
+    }
+
+    fn on_event(&mut self, event: &Event, continues: &mut bool) {
+        let EventData {
+            data,
+            event_type,
+            uri,
+        } = &event.2;
+
+        let previous = if event_type == "Delete" {
+            match self.last.remove(uri) {
+                Some(previous) => previous,
+                None => return,
+            }
+        } else {
+            match self.last.insert(uri.clone(), data.clone()) {
+                Some(previous) if previous == *data => return,
+                Some(previous) => previous,
+                None => Value::Null,
+            }
+        };
+
+        if !self.emit_diff {
+            self.subscriber.on_event(event, continues);
+            return;
+        }
+
+        let changes = if previous.is_null() || event_type == "Delete" {
+            vec![Change::Replace {
+                path: String::new(),
+                value: data.clone(),
+            }]
+        } else {
+            diff(&previous, data)
+        };
+
+        let patch = Event(
+            event.0,
+            event.1.clone(),
+            EventData {
+                data: serde_json::to_value(changes).unwrap_or_default(),
+                event_type: event_type.clone(),
+                uri: uri.clone(),
+            },
+        );
+
+        self.subscriber.on_event(&patch, continues);
+    }
+
+    fn on_unsubscribe(&mut self, event_kind: &EventKind) {
+        self.last.clear();
+        self.subscriber.on_unsubscribe(event_kind);
+    }
+
+    fn on_reconnect(&mut self, event_kind: &EventKind) {
+        self.subscriber.on_reconnect(event_kind);
+    }
+
+    fn on_connection_lost(&mut self, event_kind: &EventKind) {
+        self.subscriber.on_connection_lost(event_kind);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{diff, Change, Dedup};
+    use crate::ws::types::{Event, EventData, EventKind, RequestType};
+    use crate::ws::Subscriber;
+    use serde_json::{json, Value};
+
+    fn event(event_type: &str, data: Value) -> Event {
+        Event(
+            RequestType::Event,
+            EventKind::json_api_event(),
+            EventData {
+                data,
+                event_type: event_type.into(),
+                uri: "/lol-lobby/v2/lobby".into(),
+            },
+        )
+    }
+
+    #[test]
+    fn test_diff() {
+        let old =
+            json!({"gameConfig": {"queueId": 420}, "members": [1, 2, 3], "a/b": 1, "gone": true});
+        let new = json!({"gameConfig": {"queueId": 440}, "members": [1], "a/b": 2, "new": null});
+
+        assert_eq!(
+            diff(&old, &new),
+            [
+                Change::Replace {
+                    path: "/a~1b".into(),
+                    value: json!(2)
+                },
+                Change::Replace {
+                    path: "/gameConfig/queueId".into(),
+                    value: json!(440)
+                },
+                Change::Remove {
+                    path: "/gone".into()
+                },
+                Change::Remove {
+                    path: "/members/2".into()
+                },
+                Change::Remove {
+                    path: "/members/1".into()
+                },
+                Change::Add {
+                    path: "/new".into(),
+                    value: Value::Null
+                },
+            ]
+        );
+
+        assert!(diff(&new, &new).is_empty());
+        assert_eq!(
+            serde_json::to_value(diff(&json!([]), &json!(["a"]))).unwrap(),
+            json!([{"op": "add", "path": "/0", "value": "a"}])
+        );
+    }
+
+    #[test]
+    fn test_dedup() {
+        let mut continues = true;
+
+        let mut received = Vec::new();
+        let mut dedup = Dedup::new(|event: &Event| received.push(event.2.data.clone()));
+
+        for (event_type, data) in [
+            ("Create", json!("Lobby")),
+            ("Update", json!("Lobby")),
+            ("Update", json!("Matchmaking")),
+            ("Delete", Value::Null),
+            ("Delete", Value::Null),
+            ("Create", json!("Matchmaking")),
+        ] {
+            dedup.on_event(&event(event_type, data), &mut continues);
+        }
+
+        drop(dedup);
+        assert_eq!(
+            received,
+            [
+                json!("Lobby"),
+                json!("Matchmaking"),
+                Value::Null,
+                json!("Matchmaking")
+            ]
+        );
+    }
+
+    #[test]
+    fn test_dedup_diff() {
+        let mut continues = true;
+
+        let mut received = Vec::new();
+        let mut dedup = Dedup::with_diff(|event: &Event| received.push(event.2.data.clone()));
+
+        for data in [
+            json!({"queueId": 420}),
+            json!({"queueId": 420}),
+            json!({"queueId": 440}),
+        ] {
+            dedup.on_event(&event("Update", data), &mut continues);
+        }
+
+        drop(dedup);
+        assert_eq!(
+            received,
+            [
+                json!([{"op": "replace", "path": "", "value": {"queueId": 420}}]),
+                json!([{"op": "replace", "path": "/queueId", "value": 440}]),
+            ]
+        );
+    }
+}
@@ -8,6 +8,11 @@ pub enum Error {
     ProcessInfo(crate::process_info::Error),
     SerdeJson(serde_json::Error),
     Io(std::io::Error),
+    /// Nothing was received from the client within [`super::Heartbeat::timeout`], not even a reply to a ping
+    ConnectionLost,
+    /// Error from the REST API, only produced while polling in place of the websocket
+    #[cfg(feature = "rest")]
+    Rest(crate::Error),
 }
 
 impl Display for Error {
@@ -17,6 +22,9 @@ impl Display for Error {
             Self::ProcessInfo(e) => e.fmt(f),
             Self::SerdeJson(e) => e.fmt(f),
             Self::Io(e) => e.fmt(f),
+            Self::ConnectionLost => f.write_str("The client stopped responding to the websocket"),
+            #[cfg(feature = "rest")]
+            Self::Rest(e) => e.fmt(f),
         }
     }
 }
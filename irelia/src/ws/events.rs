@@ -0,0 +1,137 @@
+//! Typed payloads for the most used events, see [`TypedEvent`]
+
+use crate::rest::champ_select::{ChampSelectSession, CHAMP_SELECT_SESSION_ENDPOINT};
+use crate::rest::end_of_game::{EndOfGameStats, EOG_STATS_ENDPOINT};
+use crate::rest::gameflow::{GameflowPhase, GAMEFLOW_PHASE_ENDPOINT};
+use crate::rest::lobby::{Lobby, LOBBY_ENDPOINT};
+use crate::rest::matchmaking::{
+    MatchmakingSearch, ReadyCheck, READY_CHECK_ENDPOINT, SEARCH_ENDPOINT,
+};
+use serde::de::DeserializeOwned;
+
+use super::types::{EventData, EventKind};
+
+/// The data of one of the most used events, deserialized based on its uri
+///
+/// Every variant is `None` when the event is a `Delete`, such as when leaving the lobby
+///
+/// ```no_run
+/// use irelia::ws::events::TypedEvent;
+/// use irelia::ws::{types::Event, LcuWebSocket};
+///
+/// let mut ws = LcuWebSocket::new();
+///
+/// for event_kind in TypedEvent::event_kinds() {
+///     ws.subscribe_closure(event_kind, |event: &Event| {
+///         if let Some(Ok(TypedEvent::GameflowPhase(Some(phase)))) = TypedEvent::from_event(&event.2) {
+///             println!("{phase:?}");
+///         }
+///     });
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub enum TypedEvent {
+    GameflowPhase(Option<GameflowPhase>),
+    ChampSelectSession(Option<ChampSelectSession>),
+    Lobby(Option<Lobby>),
+    MatchmakingSearch(Option<MatchmakingSearch>),
+    ReadyCheck(Option<ReadyCheck>),
+    EndOfGameStats(Option<EndOfGameStats>),
+}
+
+impl TypedEvent {
+    /// The uri of every event with a variant
+    pub const URIS: [&'static str; 6] = [
+        GAMEFLOW_PHASE_ENDPOINT,
+        CHAMP_SELECT_SESSION_ENDPOINT,
+        LOBBY_ENDPOINT,
+        SEARCH_ENDPOINT,
+        READY_CHECK_ENDPOINT,
+        EOG_STATS_ENDPOINT,
+    ];
+
+    /// The event kinds to subscribe to, to receive every event with a variant
+    pub fn event_kinds() -> impl Iterator<Item = EventKind> {
+        Self::URIS
+            .into_iter()
+            .map(EventKind::json_api_event_callback_str)
+    }
+
+    /// Deserializes the data of `event`, returning `None` if its uri doesn't have a variant
+    ///
+    /// # Errors
+    /// The inner result is an error if the data is not in the expected shape
+    pub fn from_event(event: &EventData) -> Option<Result<Self, serde_json::Error>> {
+        let event = match event.uri.as_str() {
+            GAMEFLOW_PHASE_ENDPOINT => data(event).map(Self::GameflowPhase),
+            CHAMP_SELECT_SESSION_ENDPOINT => data(event).map(Self::ChampSelectSession),
+            LOBBY_ENDPOINT => data(event).map(Self::Lobby),
+            SEARCH_ENDPOINT => data(event).map(Self::MatchmakingSearch),
+            READY_CHECK_ENDPOINT => data(event).map(Self::ReadyCheck),
+            EOG_STATS_ENDPOINT => data(event).map(Self::EndOfGameStats),
+            _ => return None,
+        };
+
+        Some(event)
+    }
+}
+
+fn data<T: DeserializeOwned>(event: &EventData) -> Result<Option<T>, serde_json::Error> {
+    if event.event_type == "Delete" {
+        return Ok(None);
+    }
+
+    T::deserialize(&event.data).map(Some)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::TypedEvent;
+    use crate::rest::gameflow::GameflowPhase;
+    use crate::rest::matchmaking::ReadyCheckState;
+    use crate::ws::types::EventData;
+    use serde_json::json;
+
+    fn event(event_type: &str, uri: &str, data: serde_json::Value) -> EventData {
+        EventData {
+            data,
+            event_type: event_type.into(),
+            uri: uri.into(),
+        }
+    }
+
+    #[test]
+    fn typed_event() {
+        let phase = event("Update", "/lol-gameflow/v1/gameflow-phase", json!("Lobby"));
+        assert_eq!(
+            TypedEvent::from_event(&phase).unwrap().unwrap(),
+            TypedEvent::GameflowPhase(Some(GameflowPhase::Lobby))
+        );
+
+        let ready_check = event(
+            "Create",
+            "/lol-matchmaking/v1/ready-check",
+            json!({"declinerIds": [], "playerResponse": "Accepted", "state": "InProgress", "timer": 1.0}),
+        );
+        let Some(Ok(TypedEvent::ReadyCheck(Some(ready_check)))) =
+            TypedEvent::from_event(&ready_check)
+        else {
+            panic!("Expected a ready check");
+        };
+        assert_eq!(ready_check.state, ReadyCheckState::InProgress);
+
+        let deleted = event("Delete", "/lol-lobby/v2/lobby", json!(null));
+        assert_eq!(
+            TypedEvent::from_event(&deleted).unwrap().unwrap(),
+            TypedEvent::Lobby(None)
+        );
+
+        let invalid = event("Update", "/lol-lobby/v2/lobby", json!(1));
+        assert!(TypedEvent::from_event(&invalid).unwrap().is_err());
+
+        let other = event("Update", "/lol-chat/v1/me", json!({}));
+        assert!(TypedEvent::from_event(&other).is_none());
+
+        assert_eq!(TypedEvent::event_kinds().count(), TypedEvent::URIS.len());
+    }
+}
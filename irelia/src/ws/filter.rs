@@ -0,0 +1,214 @@
+use std::fmt::{Display, Formatter};
+
+use super::types::{Event, EventKind, RequestType};
+use super::{PoisonBehavior, Subscriber};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Token {
+    Literal(char),
+    /// `?`, any single character other than `/`
+    One,
+    /// `*`, anything within a single path segment
+    Star,
+    /// `**`, anything, across any number of path segments
+    DoubleStar,
+}
+
+/// A glob pattern matched against event uris, such as `/lol-lobby/**` or `/lol-champ-select/v1/*`
+///
+/// `?` matches a single character, and `*` matches any number of characters, both within a path segment.
+/// `**` matches across path segments, and a trailing `/**` also matches the path before it,
+/// so `/lol-lobby/**` matches both `/lol-lobby` and `/lol-lobby/v2/lobby`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Glob {
+    pattern: String,
+    tokens: Vec<Token>,
+}
+
+impl Glob {
+    #[must_use]
+    pub fn new(pattern: impl Into<String>) -> Self {
+        let pattern = pattern.into();
+        let mut tokens = Vec::new();
+        let mut chars = pattern.chars().peekable();
+
+        while let Some(c) = chars.next() {
+            let token = match c {
+                '*' if chars.next_if_eq(&'*').is_some() => Token::DoubleStar,
+                '*' => Token::Star,
+                '?' => Token::One,
+                c => Token::Literal(c),
+            };
+
+            tokens.push(token);
+        }
+
+        Self { pattern, tokens }
+    }
+
+    #[must_use]
+    /// The pattern this was created from
+    pub fn as_str(&self) -> &str {
+        &self.pattern
+    }
+
+    #[must_use]
+    /// Checks whether `uri` matches the whole pattern
+    pub fn matches(&self, uri: &str) -> bool {
+        if matches_tokens(&self.tokens, uri) {
+            return true;
+        }
+
+        match self.tokens.as_slice() {
+            [prefix @ .., Token::Literal('/'), Token::DoubleStar] => matches_tokens(prefix, uri),
+            _ => false,
+        }
+    }
+}
+
+impl Display for Glob {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.pattern)
+    }
+}
+
+fn matches_tokens(tokens: &[Token], uri: &str) -> bool {
+    let Some((token, rest)) = tokens.split_first() else {
+        return uri.is_empty();
+    };
+
+    match token {
+        Token::Literal(c) => uri
+            .strip_prefix(*c)
+            .is_some_and(|uri| matches_tokens(rest, uri)),
+        Token::One => {
+            let mut chars = uri.chars();
+            matches!(chars.next(), Some(c) if c != '/') && matches_tokens(rest, chars.as_str())
+        }
+        Token::Star => {
+            let segment = uri.find('/').unwrap_or(uri.len());
+            (0..=segment)
+                .filter(|idx| uri.is_char_boundary(*idx))
+                .any(|idx| matches_tokens(rest, &uri[idx..]))
+        }
+        Token::DoubleStar => (0..=uri.len())
+            .filter(|idx| uri.is_char_boundary(*idx))
+            .any(|idx| matches_tokens(rest, &uri[idx..])),
+    }
+}
+
+/// Subscriber that only passes on events whose uri matches a [`Glob`], every other callback is passed on as is
+///
+/// This is used by [`super::LcuWebSocket::subscribe_filtered`], which subscribes to every `OnJsonApiEvent`,
+/// but it can wrap subscribers for any event kind
+pub struct Filtered<S> {
+    glob: Glob,
+    subscriber: S,
+}
+
+impl<S: Subscriber> Filtered<S> {
+    #[must_use]
+    pub fn new(glob: Glob, subscriber: S) -> Self {
+        Self { glob, subscriber }
+    }
+
+    #[must_use]
+    pub fn glob(&self) -> &Glob {
+        &self.glob
+    }
+}
+
+impl<S: Subscriber> Subscriber for Filtered<S> {
+    fn on_poison(&self) -> PoisonBehavior {
+        self.subscriber.on_poison()
+    }
+
+    fn on_subscribe(&mut self, event_kind: &EventKind, request_code: &RequestType) {
+        self.subscriber.on_subscribe(event_kind, request_code);
+    }
+
+    fn on_event(&mut self, event: &Event, continues: &mut bool) {
+        if self.glob.matches(&event.2.uri) {
+            self.subscriber.on_event(event, continues);
+        }
+    }
+
+    fn on_unsubscribe(&mut self, event_kind: &EventKind) {
+        self.subscriber.on_unsubscribe(event_kind);
+    }
+
+    fn on_reconnect(&mut self, event_kind: &EventKind) {
+        self.subscriber.on_reconnect(event_kind);
+    }
+
+    fn on_connection_lost(&mut self, event_kind: &EventKind) {
+        self.subscriber.on_connection_lost(event_kind);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Filtered, Glob};
+    use crate::ws::types::{Event, EventData, EventKind, RequestType};
+    use crate::ws::Subscriber;
+    use serde_json::Value;
+
+    #[test]
+    fn test_glob() {
+        let lobby = Glob::new("/lol-lobby/**");
+        assert!(lobby.matches("/lol-lobby"));
+        assert!(lobby.matches("/lol-lobby/v2/lobby"));
+        assert!(lobby.matches("/lol-lobby/v2/lobby/members"));
+        assert!(!lobby.matches("/lol-lobby-team-builder/v1/matchmaking"));
+
+        let session = Glob::new("/lol-champ-select/v1/*");
+        assert!(session.matches("/lol-champ-select/v1/session"));
+        assert!(!session.matches("/lol-champ-select/v1/session/timer"));
+
+        let version = Glob::new("/lol-*/v?/lobby");
+        assert!(version.matches("/lol-lobby/v2/lobby"));
+        assert!(!version.matches("/lol-lobby/v12/lobby"));
+
+        let middle = Glob::new("/lol-**/timer");
+        assert!(middle.matches("/lol-champ-select/v1/session/timer"));
+        assert!(!middle.matches("/lol-champ-select/v1/session"));
+
+        assert!(Glob::new("**").matches("/anything/at/all"));
+        assert!(Glob::new("/ünïcödé/*").matches("/ünïcödé/ö"));
+    }
+
+    #[test]
+    fn test_filtered() {
+        let event = |uri: &str| {
+            Event(
+                RequestType::Event,
+                EventKind::json_api_event(),
+                EventData {
+                    data: Value::Null,
+                    event_type: "Update".into(),
+                    uri: uri.into(),
+                },
+            )
+        };
+
+        let mut received = Vec::new();
+        let mut filtered = Filtered::new(Glob::new("/lol-lobby/**"), |event: &Event| {
+            received.push(event.2.uri.clone());
+        });
+
+        let mut continues = true;
+        for uri in [
+            "/lol-lobby/v2/lobby",
+            "/lol-gameflow/v1/gameflow-phase",
+            "/lol-lobby/v2/lobby/members",
+        ] {
+            filtered.on_event(&event(uri), &mut continues);
+        }
+
+        drop(filtered);
+        assert_eq!(
+            received,
+            ["/lol-lobby/v2/lobby", "/lol-lobby/v2/lobby/members"]
+        );
+    }
+}
@@ -58,6 +58,36 @@ where
             Err(TryLockError::WouldBlock) => {}
         }
     }
+
+    fn on_reconnect(&mut self, event_kind: &super::types::EventKind) {
+        match self.try_lock() {
+            Ok(mut guard) => {
+                let t = &mut *guard;
+                t.on_reconnect(event_kind);
+            }
+            Err(TryLockError::Poisoned(poisoned)) => match self.on_poison() {
+                super::PoisonBehavior::Clear => self.clear_poison(),
+                super::PoisonBehavior::Ignore | super::PoisonBehavior::Break => {}
+                super::PoisonBehavior::Panic => panic!("{poisoned}"),
+            },
+            Err(TryLockError::WouldBlock) => {}
+        }
+    }
+
+    fn on_connection_lost(&mut self, event_kind: &super::types::EventKind) {
+        match self.try_lock() {
+            Ok(mut guard) => {
+                let t = &mut *guard;
+                t.on_connection_lost(event_kind);
+            }
+            Err(TryLockError::Poisoned(poisoned)) => match self.on_poison() {
+                super::PoisonBehavior::Clear => self.clear_poison(),
+                super::PoisonBehavior::Ignore | super::PoisonBehavior::Break => {}
+                super::PoisonBehavior::Panic => panic!("{poisoned}"),
+            },
+            Err(TryLockError::WouldBlock) => {}
+        }
+    }
 }
 
 impl<T> Subscriber for Arc<RwLock<T>>
@@ -113,6 +143,36 @@ where
             Err(TryLockError::WouldBlock) => {}
         }
     }
+
+    fn on_reconnect(&mut self, event_kind: &super::types::EventKind) {
+        match self.try_write() {
+            Ok(mut guard) => {
+                let t = &mut *guard;
+                t.on_reconnect(event_kind);
+            }
+            Err(TryLockError::Poisoned(poisoned)) => match self.on_poison() {
+                super::PoisonBehavior::Clear => self.clear_poison(),
+                super::PoisonBehavior::Ignore | super::PoisonBehavior::Break => {}
+                super::PoisonBehavior::Panic => panic!("{poisoned}"),
+            },
+            Err(TryLockError::WouldBlock) => {}
+        }
+    }
+
+    fn on_connection_lost(&mut self, event_kind: &super::types::EventKind) {
+        match self.try_write() {
+            Ok(mut guard) => {
+                let t = &mut *guard;
+                t.on_connection_lost(event_kind);
+            }
+            Err(TryLockError::Poisoned(poisoned)) => match self.on_poison() {
+                super::PoisonBehavior::Clear => self.clear_poison(),
+                super::PoisonBehavior::Ignore | super::PoisonBehavior::Break => {}
+                super::PoisonBehavior::Panic => panic!("{poisoned}"),
+            },
+            Err(TryLockError::WouldBlock) => {}
+        }
+    }
 }
 
 impl<F, R> Subscriber for F
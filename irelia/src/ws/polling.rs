@@ -0,0 +1,142 @@
+//! Fallback used when the websocket upgrade is rejected, polling the REST API instead
+//!
+//! Only `JsonApiEvent` subscriptions with a callback can be polled, as they map to a single endpoint,
+//! subscribers to anything else are kept, but will never receive an event
+
+use std::collections::HashMap;
+use std::ops::ControlFlow;
+use std::sync::mpsc::{Receiver, RecvTimeoutError};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use hyper::StatusCode;
+use serde_json::Value;
+use tungstenite::Message;
+
+use super::types::{Event, EventData, EventKind, RequestType};
+use super::{
+    apply_message, dispatch, ChannelMessage, ErrorHandler, Flow, SubscriberMap, WebSocketError,
+};
+use crate::rest::LcuClient;
+use crate::utils::runtime::Runtime;
+
+/// The last response seen for an endpoint, `None` if it has not been polled yet
+type Snapshots = HashMap<String, Option<Option<Value>>>;
+
+pub(super) fn poll_loop<H: ErrorHandler>(
+    error_handler: &mut H,
+    receiver: &Receiver<ChannelMessage>,
+    interval: Duration,
+) {
+    let runtime = Runtime::new().expect("The polling runtime should always build");
+
+    let mut client: Option<LcuClient> = None;
+    let mut subscribers = SubscriberMap::new();
+    let mut snapshots = Snapshots::new();
+    let mut next_poll = Instant::now();
+
+    loop {
+        match receiver.recv_timeout(next_poll.saturating_duration_since(Instant::now())) {
+            Ok(message) => {
+                let polled = match &message {
+                    ChannelMessage::Subscribe(
+                        _,
+                        event_kind @ EventKind::JsonApiEvent {
+                            callback: Some(uri),
+                        },
+                        _,
+                    )
+                    | ChannelMessage::Unsubscribe(
+                        _,
+                        event_kind @ EventKind::JsonApiEvent {
+                            callback: Some(uri),
+                        },
+                    ) => Some((event_kind.clone(), uri.to_string())),
+                    _ => None,
+                };
+
+                if let Some(Message::Close(_)) = apply_message(message, &mut subscribers) {
+                    break;
+                }
+
+                if let Some((event_kind, uri)) = polled {
+                    if subscribers.get_mut(&event_kind).iter().flatten().count() == 0 {
+                        snapshots.remove(&uri);
+                    } else {
+                        snapshots.entry(uri).or_default();
+                    }
+                }
+
+                continue;
+            }
+            Err(RecvTimeoutError::Timeout) => {}
+            // Nothing can be subscribed anymore, but the existing subscribers still need to be polled
+            Err(RecvTimeoutError::Disconnected) => {
+                thread::sleep(next_poll.saturating_duration_since(Instant::now()));
+            }
+        }
+
+        next_poll = Instant::now() + interval;
+
+        let control_flow = match &client {
+            Some(client) => runtime.block_on(poll(client, &mut snapshots, &mut subscribers)),
+            None => match LcuClient::connect() {
+                Ok(connected) => {
+                    client = Some(connected);
+                    ControlFlow::Continue(Flow::Continue)
+                }
+                Err(err) => error_handler.on_error(WebSocketError::Rest(err)),
+            },
+        };
+
+        match control_flow {
+            ControlFlow::Continue(Flow::Continue) => {}
+            ControlFlow::Continue(Flow::TryReconnect) => client = None,
+            ControlFlow::Break(()) => break,
+        }
+    }
+}
+
+/// Polls every subscribed endpoint once, dispatching an event for every one that changed
+async fn poll(
+    client: &LcuClient,
+    snapshots: &mut Snapshots,
+    subscribers: &mut SubscriberMap,
+) -> ControlFlow<(), Flow> {
+    for (uri, snapshot) in snapshots.iter_mut() {
+        let current = match client.get::<Value>(uri.as_str()).await {
+            Ok(value) => Some(value),
+            Err(err) if err.status() == Some(StatusCode::NOT_FOUND) => None,
+            // The client likely closed, so the credentials need to be fetched again
+            Err(_) => return ControlFlow::Continue(Flow::TryReconnect),
+        };
+
+        // The first poll only establishes the starting state, the same as subscribing over the websocket
+        let Some(previous) = snapshot.replace(current.clone()) else {
+            continue;
+        };
+
+        let event_type = match (&previous, &current) {
+            (None, Some(_)) => "Create",
+            (Some(_), None) => "Delete",
+            (Some(previous), Some(current)) if previous != current => "Update",
+            _ => continue,
+        };
+
+        let event = Event(
+            RequestType::Event,
+            EventKind::json_api_event_callback(uri.clone()),
+            EventData {
+                data: current.unwrap_or(Value::Null),
+                event_type: event_type.into(),
+                uri: uri.clone(),
+            },
+        );
+
+        if dispatch(&event, subscribers).is_break() {
+            return ControlFlow::Break(());
+        }
+    }
+
+    ControlFlow::Continue(Flow::Continue)
+}
@@ -0,0 +1,445 @@
+use serde_derive::{Deserialize, Serialize};
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, BufWriter, Write};
+use std::ops::ControlFlow;
+use std::path::Path;
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use serde::de::DeserializeOwned;
+
+use super::stream::StreamSubscriber;
+use super::types::{Event, EventData, EventKind, RequestType};
+use super::{
+    apply_message, dispatch, ChannelMessage, EventStream, Overflow, Subscriber, SubscriberMap,
+};
+
+/// A single line of a recording, see [`Recorder`]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RecordedEvent {
+    /// When the event was received, in milliseconds since the unix epoch
+    pub timestamp: u64,
+    /// The event kind the LCU sent the event as, such as `OnJsonApiEvent`
+    pub event_kind: EventKind,
+    #[serde(flatten)]
+    pub data: EventData,
+}
+
+/// A [`RecordedEvent`] as it's written, so an unknown event kind can be reported instead of panicking
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RawRecordedEvent {
+    timestamp: u64,
+    event_kind: String,
+    #[serde(flatten)]
+    data: EventData,
+}
+
+impl TryFrom<RawRecordedEvent> for RecordedEvent {
+    type Error = io::Error;
+
+    fn try_from(raw: RawRecordedEvent) -> io::Result<Self> {
+        let event_kind = EventKind::from_event_name(&raw.event_kind).ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("Unknown event kind: {}", raw.event_kind),
+            )
+        })?;
+
+        Ok(Self {
+            timestamp: raw.timestamp,
+            event_kind,
+            data: raw.data,
+        })
+    }
+}
+
+/// Subscriber that writes every event it receives to `W` as newline delimited JSON, one [`RecordedEvent`] per line
+///
+/// Recordings can be played back with [`Replay`].
+/// Each line is flushed as it's written, so a recording is usable even if the program doesn't exit cleanly.
+/// Since the subscriber is moved into the event loop, wrap it in an `Arc<Mutex<_>>` to check [`Recorder::take_error`]
+///
+/// ```no_run
+/// use irelia::ws::{types::EventKind, LcuWebSocket, Recorder};
+///
+/// let mut ws = LcuWebSocket::new();
+///
+/// let recorder = Recorder::create("champ-select.ndjson").unwrap();
+/// ws.subscribe(EventKind::json_api_event(), recorder);
+/// ```
+pub struct Recorder<W: Write> {
+    writer: W,
+    error: Option<io::Error>,
+}
+
+impl Recorder<BufWriter<File>> {
+    /// Creates a recorder writing to the file at `path`, replacing it if it exists
+    ///
+    /// # Errors
+    /// This will return an error if the file can't be created
+    pub fn create(path: impl AsRef<Path>) -> io::Result<Self> {
+        Ok(Self::new(BufWriter::new(File::create(path)?)))
+    }
+}
+
+impl<W: Write> Recorder<W> {
+    #[must_use]
+    /// Creates a recorder writing to `writer`
+    pub fn new(writer: W) -> Self {
+        Self {
+            writer,
+            error: None,
+        }
+    }
+
+    /// Takes out the first error that happened while writing, events are skipped after an error
+    pub fn take_error(&mut self) -> Option<io::Error> {
+        self.error.take()
+    }
+
+    #[must_use]
+    /// Returns the writer, this doesn't flush it
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+
+    fn record(&mut self, event: &Event) -> io::Result<()> {
+        // A clock before the epoch is recorded as the epoch, replays only care about the difference between events
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_millis();
+
+        let recorded = RecordedEvent {
+            timestamp: u64::try_from(timestamp).unwrap_or(u64::MAX),
+            event_kind: event.1.clone(),
+            data: event.2.clone(),
+        };
+
+        let mut line = serde_json::to_vec(&recorded)?;
+        line.push(b'\n');
+
+        self.writer.write_all(&line)?;
+        self.writer.flush()
+    }
+}
+
+impl<W: Write> Subscriber for Recorder<W> {
+    fn on_event(&mut self, event: &Event, _continues: &mut bool) {
+        if self.error.is_some() {
+            return;
+        }
+
+        if let Err(e) = self.record(event) {
+            self.error = Some(e);
+        }
+    }
+}
+
+/// Plays a recording made with [`Recorder`] back to subscribers, without a running client
+///
+/// Subscribers are called the same way the websocket calls them, so any [`Subscriber`],
+/// including the streams from [`Replay::subscribe_stream`], can be tested against a recorded session.
+/// Events are dispatched as the LCU would, so a recording of `OnJsonApiEvent` is also received
+/// by subscribers of a single endpoint, and the other way around
+///
+/// ```no_run
+/// use futures_util::StreamExt;
+/// use irelia::ws::{types::EventKind, Replay, StreamEvent};
+///
+/// # async fn run() {
+/// let mut replay = Replay::open("champ-select.ndjson").unwrap().speed(10.0);
+///
+/// let mut sessions = replay.subscribe_stream::<serde_json::Value>(
+///     EventKind::json_api_event_callback_str("/lol-champ-select/v1/session"),
+/// );
+///
+/// let handle = replay.start();
+///
+/// while let Some(Ok(StreamEvent::Event(session))) = sessions.next().await {
+///     println!("{}", session.data);
+/// }
+///
+/// handle.join().unwrap();
+/// # }
+/// ```
+pub struct Replay {
+    events: Vec<RecordedEvent>,
+    speed: f64,
+    subscriptions: Vec<(EventKind, Box<dyn Subscriber + Send>)>,
+}
+
+impl Replay {
+    #[must_use]
+    /// Creates a replay of `events`, played back at their original timing
+    pub fn new(events: Vec<RecordedEvent>) -> Self {
+        Self {
+            events,
+            speed: 1.0,
+            subscriptions: Vec::new(),
+        }
+    }
+
+    /// Reads a recording made with [`Recorder`], empty lines are skipped
+    ///
+    /// # Errors
+    /// This will return an error if reading fails, or a line isn't a [`RecordedEvent`] with a known event kind,
+    /// which is an `InvalidData` error
+    pub fn from_reader(reader: impl BufRead) -> io::Result<Self> {
+        let mut events = Vec::new();
+
+        for line in reader.lines() {
+            let line = line?;
+
+            if !line.trim().is_empty() {
+                let raw: RawRecordedEvent = serde_json::from_str(&line)?;
+                events.push(raw.try_into()?);
+            }
+        }
+
+        Ok(Self::new(events))
+    }
+
+    /// Reads the recording at `path`, see [`Replay::from_reader`]
+    ///
+    /// # Errors
+    /// This will return an error if the file can't be read, or a line isn't a [`RecordedEvent`]
+    pub fn open(path: impl AsRef<Path>) -> io::Result<Self> {
+        Self::from_reader(BufReader::new(File::open(path)?))
+    }
+
+    #[must_use]
+    /// Sets how much faster than the original the recording is played back, `2.0` plays it twice as fast,
+    /// and `f64::INFINITY` plays it without waiting between events
+    ///
+    /// # Panics
+    /// If `speed` isn't above `0`
+    pub fn speed(mut self, speed: f64) -> Self {
+        assert!(speed > 0.0, "The replay speed must be above 0");
+        self.speed = speed;
+        self
+    }
+
+    #[must_use]
+    /// The events that will be played back
+    pub fn events(&self) -> &[RecordedEvent] {
+        &self.events
+    }
+
+    /// Adds a subscriber, which is called for every matching event once the replay is started
+    pub fn subscribe(
+        &mut self,
+        event_kind: EventKind,
+        subscriber: impl Subscriber + Send + 'static,
+    ) {
+        self.subscriptions.push((event_kind, Box::new(subscriber)));
+    }
+
+    /// Subscribes to a specific event kind, returning its events as an [`EventStream`],
+    /// the same as [`super::LcuWebSocket::subscribe_stream`]
+    ///
+    /// The stream ends once the replay is finished
+    pub fn subscribe_stream<T: DeserializeOwned + Send + 'static>(
+        &mut self,
+        event_kind: EventKind,
+    ) -> EventStream<T> {
+        let (subscriber, stream) = StreamSubscriber::new();
+        self.subscribe(event_kind, subscriber);
+        stream
+    }
+
+    /// The same as [`Replay::subscribe_stream`], but the stream holds at most `capacity` events,
+    /// see [`super::LcuWebSocket::subscribe_stream_bounded`]
+    pub fn subscribe_stream_bounded<T: DeserializeOwned + Send + 'static>(
+        &mut self,
+        event_kind: EventKind,
+        capacity: usize,
+        overflow: Overflow,
+    ) -> EventStream<T> {
+        let (subscriber, stream) = StreamSubscriber::bounded(capacity, overflow);
+        self.subscribe(event_kind, subscriber);
+        stream
+    }
+
+    #[must_use]
+    /// Plays the recording back on a new thread
+    ///
+    /// The thread finishes once every event has been played, or a subscriber breaks the loop,
+    /// after which every subscriber is dropped, ending their streams
+    pub fn start(self) -> JoinHandle<()> {
+        thread::spawn(move || self.run())
+    }
+
+    /// Plays the recording back on the current thread, see [`Replay::start`]
+    pub fn run(self) {
+        let mut subscribers = SubscriberMap::new();
+
+        for (event_kind, subscriber) in self.subscriptions {
+            // The replay never sends anything, so the subscribe message is ignored
+            let _ = apply_message(
+                ChannelMessage::Subscribe(RequestType::Subscribe, event_kind, subscriber),
+                &mut subscribers,
+            );
+        }
+
+        let mut previous = None;
+
+        for recorded in self.events {
+            if let Some(previous) = previous {
+                let gap = Duration::from_millis(recorded.timestamp.saturating_sub(previous));
+                thread::sleep(gap.div_f64(self.speed));
+            }
+            previous = Some(recorded.timestamp);
+
+            if replay_event(recorded, &mut subscribers).is_break() {
+                break;
+            }
+        }
+    }
+}
+
+fn replay_event(recorded: RecordedEvent, subscribers: &mut SubscriberMap) -> ControlFlow<()> {
+    let RecordedEvent {
+        event_kind, data, ..
+    } = recorded;
+
+    // The LCU sends an event both to subscribers of every event, and to subscribers of its endpoint
+    let also = match &event_kind {
+        EventKind::JsonApiEvent { callback: None } => {
+            Some(EventKind::json_api_event_callback(data.uri.clone()))
+        }
+        EventKind::JsonApiEvent { callback: Some(_) } => Some(EventKind::json_api_event()),
+        _ => None,
+    };
+
+    let event = Event(RequestType::Event, event_kind, data);
+    if dispatch(&event, subscribers).is_break() {
+        return ControlFlow::Break(());
+    }
+
+    if let Some(event_kind) = also {
+        let event = Event(RequestType::Event, event_kind, event.2);
+        if dispatch(&event, subscribers).is_break() {
+            return ControlFlow::Break(());
+        }
+    }
+
+    ControlFlow::Continue(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{RecordedEvent, Recorder, Replay};
+    use crate::ws::types::{Event, EventData, EventKind, RequestType};
+    use crate::ws::{StreamEvent, Subscriber};
+    use futures_util::StreamExt;
+    use serde_json::json;
+    use std::time::{Duration, Instant};
+
+    fn event(event_kind: EventKind, uri: &str, data: &str) -> Event {
+        Event(
+            RequestType::Event,
+            event_kind,
+            EventData {
+                data: json!(data),
+                event_type: "Update".into(),
+                uri: uri.into(),
+            },
+        )
+    }
+
+    #[tokio::test]
+    async fn test_record_replay() {
+        let mut continues = true;
+        let mut recorder = Recorder::new(Vec::new());
+
+        for phase in ["Lobby", "Matchmaking", "ReadyCheck"] {
+            recorder.on_event(
+                &event(
+                    EventKind::json_api_event(),
+                    "/lol-gameflow/v1/gameflow-phase",
+                    phase,
+                ),
+                &mut continues,
+            );
+        }
+        recorder.on_event(
+            &event(
+                EventKind::json_api_event(),
+                "/lol-lobby/v2/lobby",
+                "ignored",
+            ),
+            &mut continues,
+        );
+
+        assert!(recorder.take_error().is_none());
+
+        let recording = recorder.into_inner();
+        assert_eq!(recording.split(|b| *b == b'\n').count(), 5);
+
+        let mut replay = Replay::from_reader(recording.as_slice())
+            .unwrap()
+            .speed(f64::INFINITY);
+        assert_eq!(replay.events().len(), 4);
+
+        // Recorded as `OnJsonApiEvent`, but received by the endpoint's subscribers as well
+        let mut phases = replay.subscribe_stream::<String>(EventKind::json_api_event_callback_str(
+            "/lol-gameflow/v1/gameflow-phase",
+        ));
+        let all = replay.subscribe_stream::<String>(EventKind::json_api_event());
+
+        replay.start().join().unwrap();
+
+        let mut received = Vec::new();
+        while let Some(event) = phases.next().await {
+            match event.unwrap() {
+                StreamEvent::Event(event) => received.push(event.data),
+                event => panic!("Expected an event, got {event:?}"),
+            }
+        }
+
+        assert_eq!(received, ["Lobby", "Matchmaking", "ReadyCheck"]);
+        assert_eq!(all.count().await, 4);
+    }
+
+    #[test]
+    fn test_replay_unknown_event_kind() {
+        let recording = concat!(
+            r#"{"timestamp":1000,"eventKind":"OnJsonApiEvent","data":null,"eventType":"Update","uri":"/lol-lobby/v2/lobby"}"#,
+            "\n",
+            r#"{"timestamp":1100,"eventKind":"OnSomethingNew","data":null,"eventType":"Update","uri":"/lol-lobby/v2/lobby"}"#,
+        );
+
+        let Err(error) = Replay::from_reader(recording.as_bytes()) else {
+            panic!("An unknown event kind should not be read");
+        };
+        assert_eq!(error.kind(), std::io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn test_replay_timing() {
+        let recorded = |timestamp| RecordedEvent {
+            timestamp,
+            event_kind: EventKind::json_api_event(),
+            data: EventData {
+                data: json!(null),
+                event_type: "Update".into(),
+                uri: "/lol-gameflow/v1/gameflow-phase".into(),
+            },
+        };
+
+        let events = vec![recorded(1000), recorded(1100), recorded(1200)];
+
+        let started = Instant::now();
+        Replay::new(events.clone()).run();
+        assert!(started.elapsed() >= Duration::from_millis(200));
+
+        let started = Instant::now();
+        Replay::new(events).speed(4.0).run();
+        let elapsed = started.elapsed();
+        assert!(elapsed >= Duration::from_millis(50));
+        assert!(elapsed < Duration::from_millis(200));
+    }
+}
@@ -0,0 +1,346 @@
+use serde::de::DeserializeOwned;
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError, TryLockError};
+
+use super::stream::{EventStream, Overflow, StreamSubscriber};
+use super::types::{Event, EventKind};
+use super::Subscriber;
+
+/// The ID of a route added to a [`Router`], for removing it again
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct RouteId(usize);
+
+enum Target {
+    Handler(Box<dyn Subscriber + Send>),
+    Stream(Box<dyn StreamTarget + Send>),
+}
+
+/// A route sending its events to an [`EventStream`], which is removed once the stream is dropped
+trait StreamTarget: Subscriber {
+    fn is_dropped(&self) -> bool;
+}
+
+impl<T: DeserializeOwned> StreamTarget for StreamSubscriber<T> {
+    fn is_dropped(&self) -> bool {
+        StreamSubscriber::is_dropped(self)
+    }
+}
+
+impl Target {
+    fn lock(target: &Mutex<Self>) -> MutexGuard<'_, Self> {
+        target.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Whether the target is a stream that was dropped, a target that's in use is never dropped
+    fn is_dropped(target: &Mutex<Self>) -> bool {
+        let target = match target.try_lock() {
+            Ok(target) => target,
+            Err(TryLockError::Poisoned(poisoned)) => poisoned.into_inner(),
+            Err(TryLockError::WouldBlock) => return false,
+        };
+
+        match &*target {
+            Target::Stream(stream) => stream.is_dropped(),
+            Target::Handler(_) => false,
+        }
+    }
+}
+
+struct Route {
+    id: RouteId,
+    prefix: String,
+    /// Shared, so events are dispatched without the routes locked, letting handlers add and remove routes
+    target: Arc<Mutex<Target>>,
+}
+
+#[derive(Default)]
+struct Routes {
+    next_id: usize,
+    routes: Vec<Route>,
+}
+
+/// Sends the events of a single subscription to handlers and streams, based on the uri of each event
+///
+/// Rather than subscribing to every endpoint separately, the router is subscribed once, usually to
+/// [`EventKind::json_api_event`], and each event is passed to every route whose prefix its uri starts with.
+/// Routes can be added and removed at any time, including after the router is subscribed, as clones share their routes.
+///
+/// Unsubscribing the router removes every route, ending their streams
+///
+/// ```no_run
+/// use futures_util::StreamExt;
+/// use irelia::ws::{types::Event, LcuWebSocket, Router, StreamEvent};
+///
+/// # async fn run() {
+/// let mut ws = LcuWebSocket::new();
+/// let router = Router::new();
+///
+/// router.handle("/lol-gameflow/", |event: &Event| println!("{}", event.2.uri));
+/// let mut champ_select = router.stream::<serde_json::Value>("/lol-champ-select/");
+///
+/// ws.subscribe(Router::EVENT_KIND, router.clone());
+///
+/// while let Some(Ok(StreamEvent::Event(event))) = champ_select.next().await {
+///     println!("{} {}", event.uri, event.data);
+/// }
+/// # }
+/// ```
+#[derive(Clone, Default)]
+pub struct Router {
+    routes: Arc<Mutex<Routes>>,
+}
+
+impl Router {
+    /// The event kind every json api event is sent under, which is usually what a router is subscribed to
+    pub const EVENT_KIND: EventKind = EventKind::json_api_event();
+
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Passes events whose uri starts with `prefix` to `handler`
+    pub fn handle(
+        &self,
+        prefix: impl Into<String>,
+        handler: impl Subscriber + Send + 'static,
+    ) -> RouteId {
+        self.add(prefix.into(), Target::Handler(Box::new(handler)))
+    }
+
+    #[must_use]
+    /// Returns the events whose uri starts with `prefix` as an [`EventStream`], with their data deserialized into `T`
+    ///
+    /// The route is removed once the stream is dropped
+    pub fn stream<T: DeserializeOwned + Send + 'static>(
+        &self,
+        prefix: impl Into<String>,
+    ) -> EventStream<T> {
+        let (subscriber, stream) = StreamSubscriber::new();
+        self.add(prefix.into(), Target::Stream(Box::new(subscriber)));
+        stream
+    }
+
+    #[must_use]
+    /// The same as [`Router::stream`], but the stream holds at most `capacity` events,
+    /// with `overflow` deciding what happens to events that arrive while it's full
+    pub fn stream_bounded<T: DeserializeOwned + Send + 'static>(
+        &self,
+        prefix: impl Into<String>,
+        capacity: usize,
+        overflow: Overflow,
+    ) -> EventStream<T> {
+        let (subscriber, stream) = StreamSubscriber::bounded(capacity, overflow);
+        self.add(prefix.into(), Target::Stream(Box::new(subscriber)));
+        stream
+    }
+
+    #[must_use]
+    /// Removes a route, returning `false` if it was already removed
+    pub fn remove(&self, id: RouteId) -> bool {
+        let mut routes = self.lock();
+        let len = routes.routes.len();
+        routes.routes.retain(|route| route.id != id);
+        routes.routes.len() != len
+    }
+
+    #[must_use]
+    /// How many routes there are, including streams that were dropped since the last event
+    pub fn len(&self) -> usize {
+        self.lock().routes.len()
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn add(&self, prefix: String, target: Target) -> RouteId {
+        let mut routes = self.lock();
+        let id = RouteId(routes.next_id);
+        routes.next_id += 1;
+        routes.routes.push(Route {
+            id,
+            prefix,
+            target: Arc::new(Mutex::new(target)),
+        });
+        id
+    }
+
+    /// The targets of every route, taken so the routes aren't locked while they're called
+    fn targets(&self) -> Vec<Arc<Mutex<Target>>> {
+        let routes = self.lock();
+        routes
+            .routes
+            .iter()
+            .map(|route| route.target.clone())
+            .collect()
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Routes> {
+        // Routes are never left half updated, so they're still usable if a handler panicked
+        self.routes.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl Subscriber for Router {
+    fn on_event(&mut self, event: &Event, continues: &mut bool) {
+        let targets: Vec<_> = {
+            let mut routes = self.lock();
+            routes
+                .routes
+                .retain(|route| !Target::is_dropped(&route.target));
+
+            routes
+                .routes
+                .iter()
+                .filter(|route| event.2.uri.starts_with(route.prefix.as_str()))
+                .map(|route| route.target.clone())
+                .collect()
+        };
+
+        for target in targets {
+            match &mut *Target::lock(&target) {
+                Target::Handler(handler) => handler.on_event(event, continues),
+                Target::Stream(stream) => stream.on_event(event, continues),
+            }
+
+            if !*continues {
+                return;
+            }
+        }
+    }
+
+    fn on_reconnect(&mut self, event_kind: &EventKind) {
+        for target in self.targets() {
+            match &mut *Target::lock(&target) {
+                Target::Handler(handler) => handler.on_reconnect(event_kind),
+                Target::Stream(stream) => stream.on_reconnect(event_kind),
+            }
+        }
+    }
+
+    fn on_connection_lost(&mut self, event_kind: &EventKind) {
+        for target in self.targets() {
+            match &mut *Target::lock(&target) {
+                Target::Handler(handler) => handler.on_connection_lost(event_kind),
+                Target::Stream(stream) => stream.on_connection_lost(event_kind),
+            }
+        }
+    }
+
+    fn on_unsubscribe(&mut self, event_kind: &EventKind) {
+        let routes = std::mem::take(&mut self.lock().routes);
+
+        for route in routes {
+            if let Target::Handler(handler) = &mut *Target::lock(&route.target) {
+                handler.on_unsubscribe(event_kind);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Router;
+    use crate::ws::types::{Event, EventData, EventKind, RequestType};
+    use crate::ws::{DecodedEvent, StreamEvent, Subscriber};
+    use futures_util::StreamExt;
+    use std::sync::mpsc;
+
+    fn event(uri: &str) -> Event {
+        Event(
+            RequestType::Event,
+            EventKind::json_api_event(),
+            EventData {
+                data: uri.into(),
+                event_type: "Update".into(),
+                uri: uri.into(),
+            },
+        )
+    }
+
+    #[tokio::test]
+    async fn test_router() {
+        let router = Router::new();
+        let (sender, receiver) = mpsc::channel();
+
+        let gameflow = router.handle("/lol-gameflow/", move |event: &Event| {
+            sender.send(event.2.uri.clone()).unwrap();
+        });
+        let mut champ_select = router.stream::<String>("/lol-champ-select/");
+        let dropped = router.stream::<String>("/lol-champ-select/");
+        drop(dropped);
+
+        let mut subscriber = router.clone();
+        let mut continues = true;
+        for uri in [
+            "/lol-gameflow/v1/gameflow-phase",
+            "/lol-champ-select/v1/session",
+            "/lol-lobby/v2/lobby",
+        ] {
+            subscriber.on_event(&event(uri), &mut continues);
+        }
+
+        assert_eq!(
+            receiver.try_iter().collect::<Vec<_>>(),
+            ["/lol-gameflow/v1/gameflow-phase"]
+        );
+        assert_eq!(
+            champ_select.next().await.unwrap().unwrap(),
+            StreamEvent::Event(DecodedEvent {
+                event_type: "Update".into(),
+                uri: "/lol-champ-select/v1/session".into(),
+                data: "/lol-champ-select/v1/session".into(),
+            })
+        );
+
+        subscriber.on_reconnect(&Router::EVENT_KIND);
+        assert_eq!(
+            champ_select.next().await.unwrap().unwrap(),
+            StreamEvent::Reconnected
+        );
+
+        // The dropped stream's route is removed on the next event
+        assert_eq!(router.len(), 2);
+
+        assert!(router.remove(gameflow));
+        assert!(!router.remove(gameflow));
+        subscriber.on_event(&event("/lol-gameflow/v1/gameflow-phase"), &mut continues);
+        assert!(receiver.try_recv().is_err());
+
+        subscriber.on_unsubscribe(&Router::EVENT_KIND);
+        assert!(router.is_empty());
+        assert!(champ_select.next().await.is_none());
+    }
+
+    #[test]
+    fn test_handler_adds_route() {
+        let router = Router::new();
+        let (sender, receiver) = mpsc::channel();
+
+        // Adding a route from inside a handler would deadlock if the routes were locked while dispatching
+        let inner = router.clone();
+        router.handle("/lol-gameflow/", move |event: &Event| {
+            let sender = sender.clone();
+            let uri = event.2.uri.clone();
+            let _ = inner.handle("/lol-lobby/", move |event: &Event| {
+                sender.send((uri.clone(), event.2.uri.clone())).unwrap();
+            });
+        });
+
+        let mut subscriber = router.clone();
+        let mut continues = true;
+        subscriber.on_event(&event("/lol-gameflow/v1/gameflow-phase"), &mut continues);
+        assert_eq!(router.len(), 2);
+
+        subscriber.on_reconnect(&Router::EVENT_KIND);
+        subscriber.on_event(&event("/lol-lobby/v2/lobby"), &mut continues);
+        assert_eq!(
+            receiver.try_iter().collect::<Vec<_>>(),
+            [(
+                "/lol-gameflow/v1/gameflow-phase".to_owned(),
+                "/lol-lobby/v2/lobby".to_owned()
+            )]
+        );
+    }
+}
@@ -0,0 +1,348 @@
+use futures_core::Stream;
+use serde::de::DeserializeOwned;
+use std::collections::VecDeque;
+use std::pin::Pin;
+use std::sync::{Arc, Condvar, Mutex, MutexGuard, PoisonError};
+use std::task::{Context, Poll, Waker};
+
+use super::types::{Event, EventKind};
+use super::{Subscriber, WebSocketError};
+
+/// An event, with its data deserialized into `T`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DecodedEvent<T> {
+    /// `Create`, `Update`, or `Delete`
+    pub event_type: String,
+    pub uri: String,
+    pub data: T,
+}
+
+/// What an [`EventStream`] yields
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StreamEvent<T> {
+    Event(DecodedEvent<T>),
+    /// The connection was lost, and will be established again if the error handler reconnects,
+    /// no events are received until then
+    ConnectionLost,
+    /// The connection was lost, and has been established again, any events in between were missed,
+    /// so state built from earlier events should be fetched again
+    Reconnected,
+}
+
+/// What happens when an event arrives for a bounded [`EventStream`] that's already full
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Overflow {
+    /// Removes the oldest event to make room, counting it as dropped
+    DropOldest,
+    /// Drops the new event, counting it as dropped
+    DropNewest,
+    /// Waits until the stream has room, this stops every other subscriber from receiving events in the meantime,
+    /// as they're all run on the websocket's thread
+    Block,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Bound {
+    capacity: usize,
+    overflow: Overflow,
+}
+
+struct Shared<T> {
+    queue: VecDeque<Result<StreamEvent<T>, WebSocketError>>,
+    waker: Option<Waker>,
+    /// Set once the subscriber is dropped, after it's unsubscribed or the connection ends
+    closed: bool,
+    /// Set once the stream is dropped, so events stop being queued
+    dropped: bool,
+    /// How many events were dropped because the stream was full
+    dropped_events: u64,
+}
+
+struct Channel<T> {
+    shared: Mutex<Shared<T>>,
+    /// Notified whenever an event is taken out of the queue, or the stream is dropped, for [`Overflow::Block`]
+    space: Condvar,
+    bound: Option<Bound>,
+}
+
+impl<T> Channel<T> {
+    fn lock(&self) -> MutexGuard<'_, Shared<T>> {
+        // The queue is never left half updated, so it's still usable if another thread panicked
+        self.shared.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+/// Events of a single kind, as a [`Stream`], created with [`super::LcuWebSocket::subscribe_stream`]
+///
+/// Events that can't be deserialized into `T` are yielded as errors, rather than ending the stream.
+/// The stream ends when it's unsubscribed, or the connection is closed for good.
+/// If the connection is lost, [`StreamEvent::ConnectionLost`] is yielded,
+/// and if it's established again, [`StreamEvent::Reconnected`] is yielded
+///
+/// Streams are unbounded by default, which can use a lot of memory if they're read slower than the LCU sends events,
+/// such as in champ select, see [`super::LcuWebSocket::subscribe_stream_bounded`] to limit them
+///
+/// ```no_run
+/// use futures_util::StreamExt;
+/// use irelia::ws::{types::EventKind, LcuWebSocket, StreamEvent};
+///
+/// # async fn run() {
+/// let mut ws = LcuWebSocket::new();
+///
+/// let (_, mut phases) = ws
+///     .subscribe_stream::<String>(EventKind::json_api_event_callback_str("/lol-gameflow/v1/gameflow-phase"))
+///     .unwrap();
+///
+/// while let Some(Ok(event)) = phases.next().await {
+///     match event {
+///         StreamEvent::Event(phase) => println!("{}", phase.data),
+///         StreamEvent::ConnectionLost => println!("Waiting for the client"),
+///         StreamEvent::Reconnected => println!("Missed some events"),
+///     }
+/// }
+/// # }
+/// ```
+pub struct EventStream<T> {
+    channel: Arc<Channel<T>>,
+}
+
+impl<T> EventStream<T> {
+    #[must_use]
+    /// How many events were dropped because the stream was full, this is always `0` for unbounded streams
+    pub fn dropped_events(&self) -> u64 {
+        self.channel.lock().dropped_events
+    }
+}
+
+impl<T> Stream for EventStream<T> {
+    type Item = Result<StreamEvent<T>, WebSocketError>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let mut shared = self.channel.lock();
+
+        if let Some(event) = shared.queue.pop_front() {
+            self.channel.space.notify_one();
+            return Poll::Ready(Some(event));
+        }
+
+        if shared.closed {
+            return Poll::Ready(None);
+        }
+
+        shared.waker = Some(cx.waker().clone());
+        Poll::Pending
+    }
+}
+
+impl<T> Drop for EventStream<T> {
+    fn drop(&mut self) {
+        let mut shared = self.channel.lock();
+        shared.dropped = true;
+        shared.queue.clear();
+        self.channel.space.notify_all();
+    }
+}
+
+/// The subscriber feeding an [`EventStream`]
+pub(super) struct StreamSubscriber<T> {
+    channel: Arc<Channel<T>>,
+}
+
+impl<T> StreamSubscriber<T> {
+    pub(super) fn new() -> (Self, EventStream<T>) {
+        Self::with_bound(None)
+    }
+
+    /// Creates a stream holding at most `capacity` events, at least one is always held
+    pub(super) fn bounded(capacity: usize, overflow: Overflow) -> (Self, EventStream<T>) {
+        Self::with_bound(Some(Bound {
+            capacity: capacity.max(1),
+            overflow,
+        }))
+    }
+
+    fn with_bound(bound: Option<Bound>) -> (Self, EventStream<T>) {
+        let channel = Arc::new(Channel {
+            shared: Mutex::new(Shared {
+                queue: VecDeque::new(),
+                waker: None,
+                closed: false,
+                dropped: false,
+                dropped_events: 0,
+            }),
+            space: Condvar::new(),
+            bound,
+        });
+
+        let subscriber = Self {
+            channel: channel.clone(),
+        };
+
+        (subscriber, EventStream { channel })
+    }
+
+    /// Whether the stream was dropped, so nothing is listening for events anymore
+    pub(super) fn is_dropped(&self) -> bool {
+        self.channel.lock().dropped
+    }
+
+    fn push(&self, event: Result<StreamEvent<T>, WebSocketError>) {
+        let mut shared = self.channel.lock();
+
+        if let Some(bound) = self.channel.bound {
+            while !shared.dropped && shared.queue.len() >= bound.capacity {
+                match bound.overflow {
+                    Overflow::DropOldest => {
+                        shared.queue.pop_front();
+                        shared.dropped_events += 1;
+                    }
+                    Overflow::DropNewest => {
+                        shared.dropped_events += 1;
+                        return;
+                    }
+                    Overflow::Block => {
+                        shared = self
+                            .channel
+                            .space
+                            .wait(shared)
+                            .unwrap_or_else(PoisonError::into_inner);
+                    }
+                }
+            }
+        }
+
+        if shared.dropped {
+            return;
+        }
+
+        shared.queue.push_back(event);
+
+        if let Some(waker) = shared.waker.take() {
+            waker.wake();
+        }
+    }
+}
+
+impl<T: DeserializeOwned> Subscriber for StreamSubscriber<T> {
+    fn on_event(&mut self, event: &Event, _continues: &mut bool) {
+        let event = T::deserialize(&event.2.data)
+            .map(|data| {
+                StreamEvent::Event(DecodedEvent {
+                    event_type: event.2.event_type.clone(),
+                    uri: event.2.uri.clone(),
+                    data,
+                })
+            })
+            .map_err(WebSocketError::from);
+
+        self.push(event);
+    }
+
+    fn on_reconnect(&mut self, _event_kind: &EventKind) {
+        self.push(Ok(StreamEvent::Reconnected));
+    }
+
+    fn on_connection_lost(&mut self, _event_kind: &EventKind) {
+        self.push(Ok(StreamEvent::ConnectionLost));
+    }
+}
+
+impl<T> Drop for StreamSubscriber<T> {
+    fn drop(&mut self) {
+        let mut shared = self.channel.lock();
+        shared.closed = true;
+
+        if let Some(waker) = shared.waker.take() {
+            waker.wake();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Overflow, StreamEvent, StreamSubscriber};
+    use crate::ws::types::{Event, EventData, EventKind, RequestType};
+    use crate::ws::Subscriber;
+    use futures_util::StreamExt;
+    use std::thread;
+    use std::time::Duration;
+
+    fn event(data: u32) -> Event {
+        Event(
+            RequestType::Event,
+            EventKind::json_api_event(),
+            EventData {
+                data: data.into(),
+                event_type: "Update".into(),
+                uri: "/example".into(),
+            },
+        )
+    }
+
+    async fn next(stream: &mut super::EventStream<u32>) -> u32 {
+        match stream.next().await.unwrap().unwrap() {
+            StreamEvent::Event(event) => event.data,
+            StreamEvent::ConnectionLost | StreamEvent::Reconnected => panic!("Expected an event"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_overflow() {
+        let mut continues = true;
+
+        let (mut subscriber, mut stream) =
+            StreamSubscriber::<u32>::bounded(2, Overflow::DropOldest);
+        for data in 0..5 {
+            subscriber.on_event(&event(data), &mut continues);
+        }
+        assert_eq!(stream.dropped_events(), 3);
+        assert_eq!(next(&mut stream).await, 3);
+        assert_eq!(next(&mut stream).await, 4);
+
+        let (mut subscriber, mut stream) =
+            StreamSubscriber::<u32>::bounded(2, Overflow::DropNewest);
+        for data in 0..5 {
+            subscriber.on_event(&event(data), &mut continues);
+        }
+        assert_eq!(stream.dropped_events(), 3);
+        assert_eq!(next(&mut stream).await, 0);
+        assert_eq!(next(&mut stream).await, 1);
+
+        let (mut subscriber, stream) = StreamSubscriber::<u32>::new();
+        for data in 0..5 {
+            subscriber.on_event(&event(data), &mut continues);
+        }
+        assert_eq!(stream.dropped_events(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_block() {
+        let (mut subscriber, mut stream) = StreamSubscriber::<u32>::bounded(1, Overflow::Block);
+
+        let sender = thread::spawn(move || {
+            let mut continues = true;
+            for data in 0..3 {
+                subscriber.on_event(&event(data), &mut continues);
+            }
+            subscriber
+        });
+
+        // The sender can only get one event ahead of the stream
+        thread::sleep(Duration::from_millis(20));
+        assert!(!sender.is_finished());
+
+        for data in 0..3 {
+            assert_eq!(next(&mut stream).await, data);
+        }
+
+        let mut subscriber = sender.join().unwrap();
+        assert_eq!(stream.dropped_events(), 0);
+
+        // Dropping the stream unblocks the sender
+        let mut continues = true;
+        subscriber.on_event(&event(3), &mut continues);
+        let sender = thread::spawn(move || subscriber.on_event(&event(4), &mut continues));
+        drop(stream);
+        sender.join().unwrap();
+    }
+}
@@ -0,0 +1,122 @@
+//! Utilities for testing websocket consumers without a running client
+//!
+//! [`mock_stream`] creates an in-memory pair of streams, one is passed to [`LcuWebSocket::from_stream`],
+//! and the other is used to push crafted events, and read back what the client sent
+//!
+//! [`LcuWebSocket::from_stream`]: super::LcuWebSocket::from_stream
+
+use std::collections::VecDeque;
+use std::io::{ErrorKind, Read, Write};
+use std::sync::{Arc, Mutex, PoisonError};
+use tungstenite::protocol::Role;
+use tungstenite::util::NonBlockingResult;
+use tungstenite::{Message, WebSocket};
+
+use super::types::Event;
+use super::WebSocketError;
+
+type Buffer = Arc<Mutex<VecDeque<u8>>>;
+
+/// One half of an in-memory duplex stream
+///
+/// Reading from an empty stream returns `WouldBlock`, the same as a nonblocking socket
+pub struct MockStream {
+    read: Buffer,
+    write: Buffer,
+}
+
+impl Read for MockStream {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let mut read = self.read.lock().unwrap_or_else(PoisonError::into_inner);
+
+        if read.is_empty() {
+            return Err(ErrorKind::WouldBlock.into());
+        }
+
+        read.read(buf)
+    }
+}
+
+impl Write for MockStream {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        let mut write = self.write.lock().unwrap_or_else(PoisonError::into_inner);
+        write.extend(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+/// The server half of [`mock_stream`], used to act as the LCU
+pub struct MockServer {
+    socket: WebSocket<MockStream>,
+}
+
+impl MockServer {
+    /// Sends an event to the client, in the same format the LCU uses
+    ///
+    /// # Errors
+    /// This will return an error if the event can't be serialized, or the client closed the connection
+    pub fn send_event(&mut self, event: &Event) -> Result<(), WebSocketError> {
+        let json = serde_json::to_string(event)?;
+        self.send_text(json)
+    }
+
+    /// Sends a raw text frame to the client, such as `[8, "OnJsonApiEvent", {..}]`
+    ///
+    /// # Errors
+    /// This will return an error if the client closed the connection
+    pub fn send_text(&mut self, text: impl Into<String>) -> Result<(), WebSocketError> {
+        self.socket.send(Message::text(text.into()))?;
+        Ok(())
+    }
+
+    /// Closes the connection, as the LCU does when the client exits
+    ///
+    /// # Errors
+    /// This will return an error if the connection was already closed
+    pub fn close(&mut self) -> Result<(), WebSocketError> {
+        self.socket.close(None)?;
+        Ok(())
+    }
+
+    /// Reads the next message the client sent, such as subscribe or unsubscribe requests
+    ///
+    /// Returns `None` if the client hasn't sent anything yet
+    ///
+    /// # Errors
+    /// This will return an error if the client sent an invalid frame
+    pub fn receive(&mut self) -> Result<Option<Message>, WebSocketError> {
+        Ok(self.socket.read().no_block()?)
+    }
+}
+
+#[must_use]
+/// Creates an in-memory websocket connection
+///
+/// The returned `WebSocket` is the client half, and can be passed to [`LcuWebSocket::from_stream`]
+///
+/// [`LcuWebSocket::from_stream`]: super::LcuWebSocket::from_stream
+pub fn mock_stream() -> (WebSocket<MockStream>, MockServer) {
+    let to_client = Buffer::default();
+    let to_server = Buffer::default();
+
+    let client = MockStream {
+        read: to_client.clone(),
+        write: to_server.clone(),
+    };
+
+    let server = MockStream {
+        read: to_server,
+        write: to_client,
+    };
+
+    (
+        WebSocket::from_raw_socket(client, Role::Client, None),
+        MockServer {
+            socket: WebSocket::from_raw_socket(server, Role::Server, None),
+        },
+    )
+}
@@ -59,6 +59,44 @@ impl EventKind {
         }
     }
 
+    #[must_use]
+    /// The name the LCU uses for this event kind, such as `OnJsonApiEvent_lol-lobby_v2_lobby` for `/lol-lobby/v2/lobby`
+    pub fn event_name(&self) -> Cow<'static, str> {
+        self.to_string()
+    }
+
+    #[must_use]
+    /// Parses a name the LCU uses for an event kind, the inverse of [`EventKind::event_name`]
+    ///
+    /// JSON API callbacks are turned back into the endpoint they were made from, so an endpoint that had an
+    /// underscore in it comes back with a slash instead
+    ///
+    /// Returns `None` if the name isn't a known event kind
+    pub fn from_event_name(name: &str) -> Option<Self> {
+        let (event_kind, callback) = match name.split_once('_') {
+            Some((event_kind, callback)) => (event_kind, Some(callback)),
+            None => (name, None),
+        };
+
+        let event_kind = match (event_kind, callback) {
+            ("OnJsonApiEvent", callback) => Self::JsonApiEvent {
+                callback: callback
+                    .map(|callback| format!("/{}", callback.replace('_', "/")).into()),
+            },
+            ("OnLcdsEvent", callback) => Self::LcdsEvent {
+                callback: callback.map(|callback| callback.to_string().into()),
+            },
+            ("OnLog", None) => Self::Log,
+            ("OnRegionLocaleChanged", None) => Self::RegionLocaleChanged,
+            ("OnServiceProxyAsyncEvent", None) => Self::ServiceProxyAsyncEvent,
+            ("OnServiceProxyMethodEvent", None) => Self::ServiceProxyMethodEvent,
+            ("OnServiceProxyUuidEvent", None) => Self::ServiceProxyUuidEvent,
+            _ => return None,
+        };
+
+        Some(event_kind)
+    }
+
     #[must_use]
     pub const fn lcds_event() -> Self {
         Self::LcdsEvent { callback: None }
@@ -275,4 +313,45 @@ mod test {
 
         assert_eq!(event, baseline_event);
     }
+
+    #[test]
+    fn test_event_name() {
+        let lobby = EventKind::json_api_event_callback_str("/lol-lobby/v2/lobby");
+        assert_eq!(lobby.event_name(), "OnJsonApiEvent_lol-lobby_v2_lobby");
+
+        let parsed = EventKind::from_event_name("OnJsonApiEvent_lol-lobby_v2_lobby").unwrap();
+        assert_eq!(parsed, lobby);
+
+        assert_eq!(
+            EventKind::from_event_name("OnJsonApiEvent"),
+            Some(EventKind::json_api_event())
+        );
+        assert_eq!(EventKind::from_event_name("OnLog"), Some(EventKind::Log));
+        assert_eq!(EventKind::from_event_name("OnLog_lol-lobby"), None);
+        assert_eq!(EventKind::from_event_name("OnSomethingElse"), None);
+    }
+
+    #[test]
+    fn test_event_name_round_trip() {
+        let event_kinds = [
+            EventKind::json_api_event(),
+            EventKind::json_api_event_callback_str("/lol-lobby/v2/lobby"),
+            EventKind::lcds_event(),
+            EventKind::lcds_event_callback_str("com.riotgames.platform.gameclient"),
+            EventKind::Log,
+            EventKind::RegionLocaleChanged,
+            EventKind::ServiceProxyAsyncEvent,
+            EventKind::ServiceProxyMethodEvent,
+            EventKind::ServiceProxyUuidEvent,
+        ];
+
+        for event_kind in event_kinds {
+            let name = event_kind.event_name();
+            assert_eq!(
+                EventKind::from_event_name(&name),
+                Some(event_kind),
+                "{name}"
+            );
+        }
+    }
 }
@@ -73,6 +73,46 @@ impl<V: Default> EventMap<V> {
 
         events
     }
+
+    /// Iterates over every event kind that has been used, along with its value
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (EventKind, &mut V)> {
+        let fixed = [
+            (EventKind::json_api_event(), &mut self.json_api_event),
+            (EventKind::lcds_event(), &mut self.lcds_event),
+            (EventKind::Log, &mut self.log),
+            (
+                EventKind::RegionLocaleChanged,
+                &mut self.region_locale_changed,
+            ),
+            (
+                EventKind::ServiceProxyAsyncEvent,
+                &mut self.service_proxy_async_event,
+            ),
+            (
+                EventKind::ServiceProxyMethodEvent,
+                &mut self.service_proxy_method_event,
+            ),
+            (
+                EventKind::ServiceProxyUuidEvent,
+                &mut self.service_proxy_uuid_event,
+            ),
+        ];
+
+        let json_api_event_callback = self
+            .json_api_event_callback
+            .iter_mut()
+            .map(|(key, value)| (EventKind::json_api_event_callback(key.clone()), value));
+
+        let lcds_event_callback = self
+            .lcds_event_callback
+            .iter_mut()
+            .map(|(key, value)| (EventKind::lcds_event_callback(key.clone()), value));
+
+        fixed
+            .into_iter()
+            .chain(json_api_event_callback)
+            .chain(lcds_event_callback)
+    }
 }
 
 impl<V> Index<&EventKind> for EventMap<V> {
@@ -0,0 +1,179 @@
+#![warn(clippy::perf)]
+#![warn(clippy::pedantic)]
+#![forbid(unsafe_code)]
+
+//! Derive macros for irelia, these are re-exported by irelia with the `macros` feature, and shouldn't be depended on directly
+
+use proc_macro::TokenStream;
+use proc_macro2::Span;
+use quote::quote;
+use syn::{parse_macro_input, Data, DeriveInput, Fields, Ident, LitStr, Type};
+
+/// Implements `irelia::rest::endpoint::Endpoint`, see the trait for usage
+///
+/// - `#[endpoint(method = "GET", path = "/path/{field}", response = Type)]` on the struct,
+///   `{field}` being replaced with the field's `Display` output, and `response` defaulting to `()`
+/// - `#[endpoint(body)]` on at most one field, which is sent as the body
+#[proc_macro_derive(Endpoint, attributes(endpoint))]
+pub fn derive_endpoint(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    expand(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+fn expand(input: &DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
+    let mut method = None;
+    let mut path = None;
+    let mut response = None;
+
+    for attr in input
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("endpoint"))
+    {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("method") {
+                method = Some(meta.value()?.parse::<LitStr>()?);
+            } else if meta.path.is_ident("path") {
+                path = Some(meta.value()?.parse::<LitStr>()?);
+            } else if meta.path.is_ident("response") {
+                response = Some(meta.value()?.parse::<Type>()?);
+            } else {
+                return Err(meta.error("expected `method`, `path`, or `response`"));
+            }
+
+            Ok(())
+        })?;
+    }
+
+    let method = method.ok_or_else(|| {
+        syn::Error::new(Span::call_site(), "missing `#[endpoint(method = \"...\")]`")
+    })?;
+    let path = path.ok_or_else(|| {
+        syn::Error::new(Span::call_site(), "missing `#[endpoint(path = \"...\")]`")
+    })?;
+    let response = response.map_or_else(|| quote!(()), |response| quote!(#response));
+
+    let fields = match &input.data {
+        Data::Struct(data) => &data.fields,
+        _ => {
+            return Err(syn::Error::new_spanned(
+                &input.ident,
+                "`Endpoint` can only be derived for structs",
+            ))
+        }
+    };
+
+    let field_names: Vec<&Ident> = match fields {
+        Fields::Named(fields) => fields
+            .named
+            .iter()
+            .filter_map(|field| field.ident.as_ref())
+            .collect(),
+        _ => Vec::new(),
+    };
+
+    let mut body = None;
+
+    for field in fields {
+        for attr in field
+            .attrs
+            .iter()
+            .filter(|attr| attr.path().is_ident("endpoint"))
+        {
+            attr.parse_nested_meta(|meta| {
+                if !meta.path.is_ident("body") {
+                    return Err(meta.error("expected `body`"));
+                }
+
+                if body.is_some() {
+                    return Err(meta.error("only one field can be the body"));
+                }
+
+                let Some(ident) = &field.ident else {
+                    return Err(meta.error("the body must be a named field"));
+                };
+
+                body = Some((ident.clone(), field.ty.clone()));
+                Ok(())
+            })?;
+        }
+    }
+
+    let path_fn = path_fn(&path, &field_names)?;
+
+    let (body_type, into_body) = match body {
+        Some((ident, ty)) => (
+            quote!(#ty),
+            quote!(::core::option::Option::Some(self.#ident)),
+        ),
+        None => (quote!(()), quote!(::core::option::Option::None)),
+    };
+
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics ::irelia::rest::endpoint::Endpoint for #name #ty_generics #where_clause {
+            type Body = #body_type;
+            type Response = #response;
+
+            const METHOD: &'static str = #method;
+
+            #path_fn
+
+            fn into_body(self) -> ::core::option::Option<Self::Body> {
+                #into_body
+            }
+        }
+    })
+}
+
+/// Builds `fn path`, replacing each `{field}` in the template with that field
+fn path_fn(template: &LitStr, fields: &[&Ident]) -> syn::Result<proc_macro2::TokenStream> {
+    let value = template.value();
+    let mut format = String::with_capacity(value.len());
+    let mut args = Vec::new();
+    let mut rest = value.as_str();
+
+    while let Some(start) = rest.find('{') {
+        let Some(end) = rest[start..].find('}') else {
+            return Err(syn::Error::new_spanned(template, "unclosed `{` in path"));
+        };
+
+        let name = &rest[start + 1..start + end];
+        let Some(field) = fields.iter().find(|field| **field == name) else {
+            return Err(syn::Error::new_spanned(
+                template,
+                format!("`{name}` is not a field of this struct"),
+            ));
+        };
+
+        format.push_str(&rest[..start]);
+        format.push_str("{}");
+        args.push(field);
+        rest = &rest[start + end + 1..];
+    }
+
+    if rest.contains('}') {
+        return Err(syn::Error::new_spanned(template, "unopened `}` in path"));
+    }
+
+    format.push_str(rest);
+
+    if args.is_empty() {
+        return Ok(quote! {
+            fn path(&self) -> ::std::borrow::Cow<'_, str> {
+                ::std::borrow::Cow::Borrowed(#template)
+            }
+        });
+    }
+
+    Ok(quote! {
+        fn path(&self) -> ::std::borrow::Cow<'_, str> {
+            ::std::borrow::Cow::Owned(::std::format!(#format, #(self.#args),*))
+        }
+    })
+}